@@ -9,10 +9,12 @@ mod saturated_reachability;
 mod xie_beerel;
 
 use crate::algorithms::eval_dynamic::_attractors::itgr::interleaved_transition_guided_reduction;
+use crate::algorithms::eval_dynamic::_attractors::saturated_reachability::{reach_bwd, reach_fwd};
 use crate::algorithms::eval_dynamic::_attractors::xie_beerel::xie_beerel_attractors;
 use biodivine_lib_param_bn::{
     biodivine_std::traits::Set,
     symbolic_async_graph::{GraphColoredVertices, GraphColors, SymbolicAsyncGraph},
+    VariableId,
 };
 
 /// Compute terminal SCCs, and sort all the colors according to how many attractors they have.
@@ -33,6 +35,55 @@ pub fn sort_colors_by_attr_num(graph: &SymbolicAsyncGraph) -> Vec<GraphColors> {
     colors_by_num_attrs
 }
 
+/// Compute states of all attractors (terminal SCCs), for all colors.
+pub fn attractor_states(graph: &SymbolicAsyncGraph) -> GraphColoredVertices {
+    let (universe, active_variables) =
+        interleaved_transition_guided_reduction(graph, graph.mk_unit_colored_vertices());
+
+    let mut attractors = graph.mk_empty_colored_vertices();
+    xie_beerel_attractors(graph, &universe, &active_variables, |component| {
+        attractors = attractors.union(&component);
+    });
+    attractors
+}
+
+/// Get colors for which each of the `targets` sets contains a state reachable from the
+/// `initial` set of states.
+pub fn colors_where_reachable(
+    graph: &SymbolicAsyncGraph,
+    initial: &GraphColoredVertices,
+    targets: &[GraphColoredVertices],
+) -> GraphColors {
+    let variables: Vec<VariableId> = graph.variables().collect();
+    let reachable = reach_fwd(graph, initial, graph.unit_colored_vertices(), &variables);
+    targets
+        .iter()
+        .fold(graph.mk_unit_colors(), |colors, target| {
+            colors.intersect(&reachable.intersect(target).colors())
+        })
+}
+
+/// Get colors for which all the `states` lie in the (weak) basins of attractors intersecting
+/// the `phenotypes` set. That is, from each of these states, such an attractor is reachable.
+pub fn colors_where_in_basins(
+    graph: &SymbolicAsyncGraph,
+    states: &[GraphColoredVertices],
+    phenotypes: &GraphColoredVertices,
+) -> GraphColors {
+    let variables: Vec<VariableId> = graph.variables().collect();
+    // if a phenotype intersects an attractor, the whole attractor is reachable from its basin
+    let phenotype_attractors = attractor_states(graph).intersect(phenotypes);
+    let basins = reach_bwd(
+        graph,
+        &phenotype_attractors,
+        graph.unit_colored_vertices(),
+        &variables,
+    );
+    states.iter().fold(graph.mk_unit_colors(), |colors, set| {
+        colors.minus(&set.minus(&basins).colors())
+    })
+}
+
 /// Process a component found by Xie-Beerel (attractor component for a subset of colors).
 /// Update the `colors_by_num_attrs` so that on index i are all colors with i attractors,
 /// after taking the new component into account.
@@ -93,19 +93,22 @@ fn encode_obs_as_singleton_space(
 
     // encode the observation into Space instance
     let mut obs_space = Space::new(bn);
-    var_names.iter().enumerate().for_each(|(i, var_name)| {
-        let var = bn.as_graph().find_variable(var_name).unwrap();
+    var_names
+        .iter()
+        .zip(observation.iter_values())
+        .for_each(|(var_name, value)| {
+            let var = bn.as_graph().find_variable(var_name).unwrap();
 
-        match observation.get_values()[i] {
-            VarValue::True => {
-                obs_space[var] = ExtendedBoolean::One;
+            match value {
+                VarValue::True => {
+                    obs_space[var] = ExtendedBoolean::One;
+                }
+                VarValue::False => {
+                    obs_space[var] = ExtendedBoolean::Zero;
+                }
+                VarValue::Any => {}
             }
-            VarValue::False => {
-                obs_space[var] = ExtendedBoolean::Zero;
-            }
-            VarValue::Any => {}
-        }
-    });
+        });
 
     // compute BDD for the space, so we can create "NetworkColoredSpaces" instance
     let obs_space_bdd = ctx.mk_space(&obs_space);
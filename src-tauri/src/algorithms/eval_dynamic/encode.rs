@@ -1,7 +1,9 @@
 use crate::algorithms::eval_dynamic::processed_props::DataEncodingType;
 use crate::sketchbook::ids::ObservationId;
-use crate::sketchbook::observations::{Dataset, Observation, VarValue};
+use crate::sketchbook::observations::{Dataset, MultiValuedVar, Observation, VarValue};
 use crate::sketchbook::properties::HctlFormula;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
 use std::fmt::Write;
 
 /// Encode a dataset of observations as a single HCTL formula. The particular formula
@@ -26,12 +28,17 @@ pub fn encode_dataset_hctl_str(
         .map(|v| v.to_string())
         .collect::<Vec<String>>();
 
+    let multi_valued = dataset.multi_valued_vars();
     let encoded_observations = if let Some(obs_id) = observation_id {
         let observation = dataset.get_obs(&obs_id)?;
-        vec![encode_observation_str(observation, &var_names)?]
+        vec![encode_observation_str(
+            observation,
+            &var_names,
+            multi_valued,
+        )?]
     } else {
         let observations = dataset.observations();
-        encode_multiple_observations_str(observations, &var_names)?
+        encode_multiple_observations_str(observations, &var_names, multi_valued)?
     };
 
     match category {
@@ -49,7 +56,7 @@ pub fn try_encode_observation(
     obs: &Observation,
     var_names: &[String],
 ) -> Result<HctlFormula, String> {
-    let formula = encode_observation_str(obs, var_names)?;
+    let formula = encode_observation_str(obs, var_names, &[])?;
     HctlFormula::try_from_str(&formula)
 }
 
@@ -59,7 +66,7 @@ pub fn try_encode_multiple_observations(
     observations: &[Observation],
     var_names: &[String],
 ) -> Result<Vec<HctlFormula>, String> {
-    let formulae = encode_multiple_observations_str(observations, var_names)?;
+    let formulae = encode_multiple_observations_str(observations, var_names, &[])?;
     formulae
         .iter()
         .map(|f| HctlFormula::try_from_str(f))
@@ -71,9 +78,14 @@ pub fn try_encode_multiple_observations(
 /// describing that observation.
 ///
 /// `00*1*1` would end up like `!v1 & !v2 & v4 & v6`
+///
+/// Boolean variables encoding `multi_valued` variables (see [MultiValuedVar]) are encoded the
+/// same way. However, if the level of a multi-valued variable is not fully specified, we add
+/// constraints ensuring that the state is a valid encoding of some level (such as `X_2 => X_1`).
 fn encode_observation_str(
     observation: &Observation,
     prop_names: &[String],
+    multi_valued: &[MultiValuedVar],
 ) -> Result<String, String> {
     if observation.num_values() != prop_names.len() {
         return Err("Numbers of observation's values and propositions differs.".to_string());
@@ -81,12 +93,17 @@ fn encode_observation_str(
 
     let formula: String = prop_names
         .iter()
-        .enumerate()
-        .filter_map(|(i, prop)| match observation.get_values()[i] {
+        .zip(observation.iter_values())
+        .filter_map(|(prop, value)| match value {
             VarValue::True => Some(prop.to_string()),
             VarValue::False => Some(format!("~{prop}")),
             VarValue::Any => None,
         })
+        .chain(mk_encoding_constraints(
+            observation,
+            prop_names,
+            multi_valued,
+        ))
         .collect::<Vec<_>>()
         .join(" & ");
 
@@ -99,15 +116,116 @@ fn encode_observation_str(
     Ok(final_formula)
 }
 
+/// **(internal)** Make implications `X_{i+1} => X_i` ensuring valid encoding of each multi-valued
+/// variable whose level is not fully specified by the observation.
+fn mk_encoding_constraints(
+    observation: &Observation,
+    prop_names: &[String],
+    multi_valued: &[MultiValuedVar],
+) -> Vec<String> {
+    encoding_constraint_indices(observation, prop_names, multi_valued)
+        .into_iter()
+        .map(|(higher, lower)| format!("({} => {})", prop_names[higher], prop_names[lower]))
+        .collect()
+}
+
+/// **(internal)** Compute pairs of indices `(higher, lower)` of propositions that must satisfy
+/// the implication `higher => lower` (see [mk_encoding_constraints]).
+fn encoding_constraint_indices(
+    observation: &Observation,
+    prop_names: &[String],
+    multi_valued: &[MultiValuedVar],
+) -> Vec<(usize, usize)> {
+    let mut constraints = Vec::new();
+    for var in multi_valued {
+        let indices: Vec<usize> = var
+            .encoding_vars()
+            .iter()
+            .filter_map(|e| prop_names.iter().position(|p| p == e))
+            .collect();
+        if indices
+            .iter()
+            .all(|i| observation.value_at_idx(*i).is_ok_and(|v| v.is_fixed()))
+        {
+            continue;
+        }
+        for pair in indices.windows(2) {
+            constraints.push((pair[1], pair[0]));
+        }
+    }
+    constraints
+}
+
+/// Encode an observation directly as a symbolic set of states (with all colors of the `graph`),
+/// without building and parsing any formula. The set is the same as the one described by the
+/// formula from [encode_observation_str] (including the constraints for `multi_valued` vars).
+///
+/// The `var_names` must be names of network variables in the `graph`.
+pub fn encode_observation_bdd(
+    observation: &Observation,
+    var_names: &[String],
+    multi_valued: &[MultiValuedVar],
+    graph: &SymbolicAsyncGraph,
+) -> Result<GraphColoredVertices, String> {
+    if observation.num_values() != var_names.len() {
+        return Err("Numbers of observation's values and propositions differs.".to_string());
+    }
+    let ctx = graph.symbolic_context();
+    let vars = var_names
+        .iter()
+        .map(|name| {
+            ctx.find_network_variable(name)
+                .ok_or(format!("Variable `{name}` is not in the network."))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut bdd = ctx.mk_constant(true);
+    for (var, value) in vars.iter().zip(observation.iter_values()) {
+        let literal = ctx.mk_state_variable_is_true(*var);
+        match value {
+            VarValue::True => bdd = bdd.and(&literal),
+            VarValue::False => bdd = bdd.and_not(&literal),
+            VarValue::Any => {}
+        }
+    }
+    for (higher, lower) in encoding_constraint_indices(observation, var_names, multi_valued) {
+        let higher = ctx.mk_state_variable_is_true(vars[higher]);
+        let lower = ctx.mk_state_variable_is_true(vars[lower]);
+        bdd = bdd.and(&higher.imp(&lower));
+    }
+    let states = GraphColoredVertices::new(bdd, ctx);
+    Ok(graph.unit_colored_vertices().intersect(&states))
+}
+
+/// Encode each observation of a dataset directly as a symbolic set of states, one by one.
+/// If `observation_id` is specified, only this observation is encoded.
+/// See [encode_observation_bdd] for details.
+pub fn encode_dataset_bdds(
+    dataset: &Dataset,
+    observation_id: Option<&ObservationId>,
+    graph: &SymbolicAsyncGraph,
+) -> Result<Vec<GraphColoredVertices>, String> {
+    let var_names = dataset.variable_names();
+    let observations = match observation_id {
+        Some(obs_id) => vec![dataset.get_obs(obs_id)?],
+        None => dataset.observations().iter().collect(),
+    };
+    observations
+        .into_iter()
+        .map(|o| encode_observation_bdd(o, &var_names, dataset.multi_valued_vars(), graph))
+        .collect()
+}
+
 /// Encode several observation vectors with conjunction formulae, one by one.
 /// Also see [encode_observation_str] for details.
 fn encode_multiple_observations_str(
     observations: &[Observation],
     prop_names: &[String],
+    multi_valued: &[MultiValuedVar],
 ) -> Result<Vec<String>, String> {
     observations
         .iter()
-        .map(|o| encode_observation_str(o, prop_names))
+        .map(|o| encode_observation_str(o, prop_names, multi_valued))
         .collect::<Result<Vec<String>, String>>()
 }
 
@@ -334,6 +452,8 @@ pub fn mk_formula_reachability_chain(states_sequence: &[String]) -> String {
 mod tests {
     use super::*;
     use crate::sketchbook::observations::Observation;
+    use biodivine_hctl_model_checker::model_checking::model_check_formula_dirty;
+    use biodivine_lib_param_bn::BooleanNetwork;
 
     #[test]
     /// Test encoding of an observation.
@@ -349,29 +469,72 @@ mod tests {
         let obs1 = Observation::try_from_str("001*1", "o1").unwrap();
         let encoded1 = "(~a & ~b & c & e)";
         assert_eq!(
-            encode_observation_str(&obs1, &prop_names).unwrap(),
+            encode_observation_str(&obs1, &prop_names, &[]).unwrap(),
             encoded1
         );
 
         let obs2 = Observation::try_from_str("001**", "o2").unwrap();
         let encoded2 = "(~a & ~b & c)";
         assert_eq!(
-            encode_observation_str(&obs2, &prop_names).unwrap(),
+            encode_observation_str(&obs2, &prop_names, &[]).unwrap(),
             encoded2
         );
 
         let obs3 = Observation::try_from_str("*****", "o3").unwrap();
         let encoded3 = "(true)";
         assert_eq!(
-            encode_observation_str(&obs3, &prop_names).unwrap(),
+            encode_observation_str(&obs3, &prop_names, &[]).unwrap(),
             encoded3
         );
 
         let multiple_encoded =
-            encode_multiple_observations_str(&vec![obs1, obs2, obs3], &prop_names).unwrap();
+            encode_multiple_observations_str(&vec![obs1, obs2, obs3], &prop_names, &[]).unwrap();
         assert_eq!(multiple_encoded, vec![encoded1, encoded2, encoded3]);
     }
 
+    #[test]
+    /// Test encoding of observations with multi-valued variables.
+    fn test_multi_valued_observation_encoding() {
+        let prop_names = vec!["X_1".to_string(), "X_2".to_string(), "y".to_string()];
+        let multi_valued = vec![MultiValuedVar::with_num_levels("X", 3).unwrap()];
+
+        // fully specified level needs no additional constraints
+        let obs1 = Observation::try_from_str("101", "o1").unwrap();
+        let encoded1 = encode_observation_str(&obs1, &prop_names, &multi_valued).unwrap();
+        assert_eq!(encoded1, "(X_1 & ~X_2 & y)");
+
+        // partially specified levels must be valid encodings
+        let obs2 = Observation::try_from_str("1*0", "o2").unwrap();
+        let encoded2 = encode_observation_str(&obs2, &prop_names, &multi_valued).unwrap();
+        assert_eq!(encoded2, "(X_1 & ~y & (X_2 => X_1))");
+
+        let obs3 = Observation::try_from_str("***", "o3").unwrap();
+        let encoded3 = encode_observation_str(&obs3, &prop_names, &multi_valued).unwrap();
+        assert_eq!(encoded3, "((X_2 => X_1))");
+    }
+
+    #[test]
+    /// Test that direct symbolic encoding of observations matches their formula encoding.
+    fn test_observation_bdd_encoding() {
+        let bn = BooleanNetwork::try_from("X_1 -> X_2\nX_2 -| y\ny -> X_1").unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let prop_names = vec!["X_1".to_string(), "X_2".to_string(), "y".to_string()];
+        let multi_valued = vec![MultiValuedVar::with_num_levels("X", 3).unwrap()];
+
+        for values in ["101", "1*0", "***", "0*1"] {
+            let obs = Observation::try_from_str(values, "o").unwrap();
+            let formula = encode_observation_str(&obs, &prop_names, &multi_valued).unwrap();
+            // results of model checking might not be restricted to the valid colors
+            let expected = model_check_formula_dirty(&formula, &graph).unwrap();
+            let expected = expected.intersect(graph.unit_colored_vertices());
+            let encoded = encode_observation_bdd(&obs, &prop_names, &multi_valued, &graph).unwrap();
+            assert_eq!(encoded.as_bdd(), expected.as_bdd());
+        }
+
+        let obs = Observation::try_from_str("10", "o").unwrap();
+        assert!(encode_observation_bdd(&obs, &prop_names, &[], &graph).is_err());
+    }
+
     #[test]
     /// Test generating different kinds of general attractor formulae.
     fn test_attractor_encodings() {
@@ -1,13 +1,25 @@
-use crate::algorithms::eval_dynamic::_attractors::sort_colors_by_attr_num;
+use crate::algorithms::eval_dynamic::_attractors::{
+    colors_where_in_basins, colors_where_reachable, sort_colors_by_attr_num,
+};
 use crate::algorithms::eval_dynamic::_trap_spaces::{
     colors_where_essential_traps, colors_where_minimal_traps,
 };
-use crate::algorithms::eval_dynamic::encode::encode_dataset_hctl_str;
+use crate::algorithms::eval_dynamic::encode::encode_dataset_bdds;
 use crate::algorithms::eval_dynamic::prepare_graph::get_ts_extended_symbolic_graph;
-use crate::algorithms::eval_dynamic::processed_props::{DataEncodingType, ProcessedDynProp};
-use biodivine_hctl_model_checker::model_checking::model_check_formula_dirty;
+use crate::algorithms::eval_dynamic::processed_props::{
+    DataEncodingType, ProcessedDynProp, ProcessedSoftConstraints, ProcessedStateData,
+    ProcessedStateSet, StateSetRequirement,
+};
+use crate::sketchbook::observations::Dataset;
+use biodivine_hctl_model_checker::model_checking::{
+    model_check_extended_formula_dirty, model_check_formula_dirty,
+};
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
-use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use biodivine_lib_param_bn::symbolic_async_graph::{
+    GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
 
 /// Model check a property and get colors for which the property holds universally (in every state).
 fn model_check_colors_universal(
@@ -16,17 +28,158 @@ fn model_check_colors_universal(
 ) -> Result<GraphColors, String> {
     // run model checking to compute all valid stat-color pairs
     let mc_results = model_check_formula_dirty(formula, stg)?;
-    // do universal projection on the colors of the given `colored_vertices`.
-    let complement = stg.unit_colored_vertices().minus(&mc_results);
-    let universal_colors = stg.unit_colors().minus(&complement.colors());
-    Ok(universal_colors)
+    Ok(project_colors_universal(stg, &mc_results))
+}
+
+/// Do universal projection on the colors of the given `colored_vertices`, i.e., get colors for
+/// which all the states are contained in the set.
+fn project_colors_universal(
+    stg: &SymbolicAsyncGraph,
+    colored_vertices: &GraphColoredVertices,
+) -> GraphColors {
+    let complement = stg.unit_colored_vertices().minus(colored_vertices);
+    stg.unit_colors().minus(&complement.colors())
+}
+
+/// Get colors for which each observation of the dataset contains a fixed point or an attractor
+/// state (depending on the encoding type). Observations are encoded directly as symbolic sets.
+///
+/// Fixed points are computed directly as states with no outgoing transitions. Attractor states
+/// are computed with HCTL model checking, with the observation given as a wild-card proposition.
+fn eval_state_data(
+    prop: &ProcessedStateData,
+    graph: &SymbolicAsyncGraph,
+) -> Result<GraphColors, String> {
+    let observation_sets = encode_dataset_bdds(&prop.dataset, None, graph)?;
+    let mut sat_colors = graph.mk_unit_colors();
+    match prop.encoding {
+        DataEncodingType::FixedPoint => {
+            let unit = graph.unit_colored_vertices();
+            let can_move = graph
+                .variables()
+                .fold(graph.mk_empty_colored_vertices(), |acc, var| {
+                    acc.union(&graph.var_can_post(var, unit))
+                });
+            let fixed_points = unit.minus(&can_move);
+            for states in observation_sets {
+                sat_colors = sat_colors.intersect(&fixed_points.intersect(&states).colors());
+            }
+        }
+        DataEncodingType::Attractor => {
+            let formula = "3{x}: @{x}: (%obs% & (AG EF (%obs% & {x})))";
+            for states in observation_sets {
+                let context_sets = HashMap::from([("obs".to_string(), states)]);
+                let mc_results = model_check_extended_formula_dirty(formula, graph, &context_sets)?;
+                sat_colors = sat_colors.intersect(&project_colors_universal(graph, &mc_results));
+            }
+        }
+        _ => return Err("Only fixed-point and attractor data can be evaluated.".to_string()),
+    }
+    Ok(sat_colors)
+}
+
+/// Get colors satisfying the requirement on all states of the dataset (see [StateSetRequirement]).
+/// Observations are encoded directly as symbolic sets, and evaluated by symbolic reachability.
+fn eval_state_set(
+    prop: &ProcessedStateSet,
+    graph: &SymbolicAsyncGraph,
+) -> Result<GraphColors, String> {
+    let observation_sets = encode_dataset_bdds(&prop.dataset, None, graph)?;
+    match &prop.requirement {
+        StateSetRequirement::ReachableFrom(initial_obs) => {
+            let initial = encode_dataset_bdds(&prop.dataset, Some(initial_obs), graph)?;
+            Ok(colors_where_reachable(
+                graph,
+                &initial[0],
+                &observation_sets,
+            ))
+        }
+        StateSetRequirement::InBasins(phenotypes) => {
+            let phenotype_states = encode_dataset_bdds(phenotypes, None, graph)?
+                .iter()
+                .fold(graph.mk_empty_colored_vertices(), |acc, s| acc.union(s));
+            Ok(colors_where_in_basins(
+                graph,
+                &observation_sets,
+                &phenotype_states,
+            ))
+        }
+    }
+}
+
+/// Get colors for which each observation of the dataset is a (general) trap space. Observations
+/// are encoded directly as symbolic sets, and a color is removed if there is a transition
+/// leaving any of these sets.
+fn colors_where_trap_spaces(
+    dataset: &Dataset,
+    graph: &SymbolicAsyncGraph,
+) -> Result<GraphColors, String> {
+    let mut violating_colors = graph.mk_empty_colors();
+    for states in encode_dataset_bdds(dataset, None, graph)? {
+        for var in graph.variables() {
+            let leaving = graph.var_post(var, &states).minus(&states);
+            violating_colors = violating_colors.union(&leaving.colors());
+        }
+    }
+    Ok(graph.unit_colors().minus(&violating_colors))
+}
+
+/// Prepare the transition graph of a network with given variables fixed to constant values.
+///
+/// The state space is restricted to the sub-space where the perturbed variables have their
+/// fixed values, and only transitions within this sub-space are kept. Perturbed variables thus
+/// never change, and all their targets observe the constant value (effectively cutting the
+/// regulations of perturbed variables). The colors stay the same as in the original graph.
+///
+/// If there is no perturbation, the original graph is returned.
+pub fn perturb_graph<'a>(
+    graph: &'a SymbolicAsyncGraph,
+    perturbation: &[(String, bool)],
+) -> Result<Cow<'a, SymbolicAsyncGraph>, String> {
+    if perturbation.is_empty() {
+        return Ok(Cow::Borrowed(graph));
+    }
+    let ctx = graph.symbolic_context();
+    let fixed_values = perturbation
+        .iter()
+        .map(|(var_name, value)| {
+            ctx.find_network_variable(var_name)
+                .map(|var| (var, *value))
+                .ok_or(format!(
+                    "Perturbed variable `{var_name}` is not in the network."
+                ))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let sub_space = graph.mk_subspace(&fixed_values);
+    Ok(Cow::Owned(graph.restrict(&sub_space)))
+}
+
+/// Evaluate each soft constraint of given property, returning colors satisfying each of them
+/// (in the same order as the constraints).
+pub fn eval_soft_constraints(
+    prop: &ProcessedSoftConstraints,
+    graph: &SymbolicAsyncGraph,
+) -> Result<Vec<GraphColors>, String> {
+    let graph = perturb_graph(graph, &prop.perturbation)?;
+    prop.constraints
+        .iter()
+        .map(|c| model_check_colors_universal(&graph, &c.formula))
+        .collect()
 }
 
 /// Evaluate given dynamic property given the symbolic transition graph.
+///
+/// Properties regarding perturbation experiments are evaluated in the perturbed transition
+/// graph (see [perturb_graph]).
+///
+/// Soft properties do not restrict the colors on their own (all colors are returned), since
+/// they are optimized together (see [eval_soft_constraints]).
 pub fn eval_dyn_prop(
     dyn_prop: ProcessedDynProp,
     graph: &SymbolicAsyncGraph,
 ) -> Result<GraphColors, String> {
+    let graph = perturb_graph(graph, dyn_prop.perturbation())?;
+    let graph = graph.as_ref();
     match &dyn_prop {
         ProcessedDynProp::ProcessedHctlFormula(prop) => {
             // use HCTL model checking
@@ -50,9 +203,7 @@ pub fn eval_dyn_prop(
             // custom implementation (can definitely be made more efficient if needed)
 
             // get colors where all the observations are (general) trap spaces
-            let trap_space_formula =
-                encode_dataset_hctl_str(&prop.dataset, None, DataEncodingType::TrapSpace)?;
-            let mut sat_colors = model_check_colors_universal(graph, &trap_space_formula)?;
+            let mut sat_colors = colors_where_trap_spaces(&prop.dataset, graph)?;
 
             // if needed, restrict colors to only a set where the TSs are minimal or non-percolable
             if prop.minimal || prop.nonpercolable {
@@ -85,5 +236,8 @@ pub fn eval_dyn_prop(
 
             Ok(sat_colors)
         }
+        ProcessedDynProp::ProcessedStateData(prop) => eval_state_data(prop, graph),
+        ProcessedDynProp::ProcessedStateSet(prop) => eval_state_set(prop, graph),
+        ProcessedDynProp::ProcessedSoftConstraints(..) => Ok(graph.mk_unit_colors()),
     }
 }
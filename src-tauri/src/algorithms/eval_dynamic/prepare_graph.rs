@@ -40,9 +40,20 @@ pub fn prepare_graph_for_dynamic_hctl(
                 num_hctl_vars = max(num_hctl_vars, num_tree_vars);
             }
             // no need for any additional variables for attractor count property
+            ProcessedDynProp::ProcessedSoftConstraints(p) => {
+                for constraint in &p.constraints {
+                    let tree =
+                        parse_and_minimize_hctl_formula(&plain_context, &constraint.formula)?;
+                    let num_tree_vars = collect_unique_hctl_vars(tree).len();
+                    num_hctl_vars = max(num_hctl_vars, num_tree_vars);
+                }
+            }
             ProcessedDynProp::ProcessedAttrCount(..) => {}
-            // this one is handled entirely later during evaluation
+            // these are handled entirely later during evaluation (attractors need a single
+            // HCTL variable, which is always available)
             ProcessedDynProp::ProcessedTrapSpace(..) => {}
+            ProcessedDynProp::ProcessedStateData(..) => {}
+            ProcessedDynProp::ProcessedStateSet(..) => {}
         }
     }
 
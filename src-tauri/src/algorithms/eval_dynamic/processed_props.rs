@@ -1,4 +1,5 @@
 use crate::algorithms::eval_dynamic::encode::encode_dataset_hctl_str;
+use crate::sketchbook::ids::{DatasetId, ObservationId};
 use crate::sketchbook::observations::Dataset;
 use crate::sketchbook::properties::dynamic_props::DynPropertyType;
 use crate::sketchbook::Sketch;
@@ -12,11 +13,17 @@ pub enum DataEncodingType {
     TimeSeries,
 }
 
-/// Property requiring that a particular HCTL formula is satisfied.
+/// Variables (given by their names) fixed to constant values, modelling perturbations
+/// (knockouts and overexpressions) under which a property is evaluated.
+pub type Perturbation = Vec<(String, bool)>;
+
+/// Property requiring that a particular HCTL formula is satisfied (in a network with
+/// an optional `perturbation`).
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProcessedHctlFormula {
     pub id: String,
     pub formula: String,
+    pub perturbation: Perturbation,
 }
 
 /// Property requiring that observations in a particular dataset are trap spaces.
@@ -27,6 +34,40 @@ pub struct ProcessedTrapSpace {
     pub dataset: Dataset,
     pub minimal: bool,
     pub nonpercolable: bool,
+    pub perturbation: Perturbation,
+}
+
+/// Property requiring that each observation in a particular dataset contains a fixed point, or
+/// a state of an attractor (given by `encoding`, which is either `FixedPoint` or `Attractor`).
+///
+/// Unlike HCTL properties, observations are encoded directly as symbolic sets of states, which
+/// is much faster for large datasets.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcessedStateData {
+    pub id: String,
+    pub dataset: Dataset,
+    pub encoding: DataEncodingType,
+    pub perturbation: Perturbation,
+}
+
+/// Requirement on a set of states given by a dataset (see [ProcessedStateSet]).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StateSetRequirement {
+    /// Each observation must contain a state reachable from the given initial observation.
+    ReachableFrom(ObservationId),
+    /// All states must lie in basins of attractors intersecting observations of the dataset
+    /// with phenotypes.
+    InBasins(Dataset),
+}
+
+/// Property regarding all states of a particular dataset (e.g., single-cell data), evaluated by
+/// symbolic reachability. The particular requirement is given by `requirement`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcessedStateSet {
+    pub id: String,
+    pub dataset: Dataset,
+    pub requirement: StateSetRequirement,
+    pub perturbation: Perturbation,
 }
 
 /// Property requiring that the number of attractors falls into the range <minimal, maximal>.
@@ -37,12 +78,42 @@ pub struct ProcessedAttrCount {
     pub maximal: usize,
 }
 
+/// A single soft constraint, given by an HCTL formula encoding a set of `observations`.
+/// Satisfying the constraint contributes `weight` to the score of a network.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SoftConstraint {
+    pub observations: Vec<String>,
+    pub weight: u64,
+    pub formula: String,
+}
+
+impl SoftConstraint {
+    /// Label of the constraint used when reporting violations, in the form of
+    /// `property_id:observation_id` (with multiple observations separated by commas).
+    pub fn label(&self, prop_id: &str) -> String {
+        format!("{prop_id}:{}", self.observations.join(","))
+    }
+}
+
+/// Property consisting of soft constraints (one for each relevant observation), which
+/// are not required to be all satisfied. The inference maximizes the total weight of the
+/// satisfied constraints across all soft properties.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcessedSoftConstraints {
+    pub id: String,
+    pub constraints: Vec<SoftConstraint>,
+    pub perturbation: Perturbation,
+}
+
 /// Enum for processed variants of dynamic properties.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ProcessedDynProp {
     ProcessedAttrCount(ProcessedAttrCount),
     ProcessedTrapSpace(ProcessedTrapSpace),
+    ProcessedStateData(ProcessedStateData),
+    ProcessedStateSet(ProcessedStateSet),
     ProcessedHctlFormula(ProcessedHctlFormula),
+    ProcessedSoftConstraints(ProcessedSoftConstraints),
 }
 
 /// Simplified constructors for processed dynamic properties.
@@ -52,6 +123,7 @@ impl ProcessedDynProp {
         let property = ProcessedHctlFormula {
             id: id.to_string(),
             formula: formula.to_string(),
+            perturbation: Vec::new(),
         };
         ProcessedDynProp::ProcessedHctlFormula(property)
     }
@@ -69,10 +141,43 @@ impl ProcessedDynProp {
             dataset,
             minimal,
             nonpercolable,
+            perturbation: Vec::new(),
         };
         ProcessedDynProp::ProcessedTrapSpace(property)
     }
 
+    /// Create `ProcessedDynProp` instance requiring that observations of a dataset contain fixed
+    /// points or attractor states (depending on `encoding`).
+    /// To encode single observation, make a singleton dataset.
+    pub fn mk_state_data(
+        id: &str,
+        dataset: Dataset,
+        encoding: DataEncodingType,
+    ) -> ProcessedDynProp {
+        let property = ProcessedStateData {
+            id: id.to_string(),
+            dataset,
+            encoding,
+            perturbation: Vec::new(),
+        };
+        ProcessedDynProp::ProcessedStateData(property)
+    }
+
+    /// Create `ProcessedDynProp` instance with a requirement on all states of a dataset.
+    pub fn mk_state_set(
+        id: &str,
+        dataset: Dataset,
+        requirement: StateSetRequirement,
+    ) -> ProcessedDynProp {
+        let property = ProcessedStateSet {
+            id: id.to_string(),
+            dataset,
+            requirement,
+            perturbation: Vec::new(),
+        };
+        ProcessedDynProp::ProcessedStateSet(property)
+    }
+
     /// Create attractor-count `ProcessedDynProp` instance.
     pub fn mk_attr_count(id: &str, minimal: usize, maximal: usize) -> ProcessedDynProp {
         let property = ProcessedAttrCount {
@@ -83,13 +188,57 @@ impl ProcessedDynProp {
         ProcessedDynProp::ProcessedAttrCount(property)
     }
 
+    /// Create `ProcessedDynProp` instance consisting of soft constraints.
+    pub fn mk_soft_constraints(id: &str, constraints: Vec<SoftConstraint>) -> ProcessedDynProp {
+        let property = ProcessedSoftConstraints {
+            id: id.to_string(),
+            constraints,
+            perturbation: Vec::new(),
+        };
+        ProcessedDynProp::ProcessedSoftConstraints(property)
+    }
+
     /// Get ID of the underlying processed property.
     pub fn id(&self) -> &str {
         match &self {
             ProcessedDynProp::ProcessedHctlFormula(prop) => &prop.id,
             ProcessedDynProp::ProcessedAttrCount(prop) => &prop.id,
             ProcessedDynProp::ProcessedTrapSpace(prop) => &prop.id,
+            ProcessedDynProp::ProcessedStateData(prop) => &prop.id,
+            ProcessedDynProp::ProcessedStateSet(prop) => &prop.id,
+            ProcessedDynProp::ProcessedSoftConstraints(prop) => &prop.id,
+        }
+    }
+
+    /// Set the perturbation under which the property is evaluated. Attractor-count properties
+    /// are not related to any perturbation experiment, and stay unchanged.
+    pub fn with_perturbation(mut self, perturbation: Perturbation) -> ProcessedDynProp {
+        match &mut self {
+            ProcessedDynProp::ProcessedHctlFormula(prop) => prop.perturbation = perturbation,
+            ProcessedDynProp::ProcessedTrapSpace(prop) => prop.perturbation = perturbation,
+            ProcessedDynProp::ProcessedStateData(prop) => prop.perturbation = perturbation,
+            ProcessedDynProp::ProcessedStateSet(prop) => prop.perturbation = perturbation,
+            ProcessedDynProp::ProcessedSoftConstraints(prop) => prop.perturbation = perturbation,
+            ProcessedDynProp::ProcessedAttrCount(..) => {}
         }
+        self
+    }
+
+    /// Get the perturbation under which the property is evaluated (empty if there is none).
+    pub fn perturbation(&self) -> &[(String, bool)] {
+        match &self {
+            ProcessedDynProp::ProcessedHctlFormula(prop) => &prop.perturbation,
+            ProcessedDynProp::ProcessedTrapSpace(prop) => &prop.perturbation,
+            ProcessedDynProp::ProcessedStateData(prop) => &prop.perturbation,
+            ProcessedDynProp::ProcessedStateSet(prop) => &prop.perturbation,
+            ProcessedDynProp::ProcessedSoftConstraints(prop) => &prop.perturbation,
+            ProcessedDynProp::ProcessedAttrCount(..) => &[],
+        }
+    }
+
+    /// Check whether the property consists of soft constraints.
+    pub fn is_soft(&self) -> bool {
+        matches!(self, ProcessedDynProp::ProcessedSoftConstraints(..))
     }
 }
 
@@ -115,13 +264,17 @@ pub fn process_dynamic_props(sketch: &Sketch) -> Result<Vec<ProcessedDynProp>, S
             DynPropertyType::ExistsTrapSpace(prop) => {
                 let dataset_id = prop.dataset.clone().unwrap();
                 let mut dataset = sketch.observations.get_dataset(&dataset_id)?.clone();
+                if dataset.is_perturbed() && (prop.minimal || prop.nonpercolable) {
+                    return Err(format!(
+                        "Minimal or non-percolable trap spaces can not be evaluated for perturbed dataset `{dataset_id}`."
+                    ));
+                }
 
                 // if we only want to encode single observation, lets restrict the dataset
                 if let Some(obs_id) = &prop.observation {
-                    let observation = dataset.get_obs(obs_id)?.clone();
-                    let var_names = dataset.variable_names();
-                    let var_names_ref = var_names.iter().map(|v| v.as_str()).collect();
-                    dataset = Dataset::new("trap_space_data", vec![observation], var_names_ref)?;
+                    dataset = dataset
+                        .split(std::slice::from_ref(obs_id), "trap_space_data")?
+                        .1;
                 }
 
                 ProcessedDynProp::mk_trap_space(
@@ -135,29 +288,84 @@ pub fn process_dynamic_props(sketch: &Sketch) -> Result<Vec<ProcessedDynProp>, S
             DynPropertyType::GenericDynProp(prop) => {
                 ProcessedDynProp::mk_hctl(id.as_str(), prop.processed_formula.as_str())
             }
-            // encode fixed-points HCTL formula
-            DynPropertyType::ExistsFixedPoint(prop) => {
-                // TODO: if we have whole dataset, instead of using conjunction, try encoding as multiple properties
+            // soft variants encode each observation as a separate constraint
+            DynPropertyType::ExistsFixedPoint(prop) if prop.soft => {
                 let dataset_id = prop.dataset.clone().unwrap();
                 let dataset = sketch.observations.get_dataset(&dataset_id)?;
-                let formula = encode_dataset_hctl_str(
+                let constraints = encode_soft_constraints(
                     dataset,
-                    prop.observation.clone(),
+                    prop.observation.as_ref(),
                     DataEncodingType::FixedPoint,
                 )?;
-                ProcessedDynProp::mk_hctl(id.as_str(), &formula)
+                ProcessedDynProp::mk_soft_constraints(id.as_str(), constraints)
             }
-            // encode attractors with HCTL formula
-            DynPropertyType::HasAttractor(prop) => {
-                // TODO: if we have whole dataset, instead of using conjunction, try encoding as multiple properties
+            DynPropertyType::HasAttractor(prop) if prop.soft => {
                 let dataset_id = prop.dataset.clone().unwrap();
                 let dataset = sketch.observations.get_dataset(&dataset_id)?;
-                let formula = encode_dataset_hctl_str(
+                let constraints = encode_soft_constraints(
                     dataset,
-                    prop.observation.clone(),
+                    prop.observation.as_ref(),
                     DataEncodingType::Attractor,
                 )?;
-                ProcessedDynProp::mk_hctl(id.as_str(), &formula)
+                ProcessedDynProp::mk_soft_constraints(id.as_str(), constraints)
+            }
+            // the whole time series is a single constraint, weighted by all its observations
+            DynPropertyType::ExistsTrajectory(prop) if prop.soft => {
+                let dataset_id = prop.dataset.clone().unwrap();
+                let dataset = sketch.observations.get_dataset(&dataset_id)?;
+                let formula = encode_dataset_hctl_str(dataset, None, DataEncodingType::TimeSeries)?;
+                let constraint = SoftConstraint {
+                    observations: dataset
+                        .observations()
+                        .iter()
+                        .map(|o| o.get_id().to_string())
+                        .collect(),
+                    weight: dataset
+                        .observations()
+                        .iter()
+                        .map(|o| o.get_weight() as u64)
+                        .sum(),
+                    formula,
+                };
+                ProcessedDynProp::mk_soft_constraints(id.as_str(), vec![constraint])
+            }
+            // fixed-points are evaluated directly on symbolic sets of observations
+            DynPropertyType::ExistsFixedPoint(prop) => {
+                let dataset_id = prop.dataset.clone().unwrap();
+                let mut dataset = sketch.observations.get_dataset(&dataset_id)?.clone();
+                if let Some(obs_id) = &prop.observation {
+                    dataset = dataset
+                        .split(std::slice::from_ref(obs_id), "fixed_point_data")?
+                        .1;
+                }
+                ProcessedDynProp::mk_state_data(id.as_str(), dataset, DataEncodingType::FixedPoint)
+            }
+            // attractors are evaluated with HCTL, but observations are encoded directly as symbolic sets
+            DynPropertyType::HasAttractor(prop) => {
+                let dataset_id = prop.dataset.clone().unwrap();
+                let mut dataset = sketch.observations.get_dataset(&dataset_id)?.clone();
+                if let Some(obs_id) = &prop.observation {
+                    dataset = dataset
+                        .split(std::slice::from_ref(obs_id), "attractor_data")?
+                        .1;
+                }
+                ProcessedDynProp::mk_state_data(id.as_str(), dataset, DataEncodingType::Attractor)
+            }
+            // single-cell style properties are evaluated by symbolic reachability
+            DynPropertyType::StatesReachable(prop) => {
+                let dataset_id = prop.dataset.clone().unwrap();
+                let dataset = sketch.observations.get_dataset(&dataset_id)?.clone();
+                let initial_obs = prop.initial_observation.clone().unwrap();
+                let requirement = StateSetRequirement::ReachableFrom(initial_obs);
+                ProcessedDynProp::mk_state_set(id.as_str(), dataset, requirement)
+            }
+            DynPropertyType::StatesInBasins(prop) => {
+                let dataset_id = prop.dataset.clone().unwrap();
+                let dataset = sketch.observations.get_dataset(&dataset_id)?.clone();
+                let phenotypes_id = prop.phenotypes.clone().unwrap();
+                let phenotypes = sketch.observations.get_dataset(&phenotypes_id)?.clone();
+                let requirement = StateSetRequirement::InBasins(phenotypes);
+                ProcessedDynProp::mk_state_set(id.as_str(), dataset, requirement)
             }
             // encode time series with HCTL formula
             DynPropertyType::ExistsTrajectory(prop) => {
@@ -167,8 +375,57 @@ pub fn process_dynamic_props(sketch: &Sketch) -> Result<Vec<ProcessedDynProp>, S
                 ProcessedDynProp::mk_hctl(id.as_str(), &formula)
             }
         };
-        processed_props.push(dyn_prop_processed);
+
+        // properties regarding perturbation experiments are evaluated in the perturbed network
+        if let Some(dataset_id) = referenced_dataset(dyn_prop.get_prop_data()) {
+            let dataset = sketch.observations.get_dataset(dataset_id)?;
+            let perturbation = dataset
+                .perturbations()
+                .iter()
+                .map(|(var, p)| (var.to_string(), p.fixed_value()))
+                .collect();
+            processed_props.push(dyn_prop_processed.with_perturbation(perturbation));
+        } else {
+            processed_props.push(dyn_prop_processed);
+        }
     }
 
     Ok(processed_props)
 }
+
+/// **(internal)** Get ID of a dataset referenced by a dynamic property (if there is any).
+fn referenced_dataset(prop: &DynPropertyType) -> Option<&DatasetId> {
+    match prop {
+        DynPropertyType::ExistsFixedPoint(p) => p.dataset.as_ref(),
+        DynPropertyType::ExistsTrapSpace(p) => p.dataset.as_ref(),
+        DynPropertyType::ExistsTrajectory(p) => p.dataset.as_ref(),
+        DynPropertyType::HasAttractor(p) => p.dataset.as_ref(),
+        DynPropertyType::StatesReachable(p) => p.dataset.as_ref(),
+        DynPropertyType::StatesInBasins(p) => p.dataset.as_ref(),
+        DynPropertyType::AttractorCount(..) | DynPropertyType::GenericDynProp(..) => None,
+    }
+}
+
+/// **(internal)** Encode observations of a dataset as soft constraints, one constraint for each
+/// observation. If `observation` is specified, only this single observation is considered.
+fn encode_soft_constraints(
+    dataset: &Dataset,
+    observation: Option<&ObservationId>,
+    encoding_type: DataEncodingType,
+) -> Result<Vec<SoftConstraint>, String> {
+    let observations = match observation {
+        Some(obs_id) => vec![dataset.get_obs(obs_id)?],
+        None => dataset.observations().iter().collect(),
+    };
+    let mut constraints = Vec::new();
+    for obs in observations {
+        let obs_id = obs.get_id().clone();
+        let formula = encode_dataset_hctl_str(dataset, Some(obs_id.clone()), encoding_type)?;
+        constraints.push(SoftConstraint {
+            observations: vec![obs_id.to_string()],
+            weight: obs.get_weight() as u64,
+            formula,
+        });
+    }
+    Ok(constraints)
+}
@@ -1,9 +1,17 @@
+use crate::algorithms::eval_static::prepare_graph::{
+    count_fol_vars, get_fol_extended_symbolic_graph,
+};
 use crate::algorithms::eval_static::processed_props::ProcessedStatProp;
 use crate::algorithms::fo_logic::eval_wrappers::eval_formula_dirty;
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 
-/// Evaluate given static property.
+/// Evaluate given static property on a `graph` that must already support all symbolic
+/// variables needed for its FOL formula (see [prepare_graph_for_static_fol])
+///
+/// [prepare_graph_for_static_fol]: crate::algorithms::eval_static::prepare_graph::prepare_graph_for_static_fol.
 ///
 /// Currently, we assume that all types of template properties must be already translated
 /// to FOL generic properties.
@@ -19,3 +27,55 @@ pub fn eval_static_prop(
     let results = eval_formula_dirty(&formula, graph, base_var_name)?;
     Ok(results.colors().intersect(&initial_unit_colors))
 }
+
+/// Cache of symbolic graphs used to evaluate static properties in their own minimal contexts.
+///
+/// Properties that need the same number of FOL variables share the same context, so that we
+/// do not have to build the symbolic graph repeatedly for each property. All properties
+/// evaluated with the same cache must regard the same Boolean network.
+#[derive(Default)]
+pub struct StaticPropContexts {
+    graphs: HashMap<usize, SymbolicAsyncGraph>,
+}
+
+/// Evaluate given static property in its own minimal symbolic context.
+///
+/// The `graph` does not need to support any additional symbolic variables. We use a symbolic
+/// graph with exactly as many extra variables as the property's FOL formula needs (taking it
+/// from the `contexts` cache, or building a new one), evaluate the formula there, and transfer
+/// the resulting colors back to the context of the original `graph` (restricting them to its
+/// unit colors).
+///
+/// The unit colors are intentionally not transferred into the new context, as the transfer is
+/// often more expensive than the evaluation itself.
+///
+/// Currently, we assume that all types of template properties must be already translated
+/// to FOL generic properties.
+pub fn eval_static_prop_in_own_context(
+    static_prop: ProcessedStatProp,
+    graph: &SymbolicAsyncGraph,
+    base_var_name: &str,
+    contexts: &mut StaticPropContexts,
+) -> Result<GraphColors, String> {
+    let bn = graph
+        .as_network()
+        .ok_or("Internal error: symbolic graph has no network.".to_string())?;
+    let num_fol_vars = count_fol_vars(&static_prop, base_var_name)?;
+    let prop_graph = match contexts.graphs.entry(num_fol_vars) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => entry.insert(get_fol_extended_symbolic_graph(
+            bn,
+            num_fol_vars as u16,
+            base_var_name,
+            None,
+        )?),
+    };
+    let prop_colors = eval_static_prop(static_prop, prop_graph, base_var_name)?;
+
+    let context = graph.symbolic_context();
+    let result_bdd = context
+        .transfer_from(prop_colors.as_bdd(), prop_graph.symbolic_context())
+        .ok_or("Internal error during BDD transfer from one context to another.".to_string())?;
+    let result_colors = GraphColors::new(result_bdd, context);
+    Ok(result_colors.intersect(&graph.mk_unit_colors()))
+}
@@ -6,3 +6,7 @@ pub mod eval;
 pub mod prepare_graph;
 /// Processed variants of static properties for evaluation.
 pub mod processed_props;
+/// Find static properties that are tautologies, contradictions, or redundant.
+pub mod property_lint;
+/// Check that update functions agree with declared types of regulations.
+pub mod regulation_check;
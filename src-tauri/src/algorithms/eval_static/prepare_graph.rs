@@ -1,5 +1,5 @@
 use crate::algorithms::eval_static::processed_props::ProcessedStatProp;
-use crate::algorithms::fo_logic::parser::parse_and_minimize_fol_formula;
+use crate::algorithms::fo_logic::eval_wrappers::parse_and_optimize_fol_formula;
 use crate::algorithms::fo_logic::utils::collect_unique_fol_vars;
 use biodivine_lib_bdd::Bdd;
 use biodivine_lib_param_bn::symbolic_async_graph::{SymbolicAsyncGraph, SymbolicContext};
@@ -8,7 +8,7 @@ use std::cmp::max;
 use std::collections::HashMap;
 
 /// Prepare the symbolic context and generate the symbolic transition graph for
-/// evaluation of the static properties.
+/// evaluation of all the static properties at once (in a single shared context).
 ///
 /// Since all the static properties are encoded as FOL properties, we just need to
 /// handle this one case. This means we need to prepare symbolic variables to cover
@@ -25,17 +25,23 @@ pub fn prepare_graph_for_static_fol(
     // we now assume all properties are already encoded into generic FOL properties
 
     let mut num_fol_vars: usize = 0;
-    //let plain_context = SymbolicContext::new(&bn).unwrap();
     for prop in static_props {
-        let formula = &prop.formula;
-        let tree = parse_and_minimize_fol_formula(formula, base_var_name)?;
-        let num_tree_vars = collect_unique_fol_vars(&tree).len();
-        num_fol_vars = max(num_fol_vars, num_tree_vars);
+        num_fol_vars = max(num_fol_vars, count_fol_vars(prop, base_var_name)?);
     }
 
     get_fol_extended_symbolic_graph(bn, num_fol_vars as u16, base_var_name, unit)
 }
 
+/// Compute the number of symbolic variables needed to evaluate FOL formula of the given
+/// static property.
+pub fn count_fol_vars(
+    static_prop: &ProcessedStatProp,
+    base_var_name: &str,
+) -> Result<usize, String> {
+    let tree = parse_and_optimize_fol_formula(&static_prop.formula, base_var_name)?;
+    Ok(collect_unique_fol_vars(&tree).len())
+}
+
 /// Prepare the symbolic context and generate the symbolic transition graph for
 /// evaluation of FOL formulas. This means we need to prepare symbolic variables to
 /// cover all variables in FOL formulas.
@@ -110,7 +116,7 @@ mod tests {
         assert_eq!(graph_fol_expected.unit_colors(), graph_fol.unit_colors());
 
         // test deriving FOL context automatically from property
-        let fol_prop = ProcessedStatProp::mk_fol("doesntmatter", "3 x: true");
+        let fol_prop = ProcessedStatProp::mk_fol("doesntmatter", "3 x: x");
         let property_list = vec![fol_prop];
         let graph_fol = prepare_graph_for_static_fol(&bn, &property_list, "a", None).unwrap();
         assert_eq!(graph_fol_expected.unit_colors(), graph_fol.unit_colors());
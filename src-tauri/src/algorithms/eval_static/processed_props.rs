@@ -33,10 +33,21 @@ impl ProcessedStatProp {
 /// Process static properties from a sketch, converting them into one of the supported
 /// `ProcessedStatProp` variants. Currently, all properties are encoded into FOL, but we
 /// might support some other preprocessing in future.
+///
+/// All references to named FOL macros of the sketch are expanded in the resulting formulas.
 pub fn process_static_props(
     sketch: &Sketch,
     bn: &BooleanNetwork,
 ) -> Result<Vec<ProcessedStatProp>, String> {
+    // macros are referenced the same way as function symbols, so their names must not clash
+    for (macro_id, _) in sketch.properties.fol_macros() {
+        if bn.find_parameter(macro_id.as_str()).is_some() {
+            return Err(format!(
+                "Macro `{macro_id}` has the same name as a function symbol of the model."
+            ));
+        }
+    }
+
     let mut static_props = sketch.properties.stat_props().collect::<Vec<_>>();
     // sort properties by IDs for deterministic computation times (and get rid of the IDs)
     static_props.sort_by(|(a_id, _), (b_id, _)| a_id.cmp(b_id));
@@ -111,7 +122,13 @@ pub fn process_static_props(
                 ProcessedStatProp::mk_fol(id.as_str(), &formula)
             }
         };
-        processed_props.push(stat_prop_processed)
+
+        // expand references to named FOL macros (formula is kept as is if there are none)
+        let formula = sketch
+            .properties
+            .expand_fol_macros(&stat_prop_processed.formula)
+            .map_err(|e| format!("Error processing static property `{id}`: {e}"))?;
+        processed_props.push(ProcessedStatProp::mk_fol(id.as_str(), &formula))
     }
 
     Ok(processed_props)
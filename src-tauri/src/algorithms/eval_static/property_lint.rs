@@ -0,0 +1,163 @@
+use crate::algorithms::eval_static::eval::{eval_static_prop_in_own_context, StaticPropContexts};
+use crate::algorithms::eval_static::processed_props::process_static_props;
+use crate::inference::inference_solver::InferenceSolver;
+use crate::sketchbook::properties::static_props::StatPropertyType;
+use crate::sketchbook::Sketch;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+
+/// A static property that is vacuous (a tautology), trivially unsatisfiable (a contradiction),
+/// or redundant (implied by other static properties).
+///
+/// If `prop_id` is `None`, the issue regards the static properties as a whole.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatPropLint {
+    pub prop_id: Option<String>,
+    pub message: String,
+}
+
+impl StatPropLint {
+    /// **(internal)** Create new lint for given property and message.
+    fn new(prop_id: Option<&str>, message: &str) -> StatPropLint {
+        StatPropLint {
+            prop_id: prop_id.map(|id| id.to_string()),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Check static properties of the sketch for tautologies, contradictions, and redundancies.
+///
+/// Each property is evaluated symbolically (over all instantiations of the model's function
+/// symbols). A property satisfied by all instantiations is a tautology, a property satisfied
+/// by none is a contradiction. Remaining properties are then checked for redundancy one by
+/// one (sorted by IDs), i.e., whether they are implied by the conjunction of other remaining
+/// properties. Redundant properties are removed from further checks, so that removing all
+/// reported properties would not change the results.
+///
+/// Properties automatically generated from the regulation graph are never reported, but they
+/// are still used as constraints when checking redundancy of other properties. Conflicts of
+/// these properties with update functions are reported by a separate check (and if they are
+/// unsatisfiable, redundancy is not checked).
+///
+/// All static properties must be valid (see [Sketch::run_consistency_check]).
+pub fn lint_static_props(sketch: &Sketch) -> Result<Vec<StatPropLint>, String> {
+    if sketch.properties.num_stat_properties() == 0 || sketch.model.num_vars() == 0 {
+        return Ok(Vec::new());
+    }
+
+    let bn = InferenceSolver::extract_bn(sketch)?;
+    let static_props = process_static_props(sketch, &bn)?;
+    let base_var = bn.variables().next().unwrap();
+    let base_var_name = bn.get_variable_name(base_var).clone();
+    let graph = SymbolicAsyncGraph::new(&bn)?;
+    let unit = graph.mk_unit_colors();
+
+    let mut contexts = StaticPropContexts::default();
+    let mut lints = Vec::new();
+    // conjunction of all properties generated from the regulation graph
+    let mut generated_colors = unit.clone();
+    // other properties (their IDs and colors) that are neither tautologies nor contradictions
+    let mut remaining: Vec<(String, GraphColors)> = Vec::new();
+    for prop in static_props {
+        let prop_id = prop.id().to_string();
+        let colors = eval_static_prop_in_own_context(prop, &graph, &base_var_name, &mut contexts)?;
+
+        if is_generated_prop(sketch, &prop_id) {
+            generated_colors = generated_colors.intersect(&colors);
+        } else if colors.is_empty() {
+            let msg = "Property is a contradiction, it can't be satisfied by any network.";
+            lints.push(StatPropLint::new(Some(&prop_id), msg));
+        } else if unit.is_subset(&colors) {
+            let msg = "Property is a tautology, it is satisfied by all networks.";
+            lints.push(StatPropLint::new(Some(&prop_id), msg));
+        } else {
+            remaining.push((prop_id, colors));
+        }
+    }
+
+    // conflicts between generated properties are reported by a separate check
+    if generated_colors.is_empty() {
+        return Ok(lints);
+    }
+    let all_colors = remaining
+        .iter()
+        .fold(generated_colors.clone(), |acc, (_, colors)| {
+            acc.intersect(colors)
+        });
+    if all_colors.is_empty() {
+        // everything is implied by an unsatisfiable set, redundancy makes no sense here
+        let msg = "Static properties are jointly unsatisfiable.";
+        lints.push(StatPropLint::new(None, msg));
+        return Ok(lints);
+    }
+
+    let mut position = 0;
+    while position < remaining.len() {
+        let others = remaining
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != position)
+            .fold(generated_colors.clone(), |acc, (_, (_, colors))| {
+                acc.intersect(colors)
+            });
+        if others.is_subset(&remaining[position].1) {
+            let (prop_id, _) = remaining.remove(position);
+            let msg = "Property is redundant, it is implied by other static properties.";
+            lints.push(StatPropLint::new(Some(&prop_id), msg));
+        } else {
+            position += 1;
+        }
+    }
+    Ok(lints)
+}
+
+/// **(internal)** Check whether a static property was generated from the regulation graph.
+fn is_generated_prop(sketch: &Sketch, prop_id: &str) -> bool {
+    let prop = sketch
+        .properties
+        .get_stat_prop_id(prop_id)
+        .and_then(|id| sketch.properties.get_stat_prop(&id).cloned());
+    matches!(
+        prop.map(|p| p.get_prop_data().clone()),
+        Ok(StatPropertyType::RegulationEssential(_)) | Ok(StatPropertyType::RegulationMonotonic(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::eval_static::property_lint::lint_static_props;
+    use crate::sketchbook::properties::StatProperty;
+    use crate::sketchbook::Sketch;
+
+    /// Add a generic static property with given ID and formula to the sketch.
+    fn add_static(sketch: &mut Sketch, id: &str, formula: &str) {
+        let prop = StatProperty::try_mk_generic(id, formula, "").unwrap();
+        sketch.properties.add_static_by_str(id, prop).unwrap();
+    }
+
+    #[test]
+    /// Test that tautologies, contradictions and redundant properties are found.
+    fn static_prop_lints() {
+        let mut sketch = Sketch::from_aeon("A ->? B\nB -| A\n$B: f(A)").unwrap();
+        assert!(lint_static_props(&sketch).unwrap().is_empty());
+
+        add_static(&mut sketch, "p1_taut", "f(1) | !f(1)");
+        add_static(&mut sketch, "p2_contr", "f(1) & !f(1)");
+        add_static(&mut sketch, "p3_a", "f(1)");
+        add_static(&mut sketch, "p4_b", "f(0) => f(1)");
+        add_static(&mut sketch, "p5_c", "!f(0)");
+        let lints = lint_static_props(&sketch).unwrap();
+        let ids: Vec<_> = lints.iter().map(|l| l.prop_id.clone().unwrap()).collect();
+        assert_eq!(ids, vec!["p1_taut", "p2_contr", "p4_b"]);
+        assert!(lints[0].message.contains("tautology"));
+        assert!(lints[1].message.contains("contradiction"));
+        assert!(lints[2].message.contains("redundant"));
+
+        // jointly unsatisfiable properties
+        add_static(&mut sketch, "p6_d", "f(0)");
+        let lints = lint_static_props(&sketch).unwrap();
+        assert_eq!(lints.len(), 3);
+        assert!(lints[2].prop_id.is_none());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::algorithms::eval_static::encode::*;
+use crate::algorithms::eval_static::eval::{eval_static_prop_in_own_context, StaticPropContexts};
+use crate::algorithms::eval_static::processed_props::ProcessedStatProp;
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity, Regulation};
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use biodivine_lib_param_bn::BooleanNetwork;
+
+/// A regulation whose declared monotonicity or essentiality can't be satisfied by the
+/// (fully or partially specified) update function of its target variable.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegulationConflict {
+    pub regulator: VarId,
+    pub target: VarId,
+    /// Description of the declared (expected) and actual (admitted) types of the regulation.
+    pub message: String,
+}
+
+/// Check that the update functions of the model agree with the declared monotonicity and
+/// essentiality of regulations.
+///
+/// Only regulations whose target has a (fully or partially) specified update function
+/// are checked. For each such regulation, we evaluate (symbolically, over all
+/// instantiations of the uninterpreted functions satisfying their own constraints) whether
+/// its declared sign and essentiality are satisfiable. Regulations that can't be satisfied
+/// are returned, together with the monotonicity/essentiality that the function admits.
+pub fn find_regulation_conflicts(model: &ModelState) -> Result<Vec<RegulationConflict>, String> {
+    let mut regulations: Vec<&Regulation> = model
+        .regulations()
+        .filter(|r| {
+            let has_update = model
+                .get_update_fn(r.get_target())
+                .is_ok_and(|f| !f.is_unspecified());
+            let is_constrained = *r.get_sign() != Monotonicity::Unknown
+                || *r.get_essentiality() != Essentiality::Unknown;
+            has_update && is_constrained
+        })
+        .collect();
+    if regulations.is_empty() {
+        return Ok(Vec::new());
+    }
+    // sort regulations for deterministic results
+    regulations.sort_by_key(|r| (r.get_target().clone(), r.get_regulator().clone()));
+
+    let bn = model.to_bn_with_plain_regulations(None);
+    let base_var = bn.variables().next().unwrap();
+    let base_var_name = bn.get_variable_name(base_var).clone();
+    // each formula is evaluated in its own minimal context, and the results are transferred to
+    // the context of this basic graph (without any additional symbolic variables)
+    let graph = SymbolicAsyncGraph::new(&bn)?;
+    let mut contexts = StaticPropContexts::default();
+    let mut eval_colors = |formula: &str| -> Result<GraphColors, String> {
+        let prop = ProcessedStatProp::mk_fol("", formula);
+        eval_static_prop_in_own_context(prop, &graph, &base_var_name, &mut contexts)
+    };
+
+    // only consider instantiations of uninterpreted fns that satisfy their declared properties
+    let mut unit = graph.mk_unit_colors();
+    for formula in encode_fn_constraints(model) {
+        unit = unit.intersect(&eval_colors(&formula)?);
+    }
+    if unit.is_empty() {
+        // properties of uninterpreted functions are contradictory, nothing to check here
+        return Ok(Vec::new());
+    }
+
+    let mut eval = |formula: &str| -> Result<GraphColors, String> {
+        Ok(eval_colors(formula)?.intersect(&unit))
+    };
+    let mut conflicts = Vec::new();
+    for regulation in regulations {
+        let target = regulation.get_target();
+        let declared_sign = *regulation.get_sign();
+        let declared_essentiality = *regulation.get_essentiality();
+
+        let sign_ok = eval(&encode_sign(regulation, declared_sign, &bn))?;
+        let essentiality_ok = eval(&encode_essential(regulation, declared_essentiality, &bn))?;
+
+        let message = if sign_ok.is_empty() {
+            // find out which signs are admitted by the function instead
+            let mut admitted = Vec::new();
+            let signs = [
+                Monotonicity::Activation,
+                Monotonicity::Inhibition,
+                Monotonicity::Dual,
+            ];
+            for sign in signs {
+                if !eval(&encode_sign(regulation, sign, &bn))?.is_empty() {
+                    admitted.push(sign.as_str_full().to_string());
+                }
+            }
+            let admitted = admitted.join(", ");
+            Some(format!(
+                "Regulation `{regulation}` is declared as `{}`, but the update function of `{target}` only admits: {admitted}.",
+                declared_sign.as_str_full()
+            ))
+        } else if essentiality_ok.is_empty() {
+            // the only other option is the opposite essentiality
+            let admitted = match declared_essentiality {
+                Essentiality::True => essentiality_str(Essentiality::False),
+                _ => essentiality_str(Essentiality::True),
+            };
+            Some(format!(
+                "Regulation `{regulation}` is declared as `{}`, but the update function of `{target}` only admits: {admitted}.",
+                essentiality_str(declared_essentiality)
+            ))
+        } else if sign_ok.intersect(&essentiality_ok).is_empty() {
+            Some(format!(
+                "Regulation `{regulation}` can't be both `{}` and `{}` in the update function of `{target}`.",
+                declared_sign.as_str_full(),
+                essentiality_str(declared_essentiality)
+            ))
+        } else {
+            None
+        };
+
+        if let Some(message) = message {
+            conflicts.push(RegulationConflict {
+                regulator: regulation.get_regulator().clone(),
+                target: target.clone(),
+                message,
+            });
+        }
+    }
+    Ok(conflicts)
+}
+
+/// **(internal)** Encode declared monotonicity and essentiality of all arguments of all
+/// uninterpreted functions into FOL formulas.
+fn encode_fn_constraints(model: &ModelState) -> Vec<String> {
+    let mut formulas = Vec::new();
+    for (fn_id, uninterpreted_fn) in model.uninterpreted_fns() {
+        let arity = uninterpreted_fn.get_arity();
+        for index in 0..arity {
+            let monotonicity = *uninterpreted_fn.get_monotonic(index);
+            let essentiality = *uninterpreted_fn.get_essential(index);
+            formulas.push(encode_monotonicity(
+                arity,
+                index,
+                fn_id.as_str(),
+                monotonicity,
+            ));
+            formulas.push(encode_essentiality(
+                arity,
+                index,
+                fn_id.as_str(),
+                essentiality,
+            ));
+        }
+    }
+    formulas
+}
+
+/// **(internal)** Encode that the regulation has given monotonicity.
+fn encode_sign(regulation: &Regulation, sign: Monotonicity, bn: &BooleanNetwork) -> String {
+    let regulator = regulation.get_regulator().as_str();
+    let target = regulation.get_target().as_str();
+    encode_regulation_monotonicity(regulator, target, sign, bn)
+}
+
+/// **(internal)** Encode that the regulation has given essentiality.
+fn encode_essential(regulation: &Regulation, value: Essentiality, bn: &BooleanNetwork) -> String {
+    let regulator = regulation.get_regulator().as_str();
+    let target = regulation.get_target().as_str();
+    encode_regulation_essentiality(regulator, target, value, bn)
+}
+
+/// **(internal)** Human-readable description of an essentiality value.
+fn essentiality_str(essentiality: Essentiality) -> &'static str {
+    match essentiality {
+        Essentiality::True => "Essential",
+        Essentiality::False => "Non-essential",
+        Essentiality::Unknown => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::eval_static::regulation_check::find_regulation_conflicts;
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::ModelState;
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    /// Load a model from a string in aeon format.
+    fn load_model(aeon_str: &str) -> ModelState {
+        let bn = BooleanNetwork::try_from(aeon_str).unwrap();
+        ModelState::from_bn(&bn).unwrap()
+    }
+
+    #[test]
+    /// Test that update functions agreeing with regulations produce no conflicts.
+    fn no_regulation_conflicts() {
+        let model = load_model("A -> B\nC -| B\nB -> A\nA -?? C\n$B: A & !C\n$A: f(B)\n$C: A");
+        assert!(find_regulation_conflicts(&model).unwrap().is_empty());
+    }
+
+    #[test]
+    /// Test that update functions violating regulation signs/essentiality are reported.
+    fn regulation_conflicts() {
+        // activation written as inhibition, and an essential regulation that has no effect
+        let model = load_model("A -> B\nC -> B\nB -> A\n$B: !A & (C | !C)\n$A: B");
+        let conflicts = find_regulation_conflicts(&model).unwrap();
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].regulator, VarId::new("A").unwrap());
+        assert!(conflicts[0].message.contains("`A -> B`"));
+        assert!(conflicts[0].message.contains("only admits: Inhibition"));
+        assert_eq!(conflicts[1].regulator, VarId::new("C").unwrap());
+        assert!(conflicts[1].message.contains("only admits: Non-essential"));
+
+        // partially specified function where `A` can't be both inhibition and essential
+        let model = load_model("A -| B\nB -> B\nB -> A\n$B: A | f(B)\n$A: B");
+        let conflicts = find_regulation_conflicts(&model).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].message.contains("can't be both"));
+        assert!(conflicts[0].message.contains("`A -| B`"));
+    }
+}
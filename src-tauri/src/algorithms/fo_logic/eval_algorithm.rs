@@ -1,4 +1,5 @@
 use crate::algorithms::fo_logic::fol_tree::{FolTreeNode, NodeType};
+use crate::algorithms::fo_logic::macro_expansion::collect_all_var_names;
 use crate::algorithms::fo_logic::operator_enums::*;
 use crate::algorithms::fo_logic::utils::{get_var_base_and_offset, get_var_from_implicit};
 use biodivine_lib_bdd::Bdd;
@@ -27,10 +28,24 @@ pub fn eval_node(node: FolTreeNode, graph: &SymbolicAsyncGraph) -> GraphColoredV
                 BinaryOp::Iff => eval_equiv(graph, &left, &right),
             }
         }
-        NodeType::Quantifier(op, var_name, child) => match op {
-            Quantifier::Exists => eval_exists(graph, &eval_node(*child, graph), &var_name),
-            Quantifier::Forall => eval_forall(graph, &eval_node(*child, graph), &var_name),
-        },
+        NodeType::Quantifier(op, var_name, child) => {
+            // a whole block of directly nested quantifiers of the same type is eliminated at once
+            // (using a single BDD projection, instead of projecting the variables one by one)
+            let mut var_names = vec![var_name];
+            let mut inner = *child;
+            while let NodeType::Quantifier(inner_op, inner_var, inner_child) = &inner.node_type {
+                if *inner_op != op {
+                    break;
+                }
+                var_names.push(inner_var.clone());
+                let next = inner_child.as_ref().clone();
+                inner = next;
+            }
+            match op {
+                Quantifier::Exists => eval_exists(graph, &eval_node(inner, graph), &var_names),
+                Quantifier::Forall => eval_forall(graph, &eval_node(inner, graph), &var_names),
+            }
+        }
         NodeType::Function(fn_symbol, arguments) => {
             let name = fn_symbol.name;
             let arguments = arguments.into_iter().map(|a| *a).collect();
@@ -155,32 +170,208 @@ fn eval_applied_update_function(
     }
 }
 
-/// Evaluate existential quantifier.
+/// Evaluate existential quantification over all given variables.
 fn eval_exists(
     graph: &SymbolicAsyncGraph,
     set: &GraphColoredVertices,
-    var_name: &str,
+    var_names: &[String],
 ) -> GraphColoredVertices {
     let bn = graph.as_network().unwrap();
 
-    // we must get the correct "extra" BDD variable from the name of the variable
-    let (base_var_name, offset) = get_var_base_and_offset(var_name).unwrap();
-    let variable = bn.as_graph().find_variable(&base_var_name).unwrap();
-    let bbd_var = graph
-        .symbolic_context()
-        .get_extra_state_variable(variable, offset);
+    // we must get the correct "extra" BDD variables from the names of the variables
+    let bdd_vars: Vec<_> = var_names
+        .iter()
+        .map(|var_name| {
+            let (base_var_name, offset) = get_var_base_and_offset(var_name).unwrap();
+            let variable = bn.as_graph().find_variable(&base_var_name).unwrap();
+            graph
+                .symbolic_context()
+                .get_extra_state_variable(variable, offset)
+        })
+        .collect();
 
-    let bbd_var_singleton = vec![bbd_var];
-    let result_bdd = set.as_bdd().exists(&bbd_var_singleton);
+    let result_bdd = set.as_bdd().exists(&bdd_vars);
     // after projection we do not need to intersect with unit bdd
     GraphColoredVertices::new(result_bdd, graph.symbolic_context())
 }
 
-/// Evaluate universal quantifier.
+/// Evaluate universal quantification over all given variables.
 fn eval_forall(
     graph: &SymbolicAsyncGraph,
     set: &GraphColoredVertices,
-    var_name: &str,
+    var_names: &[String],
 ) -> GraphColoredVertices {
-    eval_neg(graph, &eval_exists(graph, &eval_neg(graph, set), var_name))
+    eval_neg(graph, &eval_exists(graph, &eval_neg(graph, set), var_names))
+}
+
+/// Push all quantifiers of the formula as deep into its syntax tree as possible (also known
+/// as "miniscoping"). This is a heuristic that makes the quantifier elimination cheaper, since
+/// the variables are projected out of smaller BDDs. It often also decreases the maximal depth
+/// of nested quantifiers, and thus the number of symbolic variables needed for evaluation.
+///
+/// The formula must already be validated, i.e., each variable is quantified at most once on
+/// each path of the tree and there are no free variables (see [validate_and_rename_vars]).
+/// The result is equivalent, but its variables should be renamed again to minimize their number.
+///
+/// The following rules are used (and their duals for universal quantifiers):
+///  - `3 x: A` is `A` if `x` does not occur in `A`,
+///  - `3 x: (A & B)` is `A & (3 x: B)` if `x` does not occur in `A` (same for `|`, `=>`),
+///  - `3 x: (A | B)` is `(3 x: A) | (3 x: B)`, and `3 x: (A => B)` is `(V x: A) => (3 x: B)`,
+///  - `3 x: !A` is `!(V x: A)`,
+///  - `3 x: 3 y: A` is `3 y: 3 x: A` (whichever variable can be pushed deeper).
+///
+/// Quantifiers are only pushed through negations and other quantifiers if it helps to push them
+/// further down.
+///
+/// [validate_and_rename_vars]: crate::algorithms::fo_logic::utils::validate_and_rename_vars
+pub fn push_quantifiers_inward(node: FolTreeNode) -> FolTreeNode {
+    match node.node_type {
+        NodeType::Terminal(_) => node,
+        NodeType::Unary(op, child) => FolTreeNode::mk_unary(push_quantifiers_inward(*child), op),
+        NodeType::Binary(op, left, right) => FolTreeNode::mk_binary(
+            push_quantifiers_inward(*left),
+            push_quantifiers_inward(*right),
+            op,
+        ),
+        NodeType::Quantifier(op, var_name, child) => {
+            push_quantifier(push_quantifiers_inward(*child), &var_name, op)
+        }
+        NodeType::Function(fn_symbol, arguments) => {
+            let arguments = arguments
+                .into_iter()
+                .map(|a| push_quantifiers_inward(*a))
+                .collect();
+            FolTreeNode::mk_function(&fn_symbol.name, arguments, fn_symbol.is_update_fn)
+        }
+    }
+}
+
+/// **(internal)** Push a single quantifier `op` over variable `var_name` into the `body`
+/// sub-formula, as deep as possible. The `body` itself is expected to be already processed.
+fn push_quantifier(body: FolTreeNode, var_name: &str, op: Quantifier) -> FolTreeNode {
+    let occurs_in = |node: &FolTreeNode| collect_all_var_names(node).contains(var_name);
+    if !occurs_in(&body) {
+        return body;
+    }
+
+    let pushed = match &body.node_type {
+        NodeType::Unary(UnaryOp::Not, child) => {
+            let inner = push_quantifier(child.as_ref().clone(), var_name, dual(op));
+            if is_quantified_by(&inner, var_name, dual(op)) {
+                None
+            } else {
+                Some(FolTreeNode::mk_unary(inner, UnaryOp::Not))
+            }
+        }
+        NodeType::Binary(bin_op, left, right)
+            if matches!(bin_op, BinaryOp::And | BinaryOp::Or | BinaryOp::Imp) =>
+        {
+            // left operand of implication is negated (`A => B` is `!A | B`)
+            let left_op = if *bin_op == BinaryOp::Imp {
+                dual(op)
+            } else {
+                op
+            };
+            let distributes = matches!(
+                (op, bin_op),
+                (Quantifier::Exists, BinaryOp::Or | BinaryOp::Imp)
+                    | (Quantifier::Forall, BinaryOp::And)
+            );
+            let (left, right) = (left.as_ref().clone(), right.as_ref().clone());
+            if !occurs_in(&left) {
+                let right = push_quantifier(right, var_name, op);
+                Some(FolTreeNode::mk_binary(left, right, *bin_op))
+            } else if !occurs_in(&right) {
+                let left = push_quantifier(left, var_name, left_op);
+                Some(FolTreeNode::mk_binary(left, right, *bin_op))
+            } else if distributes {
+                let left = push_quantifier(left, var_name, left_op);
+                let right = push_quantifier(right, var_name, op);
+                Some(FolTreeNode::mk_binary(left, right, *bin_op))
+            } else {
+                None
+            }
+        }
+        NodeType::Quantifier(inner_op, inner_var, inner_child) if *inner_op == op => {
+            // quantifiers of the same type commute, we can try to push ours below the inner one
+            let inner = push_quantifier(inner_child.as_ref().clone(), var_name, op);
+            if is_quantified_by(&inner, var_name, op) {
+                None
+            } else {
+                Some(FolTreeNode::mk_quantifier(inner, inner_var, op))
+            }
+        }
+        _ => None,
+    };
+    pushed.unwrap_or_else(|| FolTreeNode::mk_quantifier(body, var_name, op))
+}
+
+/// **(internal)** Check whether the root of the tree is a given quantifier over given variable.
+fn is_quantified_by(node: &FolTreeNode, var_name: &str, op: Quantifier) -> bool {
+    matches!(&node.node_type, NodeType::Quantifier(o, v, _) if *o == op && v == var_name)
+}
+
+/// **(internal)** Get the dual quantifier.
+fn dual(op: Quantifier) -> Quantifier {
+    match op {
+        Quantifier::Exists => Quantifier::Forall,
+        Quantifier::Forall => Quantifier::Exists,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::eval_static::prepare_graph::get_fol_extended_symbolic_graph;
+    use crate::algorithms::fo_logic::eval_algorithm::eval_node;
+    use crate::algorithms::fo_logic::eval_wrappers::parse_and_optimize_fol_formula;
+    use crate::algorithms::fo_logic::parser::parse_and_minimize_fol_formula;
+    use crate::algorithms::fo_logic::utils::collect_unique_fol_vars;
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    #[test]
+    /// Test that pushing quantifiers inward decreases the number of needed FOL variables.
+    fn quantifier_pushing_structure() {
+        let formula = "\\exists x, y: (f(x, 1) & f(y, 0))";
+        let tree = parse_and_minimize_fol_formula(formula, "a").unwrap();
+        assert_eq!(collect_unique_fol_vars(&tree).len(), 2);
+        let tree = parse_and_optimize_fol_formula(formula, "a").unwrap();
+        assert_eq!(collect_unique_fol_vars(&tree).len(), 1);
+        let expected =
+            "((\\exists a_extra_0: f(a_extra_0, 1)) & (\\exists a_extra_0: f(a_extra_0, 0)))";
+        assert_eq!(tree.to_string(), expected);
+
+        // vacuous quantifiers are removed completely
+        let tree = parse_and_optimize_fol_formula("\\forall x: f(1, 0)", "a").unwrap();
+        assert_eq!(collect_unique_fol_vars(&tree).len(), 0);
+
+        // quantifiers that can't be pushed stay where they are
+        let formula = "\\forall x: \\exists y: f(x, y)";
+        let tree_minimized = parse_and_minimize_fol_formula(formula, "a").unwrap();
+        let tree_optimized = parse_and_optimize_fol_formula(formula, "a").unwrap();
+        assert_eq!(tree_minimized, tree_optimized);
+    }
+
+    #[test]
+    /// Test that pushing quantifiers inward does not change the semantics of formulas.
+    fn quantifier_pushing_semantics() {
+        let bn = BooleanNetwork::try_from("a -> a\nb -> a\nb -| b\n$a: f(a, b)").unwrap();
+        let graph = get_fol_extended_symbolic_graph(&bn, 3, "a", None).unwrap();
+
+        let formulas = [
+            "\\exists x, y: (f(x, 1) & f(y, 0))",
+            "\\forall x, y: (f(x, y) | f(y, x))",
+            "\\forall x: (f(x, x) => (\\exists y: f(y, x)))",
+            "\\exists x: (\\forall y: f(x, y) => f(y, 1))",
+            "\\exists x: !(\\forall y: f(x, y) | f(y, x))",
+            "\\forall x: \\exists y: \\forall z: (f(x, z) ^ f(y, z))",
+            "\\exists x: \\exists y: (f(x, y) <=> !f(y, x))",
+        ];
+        for formula in formulas {
+            let tree_minimized = parse_and_minimize_fol_formula(formula, "a").unwrap();
+            let tree_optimized = parse_and_optimize_fol_formula(formula, "a").unwrap();
+            let result_minimized = eval_node(tree_minimized, &graph).colors();
+            let result_optimized = eval_node(tree_optimized, &graph).colors();
+            assert_eq!(result_minimized, result_optimized);
+        }
+    }
 }
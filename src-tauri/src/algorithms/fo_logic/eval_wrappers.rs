@@ -1,4 +1,4 @@
-use crate::algorithms::fo_logic::eval_algorithm::eval_node;
+use crate::algorithms::fo_logic::eval_algorithm::{eval_node, push_quantifiers_inward};
 use crate::algorithms::fo_logic::fol_tree::FolTreeNode;
 use crate::algorithms::fo_logic::parser::parse_and_minimize_fol_formula;
 use crate::algorithms::fo_logic::utils::*;
@@ -62,6 +62,21 @@ pub fn eval_tree(
     Ok(result[0].clone())
 }
 
+/// Parse given FOL formula into a syntactic tree with minimized set of variables, and optimize
+/// the placement of its quantifiers for evaluation (see [push_quantifiers_inward]).
+///
+/// Argument `base_var_name` is for the BN var which is used as a base for extra variables.
+pub fn parse_and_optimize_fol_formula(
+    formula: &str,
+    base_var_name: &str,
+) -> Result<FolTreeNode, String> {
+    let tree = parse_and_minimize_fol_formula(formula, base_var_name)?;
+    let tree = push_quantifiers_inward(tree);
+    // quantifiers might have moved, so the variables can be minimized again
+    validate_and_rename_vars(tree, base_var_name)
+        .map_err(|e| format!("Error during FOL formula processing: {}", e))
+}
+
 /// Parse given FOL formulas list into syntactic trees and perform compatibility check with
 /// the provided `graph` (i.e., check if `graph` object supports all needed symbolic variables).
 ///
@@ -74,7 +89,7 @@ fn parse_and_validate(
     // parse all the formulas and check that graph supports enough FOL vars and contains correct functions
     let mut parsed_trees = Vec::new();
     for formula in formulas {
-        let tree = parse_and_minimize_fol_formula(formula, base_var_name)?;
+        let tree = parse_and_optimize_fol_formula(formula, base_var_name)?;
 
         // check if all variables valid
         let fol_vars = collect_unique_fol_vars(&tree);
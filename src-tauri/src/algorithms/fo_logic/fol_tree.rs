@@ -209,6 +209,40 @@ impl FolTreeNode {
             }
         }
     }
+
+    /// Create a copy of this [FolTreeNode] with every occurrence of (uninterpreted) function
+    /// symbol `old_name` renamed to `new_name`. Update function symbols are never renamed.
+    pub fn substitute_fn_symbol(&self, old_name: &str, new_name: &str) -> FolTreeNode {
+        match &self.node_type {
+            NodeType::Terminal(_) => self.clone(),
+            NodeType::Unary(op, child) => {
+                let node = child.substitute_fn_symbol(old_name, new_name);
+                FolTreeNode::mk_unary(node, *op)
+            }
+            NodeType::Binary(op, left, right) => {
+                let node1 = left.substitute_fn_symbol(old_name, new_name);
+                let node2 = right.substitute_fn_symbol(old_name, new_name);
+                FolTreeNode::mk_binary(node1, node2, *op)
+            }
+            NodeType::Quantifier(op, quantified_var, child) => {
+                let node = child.substitute_fn_symbol(old_name, new_name);
+                FolTreeNode::mk_quantifier(node, quantified_var, *op)
+            }
+            NodeType::Function(fn_symbol, child_nodes) => {
+                let is_update = fn_symbol.is_update_fn;
+                let name = if !is_update && fn_symbol.name == old_name {
+                    new_name
+                } else {
+                    fn_symbol.name.as_str()
+                };
+                let new_children = child_nodes
+                    .iter()
+                    .map(|node| node.substitute_fn_symbol(old_name, new_name))
+                    .collect();
+                FolTreeNode::mk_function(name, new_children, is_update)
+            }
+        }
+    }
 }
 
 impl FolTreeNode {
@@ -0,0 +1,314 @@
+use crate::algorithms::fo_logic::fol_tree::{FolTreeNode, NodeType};
+use crate::algorithms::fo_logic::operator_enums::Atom;
+use crate::algorithms::fo_logic::utils::collect_unique_fol_vars;
+use std::collections::{HashMap, HashSet};
+
+/// Definitions of named FOL macros used during expansion. Each macro name is mapped to a list
+/// of its formal parameters and a syntax tree of its body.
+///
+/// Formal parameters are the only free variables allowed in the body. A macro is referenced in
+/// a formula the same way as a function symbol, e.g., `my_macro(x, f(y))`.
+pub type MacroDefinitions = HashMap<String, (Vec<String>, FolTreeNode)>;
+
+/// Expand all references to macros in the given formula tree, recursively (macro bodies can
+/// reference other macros).
+///
+/// Each reference `m(e_1, ..., e_n)` is replaced by the body of macro `m`, with every occurrence
+/// of the i-th formal parameter substituted by (already expanded) expression `e_i`.
+///
+/// Return `Err` if a macro is used with wrong number of arguments, if a macro (transitively)
+/// references itself, or if the substitution would capture a variable (i.e., a variable used in
+/// the arguments is quantified inside the macro's body).
+pub fn expand_macros(tree: &FolTreeNode, macros: &MacroDefinitions) -> Result<FolTreeNode, String> {
+    expand_macros_recursive(tree, macros, &mut Vec::new())
+}
+
+/// **(internal)** Recursively expand macros, while tracking a stack of macros that are
+/// currently being expanded (to detect cyclic definitions).
+fn expand_macros_recursive(
+    tree: &FolTreeNode,
+    macros: &MacroDefinitions,
+    expansion_stack: &mut Vec<String>,
+) -> Result<FolTreeNode, String> {
+    match &tree.node_type {
+        NodeType::Terminal(_) => Ok(tree.clone()),
+        NodeType::Unary(op, child) => {
+            let node = expand_macros_recursive(child, macros, expansion_stack)?;
+            Ok(FolTreeNode::mk_unary(node, *op))
+        }
+        NodeType::Binary(op, left, right) => {
+            let node1 = expand_macros_recursive(left, macros, expansion_stack)?;
+            let node2 = expand_macros_recursive(right, macros, expansion_stack)?;
+            Ok(FolTreeNode::mk_binary(node1, node2, *op))
+        }
+        NodeType::Quantifier(op, var, child) => {
+            let node = expand_macros_recursive(child, macros, expansion_stack)?;
+            Ok(FolTreeNode::mk_quantifier(node, var, *op))
+        }
+        NodeType::Function(fn_symbol, child_nodes) => {
+            // arguments are always expanded first (they might reference macros too)
+            let mut args = Vec::new();
+            for child in child_nodes {
+                args.push(expand_macros_recursive(child, macros, expansion_stack)?);
+            }
+
+            let Some((params, body)) = macros.get(&fn_symbol.name) else {
+                // standard function symbol (not a macro), keep it
+                return Ok(FolTreeNode::mk_function(
+                    &fn_symbol.name,
+                    args,
+                    fn_symbol.is_update_fn,
+                ));
+            };
+
+            let name = fn_symbol.name.clone();
+            if expansion_stack.contains(&name) {
+                expansion_stack.push(name);
+                return Err(format!(
+                    "Macro definitions are cyclic: {}.",
+                    expansion_stack.join(" -> ")
+                ));
+            }
+            if params.len() != args.len() {
+                return Err(format!(
+                    "Macro `{name}` expects {} arguments, but {} were given.",
+                    params.len(),
+                    args.len()
+                ));
+            }
+
+            expansion_stack.push(name.clone());
+            let expanded_body = expand_macros_recursive(body, macros, expansion_stack)?;
+            expansion_stack.pop();
+
+            // check that substituting the arguments can't capture any of their variables
+            let quantified_vars = collect_unique_fol_vars(&expanded_body);
+            for arg in args.iter() {
+                for var in collect_all_var_names(arg) {
+                    if quantified_vars.contains(&var) {
+                        return Err(format!(
+                            "Variable `{var}` used in arguments of macro `{name}` is quantified in its body."
+                        ));
+                    }
+                }
+            }
+
+            // substitute all params simultaneously (first to fresh placeholders, then to the args),
+            // so that variables in one argument are not accidentally replaced by another one
+            let mut result = expanded_body;
+            for (i, param) in params.iter().enumerate() {
+                let placeholder = FolTreeNode::mk_variable(&mk_placeholder(&name, i));
+                result = result.substitute_variable(param, &placeholder);
+            }
+            for (i, arg) in args.iter().enumerate() {
+                result = result.substitute_variable(&mk_placeholder(&name, i), arg);
+            }
+            Ok(result)
+        }
+    }
+}
+
+/// **(internal)** Name of a placeholder variable used during simultaneous substitution.
+fn mk_placeholder(macro_name: &str, index: usize) -> String {
+    format!("__{macro_name}_param_{index}")
+}
+
+/// Compute the set of names of all variables that appear in terminal nodes of the formula tree
+/// (both free and quantified ones).
+pub fn collect_all_var_names(formula_tree: &FolTreeNode) -> HashSet<String> {
+    let mut var_names = HashSet::new();
+    collect_all_var_names_recursive(formula_tree, &mut var_names);
+    var_names
+}
+
+fn collect_all_var_names_recursive(formula_tree: &FolTreeNode, var_names: &mut HashSet<String>) {
+    match &formula_tree.node_type {
+        NodeType::Terminal(Atom::Var(name)) => {
+            var_names.insert(name.clone());
+        }
+        NodeType::Terminal(_) => {}
+        NodeType::Unary(_, child) | NodeType::Quantifier(_, _, child) => {
+            collect_all_var_names_recursive(child, var_names);
+        }
+        NodeType::Binary(_, left, right) => {
+            collect_all_var_names_recursive(left, var_names);
+            collect_all_var_names_recursive(right, var_names);
+        }
+        NodeType::Function(_, child_nodes) => {
+            for child in child_nodes {
+                collect_all_var_names_recursive(child, var_names);
+            }
+        }
+    }
+}
+
+/// Compute the set of all macros (from the provided definitions) that are directly referenced
+/// in the formula tree.
+pub fn collect_macro_references(
+    formula_tree: &FolTreeNode,
+    macros: &MacroDefinitions,
+) -> HashSet<String> {
+    let mut references = HashSet::new();
+    collect_macro_references_recursive(formula_tree, macros, &mut references);
+    references
+}
+
+fn collect_macro_references_recursive(
+    formula_tree: &FolTreeNode,
+    macros: &MacroDefinitions,
+    references: &mut HashSet<String>,
+) {
+    match &formula_tree.node_type {
+        NodeType::Terminal(_) => {}
+        NodeType::Unary(_, child) | NodeType::Quantifier(_, _, child) => {
+            collect_macro_references_recursive(child, macros, references);
+        }
+        NodeType::Binary(_, left, right) => {
+            collect_macro_references_recursive(left, macros, references);
+            collect_macro_references_recursive(right, macros, references);
+        }
+        NodeType::Function(fn_symbol, child_nodes) => {
+            if macros.contains_key(&fn_symbol.name) {
+                references.insert(fn_symbol.name.clone());
+            }
+            for child in child_nodes {
+                collect_macro_references_recursive(child, macros, references);
+            }
+        }
+    }
+}
+
+/// Find a cycle in the macro definitions (a macro that transitively references itself).
+///
+/// If there is a cycle, return the sequence of macro names forming it (the first name is
+/// repeated at the end). Otherwise, return `None`.
+pub fn find_macro_cycle(macros: &MacroDefinitions) -> Option<Vec<String>> {
+    // sort the names to make the result deterministic
+    let mut names: Vec<&String> = macros.keys().collect();
+    names.sort();
+
+    let mut finished = HashSet::new();
+    for name in names {
+        let mut path = Vec::new();
+        if let Some(cycle) = find_cycle_dfs(name, macros, &mut path, &mut finished) {
+            return Some(cycle);
+        }
+    }
+    None
+}
+
+/// **(internal)** Depth-first search for a cycle in the macro reference graph.
+fn find_cycle_dfs(
+    name: &String,
+    macros: &MacroDefinitions,
+    path: &mut Vec<String>,
+    finished: &mut HashSet<String>,
+) -> Option<Vec<String>> {
+    if let Some(position) = path.iter().position(|n| n == name) {
+        let mut cycle = path[position..].to_vec();
+        cycle.push(name.clone());
+        return Some(cycle);
+    }
+    if finished.contains(name) {
+        return None;
+    }
+
+    path.push(name.clone());
+    let (_, body) = macros.get(name).unwrap();
+    let mut references: Vec<String> = collect_macro_references(body, macros).into_iter().collect();
+    references.sort();
+    for referenced in references {
+        if let Some(cycle) = find_cycle_dfs(&referenced, macros, path, finished) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    finished.insert(name.clone());
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::fo_logic::macro_expansion::*;
+    use crate::algorithms::fo_logic::parser::parse_fol_formula;
+
+    /// Shorthand to prepare macro definitions from (name, params, body) triplets.
+    fn mk_macros(definitions: Vec<(&str, Vec<&str>, &str)>) -> MacroDefinitions {
+        definitions
+            .into_iter()
+            .map(|(name, params, body)| {
+                let params = params.into_iter().map(|p| p.to_string()).collect();
+                (name.to_string(), (params, parse_fol_formula(body).unwrap()))
+            })
+            .collect()
+    }
+
+    #[test]
+    /// Test expanding simple and nested macros.
+    fn expand_valid_macros() {
+        let macros = mk_macros(vec![
+            ("act", vec!["x", "y"], "x => y"),
+            ("mono", vec!["a"], "act(f(0, a), f(1, a))"),
+        ]);
+
+        let tree = parse_fol_formula("\\forall z: act(f_A(z), g(z))").unwrap();
+        let expanded = expand_macros(&tree, &macros).unwrap();
+        let expected = parse_fol_formula("\\forall z: (f_A(z) => g(z))").unwrap();
+        assert_eq!(expanded, expected);
+
+        let tree = parse_fol_formula("\\exists v: mono(v) & mono(!v)").unwrap();
+        let expanded = expand_macros(&tree, &macros).unwrap();
+        let expected =
+            parse_fol_formula("\\exists v: (f(0, v) => f(1, v)) & (f(0, !v) => f(1, !v))").unwrap();
+        assert_eq!(expanded, expected);
+
+        // substitution is simultaneous, argument `y` is not replaced by the second argument
+        let tree = parse_fol_formula("\\forall x, y: act(y, x)").unwrap();
+        let expanded = expand_macros(&tree, &macros).unwrap();
+        let expected = parse_fol_formula("\\forall x, y: (y => x)").unwrap();
+        assert_eq!(expanded, expected);
+    }
+
+    #[test]
+    /// Test that invalid macro usage is reported.
+    fn expand_invalid_macros() {
+        let macros = mk_macros(vec![
+            ("act", vec!["x", "y"], "x => y"),
+            ("ex", vec!["a"], "\\exists z: f(z, a)"),
+            ("loop_a", vec![], "loop_b()"),
+            ("loop_b", vec![], "!loop_a()"),
+        ]);
+
+        // wrong arity
+        let tree = parse_fol_formula("act(1)").unwrap();
+        assert!(expand_macros(&tree, &macros).is_err());
+        // variable capture
+        let tree = parse_fol_formula("\\forall z: ex(z)").unwrap();
+        assert!(expand_macros(&tree, &macros).is_err());
+        // cyclic definitions
+        let tree = parse_fol_formula("loop_a()").unwrap();
+        assert!(expand_macros(&tree, &macros).is_err());
+    }
+
+    #[test]
+    /// Test detecting cycles in macro definitions.
+    fn macro_cycles() {
+        let macros = mk_macros(vec![
+            ("a", vec!["x"], "b(x) & c(x)"),
+            ("b", vec!["x"], "c(!x)"),
+            ("c", vec!["x"], "x"),
+        ]);
+        assert_eq!(find_macro_cycle(&macros), None);
+
+        let macros = mk_macros(vec![
+            ("a", vec!["x"], "b(x) & c(x)"),
+            ("b", vec!["x"], "c(!x)"),
+            ("c", vec!["x"], "a(x)"),
+        ]);
+        let expected = ["a", "b", "c", "a"];
+        assert_eq!(
+            find_macro_cycle(&macros),
+            Some(expected.iter().map(|s| s.to_string()).collect())
+        );
+    }
+}
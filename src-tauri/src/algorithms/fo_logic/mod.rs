@@ -2,6 +2,7 @@
 
 pub mod eval_wrappers;
 pub mod fol_tree;
+pub mod macro_expansion;
 pub mod operator_enums;
 pub mod parser;
 pub mod tokenizer;
@@ -0,0 +1,120 @@
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
+use biodivine_sketchbook::algorithms::eval_static::eval::{
+    eval_static_prop, eval_static_prop_in_own_context, StaticPropContexts,
+};
+use biodivine_sketchbook::algorithms::eval_static::prepare_graph::prepare_graph_for_static_fol;
+use biodivine_sketchbook::algorithms::eval_static::processed_props::{
+    process_static_props, ProcessedStatProp,
+};
+use biodivine_sketchbook::inference::inference_solver::InferenceSolver;
+use biodivine_sketchbook::logging;
+use biodivine_sketchbook::sketchbook::Sketch;
+
+use clap::Parser;
+use std::fs::File;
+use std::io::Read;
+use std::time::SystemTime;
+
+/// Structure to collect CLI arguments
+#[derive(Parser)]
+#[clap(
+    author = "Ondřej Huvar",
+    about = "Compare evaluation of static properties in a shared symbolic context with \
+    evaluation of each property in its own minimal context."
+)]
+struct Arguments {
+    /// Path to a file with a model in aeon sketch format.
+    model_path: String,
+}
+
+/// Evaluate all static properties in a single symbolic context that is extended with enough
+/// extra variables for all the properties. Return the graph restricted to valid colors.
+fn eval_in_shared_context(
+    graph: SymbolicAsyncGraph,
+    static_props: &[ProcessedStatProp],
+    base_var_name: &str,
+) -> SymbolicAsyncGraph {
+    let mut graph = graph;
+    for prop in static_props {
+        let colors = eval_static_prop(prop.clone(), &graph, base_var_name).unwrap();
+        let colored_vertices =
+            GraphColoredVertices::new(colors.into_bdd(), graph.symbolic_context());
+        graph = graph.restrict(&colored_vertices);
+    }
+    graph
+}
+
+/// Evaluate each static property in its own minimal symbolic context, transferring the results
+/// back to the given graph. Return the graph restricted to valid colors.
+fn eval_in_own_contexts(
+    graph: SymbolicAsyncGraph,
+    static_props: &[ProcessedStatProp],
+    base_var_name: &str,
+) -> SymbolicAsyncGraph {
+    let mut graph = graph;
+    let mut contexts = StaticPropContexts::default();
+    for prop in static_props {
+        let colors =
+            eval_static_prop_in_own_context(prop.clone(), &graph, base_var_name, &mut contexts)
+                .unwrap();
+        let colored_vertices =
+            GraphColoredVertices::new(colors.into_bdd(), graph.symbolic_context());
+        graph = graph.restrict(&colored_vertices);
+    }
+    graph
+}
+
+/// Compute number of milliseconds since given time.
+fn millis_since(start: SystemTime) -> u128 {
+    start.elapsed().unwrap().as_millis()
+}
+
+fn main() {
+    let args = Arguments::parse();
+    // we disable logging since it would only overflow the output
+    logging::disable_logging();
+
+    // load the sketch
+    let mut sketch_file =
+        File::open(args.model_path.as_str()).expect("Provided file does not exist.");
+    let mut file_contents = String::new();
+    sketch_file
+        .read_to_string(&mut file_contents)
+        .expect("Error reading provided file.");
+    let sketch = Sketch::from_aeon(&file_contents).expect("Error parsing the sketch.");
+
+    let bn = InferenceSolver::extract_bn(&sketch).expect("Error processing the network.");
+    let static_props = process_static_props(&sketch, &bn).expect("Error processing properties.");
+    let base_var = bn.variables().collect::<Vec<_>>()[0];
+    let base_var_name = bn.as_graph().get_variable_name(base_var).clone();
+    println!("Number of static properties: {}", static_props.len());
+
+    // a) current path - one context extended with variables for all properties
+    let start = SystemTime::now();
+    let graph = prepare_graph_for_static_fol(&bn, &static_props, &base_var_name, None).unwrap();
+    let num_bdd_vars = graph.symbolic_context().bdd_variable_set().num_vars();
+    let graph = eval_in_shared_context(graph, &static_props, &base_var_name);
+    let shared_colors = graph.mk_unit_colors();
+    println!(
+        "Shared context ({num_bdd_vars} BDD vars): {} candidates, {}ms",
+        shared_colors.exact_cardinality(),
+        millis_since(start)
+    );
+
+    // b) new path - canonical context, each property evaluated in its own minimal context
+    let start = SystemTime::now();
+    let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+    let graph = eval_in_own_contexts(graph, &static_props, &base_var_name);
+    let own_colors = graph.mk_unit_colors();
+    println!(
+        "Own contexts: {} candidates, {}ms",
+        own_colors.exact_cardinality(),
+        millis_since(start)
+    );
+
+    assert_eq!(
+        shared_colors.exact_cardinality(),
+        own_colors.exact_cardinality(),
+        "Both approaches must give the same results."
+    );
+}
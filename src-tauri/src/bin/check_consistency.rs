@@ -0,0 +1,59 @@
+use biodivine_sketchbook::logging;
+use biodivine_sketchbook::sketchbook::{JsonSerde, Sketch};
+
+use clap::Parser;
+use std::fs::File;
+use std::io::Read;
+use std::process::exit;
+
+/// Structure to collect CLI arguments
+#[derive(Parser)]
+#[clap(
+    author = "Ondřej Huvar",
+    about = "Run the consistency check on a predefined sketch and report discovered issues."
+)]
+struct Arguments {
+    /// Path to a file with a sketch in aeon format (or in custom JSON format, if the file
+    /// has a `.json` extension).
+    model_path: String,
+    /// Print the report in a structured JSON format instead of a text summary.
+    #[clap(long)]
+    json: bool,
+    /// Also run the (more expensive) lint pass over properties of a consistent sketch.
+    #[clap(long)]
+    lint: bool,
+}
+
+fn main() {
+    let args = Arguments::parse();
+    // we disable logging since it would only overflow the output
+    logging::disable_logging();
+
+    // load the sketch
+    let mut sketch_file =
+        File::open(args.model_path.as_str()).expect("Provided file does not exist.");
+    let mut file_contents = String::new();
+    sketch_file
+        .read_to_string(&mut file_contents)
+        .expect("Error reading provided file.");
+    let sketch = if args.model_path.ends_with(".json") {
+        Sketch::from_custom_json(&file_contents).expect("Error parsing the sketch.")
+    } else {
+        Sketch::from_aeon(&file_contents).expect("Error parsing the sketch.")
+    };
+
+    let mut report = sketch.run_consistency_check();
+    if args.lint && report.is_consistent() {
+        report.extend(sketch.lint_properties());
+    }
+    if args.json {
+        println!("{}", report.to_pretty_json_str());
+    } else {
+        println!("{}", report.summary());
+    }
+
+    // non-zero exit code if the sketch is inconsistent (warnings are fine)
+    if !report.is_consistent() {
+        exit(1);
+    }
+}
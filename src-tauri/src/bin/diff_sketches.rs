@@ -0,0 +1,59 @@
+use biodivine_sketchbook::logging;
+use biodivine_sketchbook::sketchbook::{JsonSerde, Sketch};
+
+use clap::Parser;
+use std::fs::File;
+use std::io::Read;
+use std::process::exit;
+
+/// Structure to collect CLI arguments
+#[derive(Parser)]
+#[clap(
+    author = "Ondřej Huvar",
+    about = "Compute a semantic diff between two versions of a sketch."
+)]
+struct Arguments {
+    /// Path to a file with the original version of the sketch in aeon format (or in custom
+    /// JSON format, if the file has a `.json` extension).
+    old_path: String,
+    /// Path to a file with the new version of the sketch (same formats as above).
+    new_path: String,
+    /// Print the diff in a structured JSON format instead of a text summary.
+    #[clap(long)]
+    json: bool,
+}
+
+/// Load the sketch from a file, choosing the format based on its extension.
+fn load_sketch(path: &str) -> Sketch {
+    let mut sketch_file = File::open(path).expect("Provided file does not exist.");
+    let mut file_contents = String::new();
+    sketch_file
+        .read_to_string(&mut file_contents)
+        .expect("Error reading provided file.");
+    if path.ends_with(".json") {
+        Sketch::from_custom_json(&file_contents).expect("Error parsing the sketch.")
+    } else {
+        Sketch::from_aeon(&file_contents).expect("Error parsing the sketch.")
+    }
+}
+
+fn main() {
+    let args = Arguments::parse();
+    // we disable logging since it would only overflow the output
+    logging::disable_logging();
+
+    let old_sketch = load_sketch(&args.old_path);
+    let new_sketch = load_sketch(&args.new_path);
+
+    let diff = old_sketch.diff(&new_sketch);
+    if args.json {
+        println!("{}", diff.to_pretty_json_str());
+    } else {
+        println!("{}", diff.summary());
+    }
+
+    // same as with the `diff` utility, non-zero exit code means the sketches differ
+    if !diff.is_empty() {
+        exit(1);
+    }
+}
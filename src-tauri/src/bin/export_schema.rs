@@ -0,0 +1,24 @@
+use biodivine_sketchbook::sketchbook::data_structs::SketchData;
+
+use clap::Parser;
+
+/// Structure to collect CLI arguments
+#[derive(Parser)]
+#[clap(
+    author = "Ondřej Huvar",
+    about = "Print (or write to a file) the JSON schema of the sketch JSON format."
+)]
+struct Arguments {
+    /// Path to a file to write the schema into (it is printed to stdout if not provided).
+    output_path: Option<String>,
+}
+
+fn main() {
+    let args = Arguments::parse();
+    let schema = SketchData::json_schema();
+    if let Some(path) = args.output_path {
+        std::fs::write(path, schema + "\n").expect("Error writing the schema.");
+    } else {
+        println!("{schema}");
+    }
+}
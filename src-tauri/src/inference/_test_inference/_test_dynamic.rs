@@ -1,5 +1,6 @@
 use super::utils::load_test_model;
-use crate::inference::_test_inference::utils::add_dyn_prop_and_infer;
+use crate::inference::_test_inference::utils::{add_dyn_prop_and_infer, run_inference};
+use crate::sketchbook::observations::PerturbationType;
 use crate::sketchbook::properties::shortcuts::*;
 use crate::sketchbook::properties::DynProperty;
 
@@ -138,3 +139,119 @@ fn inference_template_time_series() {
     let property = DynProperty::mk_trajectory(id, Some(data_id), "");
     assert_eq!(add_dyn_prop_and_infer(sketch, property, id), 16);
 }
+
+#[test]
+/// Test inference using the test model with soft fixed-point properties (and weighted
+/// observations).
+fn inference_soft_constraints() {
+    // Observations of the time series can't be fixed points all together
+    let sketch = load_test_model();
+    let data_id = sketch
+        .observations
+        .get_dataset_id("data_time_series")
+        .unwrap();
+    let mut property = DynProperty::mk_fixed_point("", Some(data_id.clone()), None, "");
+    assert_eq!(
+        add_dyn_prop_and_infer(sketch.clone(), property.clone(), "fps"),
+        0
+    );
+
+    // But some of them can
+    property.set_soft(true).unwrap();
+    let mut soft_sketch = sketch.clone();
+    soft_sketch
+        .properties
+        .add_dynamic_by_str("fps", property.clone())
+        .unwrap();
+    let results = run_inference(soft_sketch.clone());
+    let soft_results = results.soft_constraints.unwrap();
+    assert_eq!(results.num_sat_networks, 2);
+    assert_eq!(
+        (soft_results.best_weight, soft_results.total_weight),
+        (2, 4)
+    );
+    assert_eq!(soft_results.classes.len(), 1);
+    assert_eq!(soft_results.classes[0].violated, vec!["fps:a", "fps:b"]);
+
+    // Observations with larger weights are preferred
+    soft_sketch
+        .observations
+        .set_obs_weight_by_str("data_time_series", "a", 3)
+        .unwrap();
+    let results = run_inference(soft_sketch);
+    let soft_results = results.soft_constraints.unwrap();
+    assert_eq!(results.num_sat_networks, 2);
+    assert_eq!(
+        (soft_results.best_weight, soft_results.total_weight),
+        (3, 6)
+    );
+    assert_eq!(soft_results.classes.len(), 1);
+    assert_eq!(
+        soft_results.classes[0].violated,
+        vec!["fps:b", "fps:c", "fps:d"]
+    );
+}
+
+#[test]
+/// Test inference using the test model with fixed-point properties regarding datasets with
+/// perturbations (knockouts and overexpressions).
+fn inference_perturbed_fixed_point() {
+    let id = "has_1111_fixed_point";
+    let mut sketch = load_test_model();
+    let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+    let obs_id = sketch.observations.get_obs_id("data_fp", "ones").unwrap();
+    let property = DynProperty::mk_fixed_point(id, Some(data_id), Some(obs_id), "");
+
+    // without perturbation, there are 4 candidates (see `inference_template_fixed_point`), but
+    // with `A` overexpressed, the fixed point does not require `A` to be stable on its own
+    let oe = Some(PerturbationType::OverExpression);
+    sketch
+        .observations
+        .set_perturbation_by_str("data_fp", "A", oe)
+        .unwrap();
+    assert_eq!(
+        add_dyn_prop_and_infer(sketch.clone(), property.clone(), id),
+        8
+    );
+
+    // with `A` knocked out, state 1111 is not even reachable
+    let ko = Some(PerturbationType::KnockOut);
+    sketch
+        .observations
+        .set_perturbation_by_str("data_fp", "A", ko)
+        .unwrap();
+    assert_eq!(add_dyn_prop_and_infer(sketch, property, id), 0);
+}
+
+#[test]
+/// Test inference using the test model with single-cell style properties regarding all states
+/// of a dataset, and compare the results with corresponding HCTL formulas.
+fn inference_state_set() {
+    // all observations are reachable from 1000
+    let sketch = load_test_model();
+    let id = "reachable_from_1000";
+    let data_id = sketch
+        .observations
+        .get_dataset_id("data_time_series")
+        .unwrap();
+    let obs_id = sketch
+        .observations
+        .get_obs_id("data_time_series", "a")
+        .unwrap();
+    let property = DynProperty::mk_states_reachable(id, Some(data_id), Some(obs_id), "");
+    let formula = "3{x}: @{x}: (A & ~B & ~C & ~D & EF (A & B & ~C & ~D) & EF (A & B & C & ~D) & EF (A & B & C & D))";
+    let hctl_property = mk_hctl_prop(formula).unwrap();
+    assert_eq!(add_dyn_prop_and_infer(sketch.clone(), property, id), 16);
+    assert_eq!(add_dyn_prop_and_infer(sketch, hctl_property, id), 16);
+
+    // both 0000 and 1111 lie in basins of attractors intersecting 11**
+    let sketch = load_test_model();
+    let id = "in_basins";
+    let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+    let phenotypes_id = sketch.observations.get_dataset_id("data_mts").unwrap();
+    let property = DynProperty::mk_states_in_basins(id, Some(data_id), Some(phenotypes_id), "");
+    let formula = "V{x}: @{x}: (((A & B & C & D) | (~A & ~B & ~C & ~D)) => EF (A & B & (3{y}: ({y} & AG EF {y}))))";
+    let hctl_property = mk_hctl_prop(formula).unwrap();
+    assert_eq!(add_dyn_prop_and_infer(sketch.clone(), property, id), 12);
+    assert_eq!(add_dyn_prop_and_infer(sketch, hctl_property, id), 12);
+}
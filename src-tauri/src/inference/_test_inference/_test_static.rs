@@ -2,6 +2,7 @@ use super::utils::load_test_model;
 use crate::inference::_test_inference::utils::add_stat_prop_and_infer;
 use crate::sketchbook::model::{Essentiality, Monotonicity};
 use crate::sketchbook::properties::shortcuts::*;
+use crate::sketchbook::properties::static_props::FolMacro;
 use crate::sketchbook::properties::StatProperty;
 
 #[test]
@@ -29,6 +30,39 @@ fn inference_fol_monotonicity() {
     assert_eq!(add_stat_prop_and_infer(sketch, property, id), 0);
 }
 
+#[test]
+/// Test inference using the test model with monotonicity properties expressed via FOL macros.
+fn inference_fol_macros() {
+    let mk_macro = || {
+        let params = vec!["x".to_string(), "y".to_string()];
+        FolMacro::try_new("activation", params, "x => y", "").unwrap()
+    };
+
+    // activation D -> D
+    let mut sketch = load_test_model();
+    let fol_macro = mk_macro();
+    sketch
+        .properties
+        .add_fol_macro_by_str("act", fol_macro)
+        .unwrap();
+    let formula = "act(f_D(0), f_D(1))";
+    let id = "d_d_is_activation";
+    let property = mk_fol_prop(formula).unwrap();
+    assert_eq!(add_stat_prop_and_infer(sketch, property, id), 16);
+
+    // dual D -* D
+    let mut sketch = load_test_model();
+    let fol_macro = mk_macro();
+    sketch
+        .properties
+        .add_fol_macro_by_str("act", fol_macro)
+        .unwrap();
+    let formula = "!act(f_D(0), f_D(1)) & !act(f_D(1), f_D(0))";
+    let id = "d_d_is_dual";
+    let property = mk_fol_prop(formula).unwrap();
+    assert_eq!(add_stat_prop_and_infer(sketch, property, id), 0);
+}
+
 #[test]
 /// Test inference using the test model with added template monotonicity properties.
 fn inference_template_monotonicity() {
@@ -1,5 +1,6 @@
 use crate::inference::inference_status::InferenceStatusReport;
 use crate::inference::inference_type::InferenceType;
+use crate::inference::soft_constraints::SoftConstraintResults;
 use crate::inference::update_fn_details::MAX_UPDATE_FN_COUNT;
 use crate::sketchbook::JsonSerde;
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,9 @@ pub struct InferenceResults {
     pub progress_statuses: Vec<InferenceStatusReport>,
     /// Number of admissible update functions per each variable.
     pub num_update_fns_per_var: HashMap<String, usize>,
+    /// Results of the soft constraints optimization (if there are any soft constraints).
+    #[serde(default)]
+    pub soft_constraints: Option<SoftConstraintResults>,
 }
 
 impl<'de> JsonSerde<'de> for InferenceResults {}
@@ -42,9 +46,15 @@ impl InferenceResults {
             summary_message: summary_message.to_string(),
             progress_statuses,
             num_update_fns_per_var,
+            soft_constraints: None,
         }
     }
 
+    /// Set results of the soft constraints optimization.
+    pub fn set_soft_constraints(&mut self, soft_constraints: SoftConstraintResults) {
+        self.soft_constraints = Some(soft_constraints);
+    }
+
     /// Append string to the end of current metadata.
     pub fn extend_summary(&mut self, new_message: &str) {
         self.summary_message.push_str(new_message);
@@ -68,6 +78,13 @@ impl InferenceResults {
         output.push_str("--------------\n");
         output.push_str(&format!("{}\n", self.summary_message));
 
+        if let Some(soft_constraints) = &self.soft_constraints {
+            output.push_str("--------------\n");
+            output.push_str("Violated soft constraints:\n");
+            output.push_str("--------------\n");
+            output.push_str(&soft_constraints.format_to_report());
+        }
+
         output.push_str("--------------\n");
         output.push_str("Number of admissible update functions per variable:\n");
         output.push_str("--------------\n");
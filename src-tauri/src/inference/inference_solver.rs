@@ -1,14 +1,14 @@
-use crate::algorithms::eval_dynamic::eval::eval_dyn_prop;
+use crate::algorithms::eval_dynamic::eval::{eval_dyn_prop, eval_soft_constraints};
 use crate::algorithms::eval_dynamic::prepare_graph::prepare_graph_for_dynamic_hctl;
 use crate::algorithms::eval_dynamic::processed_props::{process_dynamic_props, ProcessedDynProp};
-use crate::algorithms::eval_static::eval::eval_static_prop;
-use crate::algorithms::eval_static::prepare_graph::prepare_graph_for_static_fol;
+use crate::algorithms::eval_static::eval::{eval_static_prop_in_own_context, StaticPropContexts};
 use crate::algorithms::eval_static::processed_props::{process_static_props, ProcessedStatProp};
 use crate::algorithms::fo_logic::utils::get_implicit_function_name;
 use crate::debug;
 use crate::inference::inference_results::InferenceResults;
 use crate::inference::inference_status::InferenceStatus;
 use crate::inference::inference_type::InferenceType;
+use crate::inference::soft_constraints::{optimize_soft_constraints, SoftConstraintResults};
 use crate::sketchbook::{JsonSerde, Sketch};
 use biodivine_lib_param_bn::symbolic_async_graph::{
     GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
@@ -50,6 +50,8 @@ pub struct InferenceSolver {
     dynamic_props: Option<Vec<ProcessedDynProp>>,
     /// Set of final satisfying colors ((if computation finishes successfully).
     raw_sat_colors: Option<GraphColors>,
+    /// Results of the soft constraints optimization (if there are any soft properties).
+    soft_results: Option<SoftConstraintResults>,
     /// Vector with all time-stamped status updates. The last is the latest status.
     status_updates: Vec<InferenceStatusReport>,
     /// Flag to signal cancellation.
@@ -91,6 +93,7 @@ impl InferenceSolver {
             static_props: None,
             dynamic_props: None,
             raw_sat_colors: None,
+            soft_results: None,
             status_updates: vec![initial_status],
             should_stop: Arc::new(AtomicBool::new(false)),
             sender_channel,
@@ -404,7 +407,7 @@ impl InferenceSolver {
 /// Methods related to actual inference computation.
 impl InferenceSolver {
     /// Extract and process BN component from the sketch.
-    fn extract_bn(sketch: &Sketch) -> Result<BooleanNetwork, String> {
+    pub fn extract_bn(sketch: &Sketch) -> Result<BooleanNetwork, String> {
         let bn = sketch.model.to_bn_with_plain_regulations(None);
         // remove all unused function symbols, as these would cause problems later
         let mut bn = bn.prune_unused_parameters();
@@ -432,13 +435,23 @@ impl InferenceSolver {
     /// Evaluate previously collected static properties, and restrict the unit set of the
     /// graph to the set of valid colors.
     ///
+    /// Each property is evaluated in its own minimal symbolic context (shared by properties
+    /// needing the same number of FOL variables), and the resulting colors are transferred
+    /// back to the context of the main graph.
+    ///
     /// If we discover that sketch is unsat early, skip the rest.
     fn eval_static(&mut self, base_var_name: &str) -> Result<(), String> {
+        let mut contexts = StaticPropContexts::default();
         for stat_property in self.stat_props()?.clone() {
             self.check_cancellation()?; // check if cancellation flag was set during computation
 
             let prop_id = stat_property.id().to_string();
-            let inferred_colors = eval_static_prop(stat_property, self.graph()?, base_var_name)?;
+            let inferred_colors = eval_static_prop_in_own_context(
+                stat_property,
+                self.graph()?,
+                base_var_name,
+                &mut contexts,
+            )?;
             let colored_vertices = GraphColoredVertices::new(
                 inferred_colors.into_bdd(),
                 self.graph()?.symbolic_context(),
@@ -457,9 +470,18 @@ impl InferenceSolver {
     /// Evaluate previously collected dynamic properties, and restrict the unit set of the
     /// graph to the set of valid colors.
     ///
+    /// Hard properties are evaluated first, soft properties are then optimized over the
+    /// remaining candidates (see [Self::eval_soft_dynamic]).
+    ///
     /// If we discover that sketch is unsat early, skip the rest.
     fn eval_dynamic(&mut self) -> Result<(), String> {
-        for dyn_property in self.dyn_props()?.clone() {
+        let (soft_props, hard_props): (Vec<_>, Vec<_>) = self
+            .dyn_props()?
+            .clone()
+            .into_iter()
+            .partition(|p| p.is_soft());
+
+        for dyn_property in hard_props {
             self.check_cancellation()?; // check if cancellation flag was set during computation
 
             let prop_id = dyn_property.id().to_string();
@@ -475,10 +497,41 @@ impl InferenceSolver {
                 return Ok(());
             }
         }
+        if !soft_props.is_empty() {
+            self.eval_soft_dynamic(soft_props)?;
+        }
         self.update_status(InferenceStatus::EvaluatedAllDynamic);
         Ok(())
     }
 
+    /// Evaluate all soft constraints of given soft properties, and restrict the unit set of the
+    /// graph to colors satisfying the maximal total weight of these constraints. Details on which
+    /// constraints are violated by these colors are saved.
+    fn eval_soft_dynamic(&mut self, soft_props: Vec<ProcessedDynProp>) -> Result<(), String> {
+        let mut constraints = Vec::new();
+        for dyn_property in soft_props {
+            self.check_cancellation()?; // check if cancellation flag was set during computation
+
+            if let ProcessedDynProp::ProcessedSoftConstraints(prop) = &dyn_property {
+                let sat_colors = eval_soft_constraints(prop, self.graph()?)?;
+                for (constraint, colors) in prop.constraints.iter().zip(sat_colors) {
+                    constraints.push((constraint.label(&prop.id), constraint.weight, colors));
+                }
+            }
+            let prop_id = dyn_property.id().to_string();
+            self.update_status(InferenceStatus::EvaluatedDynamic(prop_id));
+        }
+
+        let candidates = self.current_candidate_colors()?;
+        let (best_colors, results) = optimize_soft_constraints(&candidates, &constraints);
+        let colored_vertices =
+            GraphColoredVertices::new(best_colors.into_bdd(), self.graph()?.symbolic_context());
+        let new_graph: SymbolicAsyncGraph = self.graph()?.restrict(&colored_vertices);
+        self.graph = Some(new_graph);
+        self.soft_results = Some(results);
+        Ok(())
+    }
+
     /// A modular variant of the inference. You can choose which parts to select.
     /// For example, you can only consider static properties, only dynamic properties, or all.
     pub fn run_inference_modular(
@@ -516,12 +569,8 @@ impl InferenceSolver {
             let base_var = self.bn()?.variables().collect::<Vec<_>>()[0];
             let base_var_name = self.bn()?.as_graph().get_variable_name(base_var).clone();
 
-            self.graph = Some(prepare_graph_for_static_fol(
-                self.bn()?,
-                self.stat_props()?,
-                &base_var_name,
-                None,
-            )?);
+            // the main graph has no extra variables, each property gets its own extended context
+            self.graph = Some(SymbolicAsyncGraph::new(self.bn()?)?);
             self.update_status(InferenceStatus::GeneratedContextStatic);
             let msg = format!(
                 "N. of candidates before evaluating any properties: {}\n",
@@ -553,6 +602,13 @@ impl InferenceSolver {
 
             /* >> STEP 3B: actually evaluate dynamic properties */
             self.eval_dynamic()?;
+            if let Some(soft_results) = &self.soft_results {
+                let msg = format!(
+                    "Soft constraints satisfied with total weight {} (out of {})\n",
+                    soft_results.best_weight, soft_results.total_weight
+                );
+                summary_msg.push_str(&msg);
+            }
             let msg = format!(
                 "N. of candidates after evaluating dynamic props: {}\n",
                 self.current_candidate_colors()?.approx_cardinality()
@@ -598,7 +654,7 @@ impl InferenceSolver {
         let num_update_fns_per_var =
             num_update_fn_variants_per_var(self.final_sat_colors()?, self.bn()?);
         let total_time = self.total_duration().unwrap();
-        let results = InferenceResults::new(
+        let mut results = InferenceResults::new(
             inference_type,
             num_sat_networks,
             total_time,
@@ -606,6 +662,9 @@ impl InferenceSolver {
             self.status_updates.clone(),
             num_update_fns_per_var,
         );
+        if let Some(soft_results) = &self.soft_results {
+            results.set_soft_constraints(soft_results.clone());
+        }
         self.results = Some(results.clone());
         Ok(results)
     }
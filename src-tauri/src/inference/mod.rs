@@ -12,6 +12,8 @@ pub mod inference_status;
 pub mod inference_type;
 /// Struct with details regarding candidate sampling.
 pub mod sampling_data;
+/// Optimization of soft constraints (observations that might be violated).
+pub mod soft_constraints;
 
 /// Utilities to sample and download networks.
 /// Some functionality is taken from our repository [biodivine-bn-classifier].
@@ -0,0 +1,181 @@
+use crate::sketchbook::JsonSerde;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::GraphColors;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// Class of admissible networks that violate exactly the same set of soft constraints.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ViolationClass {
+    /// Labels of the violated soft constraints (see [crate::algorithms::eval_dynamic::processed_props::SoftConstraint::label]).
+    pub violated: Vec<String>,
+    /// Number of networks in this class.
+    pub num_networks: u128,
+}
+
+/// Results of the optimization of soft constraints.
+///
+/// Admissible networks are those satisfying the maximal total weight of soft constraints
+/// (`best_weight`, out of `total_weight`). They are partitioned into classes based on which
+/// constraints they violate.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SoftConstraintResults {
+    pub total_weight: u64,
+    pub best_weight: u64,
+    pub classes: Vec<ViolationClass>,
+}
+
+impl<'de> JsonSerde<'de> for SoftConstraintResults {}
+
+impl SoftConstraintResults {
+    /// Prepare a formatted summary of the results, listing all the violation classes.
+    pub fn format_to_report(&self) -> String {
+        let mut output = format!(
+            "Admissible candidates satisfy soft constraints with total weight {} (out of {}).\n",
+            self.best_weight, self.total_weight
+        );
+        for class in &self.classes {
+            let violated = if class.violated.is_empty() {
+                "none".to_string()
+            } else {
+                class.violated.join(", ")
+            };
+            output.push_str(&format!(
+                "{} candidates violating: {violated}\n",
+                class.num_networks
+            ));
+        }
+        output
+    }
+}
+
+/// Find colors (from the set of `candidates`) satisfying the maximal total weight of soft
+/// constraints. Each constraint is given by its label, weight, and a set of colors satisfying it.
+///
+/// The candidates are symbolically partitioned by their score (the sum of weights of satisfied
+/// constraints), and the partition with the maximal score is returned. These colors are then
+/// further partitioned by the exact set of constraints that they violate.
+pub fn optimize_soft_constraints(
+    candidates: &GraphColors,
+    constraints: &[(String, u64, GraphColors)],
+) -> (GraphColors, SoftConstraintResults) {
+    // sets of colors with particular score (only non-empty sets are kept)
+    let mut by_score: BTreeMap<u64, GraphColors> = BTreeMap::new();
+    if !candidates.is_empty() {
+        by_score.insert(0, candidates.clone());
+    }
+    for (_, weight, sat_colors) in constraints {
+        let mut new_by_score: BTreeMap<u64, GraphColors> = BTreeMap::new();
+        for (score, colors) in by_score {
+            let satisfied = colors.intersect(sat_colors);
+            let violated = colors.minus(sat_colors);
+            for (new_score, part) in [(score + weight, satisfied), (score, violated)] {
+                if part.is_empty() {
+                    continue;
+                }
+                let merged = match new_by_score.remove(&new_score) {
+                    Some(existing) => existing.union(&part),
+                    None => part,
+                };
+                new_by_score.insert(new_score, merged);
+            }
+        }
+        by_score = new_by_score;
+    }
+
+    let total_weight = constraints.iter().map(|(_, w, _)| w).sum();
+    let (best_weight, best_colors) = match by_score.pop_last() {
+        Some(best) => best,
+        None => (0, candidates.clone()),
+    };
+
+    // partition the best colors by the set of violated constraints
+    let mut classes: Vec<(Vec<String>, GraphColors)> = Vec::new();
+    if !best_colors.is_empty() {
+        classes.push((Vec::new(), best_colors.clone()));
+    }
+    for (label, _, sat_colors) in constraints {
+        let mut new_classes = Vec::new();
+        for (violated, colors) in classes {
+            let satisfied_part = colors.intersect(sat_colors);
+            let violated_part = colors.minus(sat_colors);
+            if !satisfied_part.is_empty() {
+                new_classes.push((violated.clone(), satisfied_part));
+            }
+            if !violated_part.is_empty() {
+                let mut violated = violated;
+                violated.push(label.clone());
+                new_classes.push((violated, violated_part));
+            }
+        }
+        classes = new_classes;
+    }
+    let mut classes: Vec<ViolationClass> = classes
+        .into_iter()
+        .map(|(violated, colors)| ViolationClass {
+            violated,
+            num_networks: colors.exact_cardinality().to_u128().unwrap_or(u128::MAX),
+        })
+        .collect();
+    classes.sort_by_key(|c| Reverse(c.num_networks));
+
+    let results = SoftConstraintResults {
+        total_weight,
+        best_weight,
+        classes,
+    };
+    (best_colors, results)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::soft_constraints::optimize_soft_constraints;
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    #[test]
+    /// Test finding colors satisfying maximal weight of soft constraints, and partitioning
+    /// them into violation classes.
+    fn soft_constraint_optimization() {
+        // two unknown parameters, four colors in total
+        let bn = BooleanNetwork::try_from("a -?? a\n$a: f | g\n").unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let ctx = graph.symbolic_context();
+        let unit = graph.mk_unit_colors();
+        let mk_colors = |name: &str| {
+            let param = bn.find_parameter(name).unwrap();
+            let bdd = ctx.mk_uninterpreted_function_is_true(param, &[]);
+            unit.copy(bdd.and(unit.as_bdd()))
+        };
+        let f_colors = mk_colors("f");
+        let g_colors = mk_colors("g");
+        let not_f_colors = unit.minus(&f_colors);
+
+        // `f` and `!f` can't hold together, `f` has larger weight
+        let constraints = vec![
+            ("p:o1".to_string(), 2, f_colors.clone()),
+            ("p:o2".to_string(), 1, not_f_colors),
+            ("q:o3".to_string(), 1, g_colors.clone()),
+        ];
+        let (best, results) = optimize_soft_constraints(&unit, &constraints);
+        assert_eq!(best, f_colors.intersect(&g_colors));
+        assert_eq!(results.total_weight, 4);
+        assert_eq!(results.best_weight, 3);
+        assert_eq!(results.classes.len(), 1);
+        assert_eq!(results.classes[0].violated, vec!["p:o2".to_string()]);
+        assert_eq!(results.classes[0].num_networks, 1);
+
+        // equal weights make two optimal classes
+        let constraints = vec![
+            ("p:o1".to_string(), 1, f_colors.clone()),
+            ("p:o2".to_string(), 1, unit.minus(&f_colors)),
+        ];
+        let (best, results) = optimize_soft_constraints(&unit, &constraints);
+        assert_eq!(best, unit);
+        assert_eq!(results.best_weight, 1);
+        assert_eq!(results.classes.len(), 2);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Error, Formatter};
+
+/// Severity of an issue found during the consistency check.
+///
+/// - `Error` means the sketch is inconsistent (and inference can't be started)
+/// - `Warning` means there is a potential problem, but the sketch is still consistent
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+/// Component of the sketch that an issue found during the consistency check relates to.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum SketchComponent {
+    Model,
+    Datasets,
+    StaticProperties,
+    DynamicProperties,
+}
+
+impl SketchComponent {
+    /// Heading of the component used in the text rendering of the report.
+    pub fn as_heading(&self) -> &str {
+        match self {
+            SketchComponent::Model => "MODEL",
+            SketchComponent::Datasets => "DATASETS",
+            SketchComponent::StaticProperties => "STATIC PROPERTIES",
+            SketchComponent::DynamicProperties => "DYNAMIC PROPERTIES",
+        }
+    }
+}
+
+/// A single issue found during the consistency check.
+///
+/// The `entity_id` is an ID of the particular entity (variable, dataset, property, ...) the
+/// issue relates to, if there is one.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ConsistencyIssue {
+    pub severity: IssueSeverity,
+    pub component: SketchComponent,
+    pub entity_id: Option<String>,
+    pub message: String,
+}
+
+impl<'de> JsonSerde<'de> for ConsistencyIssue {}
+
+impl Display for ConsistencyIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let severity = match self.severity {
+            IssueSeverity::Error => "ISSUE",
+            IssueSeverity::Warning => "WARNING",
+        };
+        if let Some(entity_id) = &self.entity_id {
+            write!(f, "> {severity} with `{entity_id}`: {}", self.message)
+        } else {
+            write!(f, "> {severity}: {}", self.message)
+        }
+    }
+}
+
+/// Results of the sketch consistency check, i.e., a list of all discovered issues.
+///
+/// The report can be serialized to JSON, or rendered as a human-readable text (by its
+/// `Display` implementation, or with a summary using [ConsistencyReport::summary]).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConsistencyReport {
+    issues: Vec<ConsistencyIssue>,
+}
+
+impl<'de> JsonSerde<'de> for ConsistencyReport {}
+
+/// Creating and editing consistency reports.
+impl ConsistencyReport {
+    /// Create new empty report.
+    pub fn new() -> ConsistencyReport {
+        ConsistencyReport::default()
+    }
+
+    /// Add an issue to the report.
+    pub fn add_issue(&mut self, issue: ConsistencyIssue) {
+        self.issues.push(issue)
+    }
+
+    /// Add all issues of another report to this report.
+    pub fn extend(&mut self, other: ConsistencyReport) {
+        self.issues.extend(other.issues)
+    }
+
+    /// Add an error regarding given component (and optionally a particular entity).
+    pub fn add_error(&mut self, component: SketchComponent, entity_id: Option<&str>, msg: &str) {
+        self.add_issue(ConsistencyIssue {
+            severity: IssueSeverity::Error,
+            component,
+            entity_id: entity_id.map(|id| id.to_string()),
+            message: msg.to_string(),
+        })
+    }
+
+    /// Add a warning regarding given component (and optionally a particular entity).
+    pub fn add_warning(&mut self, component: SketchComponent, entity_id: Option<&str>, msg: &str) {
+        self.add_issue(ConsistencyIssue {
+            severity: IssueSeverity::Warning,
+            component,
+            entity_id: entity_id.map(|id| id.to_string()),
+            message: msg.to_string(),
+        })
+    }
+}
+
+/// Observing consistency reports.
+impl ConsistencyReport {
+    /// Check whether the sketch is consistent, i.e., there are no errors (but there might
+    /// be warnings).
+    pub fn is_consistent(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Check whether there are any warnings.
+    pub fn has_warnings(&self) -> bool {
+        self.warnings().next().is_some()
+    }
+
+    /// Check whether there are no issues at all (neither errors, nor warnings).
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Get a list of all issues.
+    pub fn issues(&self) -> &Vec<ConsistencyIssue> {
+        &self.issues
+    }
+
+    /// Iterate over all errors.
+    pub fn errors(&self) -> impl Iterator<Item = &ConsistencyIssue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == IssueSeverity::Error)
+    }
+
+    /// Iterate over all warnings.
+    pub fn warnings(&self) -> impl Iterator<Item = &ConsistencyIssue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == IssueSeverity::Warning)
+    }
+
+    /// Iterate over all issues regarding given component.
+    pub fn component_issues(
+        &self,
+        component: SketchComponent,
+    ) -> impl Iterator<Item = &ConsistencyIssue> {
+        self.issues.iter().filter(move |i| i.component == component)
+    }
+
+    /// Summary message of the consistency check, followed by the text rendering of all
+    /// discovered issues (if there are any).
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            "No issues with the sketch were discovered!".to_string()
+        } else if self.is_consistent() {
+            format!("No issues with the sketch were discovered, but there are warnings:\n\n{self}")
+        } else {
+            format!("There are issues with the sketch:\n\n{self}")
+        }
+    }
+}
+
+impl Display for ConsistencyReport {
+    /// Render the issues as text, grouped by sketch components.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let components = [
+            SketchComponent::Model,
+            SketchComponent::Datasets,
+            SketchComponent::StaticProperties,
+            SketchComponent::DynamicProperties,
+        ];
+        for component in components {
+            let mut issues = self.component_issues(component).peekable();
+            if issues.peek().is_none() {
+                continue;
+            }
+            writeln!(f, "{}:", component.as_heading())?;
+            for issue in issues {
+                writeln!(f, "{issue}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::{ConsistencyReport, JsonSerde, SketchComponent};
+
+    #[test]
+    /// Test basic functionality of the report, and its text and JSON rendering.
+    fn consistency_report() {
+        let mut report = ConsistencyReport::new();
+        assert!(report.is_consistent());
+        assert_eq!(report.to_string(), "");
+
+        report.add_warning(SketchComponent::Model, Some("A"), "Warning message.");
+        assert!(report.is_consistent());
+        report.add_error(
+            SketchComponent::DynamicProperties,
+            Some("p"),
+            "Error message.",
+        );
+        report.add_error(SketchComponent::Model, None, "Another error.");
+        assert!(!report.is_consistent());
+        assert_eq!(report.errors().count(), 2);
+
+        let expected_text = "MODEL:\n> WARNING with `A`: Warning message.\n> ISSUE: Another error.\n\nDYNAMIC PROPERTIES:\n> ISSUE with `p`: Error message.\n\n";
+        assert_eq!(report.to_string(), expected_text);
+
+        let report_json = report.to_json_str();
+        assert!(report_json.contains(r#""severity":"Warning","component":"Model""#));
+        assert_eq!(
+            ConsistencyReport::from_json_str(&report_json).unwrap(),
+            report
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// A property that was dropped when extracting a sub-sketch, together with the reason.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DroppedProperty {
+    pub id: String,
+    pub reason: String,
+}
+
+/// Report summarizing the extraction of a sub-sketch induced by a set of variables.
+///
+/// The `inputs` are variables from outside of the selection that were added as inputs of
+/// the sub-sketch, and `dropped_properties` are properties that were valid in the original
+/// sketch, but referred to removed components (variables, regulations, functions, ...).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExtractionReport {
+    pub inputs: Vec<String>,
+    pub dropped_properties: Vec<DroppedProperty>,
+}
+
+impl<'de> JsonSerde<'de> for ExtractionReport {}
+
+impl ExtractionReport {
+    /// Create new empty report.
+    pub fn new() -> ExtractionReport {
+        ExtractionReport::default()
+    }
+
+    /// Record a property dropped for the given reason.
+    pub fn add_dropped(&mut self, id: &str, reason: &str) {
+        self.dropped_properties.push(DroppedProperty {
+            id: id.to_string(),
+            reason: reason.to_string(),
+        })
+    }
+
+    /// Check whether all properties were kept in the sub-sketch.
+    pub fn is_complete(&self) -> bool {
+        self.dropped_properties.is_empty()
+    }
+}
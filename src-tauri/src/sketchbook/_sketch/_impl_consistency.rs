@@ -1,15 +1,20 @@
+use crate::algorithms::eval_static::regulation_check::find_regulation_conflicts;
 use crate::sketchbook::ids::{DatasetId, ObservationId, UninterpretedFnId, VarId};
 use crate::sketchbook::properties::dynamic_props::DynPropertyType;
 use crate::sketchbook::properties::static_props::StatPropertyType;
 use crate::sketchbook::properties::{DynProperty, FirstOrderFormula, HctlFormula, StatProperty};
-use crate::sketchbook::Sketch;
+use crate::sketchbook::{ConsistencyReport, Sketch, SketchComponent};
 
 /// Utilities to perform consistency checks.
 impl Sketch {
     /// Assert that the sketch is consistent, return error otherwise.
     /// See [Self::run_consistency_check] for details on which criteria are checked.
+    ///
+    /// Only the checks that can result in errors are run (no warnings or lints are computed).
     pub fn assert_consistency(&self) -> Result<(), String> {
-        if self.run_consistency_check().0 {
+        let mut report = ConsistencyReport::new();
+        self.check_errors(&mut report);
+        if report.is_consistent() {
             Ok(())
         } else {
             Err("Sketch is not consistent.".to_string())
@@ -25,117 +30,137 @@ impl Sketch {
     ///
     /// This should include:
     /// - check that model is not empty
-    /// - check that dataset variables are valid network variables
+    /// - check that dataset variables (and perturbed variables) are valid network variables
     /// - check that various template properties reference valid variables and data
     /// - check that HCTL formulas only use valid variables as atomic propositions
-    /// - check that FOL formulas only use valid function symbols
-    pub fn run_consistency_check(&self) -> (bool, String) {
-        let mut all_consitent = true;
-        let mut message = String::new();
+    /// - check that FOL formulas only use valid function symbols (after expanding FOL macros)
+    /// - check that FOL macros do not clash with function symbols of the model
+    ///
+    /// Additionally, we check whether (fully or partially specified) update functions agree with
+    /// declared regulation signs and essentiality. Conflicts are only reported as warnings and
+    /// do not make the sketch inconsistent.
+    ///
+    /// The (more expensive) lint pass over the properties is not part of this check, and must
+    /// be run explicitly (see [Self::lint_properties]).
+    ///
+    /// All discovered issues are collected in a structured [ConsistencyReport].
+    pub fn run_consistency_check(&self) -> ConsistencyReport {
+        let mut report = ConsistencyReport::new();
+        self.check_errors(&mut report);
+
+        // warnings are reported, but do not affect consistency
+        self.check_update_fns(&mut report);
+        report
+    }
 
+    /// Run all parts of the consistency check that can discover errors.
+    /// Adds discovered issues to the report.
+    fn check_errors(&self, report: &mut ConsistencyReport) {
         // we divide the code by different components to avoid replication
-        let componets_results = vec![
-            self.check_model(),
-            self.check_datasets(),
-            self.check_static(),
-            self.check_dynamic(),
-        ];
-
-        for (consistent, sub_message) in componets_results {
-            if !consistent {
-                message += sub_message.as_str();
-                message += "\n";
-                all_consitent = false;
-            }
-        }
-
-        (all_consitent, message)
+        self.check_model(report);
+        self.check_datasets(report);
+        self.check_static(report);
+        self.check_dynamic(report);
     }
 
     /// Part of the consistency check responsible for the 'model' component.
-    /// Returns bool (whether a model is consistent) and formated message with issues.
-    fn check_model(&self) -> (bool, String) {
-        let mut consitent = true;
-        let mut message = String::new();
-        message += "MODEL:\n";
-
+    /// Adds discovered issues to the report.
+    fn check_model(&self, report: &mut ConsistencyReport) {
         if self.model.num_vars() == 0 {
-            consitent = false;
-            message += "> ISSUE: There must be at least one variable.\n";
+            let msg = "There must be at least one variable.";
+            report.add_error(SketchComponent::Model, None, msg);
         }
+    }
 
-        // TODO: in future, we can also add a check if update fn expressions match regulation properties
-
-        (consitent, message)
+    /// Part of the consistency check that verifies whether update function expressions agree with
+    /// declared monotonicity and essentiality of regulations.
+    /// Adds discovered conflicts to the report as warnings (regarding the target variables).
+    fn check_update_fns(&self, report: &mut ConsistencyReport) {
+        if self.model.num_vars() == 0 {
+            return;
+        }
+        match find_regulation_conflicts(&self.model) {
+            Ok(conflicts) => {
+                for conflict in conflicts {
+                    let target = conflict.target.as_str();
+                    report.add_warning(SketchComponent::Model, Some(target), &conflict.message);
+                }
+            }
+            Err(e) => {
+                let msg = format!("Update functions could not be checked. {e}");
+                report.add_warning(SketchComponent::Model, None, &msg);
+            }
+        }
     }
 
     /// Part of the consistency check responsible for the 'observations' (datasets) component.
-    /// Returns bool (whether a datasets are consistent) and formated message with issues.
-    fn check_datasets(&self) -> (bool, String) {
-        let mut message = String::new();
-        message += "DATASETS:\n";
-
-        let mut dataset_err_found = false;
+    /// Adds discovered issues to the report.
+    fn check_datasets(&self, report: &mut ConsistencyReport) {
         for (dataset_id, dataset) in self.observations.datasets() {
             // check that all dataset variables are part of the network
             for var_id in dataset.variables() {
                 if !self.model.is_valid_var_id(var_id) {
-                    let issue_var =
-                        format!("Variable {} is not present in the model.", var_id.as_str());
-                    let issue = format!(
-                        "> ISSUE with dataset `{}`: {issue_var}\n",
-                        dataset_id.as_str()
+                    let msg = format!("Variable {} is not present in the model.", var_id.as_str());
+                    report.add_error(SketchComponent::Datasets, Some(dataset_id.as_str()), &msg);
+                }
+            }
+            // check that all perturbed variables are part of the network
+            for var_id in dataset.perturbations().keys() {
+                if !self.model.is_valid_var_id(var_id) {
+                    let msg = format!(
+                        "Perturbed variable {} is not present in the model.",
+                        var_id.as_str()
                     );
-                    message += &issue;
-                    dataset_err_found = true;
+                    report.add_error(SketchComponent::Datasets, Some(dataset_id.as_str()), &msg);
                 }
             }
         }
-        (!dataset_err_found, message)
     }
 
     /// Part of the consistency check responsible for the 'static properties' component.
-    /// Returns bool (whether a static properties are consistent) and formated message with issues.
-    fn check_static(&self) -> (bool, String) {
-        let mut message = String::new();
-        message += "STATIC PROPERTIES:\n";
-
-        let mut stat_err_found = false;
+    /// Adds discovered issues to the report.
+    fn check_static(&self, report: &mut ConsistencyReport) {
+        let component = SketchComponent::StaticProperties;
+        for (macro_id, _) in self.properties.fol_macros() {
+            if self
+                .model
+                .is_valid_uninterpreted_fn_id_str(macro_id.as_str())
+            {
+                let msg = "Model has a function with the same ID as this macro.";
+                report.add_error(component, Some(macro_id.as_str()), msg);
+            }
+        }
         for (prop_id, prop) in self.properties.stat_props() {
             if let Err(e) = self.assert_static_prop_valid(prop) {
-                message = append_property_issue(&e, prop_id.as_str(), message);
-                stat_err_found = true;
+                report.add_error(component, Some(prop_id.as_str()), &e);
             }
         }
-        (!stat_err_found, message)
     }
 
     /// Part of the consistency check responsible for the 'dynamic properties' component.
-    /// Returns bool (whether a dynamic properties are consistent) and formated message with issues.
-    fn check_dynamic(&self) -> (bool, String) {
-        let mut message = String::new();
-        message += "DYNAMIC PROPERTIES:\n";
-
-        let mut dyn_err_found = false;
+    /// Adds discovered issues to the report.
+    fn check_dynamic(&self, report: &mut ConsistencyReport) {
+        let component = SketchComponent::DynamicProperties;
         for (prop_id, prop) in self.properties.dyn_props() {
             if let Err(e) = self.assert_dynamic_prop_valid(prop) {
-                message = append_property_issue(&e, prop_id.as_str(), message);
-                dyn_err_found = true;
+                report.add_error(component, Some(prop_id.as_str()), &e);
             }
         }
-        (!dyn_err_found, message)
     }
 
     /// Check if all fields of the static property are filled and have valid values.
     /// If not, return appropriate message.
-    fn assert_static_prop_valid(&self, prop: &StatProperty) -> Result<(), String> {
+    pub(super) fn assert_static_prop_valid(&self, prop: &StatProperty) -> Result<(), String> {
         // first just check if all required fields are filled out
         prop.assert_fully_filled()?;
 
         // now, let's validate the fields (we know the required ones are filled in)
         match prop.get_prop_data() {
             StatPropertyType::GenericStatProp(generic_prop) => {
-                FirstOrderFormula::check_syntax_with_model(&generic_prop.raw_formula, &self.model)?;
+                let formula = self
+                    .properties
+                    .expand_fol_macros(&generic_prop.raw_formula)?;
+                FirstOrderFormula::check_syntax_with_model(&formula, &self.model)?;
             }
             StatPropertyType::FnInputEssential(p)
             | StatPropertyType::FnInputEssentialContext(p) => {
@@ -167,7 +192,7 @@ impl Sketch {
 
     /// Check if all fields of the dynamic property are filled and have valid values.
     /// If not, return appropriate message.
-    fn assert_dynamic_prop_valid(&self, prop: &DynProperty) -> Result<(), String> {
+    pub(super) fn assert_dynamic_prop_valid(&self, prop: &DynProperty) -> Result<(), String> {
         // first just check if all required fields are filled out (that is usually the dataset ID)
         prop.assert_dataset_filled()?;
 
@@ -191,6 +216,15 @@ impl Sketch {
                 self.assert_dataset_valid(p.dataset.as_ref().unwrap())?;
                 self.assert_obs_valid_or_none(p.dataset.as_ref().unwrap(), p.observation.as_ref())?;
             }
+            DynPropertyType::StatesReachable(p) => {
+                let dataset_id = p.dataset.as_ref().unwrap();
+                self.assert_dataset_valid(dataset_id)?;
+                self.assert_obs_valid_or_none(dataset_id, p.initial_observation.as_ref())?;
+            }
+            DynPropertyType::StatesInBasins(p) => {
+                self.assert_dataset_valid(p.dataset.as_ref().unwrap())?;
+                self.assert_dataset_valid(p.phenotypes.as_ref().unwrap())?;
+            }
             DynPropertyType::AttractorCount(_) => {} // no fields that can be invalid
         }
         Ok(())
@@ -233,7 +267,11 @@ impl Sketch {
     /// The context can also be None, which is valid.
     fn assert_context_valid_or_none(&self, context: Option<&String>) -> Result<(), String> {
         if let Some(context_str) = context {
-            if let Err(e) = FirstOrderFormula::check_syntax_with_model(context_str, &self.model) {
+            let context_check = self
+                .properties
+                .expand_fol_macros(context_str)
+                .and_then(|c| FirstOrderFormula::check_syntax_with_model(&c, &self.model));
+            if let Err(e) = context_check {
                 let msg = format!("Invalid context formula. {e}");
                 return Err(msg);
             }
@@ -268,18 +306,11 @@ impl Sketch {
     }
 }
 
-/// **(internal)** Simple internal utility to append issue message regarding a particular property.
-fn append_property_issue(description: &str, prop_id: &str, mut log: String) -> String {
-    let issue = format!("> ISSUE with property `{}`: {description}\n", prop_id);
-    log += &issue;
-    log
-}
-
 #[cfg(test)]
 mod tests {
     use crate::sketchbook::observations::Dataset;
     use crate::sketchbook::properties::{DynProperty, StatProperty};
-    use crate::sketchbook::Sketch;
+    use crate::sketchbook::{Sketch, SketchComponent};
     use std::fs::File;
     use std::io::Read;
 
@@ -345,4 +376,23 @@ mod tests {
             .unwrap();
         assert!(sketch.assert_consistency().is_err());
     }
+
+    #[test]
+    /// Test that update functions contradicting regulation types are reported as warnings
+    /// (that do not make the sketch inconsistent).
+    fn consistency_update_fns() {
+        let sketch = Sketch::from_aeon("A -> B\nB -> A\n$A: B\n$B: A").unwrap();
+        let report = sketch.run_consistency_check();
+        assert!(report.is_empty());
+
+        // activation `A -> B` used as inhibition
+        let sketch = Sketch::from_aeon("A -> B\nB -> A\n$A: B\n$B: !A").unwrap();
+        let report = sketch.run_consistency_check();
+        assert!(report.is_consistent());
+        assert_eq!(report.warnings().count(), 1);
+        let warning = &report.warnings().next().unwrap();
+        assert_eq!(warning.component, SketchComponent::Model);
+        assert_eq!(warning.entity_id, Some("B".to_string()));
+        assert!(warning.message.contains("Regulation `A -> B`"));
+    }
 }
@@ -0,0 +1,453 @@
+use crate::sketchbook::_sketch::{DiffComponent, DiffKind, SketchDiff};
+use crate::sketchbook::data_structs::{DynPropertyData, StatPropertyData};
+use crate::sketchbook::model::{FnArgument, Regulation, UninterpretedFn, Variable};
+use crate::sketchbook::observations::{Dataset, Observation};
+use crate::sketchbook::properties::FolMacro;
+use crate::sketchbook::Sketch;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+/// Computing semantic differences between two sketches.
+impl Sketch {
+    /// Compute a semantic diff between this sketch (the original version) and the `other`
+    /// sketch (the new version).
+    ///
+    /// The diff lists all added, removed and changed variables, regulations (their sign and
+    /// essentiality), update functions, uninterpreted functions, datasets and their
+    /// observations, properties, FOL macros, and the sketch annotation. Entities are matched
+    /// by their IDs, so a renamed entity is reported as removed and added. Layouts are purely
+    /// visual, and thus ignored.
+    pub fn diff(&self, other: &Sketch) -> SketchDiff {
+        let mut diff = SketchDiff::new();
+
+        let variables: fn(&Sketch) -> BTreeMap<String, &Variable> = |s| {
+            s.model
+                .variables()
+                .map(|(id, var)| (id.to_string(), var))
+                .collect()
+        };
+        diff_entities(
+            &mut diff,
+            DiffComponent::Variables,
+            variables(self),
+            variables(other),
+            |old, new| {
+                let mut details = Vec::new();
+                describe_change(&mut details, "name", old.get_name(), new.get_name());
+                let (old_annot, new_annot) = (old.get_annotation(), new.get_annotation());
+                describe_change(&mut details, "annotation", old_annot, new_annot);
+                details
+            },
+        );
+
+        let regulations: fn(&Sketch) -> BTreeMap<String, &Regulation> = |s| {
+            s.model
+                .regulations()
+                .map(|r| (format!("{} -> {}", r.get_regulator(), r.get_target()), r))
+                .collect()
+        };
+        diff_entities(
+            &mut diff,
+            DiffComponent::Regulations,
+            regulations(self),
+            regulations(other),
+            describe_regulation_change,
+        );
+
+        // unspecified (empty) update functions are treated as missing
+        let update_fns: fn(&Sketch) -> BTreeMap<String, &str> = |s| {
+            s.model
+                .update_fns()
+                .filter(|(_, update_fn)| !update_fn.is_unspecified())
+                .map(|(id, update_fn)| (id.to_string(), update_fn.get_fn_expression()))
+                .collect()
+        };
+        diff_entities(
+            &mut diff,
+            DiffComponent::UpdateFunctions,
+            update_fns(self),
+            update_fns(other),
+            |old, new| {
+                let mut details = Vec::new();
+                describe_change(&mut details, "expression", old, new);
+                details
+            },
+        );
+
+        let uninterpreted_fns: fn(&Sketch) -> BTreeMap<String, &UninterpretedFn> = |s| {
+            s.model
+                .uninterpreted_fns()
+                .map(|(id, f)| (id.to_string(), f))
+                .collect()
+        };
+        diff_entities(
+            &mut diff,
+            DiffComponent::UninterpretedFunctions,
+            uninterpreted_fns(self),
+            uninterpreted_fns(other),
+            |old, new| {
+                let mut details = Vec::new();
+                describe_change(&mut details, "name", old.get_name(), new.get_name());
+                let (old_annot, new_annot) = (old.get_annotation(), new.get_annotation());
+                describe_change(&mut details, "annotation", old_annot, new_annot);
+                describe_change(&mut details, "arity", old.get_arity(), new.get_arity());
+                let (old_args, new_args) = (old.get_all_arguments(), new.get_all_arguments());
+                describe_change(
+                    &mut details,
+                    "arguments",
+                    args_str(old_args),
+                    args_str(new_args),
+                );
+                let (old_expr, new_expr) = (old.get_fn_expression(), new.get_fn_expression());
+                describe_change(&mut details, "expression", old_expr, new_expr);
+                details
+            },
+        );
+
+        self.diff_datasets(other, &mut diff);
+
+        let stat_props: fn(&Sketch) -> BTreeMap<String, StatPropertyData> = |s| {
+            s.properties
+                .stat_props()
+                .map(|(id, p)| (id.to_string(), StatPropertyData::from_property(id, p)))
+                .collect()
+        };
+        diff_entities(
+            &mut diff,
+            DiffComponent::StaticProperties,
+            stat_props(self),
+            stat_props(other),
+            |old, new| {
+                let mut details = Vec::new();
+                describe_change(&mut details, "name", &old.name, &new.name);
+                describe_change(&mut details, "annotation", &old.annotation, &new.annotation);
+                let old_def = serde_json::to_string(&old.variant).unwrap();
+                let new_def = serde_json::to_string(&new.variant).unwrap();
+                describe_change(&mut details, "definition", old_def, new_def);
+                details
+            },
+        );
+
+        let dyn_props: fn(&Sketch) -> BTreeMap<String, DynPropertyData> = |s| {
+            s.properties
+                .dyn_props()
+                .map(|(id, p)| (id.to_string(), DynPropertyData::from_property(id, p)))
+                .collect()
+        };
+        diff_entities(
+            &mut diff,
+            DiffComponent::DynamicProperties,
+            dyn_props(self),
+            dyn_props(other),
+            |old, new| {
+                let mut details = Vec::new();
+                describe_change(&mut details, "name", &old.name, &new.name);
+                describe_change(&mut details, "annotation", &old.annotation, &new.annotation);
+                let old_def = serde_json::to_string(&old.variant).unwrap();
+                let new_def = serde_json::to_string(&new.variant).unwrap();
+                describe_change(&mut details, "definition", old_def, new_def);
+                details
+            },
+        );
+
+        let fol_macros: fn(&Sketch) -> BTreeMap<String, &FolMacro> = |s| {
+            s.properties
+                .fol_macros()
+                .map(|(id, m)| (id.to_string(), m))
+                .collect()
+        };
+        diff_entities(
+            &mut diff,
+            DiffComponent::FolMacros,
+            fol_macros(self),
+            fol_macros(other),
+            |old, new| {
+                let mut details = Vec::new();
+                describe_change(&mut details, "name", old.get_name(), new.get_name());
+                let (old_annot, new_annot) = (old.get_annotation(), new.get_annotation());
+                describe_change(&mut details, "annotation", old_annot, new_annot);
+                let (old_params, new_params) = (old.get_params(), new.get_params());
+                describe_change(
+                    &mut details,
+                    "params",
+                    old_params.join(", "),
+                    new_params.join(", "),
+                );
+                describe_change(&mut details, "body", old.get_raw_body(), new.get_raw_body());
+                details
+            },
+        );
+
+        if self.annotation != other.annotation {
+            let details = vec!["annotation text changed".to_string()];
+            diff.add_entry(
+                DiffKind::Changed,
+                DiffComponent::Annotation,
+                "sketch",
+                details,
+            );
+        }
+        diff
+    }
+
+    /// **(internal)** Add differences of datasets of this and the `other` sketch to the `diff`.
+    /// For datasets present in both sketches, also the differences of their observations are
+    /// listed (with IDs in a form `dataset/observation`).
+    fn diff_datasets<'a>(&'a self, other: &'a Sketch, diff: &mut SketchDiff) {
+        let datasets: fn(&Sketch) -> BTreeMap<String, &Dataset> = |s| {
+            s.observations
+                .datasets()
+                .map(|(id, d)| (id.to_string(), d))
+                .collect()
+        };
+        let (old_datasets, new_datasets) = (datasets(self), datasets(other));
+        diff_entities(
+            diff,
+            DiffComponent::Datasets,
+            old_datasets.clone(),
+            new_datasets.clone(),
+            |old, new| {
+                let mut details = Vec::new();
+                describe_change(&mut details, "name", old.get_name(), new.get_name());
+                let (old_annot, new_annot) = (old.get_annotation(), new.get_annotation());
+                describe_change(&mut details, "annotation", old_annot, new_annot);
+                let (old_vars, new_vars) = (old.variable_names(), new.variable_names());
+                describe_change(
+                    &mut details,
+                    "variables",
+                    old_vars.join(", "),
+                    new_vars.join(", "),
+                );
+                let old_perturbations = format!("{:?}", old.perturbations());
+                let new_perturbations = format!("{:?}", new.perturbations());
+                describe_change(
+                    &mut details,
+                    "perturbations",
+                    old_perturbations,
+                    new_perturbations,
+                );
+                let multi_valued_str = |d: &Dataset| {
+                    let vars: Vec<_> = d
+                        .multi_valued_vars()
+                        .iter()
+                        .map(|v| format!("{}[{}]", v.get_name(), v.levels().join("|")))
+                        .collect();
+                    vars.join(", ")
+                };
+                describe_change(
+                    &mut details,
+                    "multi-valued variables",
+                    multi_valued_str(old),
+                    multi_valued_str(new),
+                );
+                // changes of individual observations are listed separately
+                let obs_ids = |d: &Dataset| {
+                    let ids: Vec<_> = d
+                        .observations()
+                        .iter()
+                        .map(|o| o.get_id().to_string())
+                        .collect();
+                    ids.join(", ")
+                };
+                describe_change(&mut details, "observations", obs_ids(old), obs_ids(new));
+                if obs_ids(old) == obs_ids(new) && old.observations() != new.observations() {
+                    details.push("observation contents changed".to_string());
+                }
+                details
+            },
+        );
+
+        for (dataset_id, old_dataset) in old_datasets {
+            let Some(new_dataset) = new_datasets.get(&dataset_id) else {
+                continue;
+            };
+            let observations = |d: &'a Dataset| -> BTreeMap<String, &'a Observation> {
+                d.observations()
+                    .iter()
+                    .map(|o| (format!("{dataset_id}/{}", o.get_id()), o))
+                    .collect()
+            };
+            diff_entities(
+                diff,
+                DiffComponent::Observations,
+                observations(old_dataset),
+                observations(new_dataset),
+                |old, new| {
+                    let mut details = Vec::new();
+                    describe_change(&mut details, "name", old.get_name(), new.get_name());
+                    let (old_annot, new_annot) = (old.get_annotation(), new.get_annotation());
+                    describe_change(&mut details, "annotation", old_annot, new_annot);
+                    describe_change(&mut details, "weight", old.get_weight(), new.get_weight());
+                    let (old_values, new_values) = (old.to_values_string(), new.to_values_string());
+                    describe_change(&mut details, "values", old_values, new_values);
+                    details
+                },
+            );
+        }
+    }
+}
+
+/// **(internal)** Add differences between two collections of entities (given by maps from
+/// their IDs) to the `diff`. The `describe` function lists details of changed entities. If it
+/// finds no details for entities that differ, a generic detail is reported instead.
+fn diff_entities<T: PartialEq>(
+    diff: &mut SketchDiff,
+    component: DiffComponent,
+    old: BTreeMap<String, T>,
+    new: BTreeMap<String, T>,
+    describe: impl Fn(&T, &T) -> Vec<String>,
+) {
+    let ids: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for id in ids {
+        match (old.get(id), new.get(id)) {
+            (Some(_), None) => diff.add_entry(DiffKind::Removed, component, id, Vec::new()),
+            (None, Some(_)) => diff.add_entry(DiffKind::Added, component, id, Vec::new()),
+            (Some(old_entity), Some(new_entity)) if old_entity != new_entity => {
+                let mut details = describe(old_entity, new_entity);
+                if details.is_empty() {
+                    details.push("changed in details that are not listed".to_string());
+                }
+                diff.add_entry(DiffKind::Changed, component, id, details);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// **(internal)** If the `old` and `new` values of a `field` differ, add a description of the
+/// change to `details`.
+fn describe_change(details: &mut Vec<String>, field: &str, old: impl Display, new: impl Display) {
+    let (old, new) = (old.to_string(), new.to_string());
+    if old != new {
+        details.push(format!("{field}: `{old}` -> `{new}`"));
+    }
+}
+
+/// **(internal)** Describe changes of the sign and essentiality of a regulation.
+fn describe_regulation_change(old: &&Regulation, new: &&Regulation) -> Vec<String> {
+    let mut details = Vec::new();
+    let (old_sign, new_sign) = (old.get_sign(), new.get_sign());
+    describe_change(
+        &mut details,
+        "sign",
+        format!("{old_sign:?}"),
+        format!("{new_sign:?}"),
+    );
+    let (old_ess, new_ess) = (old.get_essentiality(), new.get_essentiality());
+    describe_change(
+        &mut details,
+        "essentiality",
+        format!("{old_ess:?}"),
+        format!("{new_ess:?}"),
+    );
+    details
+}
+
+/// **(internal)** Render the monotonicity and essentiality of function arguments.
+fn args_str(arguments: &[FnArgument]) -> String {
+    let args: Vec<String> = arguments
+        .iter()
+        .map(|arg| format!("({:?}, {:?})", arg.monotonicity, arg.essential))
+        .collect();
+    args.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::Monotonicity;
+    use crate::sketchbook::observations::{Dataset, MultiValuedVar, Observation};
+    use crate::sketchbook::{DiffComponent, DiffKind, JsonSerde, Sketch, SketchDiff};
+
+    #[test]
+    /// Test computing differences between two versions of a sketch, and rendering them.
+    fn test_diff() {
+        let mut old = Sketch::from_aeon("A -> B\nB -| A\n$B: A\n").unwrap();
+        let obs = Observation::try_from_str("10", "o").unwrap();
+        let dataset = Dataset::new("d", vec![obs], vec!["A", "B"]).unwrap();
+        old.observations.add_dataset_by_str("d", dataset).unwrap();
+        assert!(old.diff(&old).is_empty());
+
+        let mut new = old.clone();
+        let (var_a, var_b) = (VarId::new("A").unwrap(), VarId::new("B").unwrap());
+        new.model.add_var_by_str("C", "C", "").unwrap();
+        new.model.set_var_name(&var_a, "a").unwrap();
+        new.model
+            .change_regulation_sign(&var_b, &var_a, &Monotonicity::Activation)
+            .unwrap();
+        new.model.set_update_fn(&var_b, "!A").unwrap();
+        new.model.set_update_fn(&var_a, "B").unwrap();
+        let dataset_id = new.observations.get_dataset_id("d").unwrap();
+        let obs = Observation::try_from_str("01", "o").unwrap();
+        new.observations
+            .swap_dataset_content(
+                &dataset_id,
+                Dataset::new("d", vec![obs], vec!["A", "B"]).unwrap(),
+            )
+            .unwrap();
+
+        let diff = old.diff(&new);
+        let entries: Vec<_> = diff
+            .entries()
+            .iter()
+            .map(|e| (e.component, e.kind, e.id.as_str()))
+            .collect();
+        let expected = vec![
+            (DiffComponent::Variables, DiffKind::Changed, "A"),
+            (DiffComponent::Variables, DiffKind::Added, "C"),
+            (DiffComponent::Regulations, DiffKind::Changed, "B -> A"),
+            (DiffComponent::UpdateFunctions, DiffKind::Added, "A"),
+            (DiffComponent::UpdateFunctions, DiffKind::Changed, "B"),
+            (DiffComponent::Datasets, DiffKind::Changed, "d"),
+            (DiffComponent::Observations, DiffKind::Changed, "d/o"),
+        ];
+        // static properties generated for the changed regulation also differ
+        assert_eq!(entries[..expected.len()], expected);
+        assert!(diff
+            .component_entries(DiffComponent::StaticProperties)
+            .all(|e| e.id.ends_with("_B_A")));
+        assert_eq!(
+            diff.entries()[2].details,
+            vec!["sign: `Inhibition` -> `Activation`"]
+        );
+
+        // reversed diff swaps added and removed entities
+        let reversed = new.diff(&old);
+        assert_eq!(reversed.entries()[1].kind, DiffKind::Removed);
+
+        // text and JSON rendering
+        let summary = diff.summary();
+        assert!(summary.contains("VARIABLES:\n~ A\n    name: `A` -> `a`\n+ C\n"));
+        assert!(summary.contains("d/o\n    values: `10` -> `01`"));
+        assert_eq!(
+            SketchDiff::from_json_str(&diff.to_json_str()).unwrap(),
+            diff
+        );
+    }
+
+    #[test]
+    /// Test that changed datasets are reported even if only their multi-valued variables differ.
+    fn test_diff_multi_valued_vars() {
+        let mut old = Sketch::from_aeon("A -> B\n").unwrap();
+        let obs = Observation::try_from_str("10", "o").unwrap();
+        let dataset = Dataset::new("d", vec![obs], vec!["A", "X_1"]).unwrap();
+        old.observations.add_dataset_by_str("d", dataset).unwrap();
+
+        let mut new = old.clone();
+        let dataset_id = new.observations.get_dataset_id("d").unwrap();
+        let var = MultiValuedVar::new("X", vec!["low", "high"]).unwrap();
+        new.observations
+            .set_all_multi_valued_vars(&dataset_id, vec![var])
+            .unwrap();
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.entries().len(), 1);
+        let entry = &diff.entries()[0];
+        assert_eq!(entry.component, DiffComponent::Datasets);
+        assert_eq!(entry.kind, DiffKind::Changed);
+        assert_eq!(
+            entry.details,
+            vec!["multi-valued variables: `` -> `X[low|high]`"]
+        );
+    }
+}
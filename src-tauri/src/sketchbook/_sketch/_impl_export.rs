@@ -1,12 +1,21 @@
-use biodivine_lib_param_bn::ModelAnnotation;
+use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate, ModelAnnotation, RegulatoryGraph};
 
 use crate::sketchbook::data_structs::{
-    DatasetData, DynPropertyData, SketchData, StatPropertyData, UninterpretedFnData, VariableData,
+    DatasetData, DynPropertyData, FolMacroData, LayoutData, RegulationData, SketchData,
+    StatPropertyData, UninterpretedFnData, VariableData,
 };
+use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+use crate::sketchbook::properties::static_props::StatPropertyType;
 use crate::sketchbook::{JsonSerde, Sketch};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 
+/// Namespace of the SBML annotation element carrying sketch-specific data.
+pub(super) const SBML_ANNOTATION_NAMESPACE: &str = "http://biodivine.fi.muni.cz/sketchbook";
+/// Name of the SBML annotation element carrying sketch-specific data.
+pub(super) const SBML_ANNOTATION_ELEMENT: &str = "sketchbook:data";
+
 impl Sketch {
     /// Convert the sketch instance into a custom (pretty) JSON string.
     ///
@@ -36,10 +45,14 @@ impl Sketch {
     /// This format is compatible with other biodivine tools, but might not cover all
     /// parts of the sketch.
     ///
-    /// Apart from that, most remaining details of the sketch are given via model annotations.
-    /// Currently the annotations are given simpy as
+    /// Apart from that, all remaining details of the sketch are given via model annotations, so
+    /// that the sketch can be loaded back without any loss. Currently the annotations are given
+    /// simpy as
     ///   #!entity_type: ID: #`json_string`#
-    /// These entities can be variables, functions, static/dynamic properties, and datasets.
+    /// These entities can be variables, functions, static/dynamic properties, FOL macros,
+    /// datasets, additional layouts, and regulations with types not covered by the standard
+    /// format. The annotation of the whole sketch is given as
+    ///   #!sketch_annotation: #`json_string`#
     pub fn to_aeon(&self) -> String {
         // for standard part of aeon format, we use the transformation into aeon BN
         // this loses some info (like new regulation types), but that is preserved via annotations
@@ -56,13 +69,46 @@ impl Sketch {
         }
 
         // set the rest using aeon model annotations
+        let annotation_str = self.to_model_annotation().to_string();
+        aeon_str.push_str(&annotation_str);
+        aeon_str
+    }
+
+    /// Export the sketch instance into a customized version of AEON model format.
+    ///
+    /// See [Sketch::to_aeon] for details on the actual conversion.
+    pub fn export_to_aeon(&self, filepath: &str) -> Result<(), String> {
+        let aeon_str = self.to_aeon();
+        let mut file = File::create(filepath).map_err(|e| e.to_string())?;
+        // write sketch in AEON to the file
+        file.write_all(aeon_str.as_bytes())
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// **(internal)** Collect all details of the sketch not covered by the standard model
+    /// formats (properties, datasets, FOL macros, variable and function details, additional
+    /// layouts, ...) into a model annotation object, with entities given as
+    ///   #!entity_type: ID: #`json_string`#
+    fn to_model_annotation(&self) -> ModelAnnotation {
         let mut annotation = ModelAnnotation::new();
 
+        // set annotation of the whole sketch (as JSON string, so that it fits on a single line)
+        if !self.annotation.is_empty() {
+            let annotation_json = serde_json::to_string(&self.annotation).unwrap();
+            annotation.ensure_value(&["sketch_annotation"], &annotation_json);
+        }
+
         // set static properties
         for (id, stat_prop) in self.properties.stat_props() {
             let prop_data_json = StatPropertyData::from_property(id, stat_prop).to_json_str();
             annotation.ensure_value(&["static_property", id.as_str()], &prop_data_json);
         }
+        // set FOL macros
+        for (id, fol_macro) in self.properties.fol_macros() {
+            let macro_data_json = FolMacroData::from_macro(id, fol_macro).to_json_str();
+            annotation.ensure_value(&["fol_macro", id.as_str()], &macro_data_json);
+        }
         // set dynamic properties
         for (id, dyn_prop) in self.properties.dyn_props() {
             let prop_data_json = DynPropertyData::from_property(id, dyn_prop).to_json_str();
@@ -84,21 +130,184 @@ impl Sketch {
             let fn_data_json = UninterpretedFnData::from_fn(fn_id, uninterpreted_fn).to_json_str();
             annotation.ensure_value(&["function", fn_id.as_str()], &fn_data_json);
         }
+        // set regulations with types that get lost in the standard formats (dual monotonicity
+        // and non-essentiality can not be represented in `BooleanNetwork`)
+        for regulation in self.model.regulations() {
+            if regulation.get_sign() == &Monotonicity::Dual
+                || regulation.get_essentiality() == &Essentiality::False
+            {
+                let reg_id = format!(
+                    "{}->{}",
+                    regulation.get_regulator(),
+                    regulation.get_target()
+                );
+                let reg_data_json = RegulationData::from_reg(regulation).to_json_str();
+                annotation.ensure_value(&["regulation", &reg_id], &reg_data_json);
+            }
+        }
+        // set additional layouts (default layout is covered by the standard formats)
+        let default_layout = ModelState::get_default_layout_id();
+        for (layout_id, layout) in self.model.layouts() {
+            if layout_id != &default_layout {
+                let layout_data_json = LayoutData::from_layout(layout_id, layout).to_json_str();
+                annotation.ensure_value(&["layout", layout_id.as_str()], &layout_data_json);
+            }
+        }
+        annotation
+    }
+}
 
-        // push the annotations to the aeon string
-        let annotation_str = annotation.to_string();
-        aeon_str.push_str(&annotation_str);
-        aeon_str
+impl Sketch {
+    /// Convert the sketch instance into a BoolNet `.bnet` model format, together with a list
+    /// of warnings about parts of the sketch that can not be represented in the format.
+    ///
+    /// The `.bnet` format only covers variables and fully specified update functions. Update
+    /// functions that are unspecified or contain uninterpreted functions are written as comments,
+    /// and their variables are exported as free inputs (e.g., `X, X`), so that no variable is
+    /// lost. The same holds for variables without regulators and update functions. Properties,
+    /// datasets, layout, and details like variable names or annotations are not exported.
+    /// Warnings are also included as comments at the start of the model string.
+    pub fn to_bnet(&self) -> Result<(String, Vec<String>), String> {
+        let bn = self.model.to_bn();
+        let mut warnings = Vec::new();
+        let mut unsupported_lines = Vec::new();
+
+        // variables whose functions can not be represented have all regulations removed, and
+        // are exported as free inputs (with identity update functions)
+        let mut graph = RegulatoryGraph::new(bn.as_graph().variable_names());
+        let mut supported_fns = Vec::new();
+        let mut input_vars = Vec::new();
+        for var in bn.variables() {
+            let var_name = bn.get_variable_name(var);
+            match bn.get_update_function(var) {
+                Some(update_fn) if update_fn.collect_parameters().is_empty() => {
+                    supported_fns.push((var, update_fn.clone()));
+                    for regulation in bn.as_graph().regulations() {
+                        if regulation.target == var {
+                            graph.add_raw_regulation(regulation.clone())?;
+                        }
+                    }
+                    continue;
+                }
+                Some(update_fn) => {
+                    let fn_str = update_fn.to_string(&bn);
+                    unsupported_lines.push(format!("# {var_name}, {fn_str}"));
+                    warnings.push(format!(
+                        "Update function of `{var_name}` uses uninterpreted functions, which can not be represented in bnet (exported as `{var_name}, {var_name}`)."
+                    ));
+                }
+                None if bn.regulators(var).is_empty() => {}
+                None => {
+                    unsupported_lines.push(format!("# {var_name}, ?"));
+                    warnings.push(format!(
+                        "Update function of `{var_name}` is unspecified, which can not be represented in bnet (exported as `{var_name}, {var_name}`)."
+                    ));
+                }
+            }
+            graph.add_regulation(var_name, var_name, false, None)?;
+            input_vars.push(var);
+        }
+        let mut bnet_model = BooleanNetwork::new(graph);
+        for (var, update_fn) in supported_fns {
+            bnet_model.set_update_function(var, Some(update_fn))?;
+        }
+        for var in input_vars {
+            bnet_model.set_update_function(var, Some(FnUpdate::Var(var)))?;
+        }
+
+        // static properties generated from regulations are covered by the model
+        let num_props = self.properties.dyn_props().count()
+            + self
+                .properties
+                .stat_props()
+                .filter(|(_, p)| {
+                    !matches!(
+                        p.get_prop_data(),
+                        StatPropertyType::RegulationEssential(_)
+                            | StatPropertyType::RegulationMonotonic(_)
+                    )
+                })
+                .count();
+        if num_props > 0 {
+            warnings.push(format!(
+                "{num_props} properties can not be represented in bnet."
+            ));
+        }
+        let num_datasets = self.observations.num_datasets();
+        if num_datasets > 0 {
+            warnings.push(format!(
+                "{num_datasets} datasets can not be represented in bnet."
+            ));
+        }
+
+        let mut bnet_str: String = warnings
+            .iter()
+            .map(|w| format!("# Warning: {w}\n"))
+            .collect();
+        bnet_str.push_str(&bnet_model.to_bnet(false)?);
+        for line in unsupported_lines {
+            bnet_str.push_str(&format!("{line}\n"));
+        }
+        Ok((bnet_str, warnings))
     }
 
-    /// Export the sketch instance into a customized version of AEON model format.
+    /// Export the sketch instance into a BoolNet `.bnet` model format, returning warnings
+    /// about the parts of the sketch that could not be exported.
     ///
-    /// See [Sketch::to_aeon] for details on the actual conversion.
-    pub fn export_to_aeon(&self, filepath: &str) -> Result<(), String> {
-        let aeon_str = self.to_aeon();
+    /// See [Sketch::to_bnet] for details on the actual conversion.
+    pub fn export_to_bnet(&self, filepath: &str) -> Result<Vec<String>, String> {
+        let (bnet_str, warnings) = self.to_bnet()?;
         let mut file = File::create(filepath).map_err(|e| e.to_string())?;
-        // write sketch in AEON to the file
-        file.write_all(aeon_str.as_bytes())
+        // write sketch in bnet to the file
+        file.write_all(bnet_str.as_bytes())
+            .map_err(|e| e.to_string())?;
+        Ok(warnings)
+    }
+}
+
+impl Sketch {
+    /// Convert the sketch instance into SBML-qual model format.
+    ///
+    /// The standard part of the format covers variables, regulations (with their signs), update
+    /// functions, and positions of the default layout. Unspecified update functions are left as
+    /// transitions without any function terms, and uninterpreted functions are written as
+    /// MathML `csymbol` applications.
+    ///
+    /// All the remaining details of the sketch (properties, datasets, ...) are preserved in
+    /// a model-level SBML annotation, using the same entities as in the AEON format (see
+    /// [Sketch::to_aeon]). These are recovered by [Sketch::from_sbml].
+    pub fn to_sbml(&self) -> String {
+        let bn = self.model.to_bn();
+        let default_layout = self.model.get_default_layout();
+        let layout: HashMap<String, (f64, f64)> = default_layout
+            .layout_nodes()
+            .map(|(var_id, node)| {
+                let pos = node.get_position();
+                (var_id.to_string(), (pos.0 as f64, pos.1 as f64))
+            })
+            .collect();
+        let sbml_str = bn.to_sbml(Some(&layout));
+
+        // annotation content is wrapped in CDATA section (which can not contain its end marker)
+        let annotation_str = self
+            .to_model_annotation()
+            .to_string()
+            .replace("]]>", "]]]]><![CDATA[>");
+        let annotation_element = format!(
+            "<annotation><{SBML_ANNOTATION_ELEMENT} xmlns:sketchbook=\"{SBML_ANNOTATION_NAMESPACE}\"><![CDATA[{annotation_str}]]></{SBML_ANNOTATION_ELEMENT}></annotation>"
+        );
+        // SBML annotation must be the first child of the model element
+        sbml_str.replacen("<model>", &format!("<model>{annotation_element}"), 1)
+    }
+
+    /// Export the sketch instance into SBML-qual model format.
+    ///
+    /// See [Sketch::to_sbml] for details on the actual conversion.
+    pub fn export_to_sbml(&self, filepath: &str) -> Result<(), String> {
+        let sbml_str = self.to_sbml();
+        let mut file = File::create(filepath).map_err(|e| e.to_string())?;
+        // write sketch in SBML to the file
+        file.write_all(sbml_str.as_bytes())
             .map_err(|e| e.to_string())?;
         Ok(())
     }
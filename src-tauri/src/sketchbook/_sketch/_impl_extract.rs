@@ -0,0 +1,237 @@
+use crate::sketchbook::_sketch::ExtractionReport;
+use crate::sketchbook::ids::{DatasetId, StatPropertyId, VarId};
+use crate::sketchbook::model::{Essentiality, InputMode, Monotonicity};
+use crate::sketchbook::properties::shortcuts::{mk_essentiality_prop, mk_monotonicity_prop};
+use crate::sketchbook::properties::StatProperty;
+use crate::sketchbook::Sketch;
+
+/// Extracting sub-sketches.
+impl Sketch {
+    /// Extract a sub-sketch induced by the given set of `variables`.
+    ///
+    /// The model is restricted to the selected variables, and regulators from outside of the
+    /// selection become inputs handled according to `input_mode` (see
+    /// [crate::sketchbook::model::ModelState::extract_submodel]). Datasets are projected to
+    /// the columns of the remaining variables. Properties that were valid in this sketch, but
+    /// refer to removed variables, regulations, functions, or observations, are dropped.
+    ///
+    /// Returns the sub-sketch together with a report listing the inputs and all dropped
+    /// properties.
+    pub fn extract_sub_sketch(
+        &self,
+        variables: &[VarId],
+        input_mode: InputMode,
+    ) -> Result<(Sketch, ExtractionReport), String> {
+        let (model, inputs) = self.model.extract_submodel(variables, input_mode)?;
+        let mut sub_sketch = Sketch {
+            model,
+            observations: self.observations.clone(),
+            properties: self.properties.clone(),
+            annotation: self.annotation.clone(),
+        };
+        let mut report = ExtractionReport::new();
+        report.inputs = inputs.iter().map(|v| v.to_string()).collect();
+
+        sub_sketch.project_datasets_to_model()?;
+
+        // self-regulations of constant sources get the corresponding generated properties
+        if input_mode == InputMode::ConstantSource {
+            for input in &inputs {
+                let prop_id = StatProperty::get_essentiality_prop_id(input, input);
+                let prop = mk_essentiality_prop(input, input, Essentiality::True);
+                sub_sketch.replace_stat_prop(prop_id, prop)?;
+                let prop_id = StatProperty::get_monotonicity_prop_id(input, input);
+                let prop = mk_monotonicity_prop(input, input, Monotonicity::Activation);
+                sub_sketch.replace_stat_prop(prop_id, prop)?;
+            }
+        }
+
+        // drop properties that are no longer valid (but keep those that were invalid before)
+        let mut stat_props: Vec<_> = sub_sketch
+            .properties
+            .stat_props()
+            .map(|(id, p)| (id.clone(), p.clone()))
+            .collect();
+        stat_props.sort_by_key(|(id, _)| id.clone());
+        for (prop_id, prop) in stat_props {
+            if self.assert_static_prop_valid(&prop).is_err() {
+                continue;
+            }
+            let validity = sub_sketch
+                .assert_static_prop_valid(&prop)
+                .and_then(|_| sub_sketch.assert_regulation_present(&prop));
+            if let Err(reason) = validity {
+                sub_sketch.properties.remove_static(&prop_id)?;
+                report.add_dropped(prop_id.as_str(), &reason);
+            }
+        }
+
+        let mut dyn_props: Vec<_> = sub_sketch
+            .properties
+            .dyn_props()
+            .map(|(id, p)| (id.clone(), p.clone()))
+            .collect();
+        dyn_props.sort_by_key(|(id, _)| id.clone());
+        for (prop_id, prop) in dyn_props {
+            if self.assert_dynamic_prop_valid(&prop).is_err() {
+                continue;
+            }
+            if let Err(reason) = sub_sketch.assert_dynamic_prop_valid(&prop) {
+                sub_sketch.properties.remove_dynamic(&prop_id)?;
+                report.add_dropped(prop_id.as_str(), &reason);
+            }
+        }
+
+        Ok((sub_sketch, report))
+    }
+
+    /// **(internal)** Project all datasets to the columns of variables present in the model,
+    /// removing the columns (and perturbations) of the variables that are not there.
+    pub(super) fn project_datasets_to_model(&mut self) -> Result<(), String> {
+        let dataset_ids: Vec<DatasetId> = self
+            .observations
+            .datasets()
+            .map(|(id, _)| id.clone())
+            .collect();
+        for dataset_id in dataset_ids {
+            let dataset = self.observations.get_dataset(&dataset_id)?;
+            let removed_vars: Vec<VarId> = dataset
+                .variables()
+                .iter()
+                .filter(|v| !self.model.is_valid_var_id(v))
+                .cloned()
+                .collect();
+            // perturbed variables do not have to be captured by the dataset's observations
+            let removed_perturbations: Vec<VarId> = dataset
+                .perturbations()
+                .keys()
+                .filter(|v| !self.model.is_valid_var_id(v))
+                .cloned()
+                .collect();
+            for var_id in removed_perturbations {
+                self.observations
+                    .set_perturbation(&dataset_id, var_id, None)?;
+            }
+            for var_id in removed_vars {
+                self.observations.remove_var(&dataset_id, &var_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Check that a regulation property refers to an existing regulation. Other
+    /// kinds of properties are always fine.
+    fn assert_regulation_present(&self, prop: &StatProperty) -> Result<(), String> {
+        if let Ok((Some(regulator), Some(target))) = prop.clone().get_regulator_and_target() {
+            if self.model.get_regulation(&regulator, &target).is_err() {
+                let msg = format!("Regulation `{regulator} -> {target}` is not in the model.");
+                return Err(msg);
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Add static property with given ID, replacing the previous one (if any).
+    fn replace_stat_prop(
+        &mut self,
+        prop_id: StatPropertyId,
+        prop: StatProperty,
+    ) -> Result<(), String> {
+        if self.properties.is_valid_stat_property_id(&prop_id) {
+            self.properties.remove_static(&prop_id)?;
+        }
+        self.properties.add_static(prop_id, prop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::{DatasetId, VarId};
+    use crate::sketchbook::model::InputMode;
+    use crate::sketchbook::observations::{Dataset, Observation, PerturbationType};
+    use crate::sketchbook::properties::shortcuts::mk_hctl_prop;
+    use crate::sketchbook::properties::DynProperty;
+    use crate::sketchbook::Sketch;
+
+    #[test]
+    /// Test extracting a sub-sketch, including projection of datasets and dropped properties.
+    fn test_extract_sub_sketch() {
+        let mut sketch = Sketch::from_aeon("A -> B\nB -| C\nC -> A\n$B: A\n").unwrap();
+        let obs = Observation::try_from_str("101", "o").unwrap();
+        let dataset = Dataset::new("d", vec![obs], vec!["A", "B", "C"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+        let dataset_id = DatasetId::new("d").unwrap();
+        let fixed_point = DynProperty::mk_fixed_point("fp", Some(dataset_id.clone()), None, "");
+        let properties = &mut sketch.properties;
+        properties.add_dynamic_by_str("fp", fixed_point).unwrap();
+        let hctl_prop = mk_hctl_prop("3{x}: @{x}: C").unwrap();
+        properties.add_dynamic_by_str("hctl", hctl_prop).unwrap();
+
+        let selected = vec![VarId::new("B").unwrap()];
+        let (sub_sketch, report) = sketch
+            .extract_sub_sketch(&selected, InputMode::Free)
+            .unwrap();
+        assert_eq!(report.inputs, vec!["A".to_string()]);
+        assert_eq!(sub_sketch.model.num_vars(), 2);
+        assert_eq!(sub_sketch.model.num_regulations(), 1);
+
+        // dataset is projected to the remaining variables
+        let dataset = sub_sketch.observations.get_dataset(&dataset_id).unwrap();
+        assert_eq!(dataset.num_variables(), 2);
+        let observation = &dataset.observations()[0];
+        assert_eq!(observation.to_values_string(), "10");
+
+        // properties of the removed regulations and the HCTL property are dropped
+        let dropped: Vec<_> = report
+            .dropped_properties
+            .iter()
+            .map(|p| p.id.as_str())
+            .collect();
+        assert_eq!(
+            dropped,
+            vec![
+                "essentiality_B_C",
+                "essentiality_C_A",
+                "monotonicity_B_C",
+                "monotonicity_C_A",
+                "hctl",
+            ]
+        );
+        assert!(!report.is_complete());
+        assert_eq!(sub_sketch.properties.num_dyn_properties(), 1);
+        assert_eq!(sub_sketch.properties.num_stat_properties(), 2);
+
+        // constant sources get a self-regulation with the generated properties
+        let mode = InputMode::ConstantSource;
+        let (sub_sketch, _) = sketch.extract_sub_sketch(&selected, mode).unwrap();
+        assert_eq!(sub_sketch.model.num_regulations(), 2);
+        assert_eq!(sub_sketch.properties.num_stat_properties(), 4);
+    }
+
+    #[test]
+    /// Test that perturbations of variables outside of the sub-model are dropped, even if the
+    /// variables are not captured by the dataset's observations.
+    fn test_extract_sub_sketch_perturbations() {
+        let mut sketch = Sketch::from_aeon("A -> B\nB -| C\nC -> A\n").unwrap();
+        let obs = Observation::try_from_str("10", "o").unwrap();
+        let mut dataset = Dataset::new("d", vec![obs], vec!["A", "B"]).unwrap();
+        let (var_b, var_c) = (VarId::new("B").unwrap(), VarId::new("C").unwrap());
+        dataset.set_perturbation(var_b.clone(), Some(PerturbationType::KnockOut));
+        dataset.set_perturbation(var_c, Some(PerturbationType::OverExpression));
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+
+        let selected = vec![var_b.clone()];
+        let (sub_sketch, _) = sketch
+            .extract_sub_sketch(&selected, InputMode::Free)
+            .unwrap();
+        let dataset = sub_sketch.observations.get_dataset_by_str("d").unwrap();
+        let perturbed: Vec<_> = dataset.perturbations().keys().collect();
+        assert_eq!(perturbed, vec![&var_b]);
+    }
+}
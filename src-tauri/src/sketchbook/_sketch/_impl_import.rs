@@ -1,7 +1,9 @@
+use crate::sketchbook::_sketch::_impl_export::SBML_ANNOTATION_ELEMENT;
 use crate::sketchbook::data_structs::{
-    DatasetData, DynPropertyData, SketchData, StatPropertyData, StatPropertyTypeData,
-    UninterpretedFnData, VariableData,
+    DatasetData, DynPropertyData, FolMacroData, LayoutData, RegulationData, SketchData,
+    StatPropertyData, StatPropertyTypeData, UninterpretedFnData, VariableData,
 };
+use crate::sketchbook::ids::{LayoutId, StatPropertyId};
 use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
 use crate::sketchbook::properties::shortcuts::*;
 use crate::sketchbook::properties::{DynProperty, StatProperty};
@@ -21,7 +23,8 @@ impl Sketch {
     /// Currently the annotations are given simpy as
     ///   #!entity_type: ID: #`json_string`#
     /// These annotations either cover additional information (complementing variables and
-    /// functions), or completely new components like static/dynamic properties and datasets.
+    /// functions), or completely new components like static/dynamic properties, FOL macros,
+    /// and datasets.
     ///
     /// We allow a special case for writing HCTL and FOL properties, compatible with the
     /// original BN sketches prototype format:
@@ -44,51 +47,124 @@ impl Sketch {
 
         // recover the remaining components from aeon model annotations
         let aeon_annotations = ModelAnnotation::from_model_string(aeon_str);
-        let variables = Self::extract_entities(&aeon_annotations, "variable")?;
-        let functions = Self::extract_entities(&aeon_annotations, "function")?;
-        let datasets = Self::extract_entities(&aeon_annotations, "dataset")?;
-        let stat_props = Self::extract_entities(&aeon_annotations, "static_property")?;
-        let dyn_props = Self::extract_entities(&aeon_annotations, "dynamic_property")?;
+        sketch.apply_model_annotation(&aeon_annotations)?;
+        Ok(sketch)
+    }
+
+    /// Create sketch instance from a SBML model format. This variant includes:
+    /// - variables
+    /// - regulations (and corresponding automatically generated static properties)
+    /// - update functions and function symbols
+    /// - layout information
+    ///
+    /// If the model contains a sketch annotation (as produced by [Sketch::to_sbml]), the
+    /// remaining components of the sketch (properties, datasets, ...) are recovered from it.
+    pub fn from_sbml(sbml_str: &str) -> Result<Sketch, String> {
+        // set psbn info (variables, functions, regulations and corresponding properties)
+        let (bn, layout_map) = BooleanNetwork::try_from_sbml(sbml_str)?;
+        let mut sketch = Sketch::from_boolean_network(&bn)?;
+
+        let default_layout = ModelState::get_default_layout_id();
+        for (node, (px, py)) in layout_map {
+            let node_id = sketch.model.get_var_id(&node)?;
+            sketch
+                .model
+                .update_position(&default_layout, &node_id, px as f32, py as f32)?;
+        }
+
+        if let Some(annotation_str) = Self::extract_sbml_annotation(sbml_str) {
+            let annotations = ModelAnnotation::from_model_string(&annotation_str);
+            sketch.apply_model_annotation(&annotations)?;
+        }
+        Ok(sketch)
+    }
+
+    /// **(internal)** Recover the sketch components given by the model annotation entities
+    /// (as produced by [Sketch::to_aeon] or [Sketch::to_sbml]), and add them to this sketch.
+    /// The sketch must already contain the corresponding model (variables, functions, ...).
+    fn apply_model_annotation(&mut self, annotations: &ModelAnnotation) -> Result<(), String> {
+        let variables = Self::extract_entities(annotations, "variable")?;
+        let functions = Self::extract_entities(annotations, "function")?;
+        let datasets = Self::extract_entities(annotations, "dataset")?;
+        let stat_props = Self::extract_entities(annotations, "static_property")?;
+        let dyn_props = Self::extract_entities(annotations, "dynamic_property")?;
+        let fol_macros = Self::extract_entities(annotations, "fol_macro")?;
+        let regulations = Self::extract_entities(annotations, "regulation")?;
+        let layouts = Self::extract_entities(annotations, "layout")?;
+
+        if let Some(annotation) = annotations.get_child(&["sketch_annotation"]) {
+            let annotation_json = annotation.value().map(|v| v.as_str()).unwrap_or("\"\"");
+            self.annotation = serde_json::from_str(annotation_json)
+                .map_err(|e| format!("Invalid sketch annotation. {e}"))?;
+        }
 
         // for variables and functions, there can be additional info (like names, annotations, ...)
         for (id, variable_str) in variables {
             let var_data = VariableData::from_json_str(&variable_str)?;
-            let var_id = sketch.model.get_var_id(&id)?;
-            sketch.model.set_raw_var(&var_id, var_data.to_var()?)?;
-            sketch.model.set_update_fn(&var_id, &var_data.update_fn)?;
+            let var_id = self.model.get_var_id(&id)?;
+            self.model.set_raw_var(&var_id, var_data.to_var()?)?;
+            self.model.set_update_fn(&var_id, &var_data.update_fn)?;
         }
         for (id, fn_str) in functions {
             let fn_data = UninterpretedFnData::from_json_str(&fn_str)?;
-            let fn_id = sketch.model.get_uninterpreted_fn_id(&id)?;
-            sketch
-                .model
-                .set_raw_function(&fn_id, fn_data.to_uninterpreted_fn(&sketch.model)?)?;
+            let fn_id = self.model.get_uninterpreted_fn_id(&id)?;
+            self.model
+                .set_raw_function(&fn_id, fn_data.to_uninterpreted_fn(&self.model)?)?;
+        }
+
+        // regulation types that are not covered by the standard formats
+        for (_, regulation_str) in regulations {
+            let reg_data = RegulationData::from_json_str(&regulation_str)?;
+            let regulator = self.model.get_var_id(&reg_data.regulator)?;
+            let target = self.model.get_var_id(&reg_data.target)?;
+            self.model
+                .change_regulation_sign(&regulator, &target, &reg_data.sign)?;
+            self.model
+                .change_regulation_essentiality(&regulator, &target, &reg_data.essential)?;
+        }
+        // layouts are added from scratch (or they replace the default layout)
+        for (id, layout_str) in layouts {
+            let layout = LayoutData::from_json_str(&layout_str)?.to_layout()?;
+            self.model
+                .add_or_update_layout_raw(LayoutId::new(&id)?, layout)?;
         }
 
         // datasets have to be added from scratch
         for (id, dataset_str) in datasets {
             let dataset_data = DatasetData::from_json_str(&dataset_str)?;
-            sketch
-                .observations
+            self.observations
                 .add_dataset_by_str(&id, dataset_data.to_dataset()?)?;
         }
 
+        // macros have to be added from scratch
+        for (id, macro_str) in fol_macros {
+            let macro_data = FolMacroData::from_json_str(&macro_str)?;
+            self.properties
+                .add_fol_macro_by_str(&id, macro_data.to_macro()?)?;
+        }
+
         // properties have to be added from scratch (apart from automatically generated static props)
         // we allow two modes - a JSON string for any property, or formula string for HCTL/FOL properties
         for (id, content_str) in stat_props {
             // try parsing formula
             if let Ok(prop) = StatProperty::try_mk_generic(&id, &content_str, "") {
-                sketch.properties.add_static_by_str(&id, prop)?
+                self.properties.add_static_by_str(&id, prop)?
             } else {
                 let prop_data = StatPropertyData::from_json_str(&content_str)?;
                 let property = prop_data.to_property()?;
 
-                // ignore automatically generated static props as they were added before
+                // automatically generated static props were added before (but possibly without
+                // all details, such as names or types not covered by standard formats)
+                let prop_id = StatPropertyId::new(&id)?;
                 match prop_data.variant {
                     StatPropertyTypeData::RegulationEssential(..)
-                    | StatPropertyTypeData::RegulationMonotonic(..) => {}
+                    | StatPropertyTypeData::RegulationMonotonic(..)
+                        if self.properties.is_valid_stat_property_id(&prop_id) =>
+                    {
+                        self.properties.swap_stat_content(&prop_id, property)?;
+                    }
                     _ => {
-                        sketch.properties.add_static_by_str(&id, property)?;
+                        self.properties.add_static(prop_id, property)?;
                     }
                 }
             }
@@ -96,37 +172,42 @@ impl Sketch {
         for (id, content_str) in dyn_props {
             // try parsing formula
             if let Ok(prop) = DynProperty::try_mk_generic(&id, &content_str, "") {
-                sketch.properties.add_dynamic_by_str(&id, prop)?
+                self.properties.add_dynamic_by_str(&id, prop)?
             } else {
                 let prop_data = DynPropertyData::from_json_str(&content_str)?;
-                sketch
-                    .properties
+                self.properties
                     .add_dynamic_by_str(&id, prop_data.to_property()?)?;
             }
         }
-
-        Ok(sketch)
+        Ok(())
     }
 
-    /// Create sketch instance from a SBML model format. This variant includes:
-    /// - variables
-    /// - regulations (and corresponding automatically generated static properties)
-    /// - update functions and function symbols
-    /// - layout information
-    pub fn from_sbml(sbml_str: &str) -> Result<Sketch, String> {
-        // set psbn info (variables, functions, regulations and corresponding properties)
-        let (bn, layout_map) = BooleanNetwork::try_from_sbml(sbml_str)?;
-        let mut sketch = Sketch::from_boolean_network(&bn)?;
-
-        let default_layout = ModelState::get_default_layout_id();
-        for (node, (px, py)) in layout_map {
-            let node_id = sketch.model.get_var_id(&node)?;
-            sketch
-                .model
-                .update_position(&default_layout, &node_id, px as f32, py as f32)?;
-        }
+    /// **(internal)** Extract the content of the sketch annotation element from a SBML string,
+    /// if there is any. The content is given as (possibly split) CDATA sections.
+    fn extract_sbml_annotation(sbml_str: &str) -> Option<String> {
+        let start_tag = format!("<{SBML_ANNOTATION_ELEMENT}");
+        let end_tag = format!("</{SBML_ANNOTATION_ELEMENT}>");
+        let start = sbml_str.find(&start_tag)?;
+        let content_start = start + sbml_str[start..].find('>')? + 1;
+        let content_end = content_start + sbml_str[content_start..].find(&end_tag)?;
+        let content = sbml_str[content_start..content_end].trim();
+        let content = content
+            .strip_prefix("<![CDATA[")?
+            .strip_suffix("]]>")?
+            .replace("]]><![CDATA[", "");
+        Some(content)
+    }
 
-        Ok(sketch)
+    /// Create sketch instance from a BoolNet `.bnet` model format. This variant includes:
+    /// - variables (both targets and variables only appearing as inputs)
+    /// - update functions
+    /// - regulations, inferred from the update functions, together with their signs and
+    ///   essentiality (and corresponding automatically generated static properties)
+    pub fn from_bnet(bnet_str: &str) -> Result<Sketch, String> {
+        let bn = BooleanNetwork::try_from_bnet(bnet_str)?;
+        // bnet has no regulations, so they are inferred (with monotonicity) from the functions
+        let bn = bn.infer_valid_graph()?;
+        Sketch::from_boolean_network(&bn)
     }
 
     /// Create sketch instance from a BooleanNetwork instance of `lib-param-bn`.
@@ -244,7 +325,8 @@ impl Sketch {
 }
 
 impl Sketch {
-    /// Create sketch instance from a custom JSON model format.
+    /// Create sketch instance from a custom JSON model format. Files of older format versions
+    /// are upgraded first, and files of newer (unsupported) versions are rejected.
     ///
     /// See [SketchData::from_json_str] for details on the actual parsing.
     pub fn from_custom_json(json_str: &str) -> Result<Sketch, String> {
@@ -257,7 +339,13 @@ impl Sketch {
 
 #[cfg(test)]
 mod tests {
+    use crate::sketchbook::ids::LayoutId;
+    use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+    use crate::sketchbook::properties::shortcuts::{mk_essentiality_prop, mk_monotonicity_prop};
+    use crate::sketchbook::properties::static_props::FolMacro;
+    use crate::sketchbook::properties::StatProperty;
     use crate::sketchbook::Sketch;
+    use biodivine_lib_param_bn::BooleanNetwork;
     use std::fs::File;
     use std::io::Read;
 
@@ -302,4 +390,152 @@ mod tests {
         let sketch2 = Sketch::from_custom_json(&json_contents).unwrap();
         assert_eq!(sketch1, sketch2);
     }
+
+    #[test]
+    /// Test that a full sketch (including datasets and properties) survives export to (and
+    /// import from) SBML format, and that the exported model is readable as plain SBML-qual.
+    fn sbml_round_trip() {
+        let mut aeon_sketch_file =
+            File::open("../data/test_data/test_model_with_data.aeon").unwrap();
+        let mut aeon_contents = String::new();
+        aeon_sketch_file.read_to_string(&mut aeon_contents).unwrap();
+
+        let sketch = Sketch::from_aeon(&aeon_contents).unwrap();
+        let sbml_str = sketch.to_sbml();
+        assert_eq!(Sketch::from_sbml(&sbml_str).unwrap(), sketch);
+
+        // the standard part of the model is readable by other tools
+        let (bn, layout) = BooleanNetwork::try_from_sbml(&sbml_str).unwrap();
+        assert_eq!(bn.num_vars(), sketch.model.num_vars());
+        assert_eq!(
+            bn.as_graph().regulations().count(),
+            sketch.model.num_regulations()
+        );
+        assert_eq!(layout.len(), sketch.model.num_vars());
+    }
+
+    #[test]
+    /// Test importing a bnet model (with inferred regulations) and exporting it back, as well
+    /// as warnings about parts of a sketch that can not be exported to bnet.
+    fn bnet_round_trip() {
+        let bnet_str = "targets, factors\nA, !C\nB, A & D\nC, A | B\nD, D\n";
+        let sketch = Sketch::from_bnet(bnet_str).unwrap();
+        assert_eq!(sketch.model.num_vars(), 4);
+        assert_eq!(sketch.model.num_regulations(), 6);
+        let regulation = sketch.model.get_regulation_by_str("C", "A").unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+        assert_eq!(regulation.get_essentiality(), &Essentiality::True);
+
+        let (exported_str, warnings) = sketch.to_bnet().unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(Sketch::from_bnet(&exported_str).unwrap(), sketch);
+
+        // the test model contains properties and datasets
+        let mut aeon_sketch_file =
+            File::open("../data/test_data/test_model_with_data.aeon").unwrap();
+        let mut aeon_contents = String::new();
+        aeon_sketch_file.read_to_string(&mut aeon_contents).unwrap();
+        let sketch = Sketch::from_aeon(&aeon_contents).unwrap();
+        let (exported_str, warnings) = sketch.to_bnet().unwrap();
+        assert!(warnings.len() >= 2);
+        assert!(exported_str.starts_with("# Warning:"));
+        assert!(Sketch::from_bnet(&exported_str).is_ok());
+
+        // variables without representable update functions are kept as free inputs
+        let sketch = Sketch::from_aeon("A -> B\nB -> A\n$A: f(B)\n$B: A\nC -> C").unwrap();
+        let (exported_str, warnings) = sketch.to_bnet().unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(exported_str.contains("\nA, A\n"));
+        assert!(exported_str.contains("\nC, C\n"));
+        assert!(exported_str.contains("# A, f(B)\n"));
+        let reloaded = Sketch::from_bnet(&exported_str).unwrap();
+        assert_eq!(reloaded.model.num_vars(), 3);
+    }
+
+    #[test]
+    /// Test that FOL macros survive export to (and import from) both aeon and json format.
+    fn fol_macros_round_trip() {
+        let mut aeon_sketch_file =
+            File::open("../data/test_data/test_model_with_data.aeon").unwrap();
+        let mut aeon_contents = String::new();
+        aeon_sketch_file.read_to_string(&mut aeon_contents).unwrap();
+
+        let mut sketch = Sketch::from_aeon(&aeon_contents).unwrap();
+        let params = vec!["x".to_string(), "y".to_string()];
+        let fol_macro = FolMacro::try_new("activation", params, "x => y", "note").unwrap();
+        sketch
+            .properties
+            .add_fol_macro_by_str("act", fol_macro)
+            .unwrap();
+
+        let sketch_aeon = Sketch::from_aeon(&sketch.to_aeon()).unwrap();
+        let sketch_json = Sketch::from_custom_json(&sketch.to_custom_json()).unwrap();
+        assert_eq!(sketch, sketch_aeon);
+        assert_eq!(sketch, sketch_json);
+        assert_eq!(sketch.properties.num_fol_macros(), 1);
+    }
+
+    #[test]
+    /// Test that sketches survive export to (and import from) aeon format without any loss,
+    /// both for all the benchmark sketches and for a sketch using features not covered by the
+    /// standard aeon format (additional layouts, sketch annotation, dual or non-essential
+    /// regulations).
+    fn aeon_round_trip() {
+        let benchmarks = std::fs::read_dir("../data/benchmarks").unwrap();
+        let mut num_benchmarks = 0;
+        for dir in benchmarks.map(|entry| entry.unwrap().path()) {
+            let name = dir.file_name().unwrap().to_str().unwrap().to_string();
+            let sketch_path = dir.join(format!("{name}_sketch.aeon"));
+            if !sketch_path.exists() {
+                continue;
+            }
+            let aeon_contents = std::fs::read_to_string(sketch_path).unwrap();
+            let sketch = Sketch::from_aeon(&aeon_contents).unwrap();
+            let exported = Sketch::from_aeon(&sketch.to_aeon()).unwrap();
+            assert_eq!(
+                exported, sketch,
+                "Benchmark `{name}` changed in aeon round-trip."
+            );
+            num_benchmarks += 1;
+        }
+        assert!(num_benchmarks > 0);
+
+        let aeon_contents =
+            std::fs::read_to_string("../data/test_data/test_model_with_data.aeon").unwrap();
+        let mut sketch = Sketch::from_aeon(&aeon_contents).unwrap();
+        sketch.set_annotation("Sketch note:\nwith `multiple` lines.");
+        let default_layout = ModelState::get_default_layout_id();
+        let layout_id = LayoutId::new("layout_2").unwrap();
+        sketch
+            .model
+            .add_layout_copy(layout_id.clone(), "second layout", &default_layout)
+            .unwrap();
+        let var_id = sketch.model.get_var_id("A").unwrap();
+        sketch
+            .model
+            .update_position(&layout_id, &var_id, 1.25, -3.5)
+            .unwrap();
+
+        // dual regulation and non-essential regulation (with corresponding static properties)
+        let var_b = sketch.model.get_var_id("B").unwrap();
+        let var_c = sketch.model.get_var_id("C").unwrap();
+        sketch
+            .model
+            .change_regulation_sign(&var_id, &var_b, &Monotonicity::Dual)
+            .unwrap();
+        let prop_id = StatProperty::get_monotonicity_prop_id(&var_id, &var_b);
+        let prop = mk_monotonicity_prop(&var_id, &var_b, Monotonicity::Dual);
+        sketch.properties.swap_stat_content(&prop_id, prop).unwrap();
+        sketch
+            .model
+            .change_regulation_essentiality(&var_c, &var_id, &Essentiality::False)
+            .unwrap();
+        let prop_id = StatProperty::get_essentiality_prop_id(&var_c, &var_id);
+        let prop = mk_essentiality_prop(&var_c, &var_id, Essentiality::False);
+        sketch.properties.add_static(prop_id, prop).unwrap();
+
+        let aeon_sketch = Sketch::from_aeon(&sketch.to_aeon()).unwrap();
+        assert_eq!(aeon_sketch, sketch);
+        assert_eq!(aeon_sketch.model.num_layouts(), 2);
+    }
 }
@@ -0,0 +1,209 @@
+use crate::algorithms::eval_static::property_lint::lint_static_props;
+use crate::sketchbook::ids::DynPropertyId;
+use crate::sketchbook::properties::dynamic_props::DynPropertyType;
+use crate::sketchbook::{ConsistencyReport, Sketch, SketchComponent};
+
+/// Utilities to lint properties of the sketch.
+impl Sketch {
+    /// Lint pass over the properties of the sketch, looking for properties that are vacuous
+    /// (tautologies), trivially unsatisfiable (contradictions), or redundant (implied by other
+    /// properties). All the discovered issues are reported as warnings.
+    ///
+    /// This includes:
+    /// - static properties that are tautologies, contradictions, or implied by other static
+    ///   properties (evaluated symbolically, see [lint_static_props])
+    /// - `AttractorCount` properties with ranges impossible for the number of variables
+    /// - dynamic properties derived from datasets that duplicate (or are implied by) other ones
+    ///
+    /// The sketch must be consistent (see [Self::run_consistency_check]), otherwise the results
+    /// for static properties are not computed.
+    pub fn lint_properties(&self) -> ConsistencyReport {
+        let mut report = ConsistencyReport::new();
+        self.lint_static(&mut report);
+        self.lint_dynamic(&mut report);
+        report
+    }
+
+    /// Part of the lint pass responsible for the 'static properties' component.
+    fn lint_static(&self, report: &mut ConsistencyReport) {
+        let component = SketchComponent::StaticProperties;
+        match lint_static_props(self) {
+            Ok(lints) => {
+                for lint in lints {
+                    report.add_warning(component, lint.prop_id.as_deref(), &lint.message);
+                }
+            }
+            Err(e) => {
+                let msg = format!("Static properties could not be linted. {e}");
+                report.add_warning(component, None, &msg);
+            }
+        }
+    }
+
+    /// Part of the lint pass responsible for the 'dynamic properties' component.
+    fn lint_dynamic(&self, report: &mut ConsistencyReport) {
+        let component = SketchComponent::DynamicProperties;
+        let mut dyn_props = self.properties.dyn_props().collect::<Vec<_>>();
+        dyn_props.sort_by_key(|(id, _)| *id);
+
+        // check attractor count ranges (async network with N variables has at most 2^N attractors)
+        let mut vacuous_props = Vec::new();
+        let num_vars = self.model.num_vars();
+        let max_attractors = 1_usize.checked_shl(num_vars as u32).unwrap_or(usize::MAX);
+        for (prop_id, prop) in dyn_props.iter() {
+            if let DynPropertyType::AttractorCount(p) = prop.get_prop_data() {
+                if p.minimal > max_attractors {
+                    let msg = format!("Property can't be satisfied, a network with {num_vars} variables has at most {max_attractors} attractors.");
+                    report.add_warning(component, Some(prop_id.as_str()), &msg);
+                    vacuous_props.push(*prop_id);
+                } else if p.minimal <= 1 && p.maximal >= max_attractors {
+                    let msg = format!("Property is a tautology, a network with {num_vars} variables always has between 1 and {max_attractors} attractors.");
+                    report.add_warning(component, Some(prop_id.as_str()), &msg);
+                    vacuous_props.push(*prop_id);
+                }
+            }
+        }
+
+        // check for redundancies, removing redundant properties one by one (sorted by IDs)
+        let mut remaining: Vec<(&DynPropertyId, &DynPropertyType)> = dyn_props
+            .iter()
+            .filter(|(id, _)| !vacuous_props.contains(id))
+            .map(|(id, prop)| (*id, prop.get_prop_data()))
+            .collect();
+        for (prop_id, prop) in remaining.clone() {
+            let implying_prop = remaining
+                .iter()
+                .find(|(other_id, other)| *other_id != prop_id && dyn_prop_implies(other, prop))
+                .map(|(other_id, _)| *other_id);
+            if let Some(other_id) = implying_prop {
+                let msg = format!("Property is redundant, it is implied by property `{other_id}`.");
+                report.add_warning(component, Some(prop_id.as_str()), &msg);
+                remaining.retain(|(id, _)| *id != prop_id);
+            }
+        }
+
+        // check for pairs of attractor count properties with disjoint ranges
+        for (i, (id_a, prop_a)) in remaining.iter().enumerate() {
+            for (id_b, prop_b) in remaining.iter().skip(i + 1) {
+                if let (DynPropertyType::AttractorCount(a), DynPropertyType::AttractorCount(b)) =
+                    (prop_a, prop_b)
+                {
+                    if a.maximal < b.minimal || b.maximal < a.minimal {
+                        let msg = format!("Property contradicts property `{id_b}`, their ranges of attractors are disjoint.");
+                        report.add_warning(component, Some(id_a.as_str()), &msg);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// **(internal)** Check whether dynamic property `a` trivially implies property `b`.
+///
+/// Besides identical properties, a property regarding the whole dataset implies the same kind
+/// of property regarding its particular observation, a fixed point implies an attractor,
+/// a minimal (or non-percolable) trap space implies a general one, and a narrower range of
+/// attractor numbers implies a wider one. A soft property never implies a hard one.
+fn dyn_prop_implies(a: &DynPropertyType, b: &DynPropertyType) -> bool {
+    if a == b {
+        return true;
+    }
+    if a.is_soft() && !b.is_soft() {
+        return false;
+    }
+    // observation `None` stands for all observations of the dataset
+    let covers = |obs_a: &Option<_>, obs_b: &Option<_>| obs_a.is_none() || obs_a == obs_b;
+    match (a, b) {
+        (DynPropertyType::HasAttractor(a), DynPropertyType::HasAttractor(b)) => {
+            a.dataset == b.dataset && covers(&a.observation, &b.observation)
+        }
+        (DynPropertyType::ExistsFixedPoint(a), DynPropertyType::ExistsFixedPoint(b)) => {
+            a.dataset == b.dataset && covers(&a.observation, &b.observation)
+        }
+        (DynPropertyType::ExistsFixedPoint(a), DynPropertyType::HasAttractor(b)) => {
+            a.dataset == b.dataset && covers(&a.observation, &b.observation)
+        }
+        (DynPropertyType::ExistsTrapSpace(a), DynPropertyType::ExistsTrapSpace(b)) => {
+            a.dataset == b.dataset
+                && covers(&a.observation, &b.observation)
+                && (a.minimal || !b.minimal)
+                && (a.nonpercolable || !b.nonpercolable)
+        }
+        (DynPropertyType::AttractorCount(a), DynPropertyType::AttractorCount(b)) => {
+            a.minimal >= b.minimal && a.maximal <= b.maximal
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::DatasetId;
+    use crate::sketchbook::observations::Dataset;
+    use crate::sketchbook::properties::{DynProperty, StatProperty};
+    use crate::sketchbook::{Sketch, SketchComponent};
+
+    #[test]
+    /// Test that vacuous, unsatisfiable, and redundant dynamic properties are reported.
+    fn lint_dynamic_properties() {
+        let mut sketch = Sketch::from_aeon("A -> B\nB -| A").unwrap();
+        let dataset = Dataset::new_empty("d", vec!["A", "B"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+        assert!(sketch.lint_properties().is_empty());
+
+        // two variables mean at most 4 attractors
+        let d = Some(DatasetId::new("d").unwrap());
+        let props = vec![
+            (
+                "a_count_1",
+                DynProperty::try_mk_attractor_count("", 5, 6, "").unwrap(),
+            ),
+            (
+                "a_count_2",
+                DynProperty::try_mk_attractor_count("", 1, 4, "").unwrap(),
+            ),
+            ("b_fp", DynProperty::mk_fixed_point("", d.clone(), None, "")),
+            (
+                "c_attr",
+                DynProperty::mk_has_attractor("", d.clone(), None, ""),
+            ),
+            ("d_fp", DynProperty::mk_fixed_point("", d.clone(), None, "")),
+        ];
+        for (id, prop) in props {
+            sketch.properties.add_dynamic_by_str(id, prop).unwrap();
+        }
+
+        let report = sketch.lint_properties();
+        assert!(report.is_consistent());
+        let issues: Vec<_> = report
+            .warnings()
+            .map(|i| i.entity_id.clone().unwrap())
+            .collect();
+        assert_eq!(issues, vec!["a_count_1", "a_count_2", "b_fp", "c_attr"]);
+        let messages: Vec<_> = report.warnings().map(|i| i.message.clone()).collect();
+        assert!(messages[0].contains("can't be satisfied"));
+        assert!(messages[1].contains("tautology"));
+        assert!(messages[2].contains("implied by property `d_fp`"));
+        assert!(messages[3].contains("implied by property `d_fp`"));
+    }
+
+    #[test]
+    /// Test that lints are only reported by the explicit lint pass, not by the consistency check.
+    fn lint_separate_from_consistency_check() {
+        let mut sketch = Sketch::from_aeon("A -> A").unwrap();
+        let prop = StatProperty::try_mk_generic("", "f_A(1) | !f_A(1)", "").unwrap();
+        sketch.properties.add_static_by_str("p", prop).unwrap();
+
+        let report = sketch.run_consistency_check();
+        assert!(report.is_consistent());
+        assert!(report.is_empty());
+
+        let report = sketch.lint_properties();
+        let warning = report.warnings().next().unwrap();
+        assert_eq!(warning.component, SketchComponent::StaticProperties);
+        assert_eq!(warning.entity_id, Some("p".to_string()));
+    }
+}
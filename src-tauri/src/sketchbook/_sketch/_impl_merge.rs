@@ -0,0 +1,1013 @@
+use crate::sketchbook::_sketch::{MergeReport, MergeStrategy};
+use crate::sketchbook::ids::{StatPropertyId, VarId};
+use crate::sketchbook::model::ModelState;
+use crate::sketchbook::properties::StatProperty;
+use crate::sketchbook::Sketch;
+use std::collections::HashSet;
+
+/// Components of the sketch, as they are referenced in the `MergeReport`.
+const UNINTERPRETED_FN: &str = "uninterpreted function";
+const VARIABLE: &str = "variable";
+const REGULATION: &str = "regulation";
+const UPDATE_FN: &str = "update function";
+const DATASET: &str = "dataset";
+const DYN_PROPERTY: &str = "dynamic property";
+const STAT_PROPERTY: &str = "static property";
+const FOL_MACRO: &str = "FOL macro";
+const ANNOTATION: &str = "annotation";
+
+/// Resolutions of conflicts, as they are described in the `MergeReport`.
+const KEPT_CURRENT: &str = "kept the current version";
+const TOOK_MERGED: &str = "replaced by the merged version";
+
+/// Merging two sketches together.
+impl Sketch {
+    /// Merge the `other` sketch into this one, making a union of all their components
+    /// (variables, regulations, uninterpreted functions, datasets, properties and FOL macros).
+    ///
+    /// Components with the same ID and the same content are simply identified. If the content
+    /// differs, the conflict is resolved according to the `strategy` (see [MergeStrategy]), and
+    /// reported in the resulting `MergeReport`:
+    /// - variables, uninterpreted functions, datasets, properties and FOL macros from the
+    ///   `other` sketch get a new unique ID when using `Rename` (and all references in the
+    ///   `other` sketch are adjusted accordingly)
+    /// - conflicting (non-empty) update functions and regulation types of shared variables are
+    ///   resolved by preferring one of the sketches (the current one for `Rename`)
+    ///
+    /// Positions of newly added variables are taken from the default layout of `other`.
+    /// The merge is atomic, i.e., if an error occurs, this sketch is left unchanged.
+    pub fn merge(
+        &mut self,
+        other: &Sketch,
+        strategy: MergeStrategy,
+    ) -> Result<MergeReport, String> {
+        let mut merged = self.clone();
+        // the other sketch might need to be modified when its components are renamed
+        let mut other = other.clone();
+        let mut report = MergeReport::new();
+
+        merged.merge_uninterpreted_fns(&mut other, strategy, &mut report)?;
+        merged.merge_variables(&mut other, strategy, &mut report)?;
+        let replaced_regs = merged.merge_regulations(&other, strategy, &mut report)?;
+        merged.merge_update_fns(&other, strategy, &mut report)?;
+        merged.merge_datasets(&mut other, strategy, &mut report)?;
+        merged.merge_fol_macros(&mut other, strategy, &mut report)?;
+        merged.merge_dyn_properties(&other, strategy, &mut report)?;
+        merged.merge_stat_properties(&other, &replaced_regs, strategy, &mut report)?;
+
+        if merged.annotation.is_empty() {
+            merged.annotation = other.annotation.clone();
+        } else if !other.annotation.is_empty() && merged.annotation != other.annotation {
+            let resolution = if strategy == MergeStrategy::PreferRight {
+                merged.annotation = other.annotation.clone();
+                TOOK_MERGED
+            } else {
+                KEPT_CURRENT
+            };
+            report.add_conflict(ANNOTATION, "sketch", resolution);
+        }
+
+        *self = merged;
+        Ok(report)
+    }
+
+    /// **(internal)** Merge uninterpreted functions of the `other` sketch into this one.
+    ///
+    /// With `Rename`, all conflicting functions are first renamed in `other` (propagating the
+    /// new IDs into its expressions and properties), and only then copied, so that expressions
+    /// of the copied functions always reference the final IDs.
+    fn merge_uninterpreted_fns(
+        &mut self,
+        other: &mut Sketch,
+        strategy: MergeStrategy,
+        report: &mut MergeReport,
+    ) -> Result<(), String> {
+        let mut fn_ids: Vec<_> = other
+            .model
+            .uninterpreted_fns()
+            .map(|(id, _)| id)
+            .cloned()
+            .collect();
+        fn_ids.sort();
+
+        if strategy == MergeStrategy::Rename {
+            for fn_id in fn_ids.iter_mut() {
+                let other_fn = other.model.get_uninterpreted_fn(fn_id)?;
+                match self.model.get_uninterpreted_fn(fn_id) {
+                    Ok(current_fn) if current_fn != other_fn => {
+                        let new_id = generate_id_unused_in_both(
+                            |start| {
+                                self.model
+                                    .generate_uninterpreted_fn_id(fn_id.as_str(), start)
+                            },
+                            |id| other.model.is_valid_uninterpreted_fn_id(id),
+                        );
+                        other.model.set_uninterpreted_fn_id(fn_id, new_id.clone())?;
+                        let stat_props: Vec<_> = other
+                            .properties
+                            .stat_props()
+                            .map(|(id, p)| (id.clone(), p.clone()))
+                            .collect();
+                        for (prop_id, mut prop) in stat_props {
+                            prop.set_fn_id_if_present(fn_id, new_id.clone());
+                            other.properties.swap_stat_content(&prop_id, prop)?;
+                        }
+                        let resolution = format!("renamed to `{new_id}`");
+                        report.add_conflict(UNINTERPRETED_FN, fn_id.as_str(), &resolution);
+                        *fn_id = new_id;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for fn_id in fn_ids {
+            let other_fn = other.model.get_uninterpreted_fn(&fn_id)?.clone();
+            match self.model.get_uninterpreted_fn(&fn_id) {
+                Err(_) => {
+                    let arity = other_fn.get_arity();
+                    self.model.add_empty_uninterpreted_fn(
+                        fn_id.clone(),
+                        other_fn.get_name(),
+                        arity,
+                    )?;
+                    self.model.set_raw_function(&fn_id, other_fn)?;
+                }
+                Ok(current_fn) if *current_fn == other_fn => {}
+                Ok(current_fn) => {
+                    let resolution = if strategy == MergeStrategy::PreferRight {
+                        if current_fn.get_arity() != other_fn.get_arity() {
+                            self.model
+                                .set_uninterpreted_fn_arity(&fn_id, other_fn.get_arity())
+                                .map_err(|e| {
+                                    format!(
+                                        "Can not replace uninterpreted fn `{fn_id}` by a \
+                                        function of different arity. {e}"
+                                    )
+                                })?;
+                        }
+                        self.model.set_raw_function(&fn_id, other_fn)?;
+                        TOOK_MERGED
+                    } else {
+                        KEPT_CURRENT
+                    };
+                    report.add_conflict(UNINTERPRETED_FN, fn_id.as_str(), resolution);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Merge variables of the `other` sketch into this one. New variables are
+    /// placed to the same position as in the default layout of `other`.
+    ///
+    /// With `Rename`, all conflicting variables are first renamed in `other` (propagating the
+    /// new IDs into its regulations, update functions, layouts, datasets and regulation
+    /// properties), and only then copied, so that they are merged as new variables. If a
+    /// conflicting variable encodes a multi-valued variable of some dataset, all its encoding
+    /// variables are renamed together (so that the encoding is preserved).
+    fn merge_variables(
+        &mut self,
+        other: &mut Sketch,
+        strategy: MergeStrategy,
+        report: &mut MergeReport,
+    ) -> Result<(), String> {
+        let mut var_ids: Vec<VarId> = other.model.variables().map(|(id, _)| id).cloned().collect();
+        var_ids.sort();
+
+        if strategy == MergeStrategy::Rename {
+            for var_id in var_ids.iter() {
+                // variable might have been already renamed together with a multi-valued variable
+                if !other.model.is_valid_var_id(var_id) {
+                    continue;
+                }
+                let other_var = other.model.get_variable(var_id)?;
+                match self.model.get_variable(var_id) {
+                    Ok(current_var) if current_var != other_var => {
+                        let resolution = if let Some((name, num_encoding_vars)) =
+                            other.multi_valued_var_encoded_by(var_id)
+                        {
+                            // the whole encoding must be renamed, so that it is preserved
+                            let new_name =
+                                self.generate_multi_valued_name(other, &name, num_encoding_vars);
+                            other.rename_multi_valued_var(&name, &new_name, num_encoding_vars)?;
+                            format!(
+                                "renamed to `{new_name}{}` (with multi-valued variable `{name}` \
+                                renamed to `{new_name}`)",
+                                &var_id.as_str()[name.len()..]
+                            )
+                        } else {
+                            let new_id = generate_id_unused_in_both(
+                                |start| self.model.generate_var_id(var_id.as_str(), start),
+                                |id| other.model.is_valid_var_id(id),
+                            );
+                            other.rename_variable(var_id, &new_id)?;
+                            format!("renamed to `{new_id}`")
+                        };
+                        report.add_conflict(VARIABLE, var_id.as_str(), &resolution);
+                    }
+                    _ => {}
+                }
+            }
+            var_ids = other.model.variables().map(|(id, _)| id).cloned().collect();
+            var_ids.sort();
+        }
+
+        let layout_id = ModelState::get_default_layout_id();
+        for var_id in var_ids {
+            let other_var = other.model.get_variable(&var_id)?;
+            match self.model.get_variable(&var_id) {
+                Err(_) => {
+                    let (name, annot) = (other_var.get_name(), other_var.get_annotation());
+                    self.model.add_var(var_id.clone(), name, annot)?;
+                    let position = other.model.get_node_position(&layout_id, &var_id)?;
+                    self.model
+                        .update_position(&layout_id, &var_id, position.0, position.1)?;
+                }
+                Ok(current_var) if current_var == other_var => {}
+                Ok(_) => {
+                    let resolution = if strategy == MergeStrategy::PreferRight {
+                        self.model.set_raw_var(&var_id, other_var.clone())?;
+                        TOOK_MERGED
+                    } else {
+                        KEPT_CURRENT
+                    };
+                    report.add_conflict(VARIABLE, var_id.as_str(), resolution);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Rename a variable everywhere in this sketch, i.e., in the model (its
+    /// regulations, update functions and layouts), in all datasets that contain or perturb it,
+    /// and in all static properties that reference it (as a regulator or target).
+    ///
+    /// Same as when renaming variables in the editor, formulas of generic properties are not
+    /// modified.
+    fn rename_variable(&mut self, var_id: &VarId, new_id: &VarId) -> Result<(), String> {
+        self.model.set_var_id(var_id, new_id.clone())?;
+
+        let datasets: Vec<_> = self
+            .observations
+            .datasets()
+            .map(|(id, dataset)| (id.clone(), dataset.clone()))
+            .collect();
+        for (dataset_id, mut dataset) in datasets {
+            if dataset.is_valid_variable(var_id) {
+                // perturbation of the variable (if any) is moved together with it
+                dataset.set_var_id(var_id, new_id.clone())?;
+            } else if let Some(perturbation) = dataset.perturbations().get(var_id).copied() {
+                dataset.set_perturbation(var_id.clone(), None);
+                dataset.set_perturbation(new_id.clone(), Some(perturbation));
+            } else {
+                continue;
+            }
+            self.observations
+                .swap_dataset_content(&dataset_id, dataset)?;
+        }
+
+        let stat_props: Vec<_> = self
+            .properties
+            .stat_props()
+            .map(|(id, p)| (id.clone(), p.clone()))
+            .collect();
+        for (prop_id, mut prop) in stat_props {
+            // properties that do not reference variables are simply left unchanged
+            if prop
+                .set_var_id_if_present(var_id.clone(), new_id.clone())
+                .is_ok()
+            {
+                self.properties.swap_stat_content(&prop_id, prop)?;
+            }
+        }
+        self.properties.make_generated_reg_prop_ids_consistent();
+        Ok(())
+    }
+
+    /// **(internal)** Find a multi-valued variable (declared in any dataset of this sketch)
+    /// that is encoded by given variable. Return its name and the maximal number of its
+    /// encoding variables across all datasets.
+    fn multi_valued_var_encoded_by(&self, var_id: &VarId) -> Option<(String, usize)> {
+        let var = self
+            .observations
+            .datasets()
+            .flat_map(|(_, dataset)| dataset.multi_valued_vars())
+            .find(|var| var.encoding_vars().contains(&var_id.to_string()))?;
+        let name = var.get_name().to_string();
+        let num_encoding_vars = self
+            .observations
+            .datasets()
+            .filter_map(|(_, dataset)| dataset.get_multi_valued_var(&name).ok())
+            .map(|var| var.num_levels() - 1)
+            .max()?;
+        Some((name, num_encoding_vars))
+    }
+
+    /// **(internal)** Generate a name for the multi-valued variable `name` of the `other`
+    /// sketch, such that none of its (first `num_encoding_vars`) encoding variables is used in
+    /// either of the sketches, and the name is not declared in any dataset of `other`.
+    fn generate_multi_valued_name(
+        &self,
+        other: &Sketch,
+        name: &str,
+        num_encoding_vars: usize,
+    ) -> String {
+        let is_used = |var_id: &str| {
+            self.model.is_valid_var_id_str(var_id)
+                || other.model.is_valid_var_id_str(var_id)
+                || other
+                    .observations
+                    .datasets()
+                    .any(|(_, dataset)| dataset.get_var_id(var_id).is_ok())
+        };
+        generate_id_unused_in_both(
+            |start| self.model.generate_var_id(name, start).to_string(),
+            |new_name| {
+                (1..=num_encoding_vars).any(|i| is_used(&format!("{new_name}_{i}")))
+                    || other
+                        .observations
+                        .datasets()
+                        .any(|(_, dataset)| dataset.get_multi_valued_var(new_name).is_ok())
+            },
+        )
+    }
+
+    /// **(internal)** Rename a multi-valued variable (declared in some datasets of this sketch),
+    /// together with its (first `num_encoding_vars`) Boolean encoding variables, i.e., `X_i` is
+    /// renamed to `Y_i` everywhere (see [Self::rename_variable]).
+    ///
+    /// Datasets that declare the multi-valued variable keep the (renamed) declaration.
+    fn rename_multi_valued_var(
+        &mut self,
+        name: &str,
+        new_name: &str,
+        num_encoding_vars: usize,
+    ) -> Result<(), String> {
+        let datasets: Vec<_> = self
+            .observations
+            .datasets()
+            .filter(|(_, dataset)| dataset.get_multi_valued_var(name).is_ok())
+            .map(|(id, dataset)| (id.clone(), dataset.clone()))
+            .collect();
+        for (dataset_id, mut dataset) in datasets {
+            dataset.rename_multi_valued_var(name, new_name)?;
+            self.observations
+                .swap_dataset_content(&dataset_id, dataset)?;
+        }
+
+        for i in 1..=num_encoding_vars {
+            let var_id = VarId::new(&format!("{name}_{i}"))?;
+            let new_id = VarId::new(&format!("{new_name}_{i}"))?;
+            // encoding variables that are only used in datasets are already renamed
+            if self.model.is_valid_var_id(&var_id) {
+                self.rename_variable(&var_id, &new_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Merge regulations of the `other` sketch into this one.
+    ///
+    /// Return list of regulations (regulator-target pairs) that were taken from `other`, either
+    /// because they were new or because they replaced the current ones.
+    fn merge_regulations(
+        &mut self,
+        other: &Sketch,
+        strategy: MergeStrategy,
+        report: &mut MergeReport,
+    ) -> Result<Vec<(VarId, VarId)>, String> {
+        let mut regulations: Vec<_> = other.model.regulations().cloned().collect();
+        regulations.sort_by(|r1, r2| {
+            (r1.get_regulator(), r1.get_target()).cmp(&(r2.get_regulator(), r2.get_target()))
+        });
+
+        let mut taken_regs = Vec::new();
+        for reg in regulations {
+            let (regulator, target) = (reg.get_regulator().clone(), reg.get_target().clone());
+            match self.model.get_regulation(&regulator, &target) {
+                Err(_) => {}
+                Ok(current_reg) if *current_reg == reg => continue,
+                Ok(_) => {
+                    let reg_str = format!("{regulator} -> {target}");
+                    if strategy != MergeStrategy::PreferRight {
+                        report.add_conflict(REGULATION, &reg_str, KEPT_CURRENT);
+                        continue;
+                    }
+                    report.add_conflict(REGULATION, &reg_str, TOOK_MERGED);
+                    self.model.remove_regulation(&regulator, &target)?;
+                }
+            }
+            let (essentiality, sign) = (*reg.get_essentiality(), *reg.get_sign());
+            self.model
+                .add_regulation(regulator.clone(), target.clone(), essentiality, sign)?;
+            taken_regs.push((regulator, target));
+        }
+        Ok(taken_regs)
+    }
+
+    /// **(internal)** Merge update functions of the `other` sketch into this one. Empty update
+    /// functions never cause a conflict, the non-empty one is used instead.
+    fn merge_update_fns(
+        &mut self,
+        other: &Sketch,
+        strategy: MergeStrategy,
+        report: &mut MergeReport,
+    ) -> Result<(), String> {
+        let mut var_ids: Vec<&VarId> = other.model.variables().map(|(id, _)| id).collect();
+        var_ids.sort();
+
+        for var_id in var_ids {
+            let other_expression = other.model.get_update_fn_string(var_id)?;
+            let current_expression = self.model.get_update_fn_string(var_id)?;
+            if other_expression.is_empty() || other_expression == current_expression {
+                continue;
+            }
+            if current_expression.is_empty() {
+                self.model.set_update_fn(var_id, other_expression)?;
+            } else if strategy == MergeStrategy::PreferRight {
+                self.model.set_update_fn(var_id, other_expression)?;
+                report.add_conflict(UPDATE_FN, var_id.as_str(), TOOK_MERGED);
+            } else {
+                report.add_conflict(UPDATE_FN, var_id.as_str(), KEPT_CURRENT);
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Merge datasets of the `other` sketch into this one. Renamed datasets are
+    /// also renamed in all dynamic properties of `other` that reference them.
+    fn merge_datasets(
+        &mut self,
+        other: &mut Sketch,
+        strategy: MergeStrategy,
+        report: &mut MergeReport,
+    ) -> Result<(), String> {
+        let mut dataset_ids: Vec<_> = other
+            .observations
+            .datasets()
+            .map(|(id, _)| id)
+            .cloned()
+            .collect();
+        dataset_ids.sort();
+
+        for dataset_id in dataset_ids {
+            let other_dataset = other.observations.get_dataset(&dataset_id)?.clone();
+            match self.observations.get_dataset(&dataset_id) {
+                Err(_) => {
+                    self.observations.add_dataset(dataset_id, other_dataset)?;
+                }
+                Ok(current_dataset) if *current_dataset == other_dataset => {}
+                Ok(_) => match strategy {
+                    MergeStrategy::Rename => {
+                        let new_id = generate_id_unused_in_both(
+                            |start| {
+                                self.observations
+                                    .generate_dataset_id(dataset_id.as_str(), start)
+                            },
+                            |id| other.observations.is_valid_dataset_id(id),
+                        );
+                        other
+                            .observations
+                            .set_dataset_id(&dataset_id, new_id.clone())?;
+                        let dyn_props: Vec<_> = other
+                            .properties
+                            .dyn_props()
+                            .map(|(id, p)| (id.clone(), p.clone()))
+                            .collect();
+                        for (prop_id, mut prop) in dyn_props {
+                            prop.set_dataset_id_if_present(&dataset_id, new_id.clone());
+                            other.properties.swap_dyn_content(&prop_id, prop)?;
+                        }
+                        self.observations
+                            .add_dataset(new_id.clone(), other_dataset)?;
+                        let resolution = format!("renamed to `{new_id}`");
+                        report.add_conflict(DATASET, dataset_id.as_str(), &resolution);
+                    }
+                    MergeStrategy::PreferLeft => {
+                        report.add_conflict(DATASET, dataset_id.as_str(), KEPT_CURRENT);
+                    }
+                    MergeStrategy::PreferRight => {
+                        self.observations
+                            .swap_dataset_content(&dataset_id, other_dataset)?;
+                        report.add_conflict(DATASET, dataset_id.as_str(), TOOK_MERGED);
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Merge dynamic properties of the `other` sketch into this one.
+    fn merge_dyn_properties(
+        &mut self,
+        other: &Sketch,
+        strategy: MergeStrategy,
+        report: &mut MergeReport,
+    ) -> Result<(), String> {
+        let mut props: Vec<_> = other.properties.dyn_props().collect();
+        props.sort_by_key(|(id, _)| *id);
+
+        for (prop_id, other_prop) in props {
+            match self.properties.get_dyn_prop(prop_id) {
+                Err(_) => {
+                    self.properties
+                        .add_dynamic(prop_id.clone(), other_prop.clone())?;
+                }
+                Ok(current_prop) if current_prop == other_prop => {}
+                Ok(_) => match strategy {
+                    MergeStrategy::Rename => {
+                        let new_id = generate_id_unused_in_both(
+                            |start| {
+                                self.properties
+                                    .generate_dyn_property_id(prop_id.as_str(), start)
+                            },
+                            |id| other.properties.is_valid_dyn_property_id(id),
+                        );
+                        self.properties
+                            .add_dynamic(new_id.clone(), other_prop.clone())?;
+                        let resolution = format!("renamed to `{new_id}`");
+                        report.add_conflict(DYN_PROPERTY, prop_id.as_str(), &resolution);
+                    }
+                    MergeStrategy::PreferLeft => {
+                        report.add_conflict(DYN_PROPERTY, prop_id.as_str(), KEPT_CURRENT);
+                    }
+                    MergeStrategy::PreferRight => {
+                        // the variant of the property might differ, so we can't just swap content
+                        self.properties.remove_dynamic(prop_id)?;
+                        self.properties
+                            .add_dynamic(prop_id.clone(), other_prop.clone())?;
+                        report.add_conflict(DYN_PROPERTY, prop_id.as_str(), TOOK_MERGED);
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Merge static properties of the `other` sketch into this one.
+    ///
+    /// Properties automatically generated for regulations are not merged on their own, they
+    /// follow their regulation instead (i.e., they are taken from `other` if and only if the
+    /// regulation was taken from there, given by `taken_regs`).
+    fn merge_stat_properties(
+        &mut self,
+        other: &Sketch,
+        taken_regs: &[(VarId, VarId)],
+        strategy: MergeStrategy,
+        report: &mut MergeReport,
+    ) -> Result<(), String> {
+        let mut generated_ids = HashSet::new();
+        for reg in other.model.regulations() {
+            let (regulator, target) = (reg.get_regulator(), reg.get_target());
+            generated_ids.insert(StatProperty::get_essentiality_prop_id(regulator, target));
+            generated_ids.insert(StatProperty::get_monotonicity_prop_id(regulator, target));
+        }
+
+        for (regulator, target) in taken_regs {
+            let prop_ids = [
+                StatProperty::get_essentiality_prop_id(regulator, target),
+                StatProperty::get_monotonicity_prop_id(regulator, target),
+            ];
+            for prop_id in prop_ids {
+                self.replace_stat_property(other, &prop_id)?;
+            }
+        }
+
+        let mut props: Vec<_> = other.properties.stat_props().collect();
+        props.sort_by_key(|(id, _)| *id);
+
+        for (prop_id, other_prop) in props {
+            if generated_ids.contains(prop_id) {
+                continue;
+            }
+            match self.properties.get_stat_prop(prop_id) {
+                Err(_) => {
+                    self.properties
+                        .add_static(prop_id.clone(), other_prop.clone())?;
+                }
+                Ok(current_prop) if current_prop == other_prop => {}
+                Ok(_) => match strategy {
+                    MergeStrategy::Rename => {
+                        let new_id = generate_id_unused_in_both(
+                            |start| {
+                                self.properties
+                                    .generate_stat_property_id(prop_id.as_str(), start)
+                            },
+                            |id| other.properties.is_valid_stat_property_id(id),
+                        );
+                        self.properties
+                            .add_static(new_id.clone(), other_prop.clone())?;
+                        let resolution = format!("renamed to `{new_id}`");
+                        report.add_conflict(STAT_PROPERTY, prop_id.as_str(), &resolution);
+                    }
+                    MergeStrategy::PreferLeft => {
+                        report.add_conflict(STAT_PROPERTY, prop_id.as_str(), KEPT_CURRENT);
+                    }
+                    MergeStrategy::PreferRight => {
+                        self.replace_stat_property(other, prop_id)?;
+                        report.add_conflict(STAT_PROPERTY, prop_id.as_str(), TOOK_MERGED);
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Make the static property with given ID the same as in the `other` sketch.
+    /// That means replacing or adding it, or removing it if `other` does not contain it.
+    fn replace_stat_property(
+        &mut self,
+        other: &Sketch,
+        prop_id: &StatPropertyId,
+    ) -> Result<(), String> {
+        if self.properties.is_valid_stat_property_id(prop_id) {
+            // the variant of the property might differ, so we can't just swap content
+            self.properties.remove_static(prop_id)?;
+        }
+        if let Ok(other_prop) = other.properties.get_stat_prop(prop_id) {
+            self.properties
+                .add_static(prop_id.clone(), other_prop.clone())?;
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Merge FOL macros of the `other` sketch into this one.
+    ///
+    /// With `Rename`, all conflicting macros are first renamed in `other` (propagating the new
+    /// IDs into bodies of its macros and formulas of its static properties), and only then
+    /// copied, so that the copied macros and properties always reference the final IDs.
+    fn merge_fol_macros(
+        &mut self,
+        other: &mut Sketch,
+        strategy: MergeStrategy,
+        report: &mut MergeReport,
+    ) -> Result<(), String> {
+        let mut macro_ids: Vec<_> = other
+            .properties
+            .fol_macros()
+            .map(|(id, _)| id)
+            .cloned()
+            .collect();
+        macro_ids.sort();
+
+        if strategy == MergeStrategy::Rename {
+            for macro_id in macro_ids.iter_mut() {
+                let other_macro = other.properties.get_fol_macro(macro_id)?;
+                match self.properties.get_fol_macro(macro_id) {
+                    Ok(current_macro) if current_macro != other_macro => {
+                        let new_id = generate_id_unused_in_both(
+                            |start| {
+                                self.properties
+                                    .generate_fol_macro_id(macro_id.as_str(), start)
+                            },
+                            |id| other.properties.is_valid_fol_macro_id(id),
+                        );
+                        other
+                            .properties
+                            .set_fol_macro_id(macro_id, new_id.clone())?;
+                        let macros: Vec<_> = other
+                            .properties
+                            .fol_macros()
+                            .map(|(id, m)| (id.clone(), m.clone()))
+                            .collect();
+                        for (id, mut fol_macro) in macros {
+                            fol_macro.set_macro_id_if_present(macro_id, &new_id)?;
+                            other.properties.swap_fol_macro_content(&id, fol_macro)?;
+                        }
+                        let stat_props: Vec<_> = other
+                            .properties
+                            .stat_props()
+                            .map(|(id, p)| (id.clone(), p.clone()))
+                            .collect();
+                        for (prop_id, mut prop) in stat_props {
+                            prop.set_fol_macro_id_if_present(macro_id, &new_id)?;
+                            other.properties.swap_stat_content(&prop_id, prop)?;
+                        }
+                        let resolution = format!("renamed to `{new_id}`");
+                        report.add_conflict(FOL_MACRO, macro_id.as_str(), &resolution);
+                        *macro_id = new_id;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for macro_id in macro_ids {
+            let other_macro = other.properties.get_fol_macro(&macro_id)?.clone();
+            match self.properties.get_fol_macro(&macro_id) {
+                Err(_) => {
+                    self.properties.add_fol_macro(macro_id, other_macro)?;
+                }
+                Ok(current_macro) if *current_macro == other_macro => {}
+                Ok(_) => {
+                    let resolution = if strategy == MergeStrategy::PreferRight {
+                        self.properties
+                            .swap_fol_macro_content(&macro_id, other_macro)?;
+                        TOOK_MERGED
+                    } else {
+                        KEPT_CURRENT
+                    };
+                    report.add_conflict(FOL_MACRO, macro_id.as_str(), resolution);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// **(internal)** Generate an ID that is not used in either of the two merged sketches.
+///
+/// The `generate` closure produces IDs unused in the current sketch (starting the index search
+/// at the given number), and `is_used_in_other` checks whether the ID is used in the other one.
+fn generate_id_unused_in_both<T>(
+    generate: impl Fn(Option<usize>) -> T,
+    is_used_in_other: impl Fn(&T) -> bool,
+) -> T {
+    let mut id = generate(None);
+    let mut start_index = 1;
+    while is_used_in_other(&id) {
+        id = generate(Some(start_index));
+        start_index += 1;
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::{
+        DatasetId, DynPropertyId, FolMacroId, StatPropertyId, UninterpretedFnId, VarId,
+    };
+    use crate::sketchbook::model::{Essentiality, Monotonicity};
+    use crate::sketchbook::observations::{Dataset, MultiValuedVar, Observation, PerturbationType};
+    use crate::sketchbook::properties::dynamic_props::DynPropertyType;
+    use crate::sketchbook::properties::static_props::StatPropertyType;
+    use crate::sketchbook::properties::{DynProperty, FolMacro, StatProperty};
+    use crate::sketchbook::{MergeStrategy, Sketch};
+
+    /// Prepare a sketch from an AEON string, with a single-observation dataset `d`.
+    fn prepare_sketch(aeon_str: &str, observation: &str) -> Sketch {
+        let mut sketch = Sketch::from_aeon(aeon_str).unwrap();
+        let vars: Vec<String> = sketch
+            .model
+            .variables()
+            .map(|(v, _)| v.to_string())
+            .collect();
+        let mut vars: Vec<&str> = vars.iter().map(|v| v.as_str()).collect();
+        vars.sort();
+        let obs = Observation::try_from_str(observation, "o").unwrap();
+        let dataset = Dataset::new("d", vec![obs], vars).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+        sketch
+    }
+
+    #[test]
+    /// Test merging sketches with conflicts resolved by renaming.
+    fn test_merge_rename() {
+        let mut sketch = prepare_sketch("A -> B\nB -| A\n$B: f(A)\n", "10");
+        let aeon_str = "A -> B\nC -> B\nB -> C\n$B: f(A, C)\n$C: B\n";
+        let mut other = prepare_sketch(aeon_str, "110");
+        let dataset_id = DatasetId::new("d").unwrap();
+        let fp_prop = DynProperty::mk_fixed_point("fp", Some(dataset_id.clone()), None, "");
+        other.properties.add_dynamic_by_str("fp", fp_prop).unwrap();
+        let fn_id = UninterpretedFnId::new("f").unwrap();
+        let essential = Essentiality::True;
+        let fn_prop = StatProperty::mk_fn_input_essential("p", Some(0), Some(fn_id), essential, "");
+        other.properties.add_static_by_str("p", fn_prop).unwrap();
+
+        let report = sketch.merge(&other, MergeStrategy::Rename).unwrap();
+        assert_eq!(report.conflicts_of("uninterpreted function").len(), 1);
+        assert_eq!(report.conflicts_of("update function").len(), 1);
+        assert_eq!(report.conflicts_of("dataset").len(), 1);
+
+        // union of variables and regulations, update fn of `B` kept, the one of `C` added
+        assert_eq!(sketch.model.num_vars(), 3);
+        assert_eq!(sketch.model.num_regulations(), 4);
+        let var_b = VarId::new("B").unwrap();
+        let var_c = VarId::new("C").unwrap();
+        assert_eq!(sketch.model.get_update_fn_string(&var_b).unwrap(), "f(A)");
+        assert_eq!(sketch.model.get_update_fn_string(&var_c).unwrap(), "B");
+
+        // renamed function is referenced by the static property of the merged sketch
+        let fn_ids: Vec<_> = sketch.model.uninterpreted_fns().map(|(id, _)| id).collect();
+        let new_fn_id = fn_ids
+            .into_iter()
+            .find(|id| id.as_str() != "f")
+            .unwrap()
+            .clone();
+        assert_eq!(sketch.model.get_uninterpreted_fn_arity(&new_fn_id), Ok(2));
+        let prop_id = StatPropertyId::new("p").unwrap();
+        let prop = sketch.properties.get_stat_prop(&prop_id).unwrap();
+        let expected =
+            StatProperty::mk_fn_input_essential("p", Some(0), Some(new_fn_id), essential, "");
+        assert_eq!(prop, &expected);
+
+        // renamed dataset is referenced by the dynamic property of the merged sketch
+        assert_eq!(sketch.observations.num_datasets(), 2);
+        let prop_id = DynPropertyId::new("fp").unwrap();
+        let prop = sketch.properties.get_dyn_prop(&prop_id).unwrap();
+        let DynPropertyType::ExistsFixedPoint(prop) = prop.get_prop_data() else {
+            panic!("Unexpected property variant.");
+        };
+        let new_dataset_id = prop.dataset.clone().unwrap();
+        assert_ne!(new_dataset_id, dataset_id);
+        let dataset = sketch.observations.get_dataset(&new_dataset_id).unwrap();
+        assert_eq!(dataset.num_variables(), 3);
+    }
+
+    #[test]
+    /// Test that conflicting variables are renamed (together with all their references).
+    fn test_merge_rename_variables() {
+        let mut sketch = prepare_sketch("A -> B\n$B: A\n", "10");
+        let mut other = prepare_sketch("A -| B\n$B: !A\n", "01");
+        let var_a = VarId::new("A").unwrap();
+        let var_b = VarId::new("B").unwrap();
+        other.model.set_var_name(&var_a, "Other A").unwrap();
+
+        let report = sketch.merge(&other, MergeStrategy::Rename).unwrap();
+        assert_eq!(report.conflicts_of("variable").len(), 1);
+        assert_eq!(report.conflicts_of("update function").len(), 1);
+        assert_eq!(sketch.model.num_vars(), 3);
+        let new_var = sketch
+            .model
+            .variables()
+            .map(|(id, _)| id)
+            .find(|id| **id != var_a && **id != var_b)
+            .unwrap()
+            .clone();
+        assert_eq!(sketch.model.get_var_name(&new_var).unwrap(), "Other A");
+        assert_eq!(sketch.model.get_var_name(&var_a).unwrap(), "A");
+
+        // regulation (and its generated property) of the renamed variable is merged as new one
+        let regulation = sketch.model.get_regulation(&new_var, &var_b).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+        let regulation = sketch.model.get_regulation(&var_a, &var_b).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Activation);
+        let prop_id = StatProperty::get_monotonicity_prop_id(&new_var, &var_b);
+        assert!(sketch.properties.get_stat_prop(&prop_id).is_ok());
+        assert_eq!(sketch.model.get_update_fn_string(&var_b).unwrap(), "A");
+
+        // the dataset of the other sketch references the renamed variable
+        assert_eq!(sketch.observations.num_datasets(), 2);
+        let renamed_dataset = sketch
+            .observations
+            .datasets()
+            .map(|(_, d)| d)
+            .find(|d| d.is_valid_variable(&new_var))
+            .unwrap();
+        assert!(!renamed_dataset.is_valid_variable(&var_a));
+    }
+
+    #[test]
+    /// Test that perturbations of renamed variables are renamed in all datasets (including
+    /// those that do not capture the variable), and that multi-valued variables encoded by a
+    /// renamed variable are renamed together with all their encoding variables.
+    fn test_merge_rename_perturbed_variables() {
+        let aeon_str = "A -> B\nX_1 -> B\nX_2 -> B\n";
+        let mut sketch = prepare_sketch(aeon_str, "1010");
+        let mut other = Sketch::from_aeon(aeon_str).unwrap();
+        let var_a = VarId::new("A").unwrap();
+        let var_x1 = VarId::new("X_1").unwrap();
+        other.model.set_var_name(&var_a, "Other A").unwrap();
+        other.model.set_var_name(&var_x1, "Other X_1").unwrap();
+
+        // dataset `d` declares 3-level variable `X`, dataset `p` only perturbs `A` and `X_1`
+        let obs = Observation::try_from_str("10", "o").unwrap();
+        let mut dataset = Dataset::new("d", vec![obs], vec!["X_1", "X_2"]).unwrap();
+        let var_x = MultiValuedVar::new("X", vec!["low", "mid", "high"]).unwrap();
+        dataset.add_multi_valued_var(var_x.clone()).unwrap();
+        other.observations.add_dataset_by_str("d", dataset).unwrap();
+        let obs = Observation::try_from_str("1", "o").unwrap();
+        let mut dataset = Dataset::new("p", vec![obs], vec!["B"]).unwrap();
+        let (knockout, overexpression) =
+            (PerturbationType::KnockOut, PerturbationType::OverExpression);
+        dataset.set_perturbation(var_a.clone(), Some(knockout));
+        dataset.set_perturbation(var_x1.clone(), Some(overexpression));
+        other.observations.add_dataset_by_str("p", dataset).unwrap();
+
+        let report = sketch.merge(&other, MergeStrategy::Rename).unwrap();
+        assert_eq!(report.conflicts_of("variable").len(), 2);
+        // `A` and `X_1` conflict, but also `X_2` is renamed together with `X_1`
+        assert_eq!(sketch.model.num_vars(), 7);
+        let new_var_a = sketch
+            .model
+            .variables()
+            .find(|(_, var)| var.get_name() == "Other A")
+            .map(|(id, _)| id.clone())
+            .unwrap();
+
+        // multi-valued variable is renamed together with its encoding
+        let merged_dataset = sketch
+            .observations
+            .datasets()
+            .map(|(_, d)| d)
+            .find(|d| !d.multi_valued_vars().is_empty())
+            .unwrap();
+        let new_var_x = merged_dataset.multi_valued_vars()[0].clone();
+        assert_ne!(new_var_x.get_name(), "X");
+        assert_eq!(new_var_x.levels(), var_x.levels());
+        let new_encoding = new_var_x.encoding_vars();
+        assert_eq!(merged_dataset.variable_names(), new_encoding);
+        let new_var_x1 = VarId::new(&new_encoding[0]).unwrap();
+        assert_eq!(sketch.model.get_var_name(&new_var_x1).unwrap(), "Other X_1");
+        assert!(sketch.model.is_valid_var_id_str(&new_encoding[1]));
+
+        // perturbations of a dataset without the perturbed variables are renamed too
+        let perturbed_dataset = sketch
+            .observations
+            .datasets()
+            .map(|(_, d)| d)
+            .find(|d| d.is_perturbed())
+            .unwrap();
+        let perturbations = perturbed_dataset.perturbations();
+        assert_eq!(perturbations.len(), 2);
+        assert_eq!(perturbations.get(&new_var_a), Some(&knockout));
+        assert_eq!(perturbations.get(&new_var_x1), Some(&overexpression));
+    }
+
+    #[test]
+    /// Test that renamed FOL macros are substituted in macro bodies and static properties.
+    fn test_merge_rename_fol_macros() {
+        let params = vec!["x".to_string()];
+        let mut sketch = prepare_sketch("A -> B\n", "10");
+        let current_macro = FolMacro::try_new("m", params.clone(), "x", "").unwrap();
+        sketch
+            .properties
+            .add_fol_macro_by_str("m", current_macro)
+            .unwrap();
+
+        let mut other = sketch.clone();
+        let other_macro = FolMacro::try_new("m", params.clone(), "!x", "").unwrap();
+        let macro_id = FolMacroId::new("m").unwrap();
+        other
+            .properties
+            .swap_fol_macro_content(&macro_id, other_macro)
+            .unwrap();
+        let wrapper_macro = FolMacro::try_new("n", params, "m(x) & x", "").unwrap();
+        other
+            .properties
+            .add_fol_macro_by_str("n", wrapper_macro)
+            .unwrap();
+        let prop = StatProperty::try_mk_generic("g", "\\forall x: m(x)", "").unwrap();
+        other.properties.add_static_by_str("g", prop).unwrap();
+
+        let report = sketch.merge(&other, MergeStrategy::Rename).unwrap();
+        assert_eq!(report.conflicts_of("FOL macro").len(), 1);
+        assert_eq!(sketch.properties.num_fol_macros(), 3);
+        let new_id = sketch
+            .properties
+            .fol_macros()
+            .map(|(id, _)| id.as_str())
+            .find(|id| *id != "m" && *id != "n")
+            .unwrap()
+            .to_string();
+        let new_ref = format!("{new_id}(x)");
+
+        // both the macro body and the property reference the renamed macro
+        let wrapper_id = FolMacroId::new("n").unwrap();
+        let wrapper_macro = sketch.properties.get_fol_macro(&wrapper_id).unwrap();
+        assert!(wrapper_macro.get_raw_body().contains(&new_ref));
+        let prop_id = StatPropertyId::new("g").unwrap();
+        let prop = sketch.properties.get_stat_prop(&prop_id).unwrap();
+        let StatPropertyType::GenericStatProp(prop) = prop.get_prop_data() else {
+            panic!("Unexpected property variant.");
+        };
+        assert!(prop.raw_formula.contains(&new_ref));
+        assert!(!prop.raw_formula.contains("m(x)"));
+    }
+
+    #[test]
+    /// Test merging sketches with conflicts resolved by preferring one of them.
+    fn test_merge_prefer_side() {
+        let sketch = prepare_sketch("A -> B\n$B: A\n", "10");
+        let other = prepare_sketch("A -| B\n$B: !A\n", "01");
+        let var_a = VarId::new("A").unwrap();
+        let var_b = VarId::new("B").unwrap();
+        let prop_id = StatProperty::get_monotonicity_prop_id(&var_a, &var_b);
+
+        let mut left = sketch.clone();
+        let report = left.merge(&other, MergeStrategy::PreferLeft).unwrap();
+        assert_eq!(report.conflicts.len(), 3);
+        assert_eq!(left, sketch);
+
+        let mut right = sketch.clone();
+        let report = right.merge(&other, MergeStrategy::PreferRight).unwrap();
+        assert_eq!(report.conflicts.len(), 3);
+        let regulation = right.model.get_regulation(&var_a, &var_b).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+        assert_eq!(right.model.get_update_fn_string(&var_b).unwrap(), "!A");
+        assert_eq!(right.observations, other.observations);
+        assert_eq!(
+            right.properties.get_stat_prop(&prop_id),
+            other.properties.get_stat_prop(&prop_id)
+        );
+
+        // merging a sketch into itself changes nothing
+        let report = right.merge(&other, MergeStrategy::Rename).unwrap();
+        assert!(report.is_conflict_free());
+        assert_eq!(right.observations, other.observations);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::sketchbook::ids::{StatPropertyId, VarId};
+use crate::sketchbook::model::{Essentiality, Monotonicity, ReductionReport, Regulation};
+use crate::sketchbook::observations::VarValue;
+use crate::sketchbook::properties::dynamic_props::DynPropertyType;
+use crate::sketchbook::properties::shortcuts::{mk_essentiality_prop, mk_monotonicity_prop};
+use crate::sketchbook::properties::static_props::StatPropertyType;
+use crate::sketchbook::properties::StatProperty;
+use crate::sketchbook::Sketch;
+use std::collections::HashSet;
+
+/// Reducing the network of a sketch.
+impl Sketch {
+    /// Reduce the model of this sketch (see [crate::sketchbook::model::ModelState::reduce]),
+    /// keeping all variables referenced by properties or datasets (see [Self::referenced_vars]).
+    ///
+    /// Datasets are projected to the remaining variables (which only removes columns without
+    /// any specified values), and static properties automatically generated for regulations
+    /// are updated to follow the reduced regulatory graph.
+    ///
+    /// Returns the reduced sketch together with a report listing all reduction steps.
+    pub fn reduce(&self) -> Result<(Sketch, ReductionReport), String> {
+        let (model, report) = self.model.reduce(&self.referenced_vars())?;
+        let mut reduced = Sketch {
+            model,
+            observations: self.observations.clone(),
+            properties: self.properties.clone(),
+            annotation: self.annotation.clone(),
+        };
+        reduced.project_datasets_to_model()?;
+
+        for prop_id in self.generated_reg_prop_ids() {
+            if reduced.properties.is_valid_stat_property_id(&prop_id) {
+                reduced.properties.remove_static(&prop_id)?;
+            }
+        }
+        let regulations: Vec<Regulation> = reduced.model.regulations().cloned().collect();
+        for reg in regulations {
+            let (input_var, target_var) = (reg.get_regulator(), reg.get_target());
+            if reg.get_essentiality() != &Essentiality::Unknown {
+                let prop_id = StatProperty::get_essentiality_prop_id(input_var, target_var);
+                let prop = mk_essentiality_prop(input_var, target_var, *reg.get_essentiality());
+                reduced.properties.add_static(prop_id, prop)?;
+            }
+            if reg.get_sign() != &Monotonicity::Unknown {
+                let prop_id = StatProperty::get_monotonicity_prop_id(input_var, target_var);
+                let prop = mk_monotonicity_prop(input_var, target_var, *reg.get_sign());
+                reduced.properties.add_static(prop_id, prop)?;
+            }
+        }
+        Ok((reduced, report))
+    }
+
+    /// Collect all variables referenced by datasets (perturbed, or with a specified value in
+    /// some observation) or by properties. Static properties that are automatically generated
+    /// for regulations are not considered.
+    ///
+    /// Variables in formulas are collected syntactically, so the result may also contain
+    /// variables whose IDs coincide with names used in the formula for something else.
+    pub fn referenced_vars(&self) -> HashSet<VarId> {
+        let mut referenced = HashSet::new();
+        for (_, dataset) in self.observations.datasets() {
+            for (idx, var_id) in dataset.variables().iter().enumerate() {
+                let specified = dataset
+                    .observations()
+                    .iter()
+                    .any(|obs| obs.value_at_idx(idx).is_ok_and(|v| v != VarValue::Any));
+                if specified {
+                    referenced.insert(var_id.clone());
+                }
+            }
+            referenced.extend(dataset.perturbations().keys().cloned());
+        }
+
+        let generated_ids = self.generated_reg_prop_ids();
+        for (prop_id, prop) in self.properties.stat_props() {
+            if generated_ids.contains(prop_id) {
+                continue;
+            }
+            match prop.get_prop_data() {
+                StatPropertyType::GenericStatProp(p) => {
+                    let formula = self.properties.expand_fol_macros(&p.raw_formula);
+                    let formula = formula.unwrap_or(p.raw_formula.clone());
+                    referenced.extend(self.formula_var_ids(&formula));
+                }
+                StatPropertyType::RegulationEssential(p)
+                | StatPropertyType::RegulationEssentialContext(p) => {
+                    referenced.extend(p.input.iter().chain(p.target.iter()).cloned());
+                    referenced.extend(self.formula_var_ids(p.context.as_deref().unwrap_or("")));
+                }
+                StatPropertyType::RegulationMonotonic(p)
+                | StatPropertyType::RegulationMonotonicContext(p) => {
+                    referenced.extend(p.input.iter().chain(p.target.iter()).cloned());
+                    referenced.extend(self.formula_var_ids(p.context.as_deref().unwrap_or("")));
+                }
+                StatPropertyType::FnInputEssential(p)
+                | StatPropertyType::FnInputEssentialContext(p) => {
+                    referenced.extend(self.formula_var_ids(p.context.as_deref().unwrap_or("")));
+                }
+                StatPropertyType::FnInputMonotonic(p)
+                | StatPropertyType::FnInputMonotonicContext(p) => {
+                    referenced.extend(self.formula_var_ids(p.context.as_deref().unwrap_or("")));
+                }
+            }
+        }
+        for (_, prop) in self.properties.dyn_props() {
+            if let DynPropertyType::GenericDynProp(p) = prop.get_prop_data() {
+                referenced.extend(self.formula_var_ids(&p.raw_formula));
+            }
+        }
+        referenced
+    }
+
+    /// **(internal)** IDs of static properties automatically generated for the regulations.
+    fn generated_reg_prop_ids(&self) -> HashSet<StatPropertyId> {
+        let mut generated_ids = HashSet::new();
+        for reg in self.model.regulations() {
+            let (regulator, target) = (reg.get_regulator(), reg.get_target());
+            generated_ids.insert(StatProperty::get_essentiality_prop_id(regulator, target));
+            generated_ids.insert(StatProperty::get_monotonicity_prop_id(regulator, target));
+        }
+        generated_ids
+    }
+
+    /// **(internal)** Collect IDs of all model variables appearing in the formula as tokens.
+    fn formula_var_ids(&self, formula: &str) -> HashSet<VarId> {
+        formula
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter_map(|token| VarId::new(token).ok())
+            .filter(|var_id| self.model.is_valid_var_id(var_id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::{DatasetId, StatPropertyId, VarId};
+    use crate::sketchbook::model::ReductionStep;
+    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::properties::shortcuts::mk_hctl_prop;
+    use crate::sketchbook::Sketch;
+    use std::collections::HashSet;
+
+    #[test]
+    /// Test reducing a sketch, keeping variables referenced by properties and datasets.
+    fn test_reduce_sketch() {
+        let aeon_str = "X -> X\nX -> Y\nY -> Z\nZ -> W\n$X: X\n$Y: X\n$Z: Y\n$W: Z\n";
+        let mut sketch = Sketch::from_aeon(aeon_str).unwrap();
+        let obs = Observation::try_from_str("1**0", "o").unwrap();
+        let dataset = Dataset::new("d", vec![obs], vec!["X", "Y", "Z", "W"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+        let hctl_prop = mk_hctl_prop("3{x}: @{x}: Z").unwrap();
+        let properties = &mut sketch.properties;
+        properties.add_dynamic_by_str("hctl", hctl_prop).unwrap();
+
+        let var_ids = |ids: &[&str]| -> HashSet<VarId> {
+            ids.iter().map(|id| VarId::new(id).unwrap()).collect()
+        };
+        assert_eq!(sketch.referenced_vars(), var_ids(&["W", "X", "Z"]));
+
+        let (reduced, report) = sketch.reduce().unwrap();
+        let expected_steps = vec![ReductionStep::VariableEliminated {
+            var_id: "Y".to_string(),
+            expression: "X".to_string(),
+        }];
+        assert_eq!(report.steps, expected_steps);
+        assert_eq!(reduced.model.num_vars(), 3);
+        let dataset_id = DatasetId::new("d").unwrap();
+        let dataset = reduced.observations.get_dataset(&dataset_id).unwrap();
+        assert_eq!(dataset.num_variables(), 3);
+
+        // generated properties follow the new regulations
+        let prop_ids: HashSet<StatPropertyId> = reduced
+            .properties
+            .stat_props()
+            .map(|(id, _)| id.clone())
+            .collect();
+        assert!(prop_ids.contains(&StatPropertyId::new("monotonicity_X_Z").unwrap()));
+        assert!(!prop_ids.contains(&StatPropertyId::new("monotonicity_X_Y").unwrap()));
+        assert!(reduced.assert_consistency().is_ok());
+    }
+}
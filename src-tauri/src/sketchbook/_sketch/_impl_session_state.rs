@@ -1,8 +1,11 @@
 use crate::app::event::Event;
 use crate::app::state::{Consumed, SessionHelper, SessionState};
 use crate::app::DynError;
-use crate::sketchbook::data_structs::SketchData;
+use crate::sketchbook::data_structs::{
+    ConsistencyReportData, SketchData, SketchMergeData, SketchMergeResultData, SubSketchData,
+};
 use crate::sketchbook::event_utils::{make_reversible, make_state_change};
+use crate::sketchbook::ids::VarId;
 use crate::sketchbook::{JsonSerde, Sketch};
 use std::fs::File;
 use std::io::Read;
@@ -11,8 +14,14 @@ use std::io::Read;
 
 // events being delegated to `model` subcomponent
 const MODEL_PATH: &str = "model";
+// applying text patches (handled by `model` subcomponent, but validated on the whole sketch)
+const APPLY_PATCH_PATH: &str = "apply_patch";
 // events being delegated to `observations` subcomponent
 const OBSERVATIONS_PATH: &str = "observations";
+// loading datasets (handled by `observations` subcomponent, but validated against the model)
+const LOAD_DATASET_PATH: &str = "load";
+// loading datasets with an alias table for mapping dataset variables to model variables
+const LOAD_DATASET_WITH_ALIASES_PATH: &str = "load_with_aliases";
 // events being delegated to `properties` subcomponent
 const PROPERTIES_PATH: &str = "properties";
 // create new sketch and replace the current data
@@ -21,14 +30,26 @@ const NEW_SKETCH_PATH: &str = "new_sketch";
 const EXPORT_SKETCH_PATH: &str = "export_sketch";
 // export the current sketch to extended aeon format
 const EXPORT_AEON_PATH: &str = "export_aeon";
+// export the current sketch to SBML-qual format (sketch-specific details in annotations)
+const EXPORT_SBML_PATH: &str = "export_sbml";
+// export the current model to BoolNet bnet format (only fully specified functions)
+const EXPORT_BNET_PATH: &str = "export_bnet";
 // import sketch from custom format and replace the current data
 const IMPORT_SKETCH_PATH: &str = "import_sketch";
 // import sketch from aeon format and replace the current data
 const IMPORT_AEON_PATH: &str = "import_aeon";
 // import model from sbml format and replace the current data
 const IMPORT_SBML_PATH: &str = "import_sbml";
+// import model from BoolNet bnet format and replace the current data
+const IMPORT_BNET_PATH: &str = "import_bnet";
+// merge sketch from a file (custom or aeon format) into the current sketch
+const MERGE_SKETCH_PATH: &str = "merge_sketch";
+// export the sub-sketch induced by a set of variables to a file (and report dropped properties)
+const EXTRACT_SUB_SKETCH_PATH: &str = "extract_sub_sketch";
 // check if various components of sketch are consistent together (and report issues)
 const CHECK_CONSISTENCY_PATH: &str = "check_consistency";
+// run the lint pass over properties of a consistent sketch (and report issues)
+const LINT_PROPERTIES_PATH: &str = "lint_properties";
 // assert that various components of sketch are consistent together
 const ASSERT_CONSISTENCY_PATH: &str = "assert_consistency";
 // set annotation for the sketch
@@ -42,9 +63,28 @@ impl SessionState for Sketch {
     fn perform_event(&mut self, event: &Event, at_path: &[&str]) -> Result<Consumed, DynError> {
         // just distribute the events one layer down, or answer some specific cases
         if let Some(at_path) = Self::starts_with(MODEL_PATH, at_path) {
-            self.model.perform_event(event, at_path)
+            let consumed = self.model.perform_event(event, at_path)?;
+            if at_path == [APPLY_PATCH_PATH] {
+                // the patch is broken down into many events (including changes of static
+                // properties), so the whole chain is first tried on a copy of the sketch
+                if let Consumed::Restart(events) = &consumed {
+                    self.clone().perform_event_chain(events.clone())?;
+                }
+            }
+            Ok(consumed)
         } else if let Some(at_path) = Self::starts_with(OBSERVATIONS_PATH, at_path) {
-            self.observations.perform_event(event, at_path)
+            if at_path == [LOAD_DATASET_PATH] {
+                // variables of loaded datasets are mapped to (and checked against) the model
+                let model_vars = self.model_var_names();
+                self.observations
+                    .event_load_dataset(event, Some(&model_vars))
+            } else if at_path == [LOAD_DATASET_WITH_ALIASES_PATH] {
+                let model_vars = self.model_var_names();
+                self.observations
+                    .event_load_dataset_with_aliases(event, &model_vars)
+            } else {
+                self.observations.perform_event(event, at_path)
+            }
         } else if let Some(at_path) = Self::starts_with(PROPERTIES_PATH, at_path) {
             self.properties.perform_event(event, at_path)
         } else if Self::starts_with(NEW_SKETCH_PATH, at_path).is_some() {
@@ -64,6 +104,22 @@ impl SessionState for Sketch {
             let path = Self::clone_payload_str(event, "sketch")?;
             self.export_to_aeon(&path)?;
             Ok(Consumed::NoChange)
+        } else if Self::starts_with(EXPORT_SBML_PATH, at_path).is_some() {
+            let path = Self::clone_payload_str(event, "sketch")?;
+            self.export_to_sbml(&path)?;
+            Ok(Consumed::NoChange)
+        } else if Self::starts_with(EXPORT_BNET_PATH, at_path).is_some() {
+            let path = Self::clone_payload_str(event, "sketch")?;
+            // warnings about unsupported features are written into the exported file, and also
+            // sent to the frontend
+            let warnings = self.export_to_bnet(&path)?;
+            let payload = serde_json::to_string(&warnings)?;
+            let state_change = Event::build(&["sketch", "export_bnet_warnings"], Some(&payload));
+            // irreversible change that should just bypass the stack (not reset it)
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: false,
+            })
         } else if Self::starts_with(IMPORT_SKETCH_PATH, at_path).is_some() {
             let file_path = Self::clone_payload_str(event, "sketch")?;
             // read the file contents
@@ -106,7 +162,7 @@ impl SessionState for Sketch {
             let mut contents = String::new();
             file.read_to_string(&mut contents)?;
 
-            // parse the SBML format (only psbn, no additional properties or datasets)
+            // parse the SBML format (other sketch components are recovered from annotations)
             let new_sketch = Sketch::from_sbml(&contents)?;
             self.modify_from_sketch(&new_sketch);
 
@@ -117,21 +173,98 @@ impl SessionState for Sketch {
                 state_change,
                 reset: true,
             })
-        } else if Self::starts_with(CHECK_CONSISTENCY_PATH, at_path).is_some() {
-            let (success, message) = self.run_consistency_check();
-            let results = if success {
-                "No issues with the sketch were discovered!".to_string()
+        } else if Self::starts_with(IMPORT_BNET_PATH, at_path).is_some() {
+            let file_path = Self::clone_payload_str(event, "sketch")?;
+            // read the file contents
+            let mut file = File::open(file_path)?;
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)?;
+
+            // parse the bnet format (only psbn, regulations are inferred from functions)
+            let new_sketch = Sketch::from_bnet(&contents)?;
+            self.modify_from_sketch(&new_sketch);
+
+            let sketch_data = SketchData::new_from_sketch(self);
+            let state_change = make_state_change(&["sketch", "set_all"], &sketch_data);
+            // this is probably one of the real irreversible changes
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: true,
+            })
+        } else if Self::starts_with(MERGE_SKETCH_PATH, at_path).is_some() {
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let merge_data = SketchMergeData::from_json_str(&payload)?;
+            // read the file contents
+            let mut file = File::open(&merge_data.path)?;
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)?;
+
+            // parse the sketch based on the file extension, and merge it into the current one
+            let other_sketch = if merge_data.path.to_lowercase().ends_with(".json") {
+                Sketch::from_custom_json(&contents)?
             } else {
-                format!("There are issues with the sketch:\n\n{message}")
+                Sketch::from_aeon(&contents)?
             };
+            let report = self.merge(&other_sketch, merge_data.strategy)?;
 
-            let payload = serde_json::to_string(&results).unwrap();
+            // the merged sketch is sent to the frontend together with the merge report
+            let result_data = SketchMergeResultData::new(self, &report);
+            let state_change = make_state_change(&["sketch", "merge_results"], &result_data);
+            // same as with imports, this is an irreversible change
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: true,
+            })
+        } else if Self::starts_with(EXTRACT_SUB_SKETCH_PATH, at_path).is_some() {
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let sub_sketch_data = SubSketchData::from_json_str(&payload)?;
+            let variables = sub_sketch_data
+                .variables
+                .iter()
+                .map(|v| VarId::new(v))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // properties referring to removed variables are dropped (and listed in the report)
+            let (sub_sketch, report) =
+                self.extract_sub_sketch(&variables, sub_sketch_data.input_mode)?;
+            // the current sketch stays unchanged, the sub-sketch is written to a new file
+            if sub_sketch_data.path.to_lowercase().ends_with(".json") {
+                sub_sketch.export_to_custom_json(&sub_sketch_data.path)?;
+            } else {
+                sub_sketch.export_to_aeon(&sub_sketch_data.path)?;
+            }
+
+            let payload = report.to_json_str();
+            let state_change = Event::build(&["sketch", "extraction_results"], Some(&payload));
+            // irreversible change that should just bypass the stack (not reset it)
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: false,
+            })
+        } else if Self::starts_with(CHECK_CONSISTENCY_PATH, at_path).is_some() {
+            let report = self.run_consistency_check();
+            let results = ConsistencyReportData::from_report(&report);
+
+            let payload = results.to_json_str();
             let state_change = Event::build(&["sketch", "consistency_results"], Some(&payload));
             // irreversible change that should just bypass the stack (not reset it)
             Ok(Consumed::Irreversible {
                 state_change,
                 reset: false,
             })
+        } else if Self::starts_with(LINT_PROPERTIES_PATH, at_path).is_some() {
+            // lints are only meaningful for consistent sketches
+            self.assert_consistency()?;
+            let report = self.lint_properties();
+            let results = ConsistencyReportData::from_report(&report);
+
+            let payload = results.to_json_str();
+            let state_change = Event::build(&["sketch", "lint_results"], Some(&payload));
+            // irreversible change that should just bypass the stack (not reset it)
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: false,
+            })
         } else if Self::starts_with(SET_ANNOTATION_PATH, at_path).is_some() {
             let new_annotation = Self::clone_payload_str(event, "sketch")?;
             let orig_annotation = self.get_annotation().to_string();
@@ -175,3 +308,31 @@ impl SessionState for Sketch {
         }
     }
 }
+
+impl Sketch {
+    /// **(internal)** Names of all model variables, used to map variables of loaded datasets.
+    fn model_var_names(&self) -> Vec<String> {
+        self.model.variables().map(|(v, _)| v.to_string()).collect()
+    }
+
+    /// **(internal)** Perform a list of (sketch) events given in the form of [Consumed::Restart],
+    /// together with all the events they are broken down into, in the same order as the
+    /// session state does (i.e., from the last one).
+    ///
+    /// Return `Err` if any of the events fails.
+    fn perform_event_chain(&mut self, events: Vec<Event>) -> Result<(), DynError> {
+        let mut to_perform = events;
+        while let Some(event) = to_perform.pop() {
+            let event_path: Vec<&str> = event.path.iter().map(|it| it.as_str()).collect();
+            let Some(at_path) = Self::starts_with("sketch", &event_path) else {
+                return Self::invalid_path_error_generic(&event_path);
+            };
+            match self.perform_event(&event, at_path)? {
+                Consumed::Restart(events) => to_perform.extend(events),
+                Consumed::InputError(error) => return Err(error),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -35,7 +35,7 @@ impl Sketch {
                 .zip(datasets)
                 .collect(),
         )?;
-        let prop_manager = PropertyManager::new_from_properties(
+        let mut prop_manager = PropertyManager::new_from_properties(
             sketch_data
                 .dyn_properties
                 .iter()
@@ -49,6 +49,9 @@ impl Sketch {
                 .zip(stat_properties)
                 .collect(),
         )?;
+        for macro_data in sketch_data.fol_macros.iter() {
+            prop_manager.add_fol_macro_by_str(&macro_data.id, macro_data.to_macro()?)?;
+        }
         Ok((model, obs_manager, prop_manager))
     }
 
@@ -0,0 +1,101 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Error, Formatter};
+
+/// Strategy to resolve conflicts when merging another sketch into the current one.
+///
+/// - `Rename` keeps both conflicting components, and the one coming from the other sketch
+///   gets a fresh ID (references to it are updated accordingly)
+/// - `PreferLeft` keeps the component of the current sketch
+/// - `PreferRight` replaces the component of the current sketch with the other one
+///
+/// Variables with the same ID conflict when their names or annotations differ. With `Rename`,
+/// the conflicting variable of the other sketch becomes a new variable (together with its
+/// regulations and update function). Regulations and update functions of variables shared by
+/// both sketches can not be renamed, and their conflicts are resolved as with `PreferLeft`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum MergeStrategy {
+    #[default]
+    Rename,
+    PreferLeft,
+    PreferRight,
+}
+
+impl<'de> JsonSerde<'de> for MergeStrategy {}
+
+/// A single conflict found when merging two sketches, together with the way it was resolved.
+///
+/// The `component` describes the kind of conflicting entity ("variable", "dataset", ...), and
+/// the `id` is its ID in the merged (other) sketch.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct MergeConflict {
+    pub component: String,
+    pub id: String,
+    pub resolution: String,
+}
+
+impl<'de> JsonSerde<'de> for MergeConflict {}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(
+            f,
+            "> Conflicting {} `{}`: {}",
+            self.component, self.id, self.resolution
+        )
+    }
+}
+
+/// Report summarizing conflicts found (and resolved) when merging two sketches.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MergeReport {
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl<'de> JsonSerde<'de> for MergeReport {}
+
+impl MergeReport {
+    /// Create new empty report.
+    pub fn new() -> MergeReport {
+        MergeReport::default()
+    }
+
+    /// Record a conflict of a given component (with given ID) and its resolution.
+    pub fn add_conflict(&mut self, component: &str, id: &str, resolution: &str) {
+        self.conflicts.push(MergeConflict {
+            component: component.to_string(),
+            id: id.to_string(),
+            resolution: resolution.to_string(),
+        })
+    }
+
+    /// Check whether the sketches were merged without any conflicts.
+    pub fn is_conflict_free(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Get all conflicts of a given component.
+    pub fn conflicts_of(&self, component: &str) -> Vec<&MergeConflict> {
+        self.conflicts
+            .iter()
+            .filter(|c| c.component == component)
+            .collect()
+    }
+}
+
+impl Display for MergeReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        if self.conflicts.is_empty() {
+            return write!(f, "Sketches merged without conflicts.");
+        }
+        writeln!(
+            f,
+            "Sketches merged with {} conflicts:",
+            self.conflicts.len()
+        )?;
+        for conflict in &self.conflicts {
+            writeln!(f, "{conflict}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Error, Formatter};
+
+/// Kind of a difference between two versions of a sketch.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl DiffKind {
+    /// Symbol used to mark the difference in the text rendering of the diff.
+    pub fn as_symbol(&self) -> &str {
+        match self {
+            DiffKind::Added => "+",
+            DiffKind::Removed => "-",
+            DiffKind::Changed => "~",
+        }
+    }
+}
+
+/// Component of the sketch that a difference relates to.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum DiffComponent {
+    Variables,
+    Regulations,
+    UpdateFunctions,
+    UninterpretedFunctions,
+    Datasets,
+    Observations,
+    StaticProperties,
+    DynamicProperties,
+    FolMacros,
+    Annotation,
+}
+
+impl DiffComponent {
+    /// All components, in the order used in the text rendering of the diff.
+    pub const ALL: [DiffComponent; 10] = [
+        DiffComponent::Variables,
+        DiffComponent::Regulations,
+        DiffComponent::UpdateFunctions,
+        DiffComponent::UninterpretedFunctions,
+        DiffComponent::Datasets,
+        DiffComponent::Observations,
+        DiffComponent::StaticProperties,
+        DiffComponent::DynamicProperties,
+        DiffComponent::FolMacros,
+        DiffComponent::Annotation,
+    ];
+
+    /// Heading of the component used in the text rendering of the diff.
+    pub fn as_heading(&self) -> &str {
+        match self {
+            DiffComponent::Variables => "VARIABLES",
+            DiffComponent::Regulations => "REGULATIONS",
+            DiffComponent::UpdateFunctions => "UPDATE FUNCTIONS",
+            DiffComponent::UninterpretedFunctions => "UNINTERPRETED FUNCTIONS",
+            DiffComponent::Datasets => "DATASETS",
+            DiffComponent::Observations => "OBSERVATIONS",
+            DiffComponent::StaticProperties => "STATIC PROPERTIES",
+            DiffComponent::DynamicProperties => "DYNAMIC PROPERTIES",
+            DiffComponent::FolMacros => "FOL MACROS",
+            DiffComponent::Annotation => "ANNOTATION",
+        }
+    }
+}
+
+/// A single difference between two versions of a sketch.
+///
+/// The `id` identifies the added/removed/changed entity (variable ID, regulation in a form
+/// `A -> B`, observation in a form `dataset/observation`, ...). Changed entities carry a list
+/// of human-readable `details` describing what exactly changed.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DiffEntry {
+    pub kind: DiffKind,
+    pub component: DiffComponent,
+    pub id: String,
+    pub details: Vec<String>,
+}
+
+impl<'de> JsonSerde<'de> for DiffEntry {}
+
+impl Display for DiffEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "{} {}", self.kind.as_symbol(), self.id)?;
+        for detail in &self.details {
+            write!(f, "\n    {detail}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Semantic diff between two versions of a sketch, i.e., a list of all differences.
+///
+/// The diff can be serialized to JSON, or rendered as a human-readable text (by its
+/// `Display` implementation, or with a summary using [SketchDiff::summary]).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SketchDiff {
+    entries: Vec<DiffEntry>,
+}
+
+impl<'de> JsonSerde<'de> for SketchDiff {}
+
+impl SketchDiff {
+    /// Create new empty diff.
+    pub fn new() -> SketchDiff {
+        SketchDiff::default()
+    }
+
+    /// Add a difference regarding given component and entity.
+    pub fn add_entry(
+        &mut self,
+        kind: DiffKind,
+        component: DiffComponent,
+        id: &str,
+        details: Vec<String>,
+    ) {
+        self.entries.push(DiffEntry {
+            kind,
+            component,
+            id: id.to_string(),
+            details,
+        })
+    }
+
+    /// Check whether the two sketches are the same (there are no differences).
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get a list of all differences.
+    pub fn entries(&self) -> &Vec<DiffEntry> {
+        &self.entries
+    }
+
+    /// Iterate over all differences regarding given component.
+    pub fn component_entries(&self, component: DiffComponent) -> impl Iterator<Item = &DiffEntry> {
+        self.entries
+            .iter()
+            .filter(move |e| e.component == component)
+    }
+
+    /// Summary message with numbers of added, removed and changed entities, followed by
+    /// the text rendering of all differences (if there are any).
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "The sketches are the same.".to_string();
+        }
+        let count = |kind: DiffKind| self.entries.iter().filter(|e| e.kind == kind).count();
+        format!(
+            "The sketches differ ({} added, {} removed, {} changed):\n\n{self}",
+            count(DiffKind::Added),
+            count(DiffKind::Removed),
+            count(DiffKind::Changed),
+        )
+    }
+}
+
+impl Display for SketchDiff {
+    /// Render the differences as text, grouped by sketch components.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for component in DiffComponent::ALL {
+            let mut entries = self.component_entries(component).peekable();
+            if entries.peek().is_none() {
+                continue;
+            }
+            writeln!(f, "{}:", component.as_heading())?;
+            for entry in entries {
+                writeln!(f, "{entry}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -3,16 +3,41 @@ use crate::sketchbook::observations::ObservationManager;
 use crate::sketchbook::properties::PropertyManager;
 use crate::sketchbook::Manager;
 
+pub use _consistency_report::{
+    ConsistencyIssue, ConsistencyReport, IssueSeverity, SketchComponent,
+};
+pub use _extraction_report::{DroppedProperty, ExtractionReport};
+pub use _merge_report::{MergeConflict, MergeReport, MergeStrategy};
+pub use _sketch_diff::{DiffComponent, DiffEntry, DiffKind, SketchDiff};
+
+/// **(internal)** Structured report with results of the consistency check.
+mod _consistency_report;
+/// **(internal)** Structured report with results of a sub-sketch extraction.
+mod _extraction_report;
 /// **(internal)** Utilities to check consistency of `Sketch` instances.
 mod _impl_consistency;
+/// **(internal)** Computing semantic differences between two sketches.
+mod _impl_diff;
 /// **(internal)** Exporting sketch in various formats.
 mod _impl_export;
+/// **(internal)** Extracting sub-sketches induced by a set of variables.
+mod _impl_extract;
 /// **(internal)** Importing sketch in various formats.
 mod _impl_import;
+/// **(internal)** Utilities to lint properties of `Sketch` instances.
+mod _impl_lints;
+/// **(internal)** Merging two sketches together.
+mod _impl_merge;
+/// **(internal)** Reducing the network of a sketch.
+mod _impl_reduction;
 /// **(internal)** Implementation of event-based API for the [crate::app::state::SessionState] trait.
 mod _impl_session_state;
 /// **(internal)** Utility methods for `Sketch`.
 mod _impl_sketch;
+/// **(internal)** Structured report with results of merging two sketches.
+mod _merge_report;
+/// **(internal)** Structured list of differences between two sketches.
+mod _sketch_diff;
 
 /// Object encompassing all of the individual modules of the Boolean network sketch.
 ///
@@ -1,11 +1,14 @@
-use crate::app::state::{Consumed, SessionState};
+use crate::app::event::{Event, UserAction};
+use crate::app::state::editor::EditorSession;
+use crate::app::state::{Consumed, SessionState, StackSession};
 use crate::sketchbook::_tests_events::{check_reverse, stringify_path};
 use crate::sketchbook::data_structs::*;
-use crate::sketchbook::event_utils::mk_model_event;
+use crate::sketchbook::event_utils::{mk_model_event, mk_stat_prop_event};
 use crate::sketchbook::ids::VarId;
 use crate::sketchbook::layout::NodePosition;
 use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
 use crate::sketchbook::JsonSerde;
+use std::collections::HashSet;
 
 #[test]
 /// Test adding variable via events.
@@ -383,3 +386,94 @@ fn test_refresh() {
     assert_eq!(model_data.regulations, reg_list);
     assert_eq!(model_data.layouts, layout_list);
 }
+
+#[test]
+/// Test applying a text patch via event, as a single undoable action of an editor session.
+fn test_apply_patch() {
+    let get_sketch_data = |session: &EditorSession| {
+        let at_path = ["sketch", "get_whole_sketch"];
+        let event = session
+            .refresh(&stringify_path(&at_path), &at_path)
+            .unwrap();
+        let mut sketch_data = SketchData::from_json_str(&event.payload.unwrap()).unwrap();
+        // order of properties may change after undo (re-adding them), so sort them by IDs
+        sketch_data
+            .stat_properties
+            .sort_by(|p1, p2| p1.id.cmp(&p2.id));
+        sketch_data
+    };
+    let get_prop_ids = |sketch_data: &SketchData| -> HashSet<String> {
+        let props = sketch_data.stat_properties.iter();
+        props.map(|p| p.id.clone()).collect()
+    };
+
+    // prepare the model with variables `a`, `b`, `c` and two regulations
+    let mut session = EditorSession::new("editor");
+    let mut setup_events = Vec::new();
+    for var in ["a", "b", "c"] {
+        let var_data = VariableData::new(var, var, "", "");
+        let payload = var_data.to_json_str();
+        setup_events.push(mk_model_event(&["variable", "add_raw"], Some(&payload)));
+    }
+    for regulation in ["b -| a", "c -> b"] {
+        let payload = RegulationData::try_from_reg_str(regulation)
+            .unwrap()
+            .to_json_str();
+        setup_events.push(mk_model_event(&["regulation", "add"], Some(&payload)));
+    }
+    let setup_action = UserAction {
+        events: setup_events,
+    };
+    session.perform_action(&setup_action).unwrap();
+    let orig_sketch_data = get_sketch_data(&session);
+
+    // invalid patch is rejected as a whole
+    let patch = "a -> c\na -> d\n";
+    let event = mk_model_event(&["apply_patch"], Some(patch));
+    let action = UserAction {
+        events: vec![event],
+    };
+    assert!(session.perform_action(&action).is_err());
+    assert_eq!(get_sketch_data(&session), orig_sketch_data);
+
+    // valid patch changes regulations (together with static properties) and update functions
+    let patch = "b -> a\na -| c\n-c -> b\n$c: !a\n$a: b\n";
+    let event = mk_model_event(&["apply_patch"], Some(patch));
+    let action = UserAction {
+        events: vec![event],
+    };
+    session.perform_action(&action).unwrap();
+    let sketch_data = get_sketch_data(&session);
+    let model_data = &sketch_data.model;
+    assert_eq!(model_data.regulations.len(), 2);
+    assert_eq!(model_data.variables[0].update_fn, "b");
+    assert_eq!(model_data.variables[2].update_fn, "!a");
+    let prop_ids = get_prop_ids(&sketch_data);
+    assert!(prop_ids.contains("monotonicity_a_c"));
+    assert!(!prop_ids.contains("monotonicity_c_b"));
+
+    // the whole patch is undone at once
+    let undo_event = Event::build(&["undo_stack", "undo"], None);
+    let undo_action = UserAction {
+        events: vec![undo_event],
+    };
+    session.perform_action(&undo_action).unwrap();
+    assert_eq!(get_sketch_data(&session), orig_sketch_data);
+
+    // patch that fails only when removing a (manually deleted) generated property is rejected
+    // as a whole, including its regulation operations that were valid
+    let prop_event = mk_stat_prop_event(&["monotonicity_c_b", "remove"], None);
+    let action = UserAction {
+        events: vec![prop_event],
+    };
+    session.perform_action(&action).unwrap();
+    let orig_sketch_data = get_sketch_data(&session);
+
+    let patch = "a -> c\n-c -> b\n";
+    let event = mk_model_event(&["apply_patch"], Some(patch));
+    let action = UserAction {
+        events: vec![event],
+    };
+    assert!(session.perform_action(&action).is_err());
+    assert_eq!(get_sketch_data(&session), orig_sketch_data);
+}
@@ -1,9 +1,11 @@
 use crate::app::event::Event;
-use crate::app::state::SessionState;
+use crate::app::state::{Consumed, SessionState};
 use crate::sketchbook::_tests_events::{check_reverse, stringify_path};
 use crate::sketchbook::data_structs::*;
 use crate::sketchbook::ids::DatasetId;
-use crate::sketchbook::observations::{Dataset, Observation, ObservationManager};
+use crate::sketchbook::observations::{
+    Dataset, MultiValuedVar, Observation, ObservationManager, PerturbationType,
+};
 use crate::sketchbook::JsonSerde;
 
 /// Prepare a simple dataset with 3 variables and 2 observations.
@@ -87,6 +89,31 @@ fn test_set_dataset_fields() {
     assert!(d1_ref.get_var_id("a").is_err());
     assert!(d1_ref.get_var_id("xyz").is_ok());
     check_reverse(&mut manager, &manager_orig, result, &["d1", "set_var_id"]);
+
+    // 4) event to change dataset's metadata (name, variables, perturbations)
+    let d1_id = DatasetId::new("d1").unwrap();
+    let d1_ref = manager.get_dataset(&d1_id).unwrap();
+    let mut metadata = DatasetMetaData::from_dataset(&d1_id, d1_ref);
+    metadata.name = "new_name".to_string();
+    metadata.variables = vec!["a".to_string(), "b".to_string(), "X_1".to_string()];
+    metadata
+        .perturbations
+        .insert("a".to_string(), PerturbationType::KnockOut);
+    let full_path = ["observations", "d1", "set_metadata"];
+    let event = Event::build(&full_path, Some(&metadata.to_json_str()));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    let d1_ref = manager.get_dataset(&d1_id).unwrap();
+    assert_eq!(d1_ref.get_name(), "new_name");
+    assert_eq!(d1_ref.variable_names(), metadata.variables);
+    assert!(d1_ref.is_perturbed());
+    check_reverse(&mut manager, &manager_orig, result, &["d1", "set_metadata"]);
+
+    // invalid multi-valued variable (its encoding variable `X_2` is missing) makes the whole
+    // event fail, without changing any other metadata
+    metadata.multi_valued_vars = vec![MultiValuedVar::with_num_levels("X", 3).unwrap()];
+    let event = Event::build(&full_path, Some(&metadata.to_json_str()));
+    assert!(manager.perform_event(&event, &full_path[1..]).is_err());
+    assert_eq!(manager, manager_orig);
 }
 
 #[test]
@@ -191,6 +218,116 @@ fn test_set_observation_fields() {
     );
 }
 
+#[test]
+/// Test that observation events can not break the encoding of multi-valued variables.
+fn test_observation_events_multi_valued() {
+    let obs = Observation::try_from_str("10", "o1").unwrap();
+    let mut d1 = Dataset::new("d1", vec![obs], vec!["X_1", "X_2"]).unwrap();
+    let var_x = MultiValuedVar::with_num_levels("X", 3).unwrap();
+    d1.add_multi_valued_var(var_x).unwrap();
+    let mut manager = ObservationManager::from_datasets(vec![("d1", d1)]).unwrap();
+    let d1_id = manager.get_dataset_id("d1").unwrap();
+    let manager_orig = manager.clone();
+
+    // setting values `01` (not encoding any level) fails without any change
+    let new_obs = Observation::try_from_str("01", "o1").unwrap();
+    let new_obs_data = ObservationData::from_obs(&new_obs, &d1_id);
+    let full_path = ["observations", "d1", "o1", "set_data"];
+    let event = Event::build(&full_path, Some(&new_obs_data.to_json_str()));
+    assert!(manager.perform_event(&event, &full_path[1..]).is_err());
+    assert_eq!(manager, manager_orig);
+
+    // same for pushing such observation
+    let new_obs = Observation::try_from_str("01", "o2").unwrap();
+    let new_obs_data = ObservationData::from_obs(&new_obs, &d1_id);
+    let full_path = ["observations", "d1", "push_obs"];
+    let event = Event::build(&full_path, Some(&new_obs_data.to_json_str()));
+    assert!(manager.perform_event(&event, &full_path[1..]).is_err());
+    assert_eq!(manager, manager_orig);
+}
+
+#[test]
+/// Test bulk transformations of datasets (merge, split, select variables, ...) via events.
+fn test_transform_datasets() {
+    let d1 = prepare_dataset_3v_2o();
+    let d2 = prepare_dataset_2v_1o();
+    let mut d3 = prepare_dataset_2v_1o();
+    d3.set_obs_id_by_str("o1", "o3").unwrap();
+    let dataset_list = vec![("d1", d1), ("d2", d2), ("d3", d3)];
+    let mut manager = ObservationManager::from_datasets(dataset_list).unwrap();
+    let manager_orig = manager.clone();
+
+    // 1) event to merge two datasets (observation IDs must not clash)
+    let payload = DatasetMergeData::new("d1", "d2").to_json_str();
+    let full_path = ["observations", "merge"];
+    let event = Event::build(&full_path, Some(&payload));
+    assert!(manager.perform_event(&event, &full_path[1..]).is_err());
+    let payload = DatasetMergeData::new("d1", "d3").to_json_str();
+    let event = Event::build(&full_path, Some(&payload));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    let merged = manager.get_dataset_by_str("dataset_1").unwrap();
+    assert_eq!(merged.num_variables(), 5);
+    assert_eq!(merged.num_observations(), 3);
+    check_reverse(
+        &mut manager,
+        &manager_orig,
+        result,
+        &["dataset_1", "remove"],
+    );
+
+    // 2) event to select a subset of variables
+    let full_path = ["observations", "d1", "select_vars"];
+    let event = Event::build(&full_path, Some("[\"c\", \"a\"]"));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    let dataset = manager.get_dataset_by_str("d1").unwrap();
+    assert_eq!(dataset.variable_names(), vec!["c", "a"]);
+    check_reverse(&mut manager, &manager_orig, result, &["d1", "set_content"]);
+
+    // 3) event to reorder observations
+    let full_path = ["observations", "d1", "reorder_obs"];
+    let event = Event::build(&full_path, Some("[\"o2\", \"o1\"]"));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    let dataset = manager.get_dataset_by_str("d1").unwrap();
+    assert_eq!(dataset.get_obs_id(0).as_str(), "o2");
+    check_reverse(&mut manager, &manager_orig, result, &["d1", "set_content"]);
+
+    // 4) event to deduplicate observations (there are no duplicates, so no change)
+    let full_path = ["observations", "d1", "deduplicate"];
+    let event = Event::build(&full_path, None);
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+
+    // 5) event to transpose a dataset (observations become variables and vice versa)
+    let full_path = ["observations", "d1", "transpose"];
+    let event = Event::build(&full_path, None);
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    let dataset = manager.get_dataset_by_str("d1").unwrap();
+    assert_eq!(dataset.variable_names(), vec!["o1", "o2"]);
+    assert_eq!(dataset.num_observations(), 3);
+    check_reverse(&mut manager, &manager_orig, result, &["d1", "set_content"]);
+
+    // 6) event to split dataset, which is broken into sub-events
+    let full_path = ["observations", "d1", "split"];
+    let event = Event::build(&full_path, Some("[\"o2\"]"));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    if let Consumed::Restart(mut sub_events) = result {
+        assert_eq!(sub_events.len(), 2);
+        sub_events.reverse();
+        for e in sub_events {
+            let at_path: Vec<&str> = e.path[2..].iter().map(|s| s.as_str()).collect();
+            manager.perform_event(&e, &at_path).unwrap();
+        }
+    } else {
+        unreachable!();
+    }
+    assert_eq!(
+        manager.get_dataset_by_str("d1").unwrap().num_observations(),
+        1
+    );
+    let new_dataset = manager.get_dataset_by_str("dataset_1").unwrap();
+    assert_eq!(new_dataset.get_obs_id(0).as_str(), "o2");
+}
+
 #[test]
 /// Test all of the refresh (getter) events.
 fn test_refresh() {
@@ -0,0 +1,26 @@
+use crate::sketchbook::{ConsistencyIssue, ConsistencyReport, JsonSerde};
+use serde::{Deserialize, Serialize};
+
+/// Structure for sending results of the consistency check to the frontend.
+///
+/// Compared to `ConsistencyReport`, there are additional fields `consistent` (whether
+/// the sketch has no errors) and `summary` (text rendering of the whole report).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConsistencyReportData {
+    pub consistent: bool,
+    pub issues: Vec<ConsistencyIssue>,
+    pub summary: String,
+}
+
+impl<'de> JsonSerde<'de> for ConsistencyReportData {}
+
+impl ConsistencyReportData {
+    /// Create new `ConsistencyReportData` object given a reference to a consistency report.
+    pub fn from_report(report: &ConsistencyReport) -> ConsistencyReportData {
+        ConsistencyReportData {
+            consistent: report.is_consistent(),
+            issues: report.issues().clone(),
+            summary: report.summary(),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::sketchbook::observations::BinarizationConfig;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data needed to load (and binarize) a dataset with continuous values
+/// from the frontend.
+///
+/// Field `path` is a path to the CSV file, `config` carries the binarization settings.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContinuousLoadData {
+    pub path: String,
+    pub config: BinarizationConfig,
+}
+
+impl<'de> JsonSerde<'de> for ContinuousLoadData {}
+
+impl ContinuousLoadData {
+    /// Create new `ContinuousLoadData` object given a path and binarization settings.
+    pub fn new(path: &str, config: BinarizationConfig) -> ContinuousLoadData {
+        ContinuousLoadData {
+            path: path.to_string(),
+            config,
+        }
+    }
+}
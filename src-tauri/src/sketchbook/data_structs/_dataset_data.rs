@@ -1,33 +1,43 @@
 use crate::sketchbook::data_structs::ObservationData;
-use crate::sketchbook::ids::DatasetId;
-use crate::sketchbook::observations::{Dataset, Observation};
+use crate::sketchbook::ids::{DatasetId, VarId};
+use crate::sketchbook::observations::{Dataset, MultiValuedVar, Observation, PerturbationType};
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Structure for sending data about `Dataset` .
 ///
 /// Some fields simplified compared to original typesafe versions (e.g., pure `Strings` are used
 /// instead of more complex typesafe structs) to allow for easier (de)serialization.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct DatasetData {
     pub name: String,
     pub id: String,
     pub annotation: String,
     pub observations: Vec<ObservationData>,
     pub variables: Vec<String>,
+    #[serde(default)]
+    pub perturbations: BTreeMap<String, PerturbationType>,
+    #[serde(default)]
+    pub multi_valued_vars: Vec<MultiValuedVar>,
 }
 
 /// Structure for sending *metadata* about `Dataset`. This includes name, id, variable names,
-/// but excludes all observations.
+/// perturbations, and multi-valued variables, but excludes all observations.
 ///
 /// Some fields simplified compared to original typesafe versions (e.g., pure `Strings` are used
 /// instead of more complex typesafe structs) to allow for easier (de)serialization.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct DatasetMetaData {
     pub name: String,
     pub id: String,
     pub annotation: String,
     pub variables: Vec<String>,
+    #[serde(default)]
+    pub perturbations: BTreeMap<String, PerturbationType>,
+    #[serde(default)]
+    pub multi_valued_vars: Vec<MultiValuedVar>,
 }
 
 impl<'de> JsonSerde<'de> for DatasetData {}
@@ -50,6 +60,8 @@ impl DatasetData {
             annotation,
             observations,
             variables,
+            perturbations: perturbations_to_strings(dataset),
+            multi_valued_vars: dataset.multi_valued_vars().clone(),
         }
     }
 
@@ -62,7 +74,11 @@ impl DatasetData {
             .map(|o| o.to_observation())
             .collect::<Result<Vec<Observation>, String>>()?;
         let variables = self.variables.iter().map(|v| v.as_str()).collect();
-        Dataset::new_annotated(&self.name, &self.annotation, observations, variables)
+        let mut dataset =
+            Dataset::new_annotated(&self.name, &self.annotation, observations, variables)?;
+        dataset.set_all_perturbations(perturbations_from_strings(&self.perturbations)?);
+        dataset.set_all_multi_valued_vars(self.multi_valued_vars.clone())?;
+        Ok(dataset)
     }
 }
 
@@ -75,15 +91,41 @@ impl DatasetMetaData {
             id: id.to_string(),
             annotation: dataset.get_annotation().to_string(),
             variables,
+            perturbations: perturbations_to_strings(dataset),
+            multi_valued_vars: dataset.multi_valued_vars().clone(),
         }
     }
+
+    /// Try to convert the perturbations in this metadata to their typesafe version.
+    pub fn perturbations_typesafe(&self) -> Result<BTreeMap<VarId, PerturbationType>, String> {
+        perturbations_from_strings(&self.perturbations)
+    }
+}
+
+/// **(internal)** Convert perturbations of a dataset into a map with string variable names.
+fn perturbations_to_strings(dataset: &Dataset) -> BTreeMap<String, PerturbationType> {
+    dataset
+        .perturbations()
+        .iter()
+        .map(|(v, p)| (v.to_string(), *p))
+        .collect()
+}
+
+/// **(internal)** Try to convert perturbations with string variable names into typesafe map.
+fn perturbations_from_strings(
+    perturbations: &BTreeMap<String, PerturbationType>,
+) -> Result<BTreeMap<VarId, PerturbationType>, String> {
+    perturbations
+        .iter()
+        .map(|(v, p)| Ok((VarId::new(v)?, *p)))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::sketchbook::data_structs::DatasetData;
     use crate::sketchbook::ids::DatasetId;
-    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::observations::{Dataset, Observation, PerturbationType};
 
     #[test]
     /// Test converting between `Dataset` and `DatasetData`.
@@ -91,7 +133,10 @@ mod tests {
         let dataset_id = DatasetId::new("d").unwrap();
         let obs1 = Observation::try_from_str("*1", "o1").unwrap();
         let obs2 = Observation::try_from_str("00", "o2").unwrap();
-        let dataset_before = Dataset::new("d", vec![obs1, obs2], vec!["a", "b"]).unwrap();
+        let mut dataset_before = Dataset::new("d", vec![obs1, obs2], vec!["a", "b"]).unwrap();
+        dataset_before
+            .set_perturbation_by_str("a", Some(PerturbationType::KnockOut))
+            .unwrap();
         let dataset_data = DatasetData::from_dataset(&dataset_id, &dataset_before);
         let dataset_after = dataset_data.to_dataset().unwrap();
 
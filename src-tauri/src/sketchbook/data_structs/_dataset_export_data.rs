@@ -0,0 +1,25 @@
+use crate::sketchbook::observations::DatasetExportConfig;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data needed to export datasets from the frontend.
+///
+/// Field `path` is a path to the resulting file (or a directory, when exporting all
+/// datasets), `config` carries the format and layout settings.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DatasetExportData {
+    pub path: String,
+    pub config: DatasetExportConfig,
+}
+
+impl<'de> JsonSerde<'de> for DatasetExportData {}
+
+impl DatasetExportData {
+    /// Create new `DatasetExportData` object given a path and export settings.
+    pub fn new(path: &str, config: DatasetExportConfig) -> DatasetExportData {
+        DatasetExportData {
+            path: path.to_string(),
+            config,
+        }
+    }
+}
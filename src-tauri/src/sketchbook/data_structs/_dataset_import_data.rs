@@ -0,0 +1,41 @@
+use crate::sketchbook::data_structs::DatasetData;
+use crate::sketchbook::ids::DatasetId;
+use crate::sketchbook::observations::{
+    Dataset, DatasetImportReport, ImportIssue, VarMappingReport,
+};
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for sending data about a dataset imported from a file to the frontend.
+///
+/// Besides the dataset itself, it carries all the `issues` (warnings) found during the
+/// import, and the `summary` text rendering of the import report. If the dataset variables
+/// were mapped to model variables, the `mapping` report lists renamed and unmatched variables.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DatasetImportData {
+    pub dataset: DatasetData,
+    pub issues: Vec<ImportIssue>,
+    pub summary: String,
+    #[serde(default)]
+    pub mapping: VarMappingReport,
+}
+
+impl<'de> JsonSerde<'de> for DatasetImportData {}
+
+impl DatasetImportData {
+    /// Create new `DatasetImportData` object given a reference to a dataset, its ID, the import
+    /// report, and the variable mapping report.
+    pub fn new(
+        id: &DatasetId,
+        dataset: &Dataset,
+        report: &DatasetImportReport,
+        mapping: &VarMappingReport,
+    ) -> Self {
+        DatasetImportData {
+            dataset: DatasetData::from_dataset(id, dataset),
+            issues: report.issues().clone(),
+            summary: report.summary(),
+            mapping: mapping.clone(),
+        }
+    }
+}
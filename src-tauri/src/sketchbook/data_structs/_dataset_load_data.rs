@@ -0,0 +1,24 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data needed to load a dataset with an alias table from the frontend.
+///
+/// Field `path` is a path to the CSV/TSV file with the dataset, `alias_path` is a path to the
+/// file with aliases of model variables (see [crate::sketchbook::observations::AliasTable]).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DatasetLoadData {
+    pub path: String,
+    pub alias_path: String,
+}
+
+impl<'de> JsonSerde<'de> for DatasetLoadData {}
+
+impl DatasetLoadData {
+    /// Create new `DatasetLoadData` object given paths to a dataset and an alias file.
+    pub fn new(path: &str, alias_path: &str) -> DatasetLoadData {
+        DatasetLoadData {
+            path: path.to_string(),
+            alias_path: alias_path.to_string(),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data needed to merge two datasets from the frontend.
+///
+/// Fields `first_dataset` and `second_dataset` are IDs of the merged datasets (observations of
+/// the first one go first in the result).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DatasetMergeData {
+    pub first_dataset: String,
+    pub second_dataset: String,
+}
+
+impl<'de> JsonSerde<'de> for DatasetMergeData {}
+
+impl DatasetMergeData {
+    /// Create new `DatasetMergeData` object given IDs of the two datasets.
+    pub fn new(first_dataset: &str, second_dataset: &str) -> DatasetMergeData {
+        DatasetMergeData {
+            first_dataset: first_dataset.to_string(),
+            second_dataset: second_dataset.to_string(),
+        }
+    }
+}
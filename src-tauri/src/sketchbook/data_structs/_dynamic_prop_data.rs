@@ -2,23 +2,26 @@ use crate::sketchbook::ids::{DatasetId, DynPropertyId, ObservationId};
 use crate::sketchbook::properties::dynamic_props;
 use crate::sketchbook::JsonSerde;
 use dynamic_props::{DynProperty, DynPropertyType};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Simplified variant to carry data regarding [dynamic_props::GenericDynProp] dynamic property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct GenericDynPropData {
     pub formula: String,
 }
 
 /// Simplified variant to carry data regarding [dynamic_props::ExistsFixedPoint] dynamic property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ExistsFixedPointData {
     pub dataset: Option<String>,
     pub observation: Option<String>,
+    #[serde(default)]
+    pub soft: bool,
 }
 
 /// Simplified variant to carry data regarding [dynamic_props::ExistsTrapSpace] dynamic property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ExistsTrapSpaceData {
     pub dataset: Option<String>,
     pub observation: Option<String>,
@@ -27,27 +30,45 @@ pub struct ExistsTrapSpaceData {
 }
 
 /// Simplified variant to carry data regarding [dynamic_props::ExistsTrajectory] dynamic property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ExistsTrajectoryData {
     pub dataset: Option<String>,
+    #[serde(default)]
+    pub soft: bool,
 }
 
 /// Simplified variant to carry data regarding [dynamic_props::AttractorCount] dynamic property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct AttractorCountData {
     pub minimal: usize,
     pub maximal: usize,
 }
 
 /// Simplified variant to carry data regarding [dynamic_props::HasAttractor] dynamic property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct HasAttractorData {
     pub dataset: Option<String>,
     pub observation: Option<String>,
+    #[serde(default)]
+    pub soft: bool,
+}
+
+/// Simplified variant to carry data regarding [dynamic_props::StatesReachable] dynamic property.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct StatesReachableData {
+    pub dataset: Option<String>,
+    pub initial_observation: Option<String>,
+}
+
+/// Simplified variant to carry data regarding [dynamic_props::StatesInBasins] dynamic property.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct StatesInBasinsData {
+    pub dataset: Option<String>,
+    pub phenotypes: Option<String>,
 }
 
 /// Enum covering all variants of dynamic properties and their necessary data.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "variant")]
 pub enum DynPropertyTypeData {
     GenericDynProp(GenericDynPropData),
@@ -56,13 +77,15 @@ pub enum DynPropertyTypeData {
     ExistsTrajectory(ExistsTrajectoryData),
     AttractorCount(AttractorCountData),
     HasAttractor(HasAttractorData),
+    StatesReachable(StatesReachableData),
+    StatesInBasins(StatesInBasinsData),
 }
 
 /// Structure for sending data about dynamic properties to the frontend.
 ///
 /// Some fields simplified compared to original typesafe versions (e.g., pure `Strings` are used
 /// instead of more complex typesafe structs) to allow for easier (de)serialization.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct DynPropertyData {
     pub id: String,
     pub name: String,
@@ -97,6 +120,7 @@ impl DynPropertyData {
                 DynPropertyTypeData::ExistsFixedPoint(ExistsFixedPointData {
                     dataset: p.dataset.as_ref().map(|i| i.to_string()),
                     observation: p.observation.as_ref().map(|i| i.to_string()),
+                    soft: p.soft,
                 })
             }
             DynPropertyType::ExistsTrapSpace(p) => {
@@ -110,12 +134,14 @@ impl DynPropertyData {
             DynPropertyType::ExistsTrajectory(p) => {
                 DynPropertyTypeData::ExistsTrajectory(ExistsTrajectoryData {
                     dataset: p.dataset.as_ref().map(|i| i.to_string()),
+                    soft: p.soft,
                 })
             }
             DynPropertyType::HasAttractor(p) => {
                 DynPropertyTypeData::HasAttractor(HasAttractorData {
                     dataset: p.dataset.as_ref().map(|i| i.to_string()),
                     observation: p.observation.as_ref().map(|o| o.to_string()),
+                    soft: p.soft,
                 })
             }
             DynPropertyType::AttractorCount(p) => {
@@ -124,6 +150,18 @@ impl DynPropertyData {
                     maximal: p.maximal,
                 })
             }
+            DynPropertyType::StatesReachable(p) => {
+                DynPropertyTypeData::StatesReachable(StatesReachableData {
+                    dataset: p.dataset.as_ref().map(|i| i.to_string()),
+                    initial_observation: p.initial_observation.as_ref().map(|o| o.to_string()),
+                })
+            }
+            DynPropertyType::StatesInBasins(p) => {
+                DynPropertyTypeData::StatesInBasins(StatesInBasinsData {
+                    dataset: p.dataset.as_ref().map(|i| i.to_string()),
+                    phenotypes: p.phenotypes.as_ref().map(|i| i.to_string()),
+                })
+            }
         };
         Self::new_raw(id.as_str(), name, variant, annot)
     }
@@ -132,7 +170,7 @@ impl DynPropertyData {
     pub fn to_property(&self) -> Result<DynProperty, String> {
         let name = self.name.as_str();
         let annot = self.annotation.as_str();
-        let property = match &self.variant {
+        let mut property = match &self.variant {
             DynPropertyTypeData::GenericDynProp(p) => {
                 DynProperty::try_mk_generic(name, &p.formula, annot)?
             }
@@ -169,7 +207,30 @@ impl DynPropertyData {
             DynPropertyTypeData::AttractorCount(p) => {
                 DynProperty::try_mk_attractor_count(name, p.minimal, p.maximal, annot)?
             }
+            DynPropertyTypeData::StatesReachable(p) => DynProperty::mk_states_reachable(
+                name,
+                p.dataset.as_ref().and_then(|t| DatasetId::new(t).ok()),
+                p.initial_observation
+                    .as_ref()
+                    .and_then(|t| ObservationId::new(t).ok()),
+                annot,
+            ),
+            DynPropertyTypeData::StatesInBasins(p) => DynProperty::mk_states_in_basins(
+                name,
+                p.dataset.as_ref().and_then(|t| DatasetId::new(t).ok()),
+                p.phenotypes.as_ref().and_then(|t| DatasetId::new(t).ok()),
+                annot,
+            ),
         };
+        // soft mode is only present for some variants
+        match &self.variant {
+            DynPropertyTypeData::ExistsFixedPoint(ExistsFixedPointData { soft: true, .. })
+            | DynPropertyTypeData::HasAttractor(HasAttractorData { soft: true, .. })
+            | DynPropertyTypeData::ExistsTrajectory(ExistsTrajectoryData { soft: true, .. }) => {
+                property.set_soft(true)?
+            }
+            _ => {}
+        }
         Ok(property)
     }
 
@@ -0,0 +1,54 @@
+use crate::sketchbook::ids::FolMacroId;
+use crate::sketchbook::properties::FolMacro;
+use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Structure for sending data about FOL macros to the frontend.
+///
+/// Compared to `FolMacro`, there is an additional field `id`, and only the raw version of
+/// the macro's body is carried.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FolMacroData {
+    pub id: String,
+    pub name: String,
+    pub annotation: String,
+    pub params: Vec<String>,
+    pub body: String,
+}
+
+impl<'de> JsonSerde<'de> for FolMacroData {}
+
+impl FolMacroData {
+    /// Create new `FolMacroData` object given all its components.
+    pub fn new(id: &str, name: &str, params: &[String], body: &str, annot: &str) -> FolMacroData {
+        FolMacroData {
+            id: id.to_string(),
+            name: name.to_string(),
+            annotation: annot.to_string(),
+            params: params.to_vec(),
+            body: body.to_string(),
+        }
+    }
+
+    /// Create new `FolMacroData` object given a reference to a macro and its `id`.
+    pub fn from_macro(id: &FolMacroId, fol_macro: &FolMacro) -> FolMacroData {
+        FolMacroData::new(
+            id.as_str(),
+            fol_macro.get_name(),
+            fol_macro.get_params(),
+            fol_macro.get_raw_body(),
+            fol_macro.get_annotation(),
+        )
+    }
+
+    /// Extract new `FolMacro` instance from this data (if the macro's definition is valid).
+    pub fn to_macro(&self) -> Result<FolMacro, String> {
+        FolMacro::try_new(
+            &self.name,
+            self.params.clone(),
+            &self.body,
+            &self.annotation,
+        )
+    }
+}
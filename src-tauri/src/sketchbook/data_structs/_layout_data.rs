@@ -2,6 +2,7 @@ use crate::sketchbook::data_structs::LayoutNodeData;
 use crate::sketchbook::ids::LayoutId;
 use crate::sketchbook::layout::Layout;
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Structure for sending simplified general data about `Layout` to the frontend.
@@ -11,7 +12,7 @@ use serde::{Deserialize, Serialize};
 /// of more complex typesafe structs) to allow for easier (de)serialization.
 ///
 /// See also [LayoutNodeData] for a sub-structure to carry data regarding individual `NodeLayouts`.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct LayoutData {
     pub id: String,
     pub name: String,
@@ -23,7 +24,7 @@ pub struct LayoutData {
 ///
 /// Some fields of `LayoutData` are simplified compared to `Layout` (e.g., pure `Strings` instead
 /// of more complex typesafe structs) to allow for easier (de)serialization.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct LayoutMetaData {
     pub id: String,
     pub name: String,
@@ -1,6 +1,7 @@
 use crate::sketchbook::ids::{LayoutId, VarId};
 use crate::sketchbook::layout::LayoutNode;
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Structure for sending data about `NodeLayout` to frontend.
@@ -11,7 +12,7 @@ use serde::{Deserialize, Serialize};
 /// - `layout` is a string ID of the node's layout
 /// - `variable` is a string ID of the node's variable
 /// - `px` and `py` are the node's coordinates
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct LayoutNodeData {
     pub layout: String,
     pub variable: String,
@@ -21,7 +22,7 @@ pub struct LayoutNodeData {
 
 /// The same as `LayoutNodeData`, but does not have a fixed variable ID because
 /// it is associated with a variable that does not have an ID yet.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct LayoutNodeDataPrototype {
     pub layout: String,
     pub px: f32,
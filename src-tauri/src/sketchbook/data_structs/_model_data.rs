@@ -3,10 +3,11 @@ use crate::sketchbook::data_structs::{
 };
 use crate::sketchbook::model::ModelState;
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Structure for sending/exporting all necessary data about the model part of the sketch.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ModelData {
     pub variables: Vec<VariableData>,
     pub regulations: Vec<RegulationData>,
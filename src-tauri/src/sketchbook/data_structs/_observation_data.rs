@@ -1,6 +1,7 @@
 use crate::sketchbook::ids::DatasetId;
 use crate::sketchbook::observations::Observation;
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Structure for sending data about `Observation` to the frontend.
@@ -8,13 +9,22 @@ use serde::{Deserialize, Serialize};
 /// Contains also ID of the corresponding dataset. Some fields are further simplified compared to
 /// original typesafe versions (e.g., pure `Strings` are used instead of more complex typesafe
 /// structs) to allow for easier (de)serialization.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+///
+/// Field `weight` is optional when deserializing (it defaults to 1).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ObservationData {
     pub id: String,
     pub name: String,
     pub annotation: String,
     pub dataset: String,
     pub values: String,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+/// **(internal)** Default weight of observations (for deserialization).
+fn default_weight() -> u32 {
+    1
 }
 
 impl<'de> JsonSerde<'de> for ObservationData {}
@@ -34,30 +44,35 @@ impl ObservationData {
             annotation: annot.to_string(),
             dataset: dataset_id.to_string(),
             values: values.to_string(),
+            weight: default_weight(),
         }
     }
 
     /// Create new `ObservationData` instance given a reference to a observation, and ID of
     /// its dataset.
     pub fn from_obs(obs: &Observation, dataset_id: &DatasetId) -> ObservationData {
-        ObservationData::new(
+        let mut obs_data = ObservationData::new(
             obs.get_id().as_str(),
             obs.get_name(),
             obs.get_annotation(),
             dataset_id.as_str(),
             &obs.to_values_string(),
-        )
+        );
+        obs_data.weight = obs.get_weight();
+        obs_data
     }
 
     /// Extract the corresponding `Observation` from the `ObservationData`.
     /// There is a syntax check just to make sure that the data are valid.
     pub fn to_observation(&self) -> Result<Observation, String> {
-        Observation::try_from_str_annotated(
+        let mut obs = Observation::try_from_str_annotated(
             &self.values.clone(),
             &self.id,
             &self.name,
             &self.annotation,
-        )
+        )?;
+        obs.set_weight(self.weight)?;
+        Ok(obs)
     }
 }
 
@@ -71,7 +86,8 @@ mod tests {
     /// Test converting between `Observation` and `ObservationData`.
     fn test_converting() {
         let dataset_id = DatasetId::new("d").unwrap();
-        let obs_before = Observation::try_from_str("0011*", "o").unwrap();
+        let mut obs_before = Observation::try_from_str("0011*", "o").unwrap();
+        obs_before.set_weight(3).unwrap();
         let obs_data = ObservationData::from_obs(&obs_before, &dataset_id);
         let obs_after = obs_data.to_observation().unwrap();
 
@@ -1,13 +1,14 @@
 use crate::sketchbook::ids::VarId;
 use crate::sketchbook::model::{Essentiality, Monotonicity, Regulation};
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Structure for sending simplified data about `Regulation` to the frontend.
 ///
 /// Some fields of `RegulationData` are simplified compared to `Regulation` (e.g., pure `Strings` instead
 /// of more complex typesafe structs) to allow for easier (de)serialization.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct RegulationData {
     pub regulator: String,
     pub target: String,
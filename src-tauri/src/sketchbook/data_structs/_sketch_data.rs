@@ -1,21 +1,41 @@
-use crate::sketchbook::data_structs::{DatasetData, DynPropertyData, ModelData, StatPropertyData};
+use crate::sketchbook::data_structs::{
+    migrate_sketch_json, DatasetData, DynPropertyData, FolMacroData, ModelData, StatPropertyData,
+    SKETCH_FORMAT_VERSION,
+};
 use crate::sketchbook::model::ModelState;
 use crate::sketchbook::observations::ObservationManager;
 use crate::sketchbook::properties::PropertyManager;
 use crate::sketchbook::{JsonSerde, Sketch};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Structure for sending/exporting data about the whole Sketch.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+///
+/// This is also the custom JSON format of sketch files. The format is versioned, see
+/// [SKETCH_FORMAT_VERSION] and [migrate_sketch_json].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct SketchData {
+    /// Version of the format (sketches of older versions are upgraded when loaded).
+    pub format_version: u64,
     pub model: ModelData,
     pub datasets: Vec<DatasetData>,
     pub dyn_properties: Vec<DynPropertyData>,
     pub stat_properties: Vec<StatPropertyData>,
+    /// Named FOL macros used in static properties (missing in files from older versions).
+    #[serde(default)]
+    pub fol_macros: Vec<FolMacroData>,
     pub annotation: String,
 }
 
-impl<'de> JsonSerde<'de> for SketchData {}
+impl<'de> JsonSerde<'de> for SketchData {
+    /// Parse `SketchData` from a JSON string. Documents of older format versions are upgraded
+    /// first (see [migrate_sketch_json]), and documents of newer versions are rejected.
+    fn from_json_str(s: &'de str) -> Result<Self, String> {
+        let json = serde_json::from_str(s).map_err(|e| e.to_string())?;
+        let json = migrate_sketch_json(json)?;
+        serde_json::from_value(json).map_err(|e| e.to_string())
+    }
+}
 
 impl SketchData {
     /// Create new `SketchData` instance given a reference to all necessary manager classes.
@@ -37,12 +57,18 @@ impl SketchData {
             .stat_props()
             .map(|(p_id, p)| StatPropertyData::from_property(p_id, p))
             .collect();
+        let fol_macros = properties
+            .fol_macros()
+            .map(|(m_id, m)| FolMacroData::from_macro(m_id, m))
+            .collect();
 
         SketchData {
+            format_version: SKETCH_FORMAT_VERSION,
             model: ModelData::from_model(model),
             datasets,
             dyn_properties,
             stat_properties,
+            fol_macros,
             annotation: annotation.to_string(),
         }
     }
@@ -56,4 +82,11 @@ impl SketchData {
             &sketch.annotation,
         )
     }
+
+    /// JSON Schema describing the (current version of) sketch JSON format, as a pretty JSON
+    /// string. The published version of the schema is in `data/sketch.schema.json`.
+    pub fn json_schema() -> String {
+        let schema = schemars::schema_for!(SketchData);
+        serde_json::to_string_pretty(&schema).unwrap()
+    }
 }
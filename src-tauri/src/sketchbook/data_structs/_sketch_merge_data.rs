@@ -0,0 +1,50 @@
+use crate::sketchbook::data_structs::SketchData;
+use crate::sketchbook::{JsonSerde, MergeConflict, MergeReport, MergeStrategy, Sketch};
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data needed to merge a sketch from a file into the current one
+/// from the frontend.
+///
+/// Field `path` is a path to the file with the sketch (either in custom JSON format, or in
+/// extended AEON format), `strategy` describes how conflicts are resolved (see [MergeStrategy]).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SketchMergeData {
+    pub path: String,
+    pub strategy: MergeStrategy,
+}
+
+/// Structure for sending the result of merging a sketch into the current one to the frontend.
+///
+/// Besides the whole merged sketch, it carries all the `conflicts` found during the merge
+/// (with their resolutions), and the `summary` text rendering of the merge report.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SketchMergeResultData {
+    pub sketch: SketchData,
+    pub conflicts: Vec<MergeConflict>,
+    pub summary: String,
+}
+
+impl<'de> JsonSerde<'de> for SketchMergeData {}
+impl<'de> JsonSerde<'de> for SketchMergeResultData {}
+
+impl SketchMergeData {
+    /// Create new `SketchMergeData` object given a path to a sketch file and a merge strategy.
+    pub fn new(path: &str, strategy: MergeStrategy) -> SketchMergeData {
+        SketchMergeData {
+            path: path.to_string(),
+            strategy,
+        }
+    }
+}
+
+impl SketchMergeResultData {
+    /// Create new `SketchMergeResultData` object given a reference to the merged sketch and
+    /// the corresponding merge report.
+    pub fn new(sketch: &Sketch, report: &MergeReport) -> SketchMergeResultData {
+        SketchMergeResultData {
+            sketch: SketchData::new_from_sketch(sketch),
+            conflicts: report.conflicts.clone(),
+            summary: report.to_string(),
+        }
+    }
+}
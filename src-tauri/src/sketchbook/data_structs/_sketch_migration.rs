@@ -0,0 +1,153 @@
+use serde_json::{Map, Value};
+
+/// Current version of the sketch JSON format.
+///
+/// Increase it whenever the format changes in a way that older files can not be parsed
+/// directly, and add the corresponding migration to [MIGRATIONS].
+pub const SKETCH_FORMAT_VERSION: u64 = 1;
+
+/// Name of the JSON field carrying the format version. Files without this field were created
+/// before the format was versioned, and they are treated as version 0.
+pub const FORMAT_VERSION_FIELD: &str = "format_version";
+
+/// **(internal)** Migration upgrading the JSON object of a sketch by a single format version.
+type Migration = fn(&mut Map<String, Value>) -> Result<(), String>;
+
+/// **(internal)** Chain of all migrations, the `i`-th one upgrades a sketch from version `i` to
+/// version `i + 1`.
+const MIGRATIONS: [Migration; SKETCH_FORMAT_VERSION as usize] = [migrate_v0_to_v1];
+
+/// Upgrade a JSON document with a sketch of any older format version to the current version,
+/// applying the migrations step by step.
+///
+/// Returns an error if the document has a newer version than this version of the application
+/// supports, or if some of the migrations fail.
+pub fn migrate_sketch_json(mut json: Value) -> Result<Value, String> {
+    let sketch = json
+        .as_object_mut()
+        .ok_or("Sketch JSON must be an object.")?;
+    let version = match sketch.get(FORMAT_VERSION_FIELD) {
+        None => 0,
+        Some(version) => version.as_u64().ok_or(format!(
+            "Field `{FORMAT_VERSION_FIELD}` must be a non-negative integer."
+        ))?,
+    };
+    if version > SKETCH_FORMAT_VERSION {
+        return Err(format!(
+            "The sketch uses format version {version}, but this version of the application only \
+            supports versions up to {SKETCH_FORMAT_VERSION}. Please update the application."
+        ));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(sketch)
+            .map_err(|e| format!("Failed to upgrade sketch from format version {from}. {e}"))?;
+        sketch.insert(FORMAT_VERSION_FIELD.to_string(), Value::from(from + 1));
+    }
+    Ok(json)
+}
+
+/// **(internal)** Upgrade sketches created before the format was versioned. These might miss
+/// fields that were added over time (FOL macros, perturbations and multi-valued variables of
+/// datasets, weights of observations, and `soft` flags of dynamic properties), which are filled
+/// with their default values.
+fn migrate_v0_to_v1(sketch: &mut Map<String, Value>) -> Result<(), String> {
+    sketch
+        .entry("fol_macros")
+        .or_insert(Value::Array(Vec::new()));
+
+    if let Some(datasets) = sketch.get_mut("datasets").and_then(|d| d.as_array_mut()) {
+        for dataset in datasets {
+            let dataset = dataset
+                .as_object_mut()
+                .ok_or("Dataset must be an object.")?;
+            dataset
+                .entry("perturbations")
+                .or_insert(Value::Object(Map::new()));
+            dataset
+                .entry("multi_valued_vars")
+                .or_insert(Value::Array(Vec::new()));
+            let observations = dataset
+                .get_mut("observations")
+                .and_then(|o| o.as_array_mut());
+            for obs in observations.into_iter().flatten() {
+                let obs = obs
+                    .as_object_mut()
+                    .ok_or("Observation must be an object.")?;
+                obs.entry("weight").or_insert(Value::from(1));
+            }
+        }
+    }
+
+    let dyn_properties = sketch
+        .get_mut("dyn_properties")
+        .and_then(|p| p.as_array_mut());
+    for property in dyn_properties.into_iter().flatten() {
+        let property = property
+            .as_object_mut()
+            .ok_or("Dynamic property must be an object.")?;
+        let variant = property.get("variant").and_then(|v| v.as_str());
+        if matches!(
+            variant,
+            Some("ExistsFixedPoint" | "ExistsTrajectory" | "HasAttractor")
+        ) {
+            property.entry("soft").or_insert(Value::Bool(false));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::data_structs::{
+        migrate_sketch_json, SketchData, FORMAT_VERSION_FIELD, SKETCH_FORMAT_VERSION,
+    };
+    use crate::sketchbook::{JsonSerde, Sketch};
+    use serde_json::{json, Value};
+
+    #[test]
+    /// Test upgrading a sketch file created before the format was versioned.
+    fn test_migrate_legacy_sketch() {
+        let contents = std::fs::read_to_string("../data/test_data/test_model_with_data.json");
+        let json: Value = serde_json::from_str(&contents.unwrap()).unwrap();
+        assert!(json.get(FORMAT_VERSION_FIELD).is_none());
+
+        let migrated = migrate_sketch_json(json).unwrap();
+        assert_eq!(migrated[FORMAT_VERSION_FIELD], json!(SKETCH_FORMAT_VERSION));
+        assert!(migrated["fol_macros"].is_array());
+        assert_eq!(
+            migrated["datasets"][0]["observations"][0]["weight"],
+            json!(1)
+        );
+
+        // the upgraded sketch can be loaded, and it is saved with the current version
+        let sketch = Sketch::new_from_sketch_data(&serde_json::from_value(migrated).unwrap());
+        let sketch_data = SketchData::new_from_sketch(&sketch.unwrap());
+        assert_eq!(sketch_data.format_version, SKETCH_FORMAT_VERSION);
+    }
+
+    #[test]
+    /// Test that sketches of newer (unsupported) or invalid versions are rejected.
+    fn test_migrate_invalid_versions() {
+        let newer = json!({ FORMAT_VERSION_FIELD: SKETCH_FORMAT_VERSION + 1 });
+        let err = migrate_sketch_json(newer).unwrap_err();
+        assert!(err.contains("Please update the application"));
+        assert!(migrate_sketch_json(json!({ FORMAT_VERSION_FIELD: "1" })).is_err());
+        assert!(migrate_sketch_json(json!([])).is_err());
+
+        // the same error is reported when loading the sketch
+        let sketch_json = json!({ FORMAT_VERSION_FIELD: SKETCH_FORMAT_VERSION + 1 }).to_string();
+        assert!(SketchData::from_json_str(&sketch_json).is_err());
+    }
+
+    #[test]
+    /// Test that the published JSON schema is up to date with the data structures.
+    fn test_published_schema() {
+        let published = std::fs::read_to_string("../data/sketch.schema.json").unwrap();
+        assert_eq!(
+            published.trim_end(),
+            SketchData::json_schema(),
+            "Schema is outdated, regenerate it using `cargo run --bin export-schema ../data/sketch.schema.json`."
+        );
+    }
+}
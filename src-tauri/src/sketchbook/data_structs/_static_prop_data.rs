@@ -2,6 +2,7 @@ use crate::sketchbook::ids::{StatPropertyId, UninterpretedFnId, VarId};
 use crate::sketchbook::model::{Essentiality, Monotonicity};
 use crate::sketchbook::properties::static_props;
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use static_props::{StatProperty, StatPropertyType};
 
@@ -23,13 +24,13 @@ fn input_index_to_id(input_index: usize) -> String {
 }
 
 /// Simplified variant to carry data regarding [static_props::GenericStatProp] static property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct GenericStatPropData {
     pub formula: String,
 }
 
 /// Simplified variant to carry data regarding [static_props::RegulationEssential] static property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct RegulationEssentialData {
     pub input: Option<String>,
     pub target: Option<String>,
@@ -38,7 +39,7 @@ pub struct RegulationEssentialData {
 }
 
 /// Simplified variant to carry data regarding [static_props::FnInputEssential] static property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct FnInputEssentialData {
     pub input: Option<String>,
     pub target: Option<String>,
@@ -47,7 +48,7 @@ pub struct FnInputEssentialData {
 }
 
 /// Simplified variant to carry data regarding [static_props::RegulationMonotonic] static property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct RegulationMonotonicData {
     pub input: Option<String>,
     pub target: Option<String>,
@@ -56,7 +57,7 @@ pub struct RegulationMonotonicData {
 }
 
 /// Simplified variant to carry data regarding [static_props::FnInputMonotonic] static property.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct FnInputMonotonicData {
     pub input: Option<String>,
     pub target: Option<String>,
@@ -65,7 +66,7 @@ pub struct FnInputMonotonicData {
 }
 
 /// Enum covering all variants of static properties and their necessary data.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "variant")]
 pub enum StatPropertyTypeData {
     GenericStatProp(GenericStatPropData),
@@ -83,7 +84,7 @@ pub enum StatPropertyTypeData {
 ///
 /// Some fields simplified compared to original typesafe versions (e.g., pure `Strings` are used
 /// instead of more complex typesafe pub structs) to allow for easier (de)serialization.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct StatPropertyData {
     pub id: String,
     pub name: String,
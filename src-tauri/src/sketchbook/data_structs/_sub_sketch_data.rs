@@ -0,0 +1,30 @@
+use crate::sketchbook::model::InputMode;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data needed to extract a sub-sketch from the frontend.
+///
+/// Field `variables` lists IDs of the selected variables, `input_mode` describes how their
+/// regulators from outside of the selection are handled (see [InputMode]). The sub-sketch
+/// is written to a file given by `path` (in custom JSON format, or in extended AEON format
+/// if the path does not have a `.json` extension).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SubSketchData {
+    pub variables: Vec<String>,
+    pub input_mode: InputMode,
+    pub path: String,
+}
+
+impl<'de> JsonSerde<'de> for SubSketchData {}
+
+impl SubSketchData {
+    /// Create new `SubSketchData` object given a list of variable IDs, an input mode, and
+    /// a path to the output file.
+    pub fn new(variables: &[&str], input_mode: InputMode, path: &str) -> SubSketchData {
+        SubSketchData {
+            variables: variables.iter().map(|v| v.to_string()).collect(),
+            input_mode,
+            path: path.to_string(),
+        }
+    }
+}
@@ -3,6 +3,7 @@ use crate::sketchbook::model::{
     Essentiality, FnArgument, ModelState, Monotonicity, UninterpretedFn,
 };
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Structure for sending data about `UninterpretedFn` to the frontend.
@@ -10,7 +11,7 @@ use serde::{Deserialize, Serialize};
 /// `UninterpretedFnData` does not have the exact same fields as `UninterpretedFn` (for instance, there
 /// is an additional useful field `id`). Some fields of `UninterpretedFnData` are simplified to allow easier
 /// (de)serialization and manipulation.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct UninterpretedFnData {
     pub id: String,
     pub name: String,
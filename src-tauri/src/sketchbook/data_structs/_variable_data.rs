@@ -2,6 +2,7 @@ use crate::sketchbook::data_structs::_layout_node_data::LayoutNodeDataPrototype;
 use crate::sketchbook::ids::VarId;
 use crate::sketchbook::model::{UpdateFn, Variable};
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Structure for sending data about `Variable` and its `UpdateFn` to the frontend.
@@ -9,7 +10,7 @@ use serde::{Deserialize, Serialize};
 /// `VariableData` contains similar fields as `Variable` and additional fields `id` and `update_fn`.
 /// Some fields simplified compared to original typesafe versions (e.g., pure `Strings` are used
 /// instead of more complex typesafe structs) to allow for easier (de)serialization.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct VariableData {
     pub id: String,
     pub name: String,
@@ -18,7 +19,7 @@ pub struct VariableData {
 }
 
 /// Structure for receiving data about `Variable` and all of its `Layout` data from the frontend.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct VariableWithLayoutData {
     pub variable: VariableData,
     pub layouts: Vec<LayoutNodeDataPrototype>,
@@ -1,10 +1,24 @@
+/// **(internal)** Definition and utility methods for `ConsistencyReportData`.
+mod _consistency_report_data;
+/// **(internal)** Definition and utility methods for `ContinuousLoadData`.
+mod _continuous_load_data;
 /// **(internal)** Definition and utility methods for `DatasetData` and `DatasetMetaData`.
 mod _dataset_data;
+/// **(internal)** Definition and utility methods for `DatasetExportData`.
+mod _dataset_export_data;
+/// **(internal)** Definition and utility methods for `DatasetImportData`.
+mod _dataset_import_data;
+/// **(internal)** Definition and utility methods for `DatasetLoadData`.
+mod _dataset_load_data;
+/// **(internal)** Definition and utility methods for `DatasetMergeData`.
+mod _dataset_merge_data;
 /// **(internal)** Definition and utility methods for all kinds of dynamic properties.
 mod _dynamic_prop_data;
 /// **(internal)** Definition and utility methods for `ChangeArgMonotoneData`
 /// and `ChangeArgEssentialData`.
 mod _fn_arg_change_data;
+/// **(internal)** Definition and utility methods for `FolMacroData`.
+mod _fol_macro_data;
 /// **(internal)** Definition and utility methods for `ChangeIdData`.
 mod _id_change_data;
 /// **(internal)** Definition and utility methods for `LayoutData` and `LayoutMetaData`.
@@ -19,16 +33,29 @@ mod _observation_data;
 mod _regulation_data;
 /// **(internal)** Definition and utility methods for `SketchData`.
 mod _sketch_data;
+/// **(internal)** Definition and utility methods for `SketchMergeData` and `SketchMergeResultData`.
+mod _sketch_merge_data;
+/// **(internal)** Versioning of the sketch JSON format and migrations between the versions.
+mod _sketch_migration;
 /// **(internal)** Definition and utility methods for all kinds of static properties.
 mod _static_prop_data;
+/// **(internal)** Definition and utility methods for `SubSketchData`.
+mod _sub_sketch_data;
 /// **(internal)** Definition and utility methods for `UninterpretedFnData`.
 mod _uninterpreted_fn_data;
 /// **(internal)** Definition and utility methods for `VariableData`.
 mod _variable_data;
 
+pub use _consistency_report_data::ConsistencyReportData;
+pub use _continuous_load_data::ContinuousLoadData;
 pub use _dataset_data::{DatasetData, DatasetMetaData};
+pub use _dataset_export_data::DatasetExportData;
+pub use _dataset_import_data::DatasetImportData;
+pub use _dataset_load_data::DatasetLoadData;
+pub use _dataset_merge_data::DatasetMergeData;
 pub use _dynamic_prop_data::{DynPropertyData, DynPropertyTypeData};
 pub use _fn_arg_change_data::{ChangeArgEssentialData, ChangeArgMonotoneData};
+pub use _fol_macro_data::FolMacroData;
 pub use _id_change_data::ChangeIdData;
 pub use _layout_data::{LayoutData, LayoutMetaData};
 pub use _layout_node_data::{LayoutNodeData, LayoutNodeDataPrototype};
@@ -36,6 +63,9 @@ pub use _model_data::ModelData;
 pub use _observation_data::ObservationData;
 pub use _regulation_data::RegulationData;
 pub use _sketch_data::SketchData;
+pub use _sketch_merge_data::{SketchMergeData, SketchMergeResultData};
+pub use _sketch_migration::{migrate_sketch_json, FORMAT_VERSION_FIELD, SKETCH_FORMAT_VERSION};
 pub use _static_prop_data::{StatPropertyData, StatPropertyTypeData};
+pub use _sub_sketch_data::SubSketchData;
 pub use _uninterpreted_fn_data::UninterpretedFnData;
 pub use _variable_data::{VariableData, VariableWithLayoutData};
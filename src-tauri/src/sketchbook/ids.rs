@@ -97,6 +97,7 @@ macro_rules! id_wrapper {
 
 id_wrapper!(DatasetId, "Dataset");
 id_wrapper!(DynPropertyId, "DynProperty");
+id_wrapper!(FolMacroId, "FolMacro");
 id_wrapper!(LayoutId, "Layout");
 id_wrapper!(ObservationId, "Observation");
 id_wrapper!(StatPropertyId, "StatProperty");
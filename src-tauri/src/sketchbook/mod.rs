@@ -33,7 +33,11 @@ mod utils;
 #[cfg(test)]
 mod _tests_events;
 
-pub use crate::sketchbook::_sketch::Sketch;
+pub use crate::sketchbook::_sketch::{
+    ConsistencyIssue, ConsistencyReport, DiffComponent, DiffEntry, DiffKind, DroppedProperty,
+    ExtractionReport, IssueSeverity, MergeConflict, MergeReport, MergeStrategy, Sketch,
+    SketchComponent, SketchDiff,
+};
 
 /// Trait that implements `to_json_str` and `from_json_str` wrappers to serialize and
 /// deserialize objects, utilizing [serde_json].
@@ -1,4 +1,5 @@
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Possible variants of essentiality of a `Regulation`.
@@ -6,7 +7,7 @@ use serde::{Deserialize, Serialize};
 /// - `True` means that the regulation is essential and must have an effect
 /// - `False` means that it has no effect
 /// - `Unknown` means it might or might not have an effect.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum Essentiality {
     True,
     False,
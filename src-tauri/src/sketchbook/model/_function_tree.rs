@@ -271,7 +271,7 @@ impl FnTree {
                     .collect::<Vec<_>>();
                 FnTree::UninterpretedFn(id.clone(), new_args)
             }
-            FnTree::Not(inner) => (*inner).substitute_var(old_id, new_id),
+            FnTree::Not(inner) => FnTree::Not(Box::new((*inner).substitute_var(old_id, new_id))),
             FnTree::Binary(op, l, r) => FnTree::Binary(
                 *op,
                 Box::new((*l).substitute_var(old_id, new_id)),
@@ -280,6 +280,109 @@ impl FnTree {
         }
     }
 
+    /// Use this function as a template to create a new one, but substitute all occurrences of a
+    /// given network variable with a whole `replacement` tree (e.g., the update function of the
+    /// variable).
+    pub fn substitute_var_by_tree(&self, var_id: &VarId, replacement: &FnTree) -> FnTree {
+        match self {
+            FnTree::Var(id) if id == var_id => replacement.clone(),
+            FnTree::Const(_) | FnTree::Var(_) | FnTree::PlaceholderVar(_) => self.clone(),
+            FnTree::UninterpretedFn(id, args) => {
+                let new_args = args
+                    .iter()
+                    .map(|it| it.substitute_var_by_tree(var_id, replacement))
+                    .collect::<Vec<_>>();
+                FnTree::UninterpretedFn(id.clone(), new_args)
+            }
+            FnTree::Not(inner) => {
+                FnTree::Not(Box::new(inner.substitute_var_by_tree(var_id, replacement)))
+            }
+            FnTree::Binary(op, l, r) => FnTree::Binary(
+                *op,
+                Box::new(l.substitute_var_by_tree(var_id, replacement)),
+                Box::new(r.substitute_var_by_tree(var_id, replacement)),
+            ),
+        }
+    }
+
+    /// Create an equivalent function with constants propagated through Boolean operators, so
+    /// that the result is either a single constant, or contains no constants at all (apart from
+    /// arguments of uninterpreted functions, which are only simplified recursively).
+    pub fn simplify_constants(&self) -> FnTree {
+        match self {
+            FnTree::Const(_) | FnTree::Var(_) | FnTree::PlaceholderVar(_) => self.clone(),
+            FnTree::UninterpretedFn(id, args) => {
+                let new_args = args.iter().map(|it| it.simplify_constants()).collect();
+                FnTree::UninterpretedFn(id.clone(), new_args)
+            }
+            FnTree::Not(inner) => match inner.simplify_constants() {
+                FnTree::Const(value) => FnTree::Const(!value),
+                FnTree::Not(inner_inner) => *inner_inner,
+                simplified => FnTree::Not(Box::new(simplified)),
+            },
+            FnTree::Binary(op, l, r) => {
+                let (l, r) = (l.simplify_constants(), r.simplify_constants());
+                let negate = |tree: FnTree| FnTree::Not(Box::new(tree)).simplify_constants();
+                match (op, l, r) {
+                    (_, FnTree::Const(a), FnTree::Const(b)) => FnTree::Const(match op {
+                        BinaryOp::And => a && b,
+                        BinaryOp::Or => a || b,
+                        BinaryOp::Xor => a != b,
+                        BinaryOp::Imp => !a || b,
+                        BinaryOp::Iff => a == b,
+                    }),
+                    (BinaryOp::And, FnTree::Const(c), other)
+                    | (BinaryOp::And, other, FnTree::Const(c)) => {
+                        if c {
+                            other
+                        } else {
+                            FnTree::Const(false)
+                        }
+                    }
+                    (BinaryOp::Or, FnTree::Const(c), other)
+                    | (BinaryOp::Or, other, FnTree::Const(c)) => {
+                        if c {
+                            FnTree::Const(true)
+                        } else {
+                            other
+                        }
+                    }
+                    (BinaryOp::Xor, FnTree::Const(c), other)
+                    | (BinaryOp::Xor, other, FnTree::Const(c)) => {
+                        if c {
+                            negate(other)
+                        } else {
+                            other
+                        }
+                    }
+                    (BinaryOp::Iff, FnTree::Const(c), other)
+                    | (BinaryOp::Iff, other, FnTree::Const(c)) => {
+                        if c {
+                            other
+                        } else {
+                            negate(other)
+                        }
+                    }
+                    (BinaryOp::Imp, FnTree::Const(c), other) => {
+                        if c {
+                            other
+                        } else {
+                            FnTree::Const(true)
+                        }
+                    }
+                    (BinaryOp::Imp, other, FnTree::Const(c)) => {
+                        if c {
+                            FnTree::Const(true)
+                        } else {
+                            negate(other)
+                        }
+                    }
+                    (op, l, r) => FnTree::Binary(*op, Box::new(l), Box::new(r)),
+                }
+            }
+        }
+    }
+
     /// Use this function as a template to create a new one, but substitute a given uninterpreted
     /// function's ID with a new one.
     pub fn substitute_fn_symbol(
@@ -302,7 +405,9 @@ impl FnTree {
                     FnTree::UninterpretedFn(id.clone(), new_args)
                 }
             }
-            FnTree::Not(inner) => (*inner).substitute_fn_symbol(old_id, new_id),
+            FnTree::Not(inner) => {
+                FnTree::Not(Box::new((*inner).substitute_fn_symbol(old_id, new_id)))
+            }
             FnTree::Binary(op, l, r) => FnTree::Binary(
                 *op,
                 Box::new((*l).substitute_fn_symbol(old_id, new_id)),
@@ -436,6 +541,62 @@ mod tests {
         assert_eq!(modified_tree.to_string(&model, None), "a & g(a)");
     }
 
+    #[test]
+    /// Test that variable & uninterpreted fn substitution keeps negations intact.
+    fn test_substitution_under_negation() {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 1)
+            .unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("g", "g", 1)
+            .unwrap();
+        let a = model.get_var_id("a").unwrap();
+        let b = model.get_var_id("b").unwrap();
+        let f = model.get_uninterpreted_fn_id("f").unwrap();
+        let g = model.get_uninterpreted_fn_id("g").unwrap();
+
+        let fn_tree = FnTree::try_from_str("!a | !f(!a)", &model, None).unwrap();
+
+        let modified_tree = fn_tree.substitute_var(&a, &b);
+        assert_eq!(modified_tree.to_string(&model, None), "!b | !f(!b)");
+
+        let modified_tree = fn_tree.substitute_fn_symbol(&f, &g);
+        assert_eq!(modified_tree.to_string(&model, None), "!a | !g(!a)");
+    }
+
+    #[test]
+    /// Test substituting variables by whole trees, and simplifying constants.
+    fn test_substitution_by_tree() {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 1)
+            .unwrap();
+        let a = model.get_var_id("a").unwrap();
+        let b = model.get_var_id("b").unwrap();
+
+        let fn_tree = FnTree::try_from_str("!a | f(a)", &model, None).unwrap();
+        let replacement = FnTree::try_from_str("!b", &model, None).unwrap();
+        let modified_tree = fn_tree.substitute_var_by_tree(&a, &replacement);
+        assert_eq!(modified_tree.to_string(&model, None), "!!b | f(!b)");
+        let simplified_tree = modified_tree.simplify_constants();
+        assert_eq!(simplified_tree.to_string(&model, None), "b | f(!b)");
+
+        let fn_tree = FnTree::try_from_str("(a & b) | (a ^ b)", &model, None).unwrap();
+        let modified_tree = fn_tree.substitute_var_by_tree(&a, &FnTree::Const(true));
+        assert_eq!(
+            modified_tree.simplify_constants().to_string(&model, None),
+            "b | !b"
+        );
+        let modified_tree = fn_tree.substitute_var_by_tree(&b, &FnTree::Const(false));
+        assert_eq!(
+            modified_tree.simplify_constants().to_string(&model, None),
+            "a"
+        );
+        let fn_tree = FnTree::try_from_str("a => (b | true)", &model, None).unwrap();
+        assert_eq!(fn_tree.simplify_constants(), FnTree::Const(true));
+    }
+
     #[test]
     /// Test collecting function symbols from function's expression.
     fn test_collect_fns() {
@@ -0,0 +1,42 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// A part of an imported influence graph that was ignored (an unsupported or duplicate edge,
+/// an invalid line, and so on), together with the reason.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IgnoredEntry {
+    /// Line (or row) of the input where the entry was found, if applicable.
+    pub line: Option<usize>,
+    pub reason: String,
+}
+
+/// Report summarizing the import of an influence graph (from SIF, edge-list or GraphML) into
+/// a `ModelState`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GraphImportReport {
+    pub num_variables: usize,
+    pub num_regulations: usize,
+    pub ignored: Vec<IgnoredEntry>,
+}
+
+impl<'de> JsonSerde<'de> for GraphImportReport {}
+
+impl GraphImportReport {
+    /// Create new empty report.
+    pub fn new() -> GraphImportReport {
+        GraphImportReport::default()
+    }
+
+    /// Record an ignored entry, found on the given line of the input (if applicable).
+    pub fn add_ignored(&mut self, line: Option<usize>, reason: &str) {
+        self.ignored.push(IgnoredEntry {
+            line,
+            reason: reason.to_string(),
+        })
+    }
+
+    /// Check whether the whole input was imported (nothing was ignored).
+    pub fn is_complete(&self) -> bool {
+        self.ignored.is_empty()
+    }
+}
@@ -0,0 +1,18 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Possible ways to handle regulators from outside of a selected set of variables when
+/// extracting a sub-model.
+///
+/// - `Free` means the outside regulators become free inputs, i.e., variables without any
+///   regulators and with an unspecified update function
+/// - `ConstantSource` means the outside regulators become constant sources, i.e., variables
+///   that only (positively) regulate themselves, and thus keep their initial value
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum InputMode {
+    #[default]
+    Free,
+    ConstantSource,
+}
+
+impl<'de> JsonSerde<'de> for InputMode {}
@@ -0,0 +1,103 @@
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::Regulation;
+use std::fmt::{Display, Error, Formatter};
+
+/// A single operation of a [ModelPatch].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PatchOp {
+    /// Add a regulation, or update sign and essentiality of an existing one (line `A -> B`).
+    SetRegulation(Regulation),
+    /// Remove regulation between the regulator and the target (line `-A -| B`, the sign and
+    /// essentiality are ignored).
+    RemoveRegulation(VarId, VarId),
+    /// Set the update function of a variable (line `$A: B & !C`, an empty expression makes
+    /// the function unspecified).
+    SetUpdateFn(VarId, String),
+}
+
+impl Display for PatchOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            PatchOp::SetRegulation(regulation) => write!(f, "{regulation}"),
+            PatchOp::RemoveRegulation(regulator, target) => write!(f, "-{regulator} -?? {target}"),
+            PatchOp::SetUpdateFn(var_id, expression) => write!(f, "${var_id}: {expression}"),
+        }
+    }
+}
+
+/// Text "patch" describing bulk edits of regulations and update functions of a `ModelState`.
+///
+/// Each line of the patch is one of:
+/// - a regulation in the standard format (e.g., `A -> B`), which is added, or replaces an
+///   existing regulation between the same variables,
+/// - a regulation prefixed with `-` (e.g., `-A -| C`), which is removed,
+/// - an update function in the AEON format (e.g., `$C: A & !B`), which is set.
+///
+/// Empty lines and lines starting with `#` are ignored. Operations are applied in the order of
+/// lines, see [crate::sketchbook::model::ModelState::apply_patch].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ModelPatch {
+    /// Operations together with the (1-based) numbers of lines they come from.
+    operations: Vec<(usize, PatchOp)>,
+}
+
+impl ModelPatch {
+    /// Parse the patch from a string. All lines are parsed, and if some of them are invalid,
+    /// the error lists all of them (with line numbers).
+    pub fn try_from_str(patch_str: &str) -> Result<ModelPatch, String> {
+        let mut operations = Vec::new();
+        let mut errors = Vec::new();
+        for (idx, line) in patch_str.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match Self::parse_line(line) {
+                Ok(op) => operations.push((idx + 1, op)),
+                Err(e) => errors.push(format!("Line {}: {e}", idx + 1)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(ModelPatch { operations })
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+
+    /// **(internal)** Parse a single (trimmed, non-empty) line of the patch.
+    fn parse_line(line: &str) -> Result<PatchOp, String> {
+        if let Some(update_fn_str) = line.strip_prefix('$') {
+            let Some((var_id, expression)) = update_fn_str.split_once(':') else {
+                return Err(format!("Update function `{line}` is missing `:`."));
+            };
+            let var_id = VarId::new(var_id.trim())?;
+            Ok(PatchOp::SetUpdateFn(var_id, expression.trim().to_string()))
+        } else if let Some(regulation_str) = line.strip_prefix('-') {
+            let regulation = Regulation::try_from_string(regulation_str.trim())?;
+            let (regulator, target) = (regulation.get_regulator(), regulation.get_target());
+            Ok(PatchOp::RemoveRegulation(regulator.clone(), target.clone()))
+        } else {
+            Ok(PatchOp::SetRegulation(Regulation::try_from_string(line)?))
+        }
+    }
+
+    /// Get all operations of the patch, together with the numbers of lines they come from.
+    pub fn operations(&self) -> &Vec<(usize, PatchOp)> {
+        &self.operations
+    }
+
+    /// Check whether the patch contains no operations.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+}
+
+impl Display for ModelPatch {
+    /// Render the patch in a format that can be parsed back.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for (_, op) in &self.operations {
+            writeln!(f, "{op}")?;
+        }
+        Ok(())
+    }
+}
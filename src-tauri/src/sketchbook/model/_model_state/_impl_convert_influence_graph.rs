@@ -0,0 +1,470 @@
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::{Essentiality, GraphImportReport, ModelState, Monotonicity};
+use std::collections::HashMap;
+
+/// Methods for importing `ModelState` from common influence graph formats (SIF, edge-lists,
+/// and GraphML).
+///
+/// Only variables and regulations are imported, update functions stay unspecified. Interaction
+/// types (such as `activates`, `inhibits` or `unknown`) are mapped to `Monotonicity`, and
+/// essentiality of all the regulations is unknown. Parts of the input that can not be imported
+/// (unsupported interaction types, duplicate edges, ...) are skipped and listed in the returned
+/// `GraphImportReport`.
+impl ModelState {
+    /// Import an influence graph in the SIF format. Each line contains a source node, an
+    /// interaction type, and one or more target nodes, such as `A activates B C`. Items are
+    /// separated by tabs (if the line contains any) or by spaces. Lines with a single node
+    /// add isolated variables. Empty lines and lines starting with `#` are skipped.
+    pub fn from_sif(sif: &str) -> Result<(ModelState, GraphImportReport), String> {
+        let mut importer = GraphImporter::new();
+        for (i, line) in sif.lines().enumerate() {
+            let line_num = Some(i + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let items: Vec<&str> = if line.contains('\t') {
+                line.split('\t')
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            } else {
+                line.split_whitespace().collect()
+            };
+            match items.as_slice() {
+                [node] => {
+                    importer.add_node(node, node)?;
+                }
+                [_, _] => importer
+                    .report
+                    .add_ignored(line_num, "Line has an interaction but no target node."),
+                [source, interaction, targets @ ..] => {
+                    for target in targets {
+                        importer.add_edge(line_num, source, target, interaction)?;
+                    }
+                }
+                [] => unreachable!("Line is not empty."),
+            }
+        }
+        Ok(importer.finish())
+    }
+
+    /// Import an influence graph given as an edge-list (CSV or TSV). Each row contains a source
+    /// node, a target node, and optionally an interaction type (missing interaction means
+    /// unknown monotonicity). Items are separated by commas, tabs or spaces (based on the first
+    /// line that is not a comment).
+    ///
+    /// If the first row is a header with `source` and `target` columns (or their synonyms, such
+    /// as `from` and `to`), columns are selected accordingly (and the interaction is taken from
+    /// `sign`, `interaction`, `effect`, or similar column). Empty lines and lines starting with
+    /// `#` (possibly indented) are skipped.
+    pub fn from_edge_list(edge_list: &str) -> Result<(ModelState, GraphImportReport), String> {
+        // all non-empty lines that are not comments, with their line numbers
+        let lines: Vec<(usize, &str)> = edge_list
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                let line = line.trim_start();
+                !line.is_empty() && !line.starts_with('#')
+            })
+            .map(|(i, line)| (i + 1, line))
+            .collect();
+
+        // delimiter is detected from the first actual row (items are separated by any number
+        // of spaces if there are no tabs or commas)
+        let first_line = lines.first().map_or("", |(_, line)| *line);
+        let delimiter = if first_line.contains('\t') {
+            Some(b'\t')
+        } else if first_line.contains(',') {
+            Some(b',')
+        } else {
+            None
+        };
+        let mut rows: Vec<(Option<usize>, Vec<String>)> = Vec::new();
+        for (line_num, line) in lines {
+            let items = match delimiter {
+                Some(delimiter) => parse_delimited_line(line, delimiter)
+                    .map_err(|e| format!("Invalid edge-list (line {line_num}). {e}"))?,
+                None => line.split_whitespace().map(|s| s.to_string()).collect(),
+            };
+            rows.push((Some(line_num), items));
+        }
+
+        let mut importer = GraphImporter::new();
+        let mut columns = (0, 1, Some(2));
+        for (i, (line_num, row)) in rows.iter().enumerate() {
+            if i == 0 {
+                if let Some(header_columns) = edge_list_columns(row) {
+                    columns = header_columns;
+                    continue;
+                }
+            }
+            let field = |idx: usize| row.get(idx).map_or("", |s| s.as_str());
+            let (source, target) = (field(columns.0), field(columns.1));
+            if source.is_empty() || target.is_empty() {
+                let msg = "Row does not contain both source and target node.";
+                importer.report.add_ignored(*line_num, msg);
+                continue;
+            }
+            let interaction = columns.2.map(field).unwrap_or("");
+            importer.add_edge(*line_num, source, target, interaction)?;
+        }
+        Ok(importer.finish())
+    }
+
+    /// Import an influence graph in the GraphML format (only the first graph of the file is used).
+    ///
+    /// Node labels are taken from `label` or `name` data attributes (or yEd node labels), and
+    /// node IDs are used otherwise. Interaction types are taken from edge data attributes such
+    /// as `sign` or `interaction`, or from yEd arrow types (`standard` arrows are activations,
+    /// `t_shape` arrows are inhibitions).
+    ///
+    /// If `import_layout` is set, node coordinates (`x` and `y` data attributes, or yEd node
+    /// geometry) are used as positions in the default layout.
+    pub fn from_graphml(
+        graphml: &str,
+        import_layout: bool,
+    ) -> Result<(ModelState, GraphImportReport), String> {
+        let doc =
+            roxmltree::Document::parse(graphml).map_err(|e| format!("Invalid GraphML. {e}"))?;
+        let line_of =
+            |node: &roxmltree::Node| Some(doc.text_pos_at(node.range().start).row as usize);
+
+        // map IDs of data keys to the (lowercase) names of the attributes
+        let keys: HashMap<&str, String> = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("key"))
+            .filter_map(|n| Some((n.attribute("id")?, n.attribute("attr.name")?.to_lowercase())))
+            .collect();
+        let data_value = |element: &roxmltree::Node, names: &[&str]| -> Option<String> {
+            element
+                .children()
+                .filter(|n| n.has_tag_name("data"))
+                .find(|n| {
+                    let key = n.attribute("key").and_then(|k| keys.get(k));
+                    key.is_some_and(|k| names.contains(&k.as_str()))
+                })
+                .and_then(|n| n.text())
+                .map(|t| t.trim().to_string())
+        };
+
+        let graph = doc
+            .descendants()
+            .find(|n| n.has_tag_name("graph"))
+            .ok_or("GraphML does not contain any graph.")?;
+        let mut importer = GraphImporter::new();
+        let layout_id = ModelState::get_default_layout_id();
+
+        for node in graph.children().filter(|n| n.has_tag_name("node")) {
+            let Some(node_id) = node.attribute("id") else {
+                importer
+                    .report
+                    .add_ignored(line_of(&node), "Node without an ID.");
+                continue;
+            };
+            let yed_label = node
+                .descendants()
+                .find(|n| n.has_tag_name("NodeLabel"))
+                .and_then(|n| n.text())
+                .map(|t| t.trim().to_string());
+            let name = data_value(&node, &["label", "name"])
+                .or(yed_label)
+                .filter(|name| !name.is_empty())
+                .unwrap_or(node_id.to_string());
+            let var_id = importer.add_node(node_id, &name)?;
+
+            if import_layout {
+                let geometry = node.descendants().find(|n| n.has_tag_name("Geometry"));
+                let x = data_value(&node, &["x"]).or(geometry
+                    .and_then(|g| g.attribute("x"))
+                    .map(|x| x.to_string()));
+                let y = data_value(&node, &["y"]).or(geometry
+                    .and_then(|g| g.attribute("y"))
+                    .map(|y| y.to_string()));
+                let position = x
+                    .zip(y)
+                    .and_then(|(x, y)| Some((x.parse::<f32>().ok()?, y.parse::<f32>().ok()?)));
+                match position {
+                    Some((x, y)) => importer.model.update_position(&layout_id, &var_id, x, y)?,
+                    None => {
+                        let msg = format!("Node `{node_id}` has no valid coordinates.");
+                        importer.report.add_ignored(line_of(&node), &msg);
+                    }
+                }
+            }
+        }
+
+        for hyperedge in graph.children().filter(|n| n.has_tag_name("hyperedge")) {
+            let msg = "Hyperedges are not supported.";
+            importer.report.add_ignored(line_of(&hyperedge), msg);
+        }
+
+        for edge in graph.children().filter(|n| n.has_tag_name("edge")) {
+            let line_num = line_of(&edge);
+            let (Some(source), Some(target)) = (edge.attribute("source"), edge.attribute("target"))
+            else {
+                let msg = "Edge without a source or target node.";
+                importer.report.add_ignored(line_num, msg);
+                continue;
+            };
+            if let Some(node) = [source, target]
+                .into_iter()
+                .find(|n| !importer.node_ids.contains_key(*n))
+            {
+                let msg =
+                    format!("Edge `{source}` -> `{target}` refers to undeclared node `{node}`.");
+                importer.report.add_ignored(line_num, &msg);
+                continue;
+            }
+            let yed_arrow = edge
+                .descendants()
+                .find(|n| n.has_tag_name("Arrows"))
+                .and_then(|n| n.attribute("target"))
+                .map(|arrow| match arrow {
+                    "standard" | "delta" => "activates".to_string(),
+                    "t_shape" => "inhibits".to_string(),
+                    other => other.to_string(),
+                });
+            let interaction = data_value(&edge, INTERACTION_COLUMNS)
+                .or(yed_arrow)
+                .unwrap_or_default();
+            importer.add_edge(line_num, source, target, &interaction)?;
+        }
+        Ok(importer.finish())
+    }
+}
+
+/// Names of columns (or data attributes) that might contain the interaction type of an edge.
+const INTERACTION_COLUMNS: &[&str] = &[
+    "sign",
+    "interaction",
+    "effect",
+    "type",
+    "monotonicity",
+    "regulation",
+];
+
+/// **(internal)** Map an interaction type (such as `activates` or `-|`) to `Monotonicity`. Empty
+/// interaction means unknown monotonicity. Returns `None` for unsupported interaction types.
+fn parse_interaction(interaction: &str) -> Option<Monotonicity> {
+    match interaction.trim().to_lowercase().as_str() {
+        "activates" | "activation" | "activator" | "stimulates" | "positive" | "+" | "->" | "1" => {
+            Some(Monotonicity::Activation)
+        }
+        "inhibits" | "inhibition" | "inhibitor" | "represses" | "negative" | "-" | "-|" | "-1" => {
+            Some(Monotonicity::Inhibition)
+        }
+        "dual" | "+-" => Some(Monotonicity::Dual),
+        "unknown" | "regulates" | "?" | "-?" | "0" | "" => Some(Monotonicity::Unknown),
+        _ => None,
+    }
+}
+
+/// **(internal)** Parse a single line of a delimited edge-list into its (trimmed) items.
+fn parse_delimited_line(line: &str, delimiter: u8) -> Result<Vec<String>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .delimiter(delimiter)
+        .from_reader(line.as_bytes());
+    match reader.records().next() {
+        Some(record) => {
+            let record = record.map_err(|e| e.to_string())?;
+            Ok(record.iter().map(|s| s.to_string()).collect())
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+/// **(internal)** If the given edge-list row is a header, find the indices of its source, target
+/// and (optional) interaction columns.
+fn edge_list_columns(row: &[String]) -> Option<(usize, usize, Option<usize>)> {
+    let find = |names: &[&str]| {
+        row.iter()
+            .position(|col| names.contains(&col.to_lowercase().as_str()))
+    };
+    let source = find(&["source", "from", "regulator", "src"])?;
+    let target = find(&["target", "to", "tgt"])?;
+    Some((source, target, find(INTERACTION_COLUMNS)))
+}
+
+/// **(internal)** Helper structure for incrementally building a `ModelState` from an influence
+/// graph, keeping track of the node-variable mapping and of the ignored entries.
+struct GraphImporter {
+    model: ModelState,
+    node_ids: HashMap<String, VarId>,
+    report: GraphImportReport,
+}
+
+impl GraphImporter {
+    /// Start with an empty model.
+    fn new() -> GraphImporter {
+        GraphImporter {
+            model: ModelState::new_empty(),
+            node_ids: HashMap::new(),
+            report: GraphImportReport::new(),
+        }
+    }
+
+    /// Add a variable for the given node (if it was not added before), and return its ID. The
+    /// ID is derived from the given name (with invalid characters removed).
+    fn add_node(&mut self, node: &str, name: &str) -> Result<VarId, String> {
+        if let Some(var_id) = self.node_ids.get(node) {
+            return Ok(var_id.clone());
+        }
+        let var_id = self.model.generate_var_id(name, None);
+        self.model.add_var(var_id.clone(), name, "")?;
+        self.node_ids.insert(node.to_string(), var_id.clone());
+        Ok(var_id)
+    }
+
+    /// Add a regulation for the given edge (and variables for its nodes). Edges with unsupported
+    /// interaction types (and their nodes) and duplicate edges are ignored (only the first one
+    /// is kept).
+    fn add_edge(
+        &mut self,
+        line: Option<usize>,
+        source: &str,
+        target: &str,
+        interaction: &str,
+    ) -> Result<(), String> {
+        // nodes of ignored edges are not added (unless they appear elsewhere)
+        let Some(monotonicity) = parse_interaction(interaction) else {
+            let msg = format!(
+                "Edge `{source}` -> `{target}` has unsupported interaction type `{interaction}`."
+            );
+            self.report.add_ignored(line, &msg);
+            return Ok(());
+        };
+        let regulator_id = self.add_node(source, source)?;
+        let target_id = self.add_node(target, target)?;
+        if self.model.get_regulation(&regulator_id, &target_id).is_ok() {
+            let msg = format!("Edge `{source}` -> `{target}` is a duplicate.");
+            self.report.add_ignored(line, &msg);
+            return Ok(());
+        }
+        self.model
+            .add_regulation(regulator_id, target_id, Essentiality::Unknown, monotonicity)
+    }
+
+    /// Finalize the import, filling in the summary of the report.
+    fn finish(mut self) -> (ModelState, GraphImportReport) {
+        self.report.num_variables = self.model.num_vars();
+        self.report.num_regulations = self.model.num_regulations();
+        (self.model, self.report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::{ModelState, Monotonicity};
+
+    #[test]
+    /// Test importing an influence graph in SIF format.
+    fn test_from_sif() {
+        let sif = "# comment\nA activates B C\nB\t-|\tA\nC binds A\nA -> B\nD\nNF-kB unknown A\n";
+        let (model, report) = ModelState::from_sif(sif).unwrap();
+        assert_eq!(model.num_vars(), 5);
+        assert_eq!(model.num_regulations(), 4);
+        assert_eq!(report.num_regulations, 4);
+        // unsupported interaction and duplicate edge are reported with their lines
+        assert_eq!(report.ignored.len(), 2);
+        assert_eq!(report.ignored[0].line, Some(4));
+        assert_eq!(report.ignored[1].line, Some(5));
+
+        let regulation = model.get_regulation_by_str("B", "A").unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+        // nodes of ignored edges are not added
+        let (small_model, _) = ModelState::from_sif("A activates B\nC binds A\n").unwrap();
+        assert_eq!(small_model.num_vars(), 2);
+        // invalid characters are removed from IDs, but names are kept
+        let var_id = model.get_var_id("NFkB").unwrap();
+        assert_eq!(model.get_var_name(&var_id).unwrap(), "NF-kB");
+    }
+
+    #[test]
+    /// Test importing an influence graph given as an edge-list, with and without a header.
+    fn test_from_edge_list() {
+        let csv = "from,sign,to\nA,positive,B\nB,negative,A\nA,,A\nC,,\n";
+        let (model, report) = ModelState::from_edge_list(csv).unwrap();
+        assert_eq!(model.num_vars(), 2);
+        assert_eq!(model.num_regulations(), 3);
+        assert_eq!(report.ignored.len(), 1);
+        let regulation = model.get_regulation_by_str("A", "A").unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Unknown);
+
+        // space-separated items can be aligned by repeated spaces
+        let ssv = "# comment\nA    B  -|\n\nB C\n";
+        let (model, report) = ModelState::from_edge_list(ssv).unwrap();
+        assert!(report.is_complete());
+        assert_eq!(model.num_regulations(), 2);
+        let regulation = model.get_regulation_by_str("A", "B").unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+
+        let tsv = "A\tB\t-|\nB\tC\n";
+        let (model, report) = ModelState::from_edge_list(tsv).unwrap();
+        assert!(report.is_complete());
+        let regulation = model.get_regulation_by_str("A", "B").unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+    }
+
+    #[test]
+    /// Test that (possibly indented) comment lines are skipped, including when detecting
+    /// the delimiter.
+    fn test_from_edge_list_comments() {
+        // the leading comment contains a tab, but the rows are comma-separated
+        let csv = "# exported\tby tool, v1\nA,B,->\n  # B,C,-|\nB,C,-|\n";
+        let (model, report) = ModelState::from_edge_list(csv).unwrap();
+        assert!(report.is_complete());
+        assert_eq!(model.num_vars(), 3);
+        assert_eq!(model.num_regulations(), 2);
+
+        // indented comment in a space-separated list is not parsed as an edge
+        let ssv = "# comment\n  # X Y\nA B ->\n\tB C -|\n";
+        let (model, report) = ModelState::from_edge_list(ssv).unwrap();
+        assert!(report.is_complete());
+        assert_eq!(model.num_vars(), 3);
+        assert!(model.get_regulation_by_str("B", "C").is_ok());
+
+        // line numbers of ignored rows are not shifted by comments
+        let csv = "# comment\nA,B\nC,\n";
+        let (_, report) = ModelState::from_edge_list(csv).unwrap();
+        assert_eq!(report.ignored[0].line, Some(3));
+    }
+
+    #[test]
+    /// Test importing an influence graph in GraphML format, including node coordinates.
+    fn test_from_graphml() {
+        let graphml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="node" attr.name="label" attr.type="string"/>
+  <key id="d1" for="node" attr.name="x" attr.type="double"/>
+  <key id="d2" for="node" attr.name="y" attr.type="double"/>
+  <key id="d3" for="edge" attr.name="interaction" attr.type="string"/>
+  <graph id="G" edgedefault="directed">
+    <node id="n0"><data key="d0">A</data><data key="d1">10.5</data><data key="d2">20</data></node>
+    <node id="n1"><data key="d0">B</data><data key="d1">30</data><data key="d2">40</data></node>
+    <edge source="n0" target="n1"><data key="d3">activates</data></edge>
+    <edge source="n1" target="n0"><data key="d3">inhibits</data></edge>
+    <edge source="n1" target="n2"/>
+  </graph>
+</graphml>"#;
+        let (model, report) = ModelState::from_graphml(graphml, true).unwrap();
+        assert_eq!(model.num_vars(), 2);
+        assert_eq!(model.num_regulations(), 2);
+        assert_eq!(report.ignored.len(), 1);
+        assert_eq!(report.ignored[0].line, Some(12));
+
+        let layout_id = ModelState::get_default_layout_id();
+        let var_id = model.get_var_id("A").unwrap();
+        let position = model
+            .get_layout(&layout_id)
+            .unwrap()
+            .get_node_position(&var_id)
+            .unwrap();
+        assert_eq!((position.0, position.1), (10.5, 20.0));
+
+        assert!(ModelState::from_graphml("<graphml>", false).is_err());
+    }
+}
@@ -997,7 +997,7 @@ impl ModelState {
     }
 
     /// **(internal)** Utility method to ensure there is a variable with given Id.
-    fn assert_valid_variable(&self, var_id: &VarId) -> Result<(), String> {
+    pub(super) fn assert_valid_variable(&self, var_id: &VarId) -> Result<(), String> {
         if self.is_valid_var_id(var_id) {
             Ok(())
         } else {
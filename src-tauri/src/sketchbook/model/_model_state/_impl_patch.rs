@@ -0,0 +1,106 @@
+use crate::sketchbook::model::{ModelPatch, ModelState, PatchOp};
+
+/// Methods for applying text patches to the model.
+impl ModelState {
+    /// Apply all operations of the `patch` (see [ModelPatch]) to this model, in the order of
+    /// their lines.
+    ///
+    /// The patch is applied atomically. Every line is validated (on a copy of the model) before
+    /// any change is made, and if some of them are invalid, the error lists all of them (with
+    /// line numbers) and the model is left unchanged.
+    pub fn apply_patch(&mut self, patch: &ModelPatch) -> Result<(), String> {
+        let mut patched_model = self.clone();
+        let mut errors = Vec::new();
+        for (line, op) in patch.operations() {
+            if let Err(e) = patched_model.apply_patch_op(op) {
+                errors.push(format!("Line {line}: {e}"));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+        *self = patched_model;
+        Ok(())
+    }
+
+    /// Parse the patch from a string and apply it to this model atomically.
+    /// See [Self::apply_patch] for details.
+    pub fn apply_patch_str(&mut self, patch_str: &str) -> Result<(), String> {
+        let patch = ModelPatch::try_from_str(patch_str)?;
+        self.apply_patch(&patch)
+    }
+
+    /// **(internal)** Apply a single operation of a patch.
+    ///
+    /// The operation is either fully applied, or an error is returned (and nothing changes).
+    pub(super) fn apply_patch_op(&mut self, op: &PatchOp) -> Result<(), String> {
+        match op {
+            PatchOp::SetRegulation(regulation) => {
+                let (regulator, target) = (regulation.get_regulator(), regulation.get_target());
+                self.assert_valid_variable(regulator)?;
+                self.assert_valid_variable(target)?;
+                if self.get_regulation(regulator, target).is_ok() {
+                    self.remove_regulation(regulator, target)?;
+                }
+                self.add_regulation(
+                    regulator.clone(),
+                    target.clone(),
+                    *regulation.get_essentiality(),
+                    *regulation.get_sign(),
+                )
+            }
+            PatchOp::RemoveRegulation(regulator, target) => {
+                self.remove_regulation(regulator, target)
+            }
+            PatchOp::SetUpdateFn(var_id, expression) => self.set_update_fn(var_id, expression),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::{ModelPatch, ModelState, Monotonicity};
+
+    #[test]
+    /// Test parsing patches and applying them to a model.
+    fn test_apply_patch() {
+        let variables = vec![("A", "A"), ("B", "B"), ("C", "C")];
+        let mut model = ModelState::new_from_vars(variables).unwrap();
+        model
+            .add_multiple_regulations(vec!["A -> C", "B -| A"])
+            .unwrap();
+        let (var_a, var_b, var_c) = (
+            VarId::new("A").unwrap(),
+            VarId::new("B").unwrap(),
+            VarId::new("C").unwrap(),
+        );
+
+        let patch_str = "# comment\nA -> B\n\nB -| C\n$C: A & !B\n-B -| A\nA -| C\n";
+        let patch = ModelPatch::try_from_str(patch_str).unwrap();
+        assert_eq!(patch.operations().len(), 5);
+        // rendering of the patch can be parsed back (only line numbers differ)
+        let rendered_patch = ModelPatch::try_from_str(&patch.to_string()).unwrap();
+        assert_eq!(rendered_patch.to_string(), patch.to_string());
+
+        model.apply_patch(&patch).unwrap();
+        assert_eq!(model.num_regulations(), 3);
+        assert!(model.get_regulation(&var_b, &var_a).is_err());
+        let regulation = model.get_regulation(&var_a, &var_c).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+        assert_eq!(model.get_update_fn_string(&var_c).unwrap(), "A & !B");
+
+        // invalid lines are all reported, and nothing is changed
+        let original_model = model.clone();
+        let patch_str = "B -> A\n$C: A & D\n-B -> A\nC -> D\n";
+        let err = model.apply_patch_str(patch_str).unwrap_err();
+        assert!(err.starts_with("Line 2: "));
+        assert_eq!(err.lines().count(), 2);
+        assert!(err.contains("Line 4: "));
+        assert_eq!(model, original_model);
+
+        // syntax errors are reported before anything is validated against the model
+        let err = ModelPatch::try_from_str("A -> B\nA => B\n$C A\n").unwrap_err();
+        assert_eq!(err.lines().count(), 2);
+    }
+}
@@ -0,0 +1,443 @@
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::{
+    BinaryOp, Essentiality, FnTree, ModelState, Monotonicity, ReductionReport, ReductionStep,
+};
+use biodivine_lib_bdd::{Bdd, BddVariableSet};
+use std::collections::HashSet;
+
+/// Methods for reducing the network.
+impl ModelState {
+    /// Reduce the network, returning the reduced model and a report listing all performed steps
+    /// (see [ReductionReport]). Following steps are applied repeatedly (one at a time) until
+    /// none of them can be used:
+    /// - a variable with a constant update function is propagated into fully specified update
+    ///   functions of all its targets (if there are any), and signs and essentiality of the
+    ///   remaining regulations of these targets are adjusted where they are no longer valid,
+    /// - an intermediate variable with a single regulator is eliminated by substituting its
+    ///   update function into (non-empty) update functions of all its targets,
+    /// - an output variable without any targets is removed.
+    ///
+    /// Variables in `protected` (e.g., those referenced by properties or datasets) are never
+    /// removed, but constant values are still propagated from them.
+    pub fn reduce(
+        &self,
+        protected: &HashSet<VarId>,
+    ) -> Result<(ModelState, ReductionReport), String> {
+        let mut reduced = self.clone();
+        let mut report = ReductionReport::new();
+        loop {
+            let step = if let Some(step) = reduced.propagate_constant(protected)? {
+                step
+            } else if let Some(step) = reduced.eliminate_intermediate(protected)? {
+                step
+            } else if let Some(step) = reduced.remove_output(protected)? {
+                step
+            } else {
+                break;
+            };
+            report.add_step(step);
+        }
+        Ok((reduced, report))
+    }
+
+    /// **(internal)** Propagate the first variable with a constant update function that has
+    /// some targets, all of them with fully specified update functions.
+    fn propagate_constant(
+        &mut self,
+        protected: &HashSet<VarId>,
+    ) -> Result<Option<ReductionStep>, String> {
+        for var_id in self.sorted_var_ids() {
+            let value = match self.get_update_fn(&var_id)?.get_fn_tree() {
+                Some(FnTree::Const(value)) => *value,
+                _ => continue,
+            };
+            let targets = self.other_targets(&var_id)?;
+            if targets.is_empty() || targets.iter().any(|t| !self.is_fully_specified(t)) {
+                continue;
+            }
+
+            let constant = FnTree::Const(value);
+            for target in &targets {
+                self.substitute_in_update_fn(target, &var_id, &constant)?;
+                self.remove_regulation(&var_id, target)?;
+                self.fix_regulations_of(target)?;
+            }
+            let removed = !protected.contains(&var_id);
+            if removed {
+                self.remove_var(&var_id)?;
+            }
+            return Ok(Some(ReductionStep::ConstantPropagated {
+                var_id: var_id.to_string(),
+                value,
+                removed,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// **(internal)** Eliminate the first unprotected variable with a single regulator (other
+    /// than itself), whose targets all have non-empty update functions.
+    ///
+    /// Regulations from the regulator to the targets are added, with the sign and essentiality
+    /// composed from the two original regulations (or merged with existing regulations).
+    fn eliminate_intermediate(
+        &mut self,
+        protected: &HashSet<VarId>,
+    ) -> Result<Option<ReductionStep>, String> {
+        for var_id in self.sorted_var_ids() {
+            if protected.contains(&var_id) {
+                continue;
+            }
+            let regulator = match self.regulators(&var_id)?.as_slice() {
+                [regulator] if *regulator != &var_id => (*regulator).clone(),
+                _ => continue,
+            };
+            let update_fn = self.get_update_fn(&var_id)?;
+            let tree = match update_fn.get_fn_tree() {
+                Some(tree) if tree.collect_variables() == HashSet::from([regulator.clone()]) => {
+                    tree.clone()
+                }
+                _ => continue,
+            };
+            let expression = update_fn.get_fn_expression().to_string();
+            let targets = self.other_targets(&var_id)?;
+            let all_specified = targets.iter().all(|t| !self.update_fns[t].is_unspecified());
+            if targets.is_empty() || !all_specified {
+                continue;
+            }
+
+            let in_regulation = self.get_regulation(&regulator, &var_id)?.clone();
+            for target in &targets {
+                let out_regulation = self.get_regulation(&var_id, target)?.clone();
+                self.substitute_in_update_fn(target, &var_id, &tree)?;
+                self.remove_regulation(&var_id, target)?;
+                if !self.update_fns[target]
+                    .collect_variables()
+                    .contains(&regulator)
+                {
+                    continue;
+                }
+
+                let mut sign = compose_signs(in_regulation.get_sign(), out_regulation.get_sign());
+                let mut essentiality = compose_essentialities(
+                    in_regulation.get_essentiality(),
+                    out_regulation.get_essentiality(),
+                );
+                if let Ok(existing) = self.get_regulation(&regulator, target) {
+                    // both paths influence the target, so we can only keep what they agree on
+                    if existing.get_sign() != &sign {
+                        sign = Monotonicity::Unknown;
+                    }
+                    if existing.get_essentiality() != &essentiality
+                        || essentiality == Essentiality::True
+                    {
+                        essentiality = Essentiality::Unknown;
+                    }
+                    self.remove_regulation(&regulator, target)?;
+                }
+                self.add_regulation(regulator.clone(), target.clone(), essentiality, sign)?;
+            }
+            self.remove_var(&var_id)?;
+            return Ok(Some(ReductionStep::VariableEliminated {
+                var_id: var_id.to_string(),
+                expression,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// **(internal)** Remove the first unprotected variable that does not regulate any other
+    /// variable (and is not used in update functions of other variables).
+    fn remove_output(
+        &mut self,
+        protected: &HashSet<VarId>,
+    ) -> Result<Option<ReductionStep>, String> {
+        for var_id in self.sorted_var_ids() {
+            if protected.contains(&var_id) || !self.other_targets(&var_id)?.is_empty() {
+                continue;
+            }
+            let used_elsewhere = self
+                .update_fns
+                .iter()
+                .any(|(v, f)| v != &var_id && f.collect_variables().contains(&var_id));
+            if used_elsewhere {
+                continue;
+            }
+
+            // the variable might still be used in its own update function
+            self.set_update_fn(&var_id, "")?;
+            self.remove_var(&var_id)?;
+            return Ok(Some(ReductionStep::OutputRemoved {
+                var_id: var_id.to_string(),
+            }));
+        }
+        Ok(None)
+    }
+
+    /// **(internal)** Substitute `var_id` by the `replacement` tree in the update function of
+    /// `target` (simplifying constants), and remove regulations of `target` from all variables
+    /// that no longer appear in its update function as a result.
+    fn substitute_in_update_fn(
+        &mut self,
+        target: &VarId,
+        var_id: &VarId,
+        replacement: &FnTree,
+    ) -> Result<(), String> {
+        let update_fn = self.get_update_fn(target)?;
+        let Some(tree) = update_fn.get_fn_tree() else {
+            return Ok(());
+        };
+        let original_vars = tree.collect_variables();
+        let new_tree = tree
+            .substitute_var_by_tree(var_id, replacement)
+            .simplify_constants();
+        let new_vars = new_tree.collect_variables();
+        self.set_update_fn(target, &new_tree.to_string(self, None))?;
+
+        for regulator in original_vars.difference(&new_vars) {
+            if regulator != var_id && self.get_regulation(regulator, target).is_ok() {
+                self.remove_regulation(regulator, target)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Make the declared sign and essentiality of all regulations of `target`
+    /// consistent with its (fully specified) update function.
+    ///
+    /// Substituting a constant can change how the remaining regulators influence the target
+    /// (e.g., `A ^ C` becomes `!C` for `A = true`, so a dual regulation of `C` turns into an
+    /// inhibition). Declared values that are no longer valid are replaced by the actual ones,
+    /// valid values (including unknown ones) are kept.
+    fn fix_regulations_of(&mut self, target: &VarId) -> Result<(), String> {
+        let Some(tree) = self.get_update_fn(target)?.get_fn_tree().cloned() else {
+            return Ok(());
+        };
+        let mut regulators: Vec<VarId> = self.regulators(target)?.into_iter().cloned().collect();
+        regulators.sort();
+        let mut var_names: Vec<&str> = regulators.iter().map(|v| v.as_str()).collect();
+        let fn_vars = tree.collect_variables();
+        var_names.extend(fn_vars.iter().map(|v| v.as_str()));
+        var_names.sort();
+        var_names.dedup();
+        let bdd_vars = BddVariableSet::new(&var_names);
+        let update_bdd = fn_tree_to_bdd(&tree, &bdd_vars)?;
+
+        for regulator in &regulators {
+            let bdd_var = bdd_vars.var_by_name(regulator.as_str()).unwrap();
+            let low = update_bdd.var_restrict(bdd_var, false);
+            let high = update_bdd.var_restrict(bdd_var, true);
+            let increasing = low.and_not(&high).is_false();
+            let decreasing = high.and_not(&low).is_false();
+            let essential = !(increasing && decreasing);
+
+            let regulation = self.get_regulation(regulator, target)?;
+            let sign_valid = match regulation.get_sign() {
+                Monotonicity::Activation => increasing,
+                Monotonicity::Inhibition => decreasing,
+                Monotonicity::Dual => !increasing && !decreasing,
+                Monotonicity::Unknown => true,
+            };
+            if !sign_valid {
+                let sign = match (increasing, decreasing) {
+                    (true, false) => Monotonicity::Activation,
+                    (false, true) => Monotonicity::Inhibition,
+                    (false, false) => Monotonicity::Dual,
+                    // non-essential regulator can not be dual
+                    (true, true) => Monotonicity::Unknown,
+                };
+                self.change_regulation_sign(regulator, target, &sign)?;
+            }
+
+            let regulation = self.get_regulation(regulator, target)?;
+            let essentiality_valid = match regulation.get_essentiality() {
+                Essentiality::True => essential,
+                Essentiality::False => !essential,
+                Essentiality::Unknown => true,
+            };
+            if !essentiality_valid {
+                let essentiality = if essential {
+                    Essentiality::True
+                } else {
+                    Essentiality::False
+                };
+                self.change_regulation_essentiality(regulator, target, &essentiality)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Check whether the variable's update function is fully specified, i.e.,
+    /// non-empty and without uninterpreted functions.
+    fn is_fully_specified(&self, var_id: &VarId) -> bool {
+        let update_fn = &self.update_fns[var_id];
+        !update_fn.is_unspecified() && update_fn.collect_fn_symbols().is_empty()
+    }
+
+    /// **(internal)** Sorted list of targets of the variable, excluding the variable itself.
+    fn other_targets(&self, var_id: &VarId) -> Result<Vec<VarId>, String> {
+        let mut targets: Vec<VarId> = self
+            .targets(var_id)?
+            .into_iter()
+            .filter(|t| *t != var_id)
+            .cloned()
+            .collect();
+        targets.sort();
+        Ok(targets)
+    }
+
+    /// **(internal)** Sorted list of IDs of all variables.
+    fn sorted_var_ids(&self) -> Vec<VarId> {
+        let mut var_ids: Vec<VarId> = self.variables.keys().cloned().collect();
+        var_ids.sort();
+        var_ids
+    }
+}
+
+/// **(internal)** Build a BDD representing a fully specified function tree (without any
+/// uninterpreted functions), with variables taken from the given variable set.
+fn fn_tree_to_bdd(tree: &FnTree, bdd_vars: &BddVariableSet) -> Result<Bdd, String> {
+    let bdd = match tree {
+        FnTree::Const(value) => {
+            if *value {
+                bdd_vars.mk_true()
+            } else {
+                bdd_vars.mk_false()
+            }
+        }
+        FnTree::Var(var_id) => bdd_vars
+            .var_by_name(var_id.as_str())
+            .map(|var| bdd_vars.mk_var(var))
+            .ok_or(format!("Variable `{var_id}` is not in the variable set."))?,
+        FnTree::Not(inner) => fn_tree_to_bdd(inner, bdd_vars)?.not(),
+        FnTree::Binary(op, left, right) => {
+            let left = fn_tree_to_bdd(left, bdd_vars)?;
+            let right = fn_tree_to_bdd(right, bdd_vars)?;
+            match op {
+                BinaryOp::And => left.and(&right),
+                BinaryOp::Or => left.or(&right),
+                BinaryOp::Xor => left.xor(&right),
+                BinaryOp::Imp => left.imp(&right),
+                BinaryOp::Iff => left.iff(&right),
+            }
+        }
+        FnTree::PlaceholderVar(_) | FnTree::UninterpretedFn(..) => {
+            return Err("Function is not fully specified.".to_string());
+        }
+    };
+    Ok(bdd)
+}
+
+/// **(internal)** Sign of a regulation path composed of two regulations.
+fn compose_signs(first: &Monotonicity, second: &Monotonicity) -> Monotonicity {
+    match (first, second) {
+        (Monotonicity::Unknown, _) | (_, Monotonicity::Unknown) => Monotonicity::Unknown,
+        (Monotonicity::Dual, _) | (_, Monotonicity::Dual) => Monotonicity::Dual,
+        (first, second) if first == second => Monotonicity::Activation,
+        _ => Monotonicity::Inhibition,
+    }
+}
+
+/// **(internal)** Essentiality of a regulation path composed of two regulations.
+fn compose_essentialities(first: &Essentiality, second: &Essentiality) -> Essentiality {
+    match (first, second) {
+        (Essentiality::False, _) | (_, Essentiality::False) => Essentiality::False,
+        (Essentiality::True, Essentiality::True) => Essentiality::True,
+        _ => Essentiality::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity, ReductionStep};
+    use std::collections::HashSet;
+
+    #[test]
+    /// Test reducing a network by all three kinds of steps.
+    fn test_reduce() {
+        let vars = vec![
+            ("A", "A"),
+            ("B", "B"),
+            ("C", "C"),
+            ("D", "D"),
+            ("E", "E"),
+            ("F", "F"),
+        ];
+        let mut model = ModelState::new_from_vars(vars).unwrap();
+        let regulations = vec!["A -> B", "C -> B", "B -| D", "D -> E", "E -> E", "E -> F"];
+        model.add_multiple_regulations(regulations).unwrap();
+        let update_fns = vec![
+            ("A", "true"),
+            ("B", "A & C"),
+            ("D", "!B"),
+            ("E", "D & E"),
+            ("F", "E"),
+        ];
+        model.set_multiple_update_fns(update_fns).unwrap();
+        let var_e = VarId::new("E").unwrap();
+        let protected = HashSet::from([var_e.clone()]);
+
+        let (reduced, report) = model.reduce(&protected).unwrap();
+        let expected_steps = vec![
+            ReductionStep::ConstantPropagated {
+                var_id: "A".to_string(),
+                value: true,
+                removed: true,
+            },
+            ReductionStep::VariableEliminated {
+                var_id: "B".to_string(),
+                expression: "C".to_string(),
+            },
+            ReductionStep::VariableEliminated {
+                var_id: "D".to_string(),
+                expression: "!C".to_string(),
+            },
+            ReductionStep::OutputRemoved {
+                var_id: "F".to_string(),
+            },
+        ];
+        assert_eq!(report.steps, expected_steps);
+        assert_eq!(report.removed_vars(), vec!["A", "B", "D", "F"]);
+        assert_eq!(reduced.num_vars(), 2);
+        assert_eq!(reduced.num_regulations(), 2);
+        assert_eq!(reduced.get_update_fn_string(&var_e).unwrap(), "!C & E");
+        let var_c = VarId::new("C").unwrap();
+        let regulation = reduced.get_regulation(&var_c, &var_e).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+
+        // protected constants are propagated, but kept
+        let protected = HashSet::from([var_e, VarId::new("A").unwrap()]);
+        let (reduced, report) = model.reduce(&protected).unwrap();
+        assert_eq!(reduced.num_vars(), 3);
+        assert_eq!(report.removed_vars(), vec!["B", "D", "F"]);
+
+        // the original model is not changed, and reducing again does nothing
+        assert_eq!(model.num_vars(), 6);
+        assert!(reduced.reduce(&protected).unwrap().1.is_empty());
+    }
+
+    #[test]
+    /// Test that propagating a constant fixes signs of regulations that are no longer valid.
+    fn test_reduce_fixes_regulation_signs() {
+        let vars = vec![("A", "A"), ("B", "B"), ("C", "C"), ("D", "D")];
+        let mut model = ModelState::new_from_vars(vars).unwrap();
+        let regulations = vec!["A -* B", "C -* B", "D -? B"];
+        model.add_multiple_regulations(regulations).unwrap();
+        let update_fns = vec![("A", "true"), ("B", "(A ^ C) | D")];
+        model.set_multiple_update_fns(update_fns).unwrap();
+        let var_b = VarId::new("B").unwrap();
+        let protected = HashSet::from([var_b.clone()]);
+
+        let (reduced, _) = model.reduce(&protected).unwrap();
+        assert_eq!(reduced.get_update_fn_string(&var_b).unwrap(), "!C | D");
+        // the dual regulation became an inhibition, the unknown one stays unknown
+        let var_c = VarId::new("C").unwrap();
+        let regulation = reduced.get_regulation(&var_c, &var_b).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+        assert_eq!(regulation.get_essentiality(), &Essentiality::True);
+        let var_d = VarId::new("D").unwrap();
+        let regulation = reduced.get_regulation(&var_d, &var_b).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Unknown);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::app::event::Event;
+use crate::app::state::{Consumed, SessionHelper};
+use crate::app::DynError;
+use crate::sketchbook::data_structs::RegulationData;
+use crate::sketchbook::event_utils::mk_model_event;
+use crate::sketchbook::model::{ModelPatch, ModelState, PatchOp};
+use crate::sketchbook::JsonSerde;
+
+/// Implementation for events applying text patches to the model.
+impl ModelState {
+    /// Perform event of applying a text patch (see [ModelPatch]) with bulk edits of regulations
+    /// and update functions.
+    ///
+    /// All lines of the patch are validated first, and if any of them is invalid, the event fails
+    /// without any change. Otherwise, the patch is broken down into the standard (reversible)
+    /// events of the individual operations, which are together recorded as a single action.
+    /// As these events also change static properties, the sketch validates the whole expanded
+    /// chain of events (on its copy) before any of them is applied.
+    pub(super) fn event_apply_patch(
+        &mut self,
+        event: &Event,
+        at_path: &[&str],
+    ) -> Result<Consumed, DynError> {
+        let component_name = "model/apply_patch";
+        Self::assert_path_length(at_path, 0, component_name)?;
+        let patch_str = Self::clone_payload_str(event, component_name)?;
+        let patch = ModelPatch::try_from_str(&patch_str)?;
+
+        // validate all lines (on a copy) before any change is made
+        self.clone().apply_patch(&patch)?;
+
+        // events of each operation are prepared with respect to the model where all previous
+        // operations are already applied
+        let mut patched_model = self.clone();
+        let mut event_list = Vec::new();
+        for (_, op) in patch.operations() {
+            event_list.extend(patched_model.patch_op_events(op));
+            patched_model.apply_patch_op(op)?;
+        }
+        if event_list.is_empty() {
+            return Ok(Consumed::NoChange);
+        }
+
+        // the event list will be reversed during processing, so the first event must be last
+        event_list.reverse();
+        Ok(Consumed::Restart(event_list))
+    }
+
+    /// **(internal)** Prepare events for a single (valid) operation of a patch.
+    ///
+    /// The events also handle the corresponding changes in static properties (for regulations).
+    fn patch_op_events(&self, op: &PatchOp) -> Vec<Event> {
+        match op {
+            PatchOp::SetRegulation(regulation) => {
+                let (regulator, target) = (regulation.get_regulator(), regulation.get_target());
+                let Ok(current_reg) = self.get_regulation(regulator, target) else {
+                    let payload = RegulationData::from_reg(regulation).to_json_str();
+                    return vec![mk_model_event(&["regulation", "add"], Some(&payload))];
+                };
+
+                let mut events = Vec::new();
+                if current_reg.get_sign() != regulation.get_sign() {
+                    let at_path = [
+                        "regulation",
+                        regulator.as_str(),
+                        target.as_str(),
+                        "set_sign",
+                    ];
+                    let payload = regulation.get_sign().to_json_str();
+                    events.push(mk_model_event(&at_path, Some(&payload)));
+                }
+                if current_reg.get_essentiality() != regulation.get_essentiality() {
+                    let at_path = [
+                        "regulation",
+                        regulator.as_str(),
+                        target.as_str(),
+                        "set_essentiality",
+                    ];
+                    let payload = regulation.get_essentiality().to_json_str();
+                    events.push(mk_model_event(&at_path, Some(&payload)));
+                }
+                events
+            }
+            PatchOp::RemoveRegulation(regulator, target) => {
+                let at_path = ["regulation", regulator.as_str(), target.as_str(), "remove"];
+                vec![mk_model_event(&at_path, None)]
+            }
+            PatchOp::SetUpdateFn(var_id, expression) => {
+                let at_path = ["variable", var_id.as_str(), "set_update_fn"];
+                vec![mk_model_event(&at_path, Some(expression))]
+            }
+        }
+    }
+}
@@ -5,6 +5,8 @@ use crate::sketchbook::model::ModelState;
 
 /// **(internal)** Implementation for events related to `layouts` of the model.
 pub mod _events_layout;
+/// **(internal)** Implementation for events applying text patches to the model.
+pub mod _events_patch;
 /// **(internal)** Implementation for events related to `regulations` of the model.
 pub mod _events_regulations;
 /// **(internal)** Implementation for events related to `uninterpreted functions` of the model.
@@ -25,6 +27,8 @@ const FN_EVENT_PATH: &str = "uninterpreted_fn";
 const REGULATION_EVENT_PATH: &str = "regulation";
 // events being delegated to `layouts` subcomponent
 const LAYOUT_EVENT_PATH: &str = "layout";
+// apply a text patch with bulk edits of regulations and update fns
+const APPLY_PATCH_PATH: &str = "apply_patch";
 
 /* Constants for refresh event path segments in `ModelState` for retrieving data. */
 
@@ -50,6 +54,7 @@ impl SessionState for ModelState {
             Some(&FN_EVENT_PATH) => self.perform_uninterpreted_fn_event(event, &at_path[1..]),
             Some(&REGULATION_EVENT_PATH) => self.perform_regulation_event(event, &at_path[1..]),
             Some(&LAYOUT_EVENT_PATH) => self.perform_layout_event(event, &at_path[1..]),
+            Some(&APPLY_PATCH_PATH) => self.event_apply_patch(event, &at_path[1..]),
             _ => Self::invalid_path_error_generic(at_path),
         }
     }
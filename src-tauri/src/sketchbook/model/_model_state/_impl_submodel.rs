@@ -0,0 +1,147 @@
+use crate::sketchbook::ids::{UninterpretedFnId, VarId};
+use crate::sketchbook::model::{Essentiality, InputMode, ModelState, Monotonicity};
+use std::collections::BTreeSet;
+
+/// Methods for extracting sub-models.
+impl ModelState {
+    /// Extract a sub-model induced by the given set of `variables`.
+    ///
+    /// The sub-model contains the selected variables, all regulations targeting them, and
+    /// their update functions (together with all uninterpreted functions they use). Regulators
+    /// from outside of the selection are kept as inputs of the sub-model, and they are handled
+    /// according to `input_mode` (see [InputMode]). Positions of all variables are kept in all
+    /// layouts.
+    ///
+    /// Returns the sub-model and a sorted list of the added input variables, or an error if
+    /// some of the given variables is not valid.
+    pub fn extract_submodel(
+        &self,
+        variables: &[VarId],
+        input_mode: InputMode,
+    ) -> Result<(ModelState, Vec<VarId>), String> {
+        for var_id in variables {
+            self.assert_valid_variable(var_id)?;
+        }
+        let selected: BTreeSet<&VarId> = variables.iter().collect();
+        let mut inputs = BTreeSet::new();
+        for var_id in &selected {
+            for regulator in self.regulators(var_id)? {
+                if !selected.contains(regulator) {
+                    inputs.insert(regulator);
+                }
+            }
+        }
+
+        let mut submodel = ModelState::new_empty();
+        for var_id in selected.iter().chain(inputs.iter()) {
+            let variable = self.get_variable(var_id)?;
+            let (name, annot) = (variable.get_name(), variable.get_annotation());
+            submodel.add_var((*var_id).clone(), name, annot)?;
+        }
+        for (layout_id, layout) in self.layouts() {
+            if !submodel.is_valid_layout_id(layout_id) {
+                submodel.add_layout_simple(layout_id.clone(), layout.get_layout_name())?;
+            }
+            for var_id in selected.iter().chain(inputs.iter()) {
+                let position = layout.get_node_position(var_id)?;
+                submodel.update_position(layout_id, var_id, position.0, position.1)?;
+            }
+        }
+
+        // uninterpreted fns used in the update fns (directly, or inside other functions)
+        let mut fn_ids: BTreeSet<UninterpretedFnId> = BTreeSet::new();
+        let mut to_process: Vec<UninterpretedFnId> = selected
+            .iter()
+            .flat_map(|v| self.get_update_fn(v).unwrap().collect_fn_symbols())
+            .collect();
+        while let Some(fn_id) = to_process.pop() {
+            if fn_ids.insert(fn_id.clone()) {
+                let uninterpreted_fn = self.get_uninterpreted_fn(&fn_id)?;
+                to_process.extend(uninterpreted_fn.collect_fn_symbols());
+            }
+        }
+        for fn_id in fn_ids {
+            let uninterpreted_fn = self.get_uninterpreted_fn(&fn_id)?;
+            let (name, arity) = (uninterpreted_fn.get_name(), uninterpreted_fn.get_arity());
+            submodel.add_empty_uninterpreted_fn(fn_id.clone(), name, arity)?;
+            submodel.set_raw_function(&fn_id, uninterpreted_fn.clone())?;
+        }
+
+        for regulation in self.regulations() {
+            if selected.contains(regulation.get_target()) {
+                submodel.add_regulation(
+                    regulation.get_regulator().clone(),
+                    regulation.get_target().clone(),
+                    *regulation.get_essentiality(),
+                    *regulation.get_sign(),
+                )?;
+            }
+        }
+        for var_id in &selected {
+            let update_fn = self.get_update_fn(var_id)?;
+            if !update_fn.is_unspecified() {
+                submodel.set_update_fn(var_id, update_fn.get_fn_expression())?;
+            }
+        }
+
+        // free inputs have no regulators and empty update fns, which is already the case
+        if input_mode == InputMode::ConstantSource {
+            for input in &inputs {
+                let (essentiality, sign) = (Essentiality::True, Monotonicity::Activation);
+                submodel.add_regulation((*input).clone(), (*input).clone(), essentiality, sign)?;
+                submodel.set_update_fn(input, input.as_str())?;
+            }
+        }
+
+        let inputs = inputs.into_iter().cloned().collect();
+        Ok((submodel, inputs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::{InputMode, ModelState};
+
+    #[test]
+    /// Test extracting sub-models with both free and constant inputs.
+    fn test_extract_submodel() {
+        let mut model =
+            ModelState::new_from_vars(vec![("A", "a"), ("B", "b"), ("C", "c")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["A -> B", "B -| C", "C -> A", "C -> B"])
+            .unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 1)
+            .unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("g", "g", 1)
+            .unwrap();
+        model
+            .set_update_fn(&VarId::new("B").unwrap(), "f(A) & C")
+            .unwrap();
+        model
+            .set_update_fn(&VarId::new("C").unwrap(), "g(B)")
+            .unwrap();
+        let selected = vec![VarId::new("B").unwrap()];
+
+        let (submodel, inputs) = model.extract_submodel(&selected, InputMode::Free).unwrap();
+        assert_eq!(
+            inputs,
+            vec![VarId::new("A").unwrap(), VarId::new("C").unwrap()]
+        );
+        assert_eq!(submodel.num_vars(), 3);
+        assert_eq!(submodel.num_regulations(), 2);
+        assert_eq!(submodel.num_uninterpreted_fns(), 1);
+        assert_eq!(submodel.get_var_name(&inputs[0]).unwrap(), "a");
+        assert!(submodel.get_update_fn(&inputs[1]).unwrap().is_unspecified());
+
+        let mode = InputMode::ConstantSource;
+        let (submodel, inputs) = model.extract_submodel(&selected, mode).unwrap();
+        assert_eq!(submodel.num_regulations(), 4);
+        assert_eq!(submodel.get_update_fn_string(&inputs[1]).unwrap(), "C");
+
+        let invalid = vec![VarId::new("D").unwrap()];
+        assert!(model.extract_submodel(&invalid, mode).is_err());
+    }
+}
@@ -6,6 +6,8 @@ use std::collections::{HashMap, HashSet};
 
 /// **(internal)** Methods for converting between `ModelState` and `BooleanNetwork`.
 mod _impl_convert_bn;
+/// **(internal)** Methods for importing `ModelState` from influence graph formats (SIF, edge-list, GraphML).
+mod _impl_convert_influence_graph;
 /// **(internal)** Methods for converting between `ModelState` and `RegulatoryGraph`.
 mod _impl_convert_reg_graph;
 /// **(internal)** Methods for safely constructing or editing instances of `ModelState`.
@@ -14,8 +16,14 @@ mod _impl_editing;
 mod _impl_id_generating;
 /// **(internal)** Methods for observing instances of `ModelState` (various getters, etc.).
 mod _impl_observing;
+/// **(internal)** Methods for applying text patches with bulk edits to the model.
+mod _impl_patch;
+/// **(internal)** Methods for reducing the network (constant propagation, variable elimination).
+mod _impl_reduction;
 /// **(internal)** Implementation of event-based API for the [crate::app::state::SessionState] trait.
 mod _impl_session_state;
+/// **(internal)** Methods for extracting sub-models induced by a set of variables.
+mod _impl_submodel;
 
 /// Object representing the state of the model in the Boolean network editor. The model encompasses
 /// variables, regulations, uninterpreted functions, update functions, and layout information.
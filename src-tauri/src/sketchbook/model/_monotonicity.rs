@@ -1,4 +1,5 @@
 use crate::sketchbook::JsonSerde;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Error, Formatter};
 
@@ -8,7 +9,7 @@ use std::fmt::{Display, Error, Formatter};
 /// - `Inhibition` means negative monotonicity
 /// - `Dual` means both positive and negative effect
 /// - `Unknown` stands for unknown effect
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum Monotonicity {
     Activation,
     Inhibition,
@@ -0,0 +1,94 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Error, Formatter};
+
+/// A single step of a network reduction.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ReductionStep {
+    /// Variable with a constant update function whose value was propagated into update functions
+    /// of its targets. The variable itself is removed, unless it was protected.
+    ConstantPropagated {
+        var_id: String,
+        value: bool,
+        removed: bool,
+    },
+    /// Intermediate variable (with a single regulator) whose update function `expression` was
+    /// substituted into update functions of its targets.
+    VariableEliminated { var_id: String, expression: String },
+    /// Output variable (without targets) that was removed.
+    OutputRemoved { var_id: String },
+}
+
+impl Display for ReductionStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            ReductionStep::ConstantPropagated {
+                var_id,
+                value,
+                removed,
+            } => {
+                let kept = if *removed { "" } else { " (kept)" };
+                write!(f, "propagated constant `{var_id} = {value}`{kept}")
+            }
+            ReductionStep::VariableEliminated { var_id, expression } => {
+                write!(f, "eliminated `{var_id} = {expression}`")
+            }
+            ReductionStep::OutputRemoved { var_id } => write!(f, "removed output `{var_id}`"),
+        }
+    }
+}
+
+/// Report summarizing a network reduction, listing all performed steps in order.
+///
+/// The steps can be used to map results on the reduced network back to the original one. Values
+/// of removed variables are given by their constants or eliminated expressions (evaluated in the
+/// reverse order of steps), while removed outputs do not affect any other variable.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ReductionReport {
+    pub steps: Vec<ReductionStep>,
+}
+
+impl<'de> JsonSerde<'de> for ReductionReport {}
+
+impl ReductionReport {
+    /// Create new empty report.
+    pub fn new() -> ReductionReport {
+        ReductionReport::default()
+    }
+
+    /// Record a reduction step.
+    pub fn add_step(&mut self, step: ReductionStep) {
+        self.steps.push(step)
+    }
+
+    /// Check whether the network was not reduced at all.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// IDs of all variables removed from the network, in the order of removal.
+    pub fn removed_vars(&self) -> Vec<&str> {
+        self.steps
+            .iter()
+            .filter_map(|step| match step {
+                ReductionStep::ConstantPropagated {
+                    var_id,
+                    removed: true,
+                    ..
+                } => Some(var_id.as_str()),
+                ReductionStep::ConstantPropagated { .. } => None,
+                ReductionStep::VariableEliminated { var_id, .. } => Some(var_id.as_str()),
+                ReductionStep::OutputRemoved { var_id } => Some(var_id.as_str()),
+            })
+            .collect()
+    }
+}
+
+impl Display for ReductionReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for step in &self.steps {
+            writeln!(f, "{step}")?;
+        }
+        Ok(())
+    }
+}
@@ -54,6 +54,11 @@ impl UpdateFn {
         &self.expression
     }
 
+    /// Get syntactic tree of the function's expression, or `None` if the function is empty.
+    pub fn get_fn_tree(&self) -> Option<&FnTree> {
+        self.tree.as_ref()
+    }
+
     /// Check if the update function is empty (fully unspecified).
     pub fn is_unspecified(&self) -> bool {
         self.tree.is_none()
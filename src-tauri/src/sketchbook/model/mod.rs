@@ -7,10 +7,18 @@ mod _binary_op;
 mod _essentiality;
 /// **(internal)** Definition and utility methods for `FnTree`.
 mod _function_tree;
+/// **(internal)** Definition and utility methods for `GraphImportReport`.
+mod _graph_import_report;
+/// **(internal)** Definition of `InputMode`.
+mod _input_mode;
+/// **(internal)** Definition and utility methods for `ModelPatch`.
+mod _model_patch;
 /// **(internal)** Definition and utility methods for the manager class `ModelState`.
 mod _model_state;
 /// **(internal)** Definition and utility methods for `Monotonicity`.
 mod _monotonicity;
+/// **(internal)** Definition and utility methods for `ReductionReport`.
+mod _reduction_report;
 /// **(internal)** Definition and utility methods for `Regulation`.
 mod _regulation;
 /// **(internal)** Definition and utility methods for `UninterpretedFn`.
@@ -25,8 +33,12 @@ mod _variable;
 pub use _binary_op::BinaryOp;
 pub use _essentiality::Essentiality;
 pub use _function_tree::FnTree;
+pub use _graph_import_report::{GraphImportReport, IgnoredEntry};
+pub use _input_mode::InputMode;
+pub use _model_patch::{ModelPatch, PatchOp};
 pub use _model_state::ModelState;
 pub use _monotonicity::Monotonicity;
+pub use _reduction_report::{ReductionReport, ReductionStep};
 pub use _regulation::Regulation;
 pub use _uninterpreted_fn::UninterpretedFn;
 pub use _uninterpreted_fn_arg::FnArgument;
@@ -0,0 +1,245 @@
+use crate::sketchbook::observations::VarValue;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Method used to compute a binarization threshold for each variable of continuous data.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BinarizationMethod {
+    /// The same fixed threshold for all variables.
+    Threshold(f64),
+    /// Mean of the values of each variable.
+    Mean,
+    /// Median of the values of each variable.
+    Median,
+    /// Boundary between two clusters of the values of each variable, found by (optimal 1D)
+    /// k-means clustering with `k = 2`.
+    KMeans,
+}
+
+impl fmt::Display for BinarizationMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BinarizationMethod::Threshold(value) => write!(f, "fixed threshold {value}"),
+            BinarizationMethod::Mean => write!(f, "mean"),
+            BinarizationMethod::Median => write!(f, "median"),
+            BinarizationMethod::KMeans => write!(f, "k-means (k=2)"),
+        }
+    }
+}
+
+/// Settings for binarization of continuous data.
+///
+/// Values above the threshold of a variable are mapped to `1`, values below to `0`. Values
+/// within the `uncertainty` band around the threshold are mapped to `*`. The band is given
+/// relative to the range of values of each variable, i.e., `uncertainty = 0.1` means that all
+/// values closer to the threshold than 10% of the variable's range are considered uncertain.
+///
+/// The settings are validated when deserialized (the same way as in [BinarizationConfig::new]).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedBinarizationConfig")]
+pub struct BinarizationConfig {
+    pub method: BinarizationMethod,
+    pub uncertainty: f64,
+}
+
+/// **(internal)** Raw form of [BinarizationConfig] used for deserialization, before the settings
+/// are validated.
+#[derive(Deserialize)]
+struct UncheckedBinarizationConfig {
+    method: BinarizationMethod,
+    uncertainty: f64,
+}
+
+impl TryFrom<UncheckedBinarizationConfig> for BinarizationConfig {
+    type Error = String;
+
+    fn try_from(config: UncheckedBinarizationConfig) -> Result<Self, Self::Error> {
+        BinarizationConfig::new(config.method, config.uncertainty)
+    }
+}
+
+impl<'de> JsonSerde<'de> for BinarizationConfig {}
+
+impl BinarizationConfig {
+    /// Create new `BinarizationConfig` given a method and the (relative) uncertainty band.
+    pub fn new(method: BinarizationMethod, uncertainty: f64) -> Result<Self, String> {
+        if !(0. ..=1.).contains(&uncertainty) {
+            return Err("Uncertainty band must be a number between 0 and 1.".to_string());
+        }
+        if let BinarizationMethod::Threshold(value) = method {
+            if !value.is_finite() {
+                return Err("Binarization threshold must be a finite number.".to_string());
+            }
+        }
+        Ok(BinarizationConfig {
+            method,
+            uncertainty,
+        })
+    }
+
+    /// Compute the binarization threshold for given values of a variable (missing values are
+    /// `None`). If there are no values, there is no threshold.
+    pub fn compute_threshold(&self, values: &[Option<f64>]) -> Option<f64> {
+        let mut values: Vec<f64> = values.iter().filter_map(|v| *v).collect();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(|a, b| a.total_cmp(b));
+        let threshold = match self.method {
+            BinarizationMethod::Threshold(value) => value,
+            BinarizationMethod::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            BinarizationMethod::Median => {
+                let mid = values.len() / 2;
+                if values.len() % 2 == 1 {
+                    values[mid]
+                } else {
+                    (values[mid - 1] + values[mid]) / 2.
+                }
+            }
+            BinarizationMethod::KMeans => kmeans_boundary(&values),
+        };
+        Some(threshold)
+    }
+
+    /// Binarize given values of a variable (missing values are `None`) using a precomputed
+    /// `threshold`. Missing values (and all values if there is no threshold) are mapped to `*`.
+    pub fn binarize(&self, values: &[Option<f64>], threshold: Option<f64>) -> Vec<VarValue> {
+        let present: Vec<f64> = values.iter().filter_map(|v| *v).collect();
+        let min = present.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = present.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let band = if present.is_empty() {
+            0.
+        } else {
+            self.uncertainty * (max - min)
+        };
+
+        values
+            .iter()
+            .map(|value| match (value, threshold) {
+                (Some(v), Some(t)) if *v > t + band => VarValue::True,
+                (Some(v), Some(t)) if *v < t - band => VarValue::False,
+                // values exactly at the threshold are considered active if there is no band
+                (Some(v), Some(t)) if band == 0. && *v == t => VarValue::True,
+                _ => VarValue::Any,
+            })
+            .collect()
+    }
+
+    /// Describe the binarization (method and computed thresholds) so that it can be stored
+    /// in the dataset's annotation and reproduced later.
+    pub fn describe(&self, thresholds: &[(String, Option<f64>)]) -> String {
+        let thresholds_str = thresholds
+            .iter()
+            .map(|(var, t)| match t {
+                Some(t) => format!("{var}={t}"),
+                None => format!("{var}=NA"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "Binarized from continuous data (method: {}, uncertainty band: {}).\nThresholds: {thresholds_str}",
+            self.method, self.uncertainty
+        )
+    }
+}
+
+/// **(internal)** Compute the boundary between two clusters of sorted values, using optimal
+/// 1D k-means clustering with `k = 2` (the split minimizing the within-cluster sum of squares).
+/// The boundary is the midpoint between the means of the two clusters.
+fn kmeans_boundary(sorted_values: &[f64]) -> f64 {
+    let n = sorted_values.len();
+    if n == 1 || sorted_values[0] == sorted_values[n - 1] {
+        return sorted_values[0];
+    }
+
+    let total_sum: f64 = sorted_values.iter().sum();
+    let total_sq: f64 = sorted_values.iter().map(|v| v * v).sum();
+    let (mut left_sum, mut left_sq) = (0., 0.);
+    let mut best = (f64::INFINITY, 0.);
+    for i in 1..n {
+        left_sum += sorted_values[i - 1];
+        left_sq += sorted_values[i - 1] * sorted_values[i - 1];
+        // only split between different values
+        if sorted_values[i - 1] == sorted_values[i] {
+            continue;
+        }
+        let (n_left, n_right) = (i as f64, (n - i) as f64);
+        let (right_sum, right_sq) = (total_sum - left_sum, total_sq - left_sq);
+        let cost =
+            (left_sq - left_sum * left_sum / n_left) + (right_sq - right_sum * right_sum / n_right);
+        if cost < best.0 {
+            let boundary = (left_sum / n_left + right_sum / n_right) / 2.;
+            best = (cost, boundary);
+        }
+    }
+    best.1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::{BinarizationConfig, BinarizationMethod, VarValue};
+    use crate::sketchbook::JsonSerde;
+
+    #[test]
+    /// Test computing thresholds with different methods.
+    fn binarization_thresholds() {
+        let values = vec![Some(1.), Some(2.), None, Some(9.), Some(10.), Some(3.)];
+        let compute = |method| {
+            let config = BinarizationConfig::new(method, 0.).unwrap();
+            config.compute_threshold(&values).unwrap()
+        };
+        assert_eq!(compute(BinarizationMethod::Threshold(4.)), 4.);
+        assert_eq!(compute(BinarizationMethod::Mean), 5.);
+        assert_eq!(compute(BinarizationMethod::Median), 3.);
+        // clusters {1, 2, 3} and {9, 10}
+        assert_eq!(compute(BinarizationMethod::KMeans), 5.75);
+
+        let config = BinarizationConfig::new(BinarizationMethod::KMeans, 0.).unwrap();
+        assert_eq!(config.compute_threshold(&[None, None]), None);
+        assert!(BinarizationConfig::new(BinarizationMethod::Mean, 2.).is_err());
+    }
+
+    #[test]
+    /// Test binarizing values, including the uncertainty band and missing values.
+    fn binarization_values() {
+        let values = vec![Some(0.), Some(4.), None, Some(5.), Some(10.)];
+        let config = BinarizationConfig::new(BinarizationMethod::Threshold(5.), 0.).unwrap();
+        let expected = vec![
+            VarValue::False,
+            VarValue::False,
+            VarValue::Any,
+            VarValue::True,
+            VarValue::True,
+        ];
+        assert_eq!(config.binarize(&values, Some(5.)), expected);
+
+        // band of 0.2 * 10 around threshold 5 makes values 4 and 5 uncertain
+        let config = BinarizationConfig::new(BinarizationMethod::Threshold(5.), 0.2).unwrap();
+        let expected = vec![
+            VarValue::False,
+            VarValue::Any,
+            VarValue::Any,
+            VarValue::Any,
+            VarValue::True,
+        ];
+        assert_eq!(config.binarize(&values, Some(5.)), expected);
+        assert_eq!(config.binarize(&values, None), vec![VarValue::Any; 5]);
+    }
+
+    #[test]
+    /// Test that deserialized settings are validated.
+    fn binarization_config_deserialization() {
+        let config = BinarizationConfig::new(BinarizationMethod::Median, 0.1).unwrap();
+        let json_str = config.to_json_str();
+        assert_eq!(
+            BinarizationConfig::from_json_str(&json_str).unwrap(),
+            config
+        );
+
+        let json_str = r#"{"method":"Mean","uncertainty":1.5}"#;
+        assert!(BinarizationConfig::from_json_str(json_str).is_err());
+        let json_str = r#"{"method":"Mean","uncertainty":-0.1}"#;
+        assert!(BinarizationConfig::from_json_str(json_str).is_err());
+    }
+}
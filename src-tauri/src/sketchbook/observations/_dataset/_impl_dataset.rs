@@ -1,7 +1,7 @@
 use crate::sketchbook::ids::{ObservationId, VarId};
-use crate::sketchbook::observations::{Dataset, Observation, VarValue};
+use crate::sketchbook::observations::{Dataset, Observation, PerturbationType, VarValue};
 use crate::sketchbook::utils::{assert_ids_unique, assert_name_valid};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Creating new `Dataset` instances.
 impl Dataset {
@@ -54,6 +54,8 @@ impl Dataset {
             annotation: annotation.to_string(),
             observations,
             variables,
+            multi_valued_vars: Vec::new(),
+            perturbations: BTreeMap::new(),
             index_map,
         })
     }
@@ -94,9 +96,11 @@ impl Dataset {
     /// Add observation at the end of the dataset.
     ///
     /// The observation must have the same length as is the number of dataset's variables, and its
-    /// id must not be already present in the dataset.
+    /// id must not be already present in the dataset. Its values must be consistent with the
+    /// encoding of multi-valued variables.
     pub fn push_obs(&mut self, obs: Observation) -> Result<(), String> {
         self.assert_no_obs(obs.get_id())?;
+        self.assert_obs_encoding_valid(&obs)?;
         self.index_map
             .insert(obs.get_id().clone(), self.observations.len());
         self.observations.push(obs);
@@ -128,12 +132,14 @@ impl Dataset {
         Ok(())
     }
 
-    /// Add observation to a given index in the dataset.
+    /// Add observation to a given index in the dataset. Its values must be consistent with the
+    /// encoding of multi-valued variables.
     ///
     /// This operation might be very costly, as we must reindex all subsequent observations.
     pub fn insert_obs(&mut self, index: usize, obs: Observation) -> Result<(), String> {
         // check that inputs are valid
         self.assert_no_obs(obs.get_id())?;
+        self.assert_obs_encoding_valid(&obs)?;
         if index > self.num_observations() {
             return Err("Index is larger than number of observations.".to_string());
         }
@@ -150,13 +156,15 @@ impl Dataset {
     }
 
     /// Remove variable and all the values corresponding to it (decrementing dimension of the
-    /// dataset in process).
+    /// dataset in process). Perturbation of the variable (if any) is removed too.
     pub fn remove_var(&mut self, var_id: &VarId) -> Result<(), String> {
         let idx = self.get_idx_of_var(var_id)?; // validity check inside
         self.variables.remove(idx);
         for obs in self.observations.iter_mut() {
             obs.remove_nth_value(idx)?;
         }
+        self.perturbations.remove(var_id);
+        self.prune_multi_valued_vars();
         Ok(())
     }
 
@@ -189,37 +197,55 @@ impl Dataset {
         self.add_var_default(var_id, index)
     }
 
-    /// Swap the whole observation data for given ID.
+    /// Swap the whole observation data for given ID. The new values must be consistent with
+    /// the encoding of multi-valued variables.
     pub fn set_observation_raw(
         &mut self,
         id: &ObservationId,
         obs: Observation,
     ) -> Result<(), String> {
         let idx = self.get_obs_index(id)?;
+        self.assert_obs_encoding_valid(&obs)?;
         self.observations[idx] = obs;
         Ok(())
     }
 
     /// Swap value vector for an observation with given ID.
-    /// The new vector of values must be of the same length as the original.
+    /// The new vector of values must be of the same length as the original, and consistent
+    /// with the encoding of multi-valued variables.
     pub fn swap_obs_values(
         &mut self,
         id: &ObservationId,
         new_values: Vec<VarValue>,
     ) -> Result<(), String> {
         let idx = self.get_obs_index(id)?;
-        self.observations[idx].set_all_values(new_values)
+        let mut new_obs = self.observations[idx].clone();
+        new_obs.set_all_values(new_values)?;
+        self.assert_obs_encoding_valid(&new_obs)?;
+        self.observations[idx] = new_obs;
+        Ok(())
     }
 
-    /// Set the id of variable with `original_id` to `new_id`.
+    /// Set the id of variable with `original_id` to `new_id`. Perturbation of the variable
+    /// (if any) is moved to the new ID.
     pub fn set_var_id(&mut self, original_id: &VarId, new_id: VarId) -> Result<(), String> {
         self.assert_valid_variable(original_id)?;
         self.assert_no_variable(&new_id)?;
+        if self.perturbations.contains_key(original_id) && self.perturbations.contains_key(&new_id)
+        {
+            return Err(format!(
+                "Variable with id {new_id} is already perturbed in this dataset."
+            ));
+        }
 
         // we already checked that the variable must exist on some position
         if let Some(idx) = self.variables.iter().position(|v| v == original_id) {
-            self.variables[idx] = new_id;
+            self.variables[idx] = new_id.clone();
+        }
+        if let Some(perturbation) = self.perturbations.remove(original_id) {
+            self.perturbations.insert(new_id, perturbation);
         }
+        self.prune_multi_valued_vars();
         Ok(())
     }
 
@@ -239,6 +265,7 @@ impl Dataset {
         assert_ids_unique(&new_variables_list)?;
 
         self.variables = new_variables_list;
+        self.prune_multi_valued_vars();
         Ok(())
     }
 
@@ -294,6 +321,38 @@ impl Dataset {
         self.observations[idx].set_annotation(new_annot);
         Ok(())
     }
+
+    /// Set weight of a given observation (used for soft constraints). Weight must be positive.
+    pub fn set_obs_weight(&mut self, id: &ObservationId, weight: u32) -> Result<(), String> {
+        let idx = self.get_obs_index(id)?;
+        self.observations[idx].set_weight(weight)
+    }
+
+    /// Set perturbation of a given variable, or remove it (if `perturbation` is `None`).
+    /// The variable does not have to be captured by the dataset's observations.
+    pub fn set_perturbation(&mut self, var_id: VarId, perturbation: Option<PerturbationType>) {
+        if let Some(perturbation) = perturbation {
+            self.perturbations.insert(var_id, perturbation);
+        } else {
+            self.perturbations.remove(&var_id);
+        }
+    }
+
+    /// Set perturbation of a variable given by string, or remove it (if `perturbation` is `None`).
+    pub fn set_perturbation_by_str(
+        &mut self,
+        id: &str,
+        perturbation: Option<PerturbationType>,
+    ) -> Result<(), String> {
+        let var_id = VarId::new(id)?;
+        self.set_perturbation(var_id, perturbation);
+        Ok(())
+    }
+
+    /// Set all perturbations of the dataset at once (replacing the existing ones).
+    pub fn set_all_perturbations(&mut self, perturbations: BTreeMap<VarId, PerturbationType>) {
+        self.perturbations = perturbations;
+    }
 }
 
 /// Observing `Dataset` instances.
@@ -373,6 +432,16 @@ impl Dataset {
         &self.variables
     }
 
+    /// Map of all perturbed variables and their perturbations.
+    pub fn perturbations(&self) -> &BTreeMap<VarId, PerturbationType> {
+        &self.perturbations
+    }
+
+    /// Check if the dataset comes from a perturbation experiment (some variables are perturbed).
+    pub fn is_perturbed(&self) -> bool {
+        !self.perturbations.is_empty()
+    }
+
     /// Vector of all variable names.
     pub fn variable_names(&self) -> Vec<String> {
         self.variables.iter().map(|v| v.to_string()).collect()
@@ -478,7 +547,7 @@ impl Dataset {
 
 #[cfg(test)]
 mod tests {
-    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::observations::{Dataset, Observation, PerturbationType};
 
     #[test]
     /// Test that valid datasets are created correctly.
@@ -585,6 +654,23 @@ mod tests {
         assert!(dataset.set_obs_id_by_str("p", "o2").is_err());
     }
 
+    #[test]
+    /// Test that perturbations are moved when renaming variables.
+    fn test_set_var_id_perturbed() {
+        let mut dataset = Dataset::new_empty("dataset", vec!["a", "b"]).unwrap();
+        let ko = Some(PerturbationType::KnockOut);
+        dataset.set_perturbation_by_str("a", ko).unwrap();
+        dataset.set_perturbation_by_str("c", ko).unwrap();
+
+        dataset.set_var_id_by_str("a", "a2").unwrap();
+        let perturbed: Vec<_> = dataset.perturbations().keys().map(|v| v.as_str()).collect();
+        assert_eq!(perturbed, vec!["a2", "c"]);
+
+        // perturbed variable can not be renamed to another perturbed one
+        assert!(dataset.set_var_id_by_str("a2", "c").is_err());
+        assert_eq!(dataset.get_var_on_idx(0).unwrap().as_str(), "a2");
+    }
+
     #[test]
     /// Test removing variable from a dataset.
     fn test_remove_variable() {
@@ -602,6 +688,19 @@ mod tests {
         assert_eq!(dataset, dataset_expected);
     }
 
+    #[test]
+    /// Test that perturbation of a removed variable is removed too.
+    fn test_remove_variable_perturbed() {
+        let mut dataset = Dataset::new_empty("dataset", vec!["a", "b"]).unwrap();
+        let oe = Some(PerturbationType::OverExpression);
+        dataset.set_perturbation_by_str("a", oe).unwrap();
+        dataset.set_perturbation_by_str("b", oe).unwrap();
+
+        dataset.remove_var_by_str("a").unwrap();
+        let perturbed: Vec<_> = dataset.perturbations().keys().map(|v| v.as_str()).collect();
+        assert_eq!(perturbed, vec!["b"]);
+    }
+
     #[test]
     /// Test adding variable with default values to a dataset.
     fn test_add_variable_default() {
@@ -619,6 +718,28 @@ mod tests {
         assert_eq!(dataset, dataset_expected);
     }
 
+    #[test]
+    /// Test setting and removing perturbations of a dataset.
+    fn test_perturbations() {
+        let mut dataset = Dataset::new_empty("dataset", vec!["a", "b"]).unwrap();
+        assert!(!dataset.is_perturbed());
+
+        // perturbed variables do not need to be captured by the dataset
+        dataset
+            .set_perturbation_by_str("c", Some(PerturbationType::KnockOut))
+            .unwrap();
+        dataset
+            .set_perturbation_by_str("a", Some(PerturbationType::OverExpression))
+            .unwrap();
+        assert!(dataset.is_perturbed());
+        let perturbed: Vec<_> = dataset.perturbations().keys().map(|v| v.as_str()).collect();
+        assert_eq!(perturbed, vec!["a", "c"]);
+
+        dataset.set_perturbation_by_str("a", None).unwrap();
+        dataset.set_perturbation_by_str("c", None).unwrap();
+        assert!(!dataset.is_perturbed());
+    }
+
     #[test]
     /// Test displaying of string description of datasets.
     fn test_debug_str() {
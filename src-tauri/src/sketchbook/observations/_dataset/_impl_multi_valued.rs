@@ -0,0 +1,292 @@
+use crate::sketchbook::ids::ObservationId;
+use crate::sketchbook::observations::{Dataset, MultiValuedVar, Observation, VarValue};
+use std::collections::HashSet;
+
+/// Declaring multi-valued variables of a `Dataset` (encoded by its Boolean variables).
+impl Dataset {
+    /// Declare a multi-valued variable. All its encoding variables (see
+    /// [MultiValuedVar::encoding_vars]) must be variables of this dataset, and they can not
+    /// encode any other multi-valued variable. Values in all observations must be consistent
+    /// with the encoding.
+    pub fn add_multi_valued_var(&mut self, var: MultiValuedVar) -> Result<(), String> {
+        let mut all_vars = self.multi_valued_vars.clone();
+        all_vars.push(var);
+        self.set_all_multi_valued_vars(all_vars)
+    }
+
+    /// Remove declaration of a multi-valued variable with given name. The Boolean encoding
+    /// variables stay in the dataset.
+    pub fn remove_multi_valued_var(&mut self, name: &str) -> Result<(), String> {
+        self.get_multi_valued_var(name)?;
+        self.multi_valued_vars.retain(|v| v.get_name() != name);
+        Ok(())
+    }
+
+    /// Set all multi-valued variables of the dataset at once (replacing the existing ones).
+    /// See [Self::add_multi_valued_var] for requirements on each of them.
+    pub fn set_all_multi_valued_vars(&mut self, vars: Vec<MultiValuedVar>) -> Result<(), String> {
+        let mut used_names = HashSet::new();
+        let mut used_encoding_vars = HashSet::new();
+        for var in &vars {
+            if !used_names.insert(var.get_name()) {
+                let msg = format!(
+                    "Multi-valued variable `{}` is declared twice.",
+                    var.get_name()
+                );
+                return Err(msg);
+            }
+            for encoding_var in var.encoding_vars() {
+                self.get_var_id(&encoding_var)?;
+                if !used_encoding_vars.insert(encoding_var.clone()) {
+                    let msg = format!("Variable `{encoding_var}` already encodes other variable.");
+                    return Err(msg);
+                }
+            }
+            for obs in &self.observations {
+                self.assert_obs_encodes_var(obs, var)?;
+            }
+        }
+        self.multi_valued_vars = vars;
+        Ok(())
+    }
+
+    /// Rename a declared multi-valued variable, together with all its Boolean encoding
+    /// variables (i.e., `X_i` is renamed to `Y_i`). Perturbations of the encoding variables
+    /// are moved to the new IDs.
+    pub fn rename_multi_valued_var(&mut self, name: &str, new_name: &str) -> Result<(), String> {
+        let var = self.get_multi_valued_var(name)?.clone();
+        let new_var = var.with_name(new_name)?;
+        if self.get_multi_valued_var(new_name).is_ok() {
+            return Err(format!(
+                "Multi-valued variable `{new_name}` is already declared."
+            ));
+        }
+        let all_vars: Vec<MultiValuedVar> = self
+            .multi_valued_vars
+            .iter()
+            .map(|v| {
+                if v == &var {
+                    new_var.clone()
+                } else {
+                    v.clone()
+                }
+            })
+            .collect();
+
+        // renaming the encoding variables one by one drops the original declaration, so we
+        // rename them on a copy and then declare the renamed variable again
+        let mut dataset = self.clone();
+        for (var_id, new_id) in var.encoding_vars().iter().zip(new_var.encoding_vars()) {
+            dataset.set_var_id_by_str(var_id, &new_id)?;
+        }
+        dataset.set_all_multi_valued_vars(all_vars)?;
+        *self = dataset;
+        Ok(())
+    }
+
+    /// Set level of a multi-valued variable in given observation (or make it unspecified if
+    /// `level` is `None`). Values of all the Boolean encoding variables are set accordingly.
+    pub fn set_obs_level(
+        &mut self,
+        id: &ObservationId,
+        var_name: &str,
+        level: Option<usize>,
+    ) -> Result<(), String> {
+        let var = self.get_multi_valued_var(var_name)?;
+        let values = var.encode_level(level)?;
+        let indices = self.encoding_indices(var);
+        let idx = self.get_obs_index(id)?;
+        for (i, value) in indices.into_iter().zip(values) {
+            self.observations[idx].set_value(i, value)?;
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Check that values of given observation are a valid encoding of all the
+    /// declared multi-valued variables (see [MultiValuedVar::is_consistent]).
+    pub(super) fn assert_obs_encoding_valid(&self, obs: &Observation) -> Result<(), String> {
+        for var in &self.multi_valued_vars {
+            self.assert_obs_encodes_var(obs, var)?;
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Check that values of given observation are a valid encoding of given
+    /// multi-valued variable. We expect that all its encoding variables are valid.
+    fn assert_obs_encodes_var(
+        &self,
+        obs: &Observation,
+        var: &MultiValuedVar,
+    ) -> Result<(), String> {
+        let values: Vec<VarValue> = self
+            .encoding_indices(var)
+            .iter()
+            .map(|i| obs.value_at_idx(*i))
+            .collect::<Result<_, String>>()?;
+        if !var.is_consistent(&values) {
+            return Err(format!(
+                "Values of observation `{}` are not a valid encoding of variable `{}`.",
+                obs.get_id(),
+                var.get_name()
+            ));
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Remove declarations of multi-valued variables whose encoding variables
+    /// are no longer all present in the dataset (e.g., after removing or renaming variables).
+    pub(super) fn prune_multi_valued_vars(&mut self) {
+        let variables = self.variable_names();
+        self.multi_valued_vars
+            .retain(|v| v.encoding_vars().iter().all(|e| variables.contains(e)));
+    }
+}
+
+/// Observing multi-valued variables of a `Dataset`.
+impl Dataset {
+    /// Vector of all declared multi-valued variables.
+    pub fn multi_valued_vars(&self) -> &Vec<MultiValuedVar> {
+        &self.multi_valued_vars
+    }
+
+    /// Multi-valued variable with given name.
+    pub fn get_multi_valued_var(&self, name: &str) -> Result<&MultiValuedVar, String> {
+        self.multi_valued_vars
+            .iter()
+            .find(|v| v.get_name() == name)
+            .ok_or(format!("Multi-valued variable `{name}` is not declared."))
+    }
+
+    /// Level of a multi-valued variable in given observation. Returns `None` if the values
+    /// of the encoding variables do not determine a single level.
+    pub fn get_obs_level(
+        &self,
+        id: &ObservationId,
+        var_name: &str,
+    ) -> Result<Option<usize>, String> {
+        let var = self.get_multi_valued_var(var_name)?;
+        let obs = self.get_obs(id)?;
+        let values: Vec<VarValue> = self
+            .encoding_indices(var)
+            .iter()
+            .map(|i| obs.value_at_idx(*i))
+            .collect::<Result<_, String>>()?;
+        var.decode_level(&values)
+    }
+
+    /// Indices of the Boolean variables encoding given multi-valued variable. We expect that
+    /// all the encoding variables are valid (should be checked before).
+    pub fn encoding_indices(&self, var: &MultiValuedVar) -> Vec<usize> {
+        var.encoding_vars()
+            .iter()
+            .filter_map(|e| self.variables.iter().position(|v| v.as_str() == e))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::{
+        Dataset, MultiValuedVar, Observation, PerturbationType, VarValue,
+    };
+
+    #[test]
+    /// Test declaring multi-valued variables and setting their levels.
+    fn test_multi_valued_vars() {
+        let obs1 = Observation::try_from_str("101", "o1").unwrap();
+        let obs2 = Observation::try_from_str("0*1", "o2").unwrap();
+        let obs_list = vec![obs1, obs2];
+        let mut dataset = Dataset::new("d", obs_list, vec!["X_1", "X_2", "Y"]).unwrap();
+        let var_x = MultiValuedVar::new("X", vec!["low", "mid", "high"]).unwrap();
+        dataset.add_multi_valued_var(var_x.clone()).unwrap();
+        // can not declare the same variable twice, or use variables outside the dataset
+        assert!(dataset.add_multi_valued_var(var_x).is_err());
+        let var_z = MultiValuedVar::with_num_levels("Z", 3).unwrap();
+        assert!(dataset.add_multi_valued_var(var_z).is_err());
+
+        let o1 = dataset.get_obs_id_by_str("o1").unwrap();
+        let o2 = dataset.get_obs_id_by_str("o2").unwrap();
+        assert_eq!(dataset.get_obs_level(&o1, "X").unwrap(), Some(1));
+        assert_eq!(dataset.get_obs_level(&o2, "X").unwrap(), None);
+        dataset.set_obs_level(&o2, "X", Some(2)).unwrap();
+        assert_eq!(dataset.get_obs(&o2).unwrap().to_values_string(), "111");
+
+        // removing an encoding variable removes the declaration
+        dataset.remove_var_by_str("X_2").unwrap();
+        assert!(dataset.multi_valued_vars().is_empty());
+    }
+
+    #[test]
+    /// Test that multi-valued variables can not be declared if observations are inconsistent
+    /// with the encoding.
+    fn test_inconsistent_multi_valued_var() {
+        let obs = Observation::try_from_str("01", "o").unwrap();
+        let mut dataset = Dataset::new("d", vec![obs], vec!["X_1", "X_2"]).unwrap();
+        let var_x = MultiValuedVar::with_num_levels("X", 3).unwrap();
+        assert!(dataset.add_multi_valued_var(var_x).is_err());
+    }
+
+    /// Prepare a dataset with a 3-level variable `X` (and a Boolean variable `Y`).
+    fn prepare_multi_valued_dataset() -> Dataset {
+        let obs = Observation::try_from_str("101", "o").unwrap();
+        let mut dataset = Dataset::new("d", vec![obs], vec!["X_1", "X_2", "Y"]).unwrap();
+        let var_x = MultiValuedVar::with_num_levels("X", 3).unwrap();
+        dataset.add_multi_valued_var(var_x).unwrap();
+        dataset
+    }
+
+    #[test]
+    /// Test that observations inconsistent with the encoding can not be added.
+    fn test_add_inconsistent_obs() {
+        let mut dataset = prepare_multi_valued_dataset();
+        let invalid_obs = Observation::try_from_str("011", "p").unwrap();
+        let valid_obs = Observation::try_from_str("*11", "p").unwrap();
+
+        assert!(dataset.push_obs(invalid_obs.clone()).is_err());
+        assert!(dataset.insert_obs(0, invalid_obs).is_err());
+        assert_eq!(dataset.num_observations(), 1);
+        dataset.insert_obs(0, valid_obs).unwrap();
+        assert_eq!(dataset.num_observations(), 2);
+    }
+
+    #[test]
+    /// Test that observations can not be edited to be inconsistent with the encoding.
+    fn test_edit_inconsistent_obs() {
+        let mut dataset = prepare_multi_valued_dataset();
+        let obs_id = dataset.get_obs_id_by_str("o").unwrap();
+
+        let invalid_obs = Observation::try_from_str("010", "o").unwrap();
+        assert!(dataset.set_observation_raw(&obs_id, invalid_obs).is_err());
+        let invalid_values = vec![VarValue::False, VarValue::True, VarValue::False];
+        assert!(dataset.swap_obs_values(&obs_id, invalid_values).is_err());
+        assert_eq!(dataset.get_obs(&obs_id).unwrap().to_values_string(), "101");
+
+        let valid_values = vec![VarValue::True, VarValue::True, VarValue::False];
+        dataset.swap_obs_values(&obs_id, valid_values).unwrap();
+        assert_eq!(dataset.get_obs(&obs_id).unwrap().to_values_string(), "110");
+    }
+
+    #[test]
+    /// Test renaming a multi-valued variable together with its encoding variables.
+    fn test_rename_multi_valued_var() {
+        let mut dataset = prepare_multi_valued_dataset();
+        let perturbation = Some(PerturbationType::KnockOut);
+        dataset
+            .set_perturbation_by_str("X_2", perturbation)
+            .unwrap();
+
+        dataset.rename_multi_valued_var("X", "Z").unwrap();
+        assert_eq!(dataset.variable_names(), vec!["Z_1", "Z_2", "Y"]);
+        let var_z = dataset.get_multi_valued_var("Z").unwrap();
+        assert_eq!(var_z, &MultiValuedVar::with_num_levels("Z", 3).unwrap());
+        assert!(dataset.get_multi_valued_var("X").is_err());
+        let var_id = dataset.get_var_id("Z_2").unwrap();
+        assert_eq!(dataset.perturbations().get(var_id), perturbation.as_ref());
+
+        // the new encoding variables must not be used already
+        let mut dataset = prepare_multi_valued_dataset();
+        dataset.add_var_default_by_str("W_1", 3).unwrap();
+        assert!(dataset.rename_multi_valued_var("X", "W").is_err());
+        assert!(dataset.get_multi_valued_var("X").is_ok());
+    }
+}
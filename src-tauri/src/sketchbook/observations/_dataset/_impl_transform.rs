@@ -0,0 +1,320 @@
+use crate::sketchbook::ids::{ObservationId, VarId};
+use crate::sketchbook::observations::{Dataset, Observation, VarValue};
+use crate::sketchbook::utils::assert_ids_unique;
+use std::collections::HashSet;
+
+/// Bulk transformations of `Dataset` instances (merging, splitting, selecting subsets, ...).
+///
+/// All these methods leave the original dataset untouched and return new instance(s).
+impl Dataset {
+    /// Merge this dataset with `other` into a new dataset with given name.
+    ///
+    /// Variables are aligned by their IDs. The resulting dataset contains all variables of
+    /// this dataset followed by the remaining variables of `other`, and values of variables
+    /// missing in the original dataset are filled with `Any`. Observations of this dataset go
+    /// first, observation IDs must not clash. Both datasets must have the same perturbations,
+    /// and multi-valued variables with the same name must have the same levels.
+    pub fn merge(&self, other: &Dataset, name: &str) -> Result<Dataset, String> {
+        if self.perturbations != other.perturbations {
+            return Err("Can not merge datasets with different perturbations.".to_string());
+        }
+
+        let mut variables = self.variables.clone();
+        for var in &other.variables {
+            if !variables.contains(var) {
+                variables.push(var.clone());
+            }
+        }
+        let align = |dataset: &Dataset| -> Vec<Observation> {
+            let indices: Vec<Option<usize>> = variables
+                .iter()
+                .map(|v| dataset.variables.iter().position(|w| w == v))
+                .collect();
+            dataset
+                .observations
+                .iter()
+                .map(|obs| {
+                    let obs_values = obs.get_values();
+                    let values = indices
+                        .iter()
+                        .map(|idx| match idx {
+                            Some(i) => obs_values.get(*i),
+                            None => VarValue::Any,
+                        })
+                        .collect();
+                    Self::with_values(obs, values)
+                })
+                .collect()
+        };
+        let mut observations = align(self);
+        observations.extend(align(other));
+
+        let mut merged = self.derive(name, observations, variables)?;
+        let mut multi_valued_vars = self.multi_valued_vars.clone();
+        for var in &other.multi_valued_vars {
+            match multi_valued_vars
+                .iter()
+                .find(|v| v.get_name() == var.get_name())
+            {
+                Some(v) if v.levels() != var.levels() => {
+                    return Err(format!(
+                        "Multi-valued variable `{}` has different levels in the two datasets ({} vs. {}).",
+                        var.get_name(),
+                        v.levels().join(", "),
+                        var.levels().join(", ")
+                    ));
+                }
+                Some(_) => {}
+                None => multi_valued_vars.push(var.clone()),
+            }
+        }
+        merged.set_all_multi_valued_vars(multi_valued_vars)?;
+        Ok(merged)
+    }
+
+    /// Split this dataset into two by the given subset of observations. Returns a pair of
+    /// datasets `(remaining, selected)`, where `selected` (with given name) contains the given
+    /// observations and `remaining` contains the rest. The relative order of observations is
+    /// kept, and both datasets keep all the variables and metadata.
+    pub fn split(
+        &self,
+        obs_ids: &[ObservationId],
+        name: &str,
+    ) -> Result<(Dataset, Dataset), String> {
+        assert_ids_unique(&obs_ids.to_vec())?;
+        for id in obs_ids {
+            self.get_obs_index(id)?;
+        }
+        let (selected, remaining): (Vec<Observation>, Vec<Observation>) = self
+            .observations
+            .iter()
+            .cloned()
+            .partition(|obs| obs_ids.contains(obs.get_id()));
+
+        let remaining = self.derive(&self.name, remaining, self.variables.clone())?;
+        let selected = self.derive(name, selected, self.variables.clone())?;
+        Ok((remaining, selected))
+    }
+
+    /// Create a new dataset containing only the given variables (in the given order).
+    /// Multi-valued variables that lose some of their encoding variables are dropped.
+    pub fn select_variables(&self, var_ids: &[VarId]) -> Result<Dataset, String> {
+        assert_ids_unique(&var_ids.to_vec())?;
+        let indices = var_ids
+            .iter()
+            .map(|v| self.get_idx_of_var(v))
+            .collect::<Result<Vec<usize>, String>>()?;
+        let observations = self
+            .observations
+            .iter()
+            .map(|obs| {
+                let obs_values = obs.get_values();
+                let values = indices.iter().map(|i| obs_values.get(*i)).collect();
+                Self::with_values(obs, values)
+            })
+            .collect();
+        self.derive(&self.name, observations, var_ids.to_vec())
+    }
+
+    /// Create a new dataset without duplicate observations. Observations are duplicate if
+    /// they have the same values. Only the first occurrence of each observation is kept, and
+    /// its weight is the sum of weights of all its occurrences.
+    pub fn deduplicated(&self) -> Dataset {
+        let mut observations: Vec<Observation> = Vec::new();
+        for obs in &self.observations {
+            let duplicate = observations
+                .iter_mut()
+                .find(|o| o.iter_values().eq(obs.iter_values()));
+            if let Some(first) = duplicate {
+                let weight = first.get_weight().saturating_add(obs.get_weight());
+                first.set_weight(weight).unwrap(); // sum of positive weights is positive
+            } else {
+                observations.push(obs.clone());
+            }
+        }
+        // observations and variables are taken from a valid dataset, we can unwrap
+        self.derive(&self.name, observations, self.variables.clone())
+            .unwrap()
+    }
+
+    /// Create a new dataset with observations reordered as given. The `order` must be
+    /// a permutation of all observation IDs of this dataset.
+    pub fn reorder_observations(&self, order: &[ObservationId]) -> Result<Dataset, String> {
+        assert_ids_unique(&order.to_vec())?;
+        if order.len() != self.num_observations() {
+            return Err("New order must contain all observations of the dataset.".to_string());
+        }
+        let observations = order
+            .iter()
+            .map(|id| self.get_obs(id).cloned())
+            .collect::<Result<Vec<Observation>, String>>()?;
+        self.derive(&self.name, observations, self.variables.clone())
+    }
+
+    /// Create a transposed dataset, i.e., variables become observations and vice versa.
+    ///
+    /// Observation IDs are used as variable IDs (and vice versa). Names, annotations and
+    /// weights of observations, as well as perturbations and multi-valued variables, are not
+    /// kept, as they would not be meaningful.
+    pub fn transposed(&self) -> Result<Dataset, String> {
+        let var_names: Vec<&str> = self
+            .observations
+            .iter()
+            .map(|o| o.get_id().as_str())
+            .collect();
+        let observations = self
+            .variables
+            .iter()
+            .enumerate()
+            .map(|(i, var)| {
+                let values: Vec<VarValue> = self
+                    .observations
+                    .iter()
+                    .map(|o| o.get_values().get(i))
+                    .collect();
+                Observation::new(values, var.as_str())
+            })
+            .collect::<Result<Vec<Observation>, String>>()?;
+        Dataset::new_annotated(&self.name, &self.annotation, observations, var_names)
+    }
+
+    /// **(internal)** Create a new dataset with given name, observations and variables, and
+    /// with annotation and perturbations of this dataset. Multi-valued variables are kept if
+    /// all their encoding variables are present.
+    fn derive(
+        &self,
+        name: &str,
+        observations: Vec<Observation>,
+        variables: Vec<VarId>,
+    ) -> Result<Dataset, String> {
+        let var_names: Vec<&str> = variables.iter().map(|v| v.as_str()).collect();
+        let mut dataset = Dataset::new_annotated(name, &self.annotation, observations, var_names)?;
+        dataset.perturbations = self.perturbations.clone();
+        let variables: HashSet<String> = dataset.variable_names().into_iter().collect();
+        dataset.multi_valued_vars = self
+            .multi_valued_vars
+            .iter()
+            .filter(|v| v.encoding_vars().iter().all(|e| variables.contains(e)))
+            .cloned()
+            .collect();
+        Ok(dataset)
+    }
+
+    /// **(internal)** Copy of the observation with a different vector of values.
+    fn with_values(obs: &Observation, values: Vec<VarValue>) -> Observation {
+        // all the fields are taken from a valid observation, we can unwrap
+        let id = obs.get_id().as_str();
+        let mut new_obs =
+            Observation::new_annotated(values, id, obs.get_name(), obs.get_annotation()).unwrap();
+        new_obs.set_weight(obs.get_weight()).unwrap();
+        new_obs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::{ObservationId, VarId};
+    use crate::sketchbook::observations::{Dataset, MultiValuedVar, Observation, PerturbationType};
+
+    /// Prepare a dataset with given variables and observations (given by strings).
+    fn prepare_dataset(vars: Vec<&str>, obs: Vec<(&str, &str)>) -> Dataset {
+        let observations = obs
+            .iter()
+            .map(|(values, id)| Observation::try_from_str(values, id).unwrap())
+            .collect();
+        Dataset::new("d", observations, vars).unwrap()
+    }
+
+    /// Shorthand to convert strings into observation IDs.
+    fn obs_ids(ids: &[&str]) -> Vec<ObservationId> {
+        ids.iter()
+            .map(|id| ObservationId::new(id).unwrap())
+            .collect()
+    }
+
+    #[test]
+    /// Test merging two datasets with partially overlapping variables.
+    fn test_merge() {
+        let d1 = prepare_dataset(vec!["a", "b"], vec![("10", "o1")]);
+        let d2 = prepare_dataset(vec!["b", "c"], vec![("01", "o2")]);
+        let merged = d1.merge(&d2, "merged").unwrap();
+        assert_eq!(merged.variable_names(), vec!["a", "b", "c"]);
+        assert_eq!(merged.get_name(), "merged");
+        assert_eq!(merged.get_obs_on_idx(0).unwrap().to_values_string(), "10*");
+        assert_eq!(merged.get_obs_on_idx(1).unwrap().to_values_string(), "*01");
+
+        // observation IDs can not clash
+        assert!(d1.merge(&d1, "merged").is_err());
+        // perturbations must be the same
+        let mut d3 = d2.clone();
+        d3.set_perturbation_by_str("a", Some(PerturbationType::KnockOut))
+            .unwrap();
+        assert!(d1.merge(&d3, "merged").is_err());
+    }
+
+    #[test]
+    /// Test merging datasets with multi-valued variables.
+    fn test_merge_multi_valued() {
+        let mut d1 = prepare_dataset(vec!["X_1", "X_2"], vec![("10", "o1")]);
+        let mut d2 = prepare_dataset(vec!["X_1", "X_2"], vec![("11", "o2")]);
+        let var = MultiValuedVar::with_num_levels("X", 3).unwrap();
+        d1.add_multi_valued_var(var.clone()).unwrap();
+        d2.add_multi_valued_var(var).unwrap();
+        let merged = d1.merge(&d2, "merged").unwrap();
+        assert_eq!(merged.multi_valued_vars().len(), 1);
+
+        // the same variable with different levels can not be merged
+        let mut d3 = prepare_dataset(vec!["X_1", "X_2"], vec![("11", "o3")]);
+        let var = MultiValuedVar::new("X", vec!["low", "mid", "high"]).unwrap();
+        d3.add_multi_valued_var(var).unwrap();
+        let err = d1.merge(&d3, "merged").unwrap_err();
+        assert!(err.contains("different levels"));
+    }
+
+    #[test]
+    /// Test splitting a dataset and selecting variable subsets.
+    fn test_split_and_select() {
+        let obs = vec![("101", "o1"), ("011", "o2"), ("000", "o3")];
+        let dataset = prepare_dataset(vec!["a", "b", "c"], obs);
+
+        let (remaining, selected) = dataset.split(&obs_ids(&["o3", "o1"]), "new").unwrap();
+        assert_eq!(remaining.num_observations(), 1);
+        assert_eq!(selected.get_name(), "new");
+        assert_eq!(selected.get_obs_id(0).as_str(), "o1");
+        assert_eq!(selected.get_obs_id(1).as_str(), "o3");
+        assert!(dataset.split(&obs_ids(&["o4"]), "new").is_err());
+
+        let vars = vec![VarId::new("c").unwrap(), VarId::new("a").unwrap()];
+        let subset = dataset.select_variables(&vars).unwrap();
+        assert_eq!(subset.variable_names(), vec!["c", "a"]);
+        assert_eq!(subset.get_obs_on_idx(0).unwrap().to_values_string(), "11");
+        assert!(dataset
+            .select_variables(&[VarId::new("x").unwrap()])
+            .is_err());
+    }
+
+    #[test]
+    /// Test deduplicating, reordering and transposing a dataset.
+    fn test_dedup_reorder_transpose() {
+        let obs = vec![("10", "o1"), ("01", "o2"), ("10", "o3")];
+        let dataset = prepare_dataset(vec!["a", "b"], obs);
+
+        let dedup = dataset.deduplicated();
+        assert_eq!(dedup.num_observations(), 2);
+        assert_eq!(dedup.get_obs_on_idx(0).unwrap().get_weight(), 2);
+
+        let reordered = dataset
+            .reorder_observations(&obs_ids(&["o3", "o2", "o1"]))
+            .unwrap();
+        assert_eq!(reordered.get_obs_id(0).as_str(), "o3");
+        assert!(dataset.reorder_observations(&obs_ids(&["o1"])).is_err());
+
+        let transposed = dataset.transposed().unwrap();
+        assert_eq!(transposed.variable_names(), vec!["o1", "o2", "o3"]);
+        assert_eq!(
+            transposed.get_obs_on_idx(0).unwrap().to_values_string(),
+            "101"
+        );
+        assert_eq!(transposed.transposed().unwrap(), dataset);
+    }
+}
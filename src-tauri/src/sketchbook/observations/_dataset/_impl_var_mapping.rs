@@ -0,0 +1,101 @@
+use crate::sketchbook::observations::{
+    normalize_var_name, suggest_similar_vars, AliasTable, Dataset, UnmatchedVar, VarMappingReport,
+    VarMatchKind, VarRename,
+};
+use std::collections::HashSet;
+
+/// Mapping variables of a `Dataset` to variables of a model.
+impl Dataset {
+    /// Rename dataset variables that do not match any of the `model_vars`, but refer to some
+    /// model variable via an alias from `aliases`, or differ from it only in capitalization.
+    /// Both aliases and capitalization are matched using [normalize_var_name].
+    /// Aliases take precedence over case-insensitive matches.
+    ///
+    /// A variable is not renamed if the match is ambiguous, if the target variable is already
+    /// in the dataset, or if it encodes a multi-valued variable. All such variables (and
+    /// variables with no match at all) are reported as unmatched, together with suggestions
+    /// of similar model variables (see [suggest_similar_vars]).
+    pub fn map_variables(
+        &mut self,
+        model_vars: &[String],
+        aliases: &AliasTable,
+    ) -> VarMappingReport {
+        let encoding_vars: HashSet<String> = self
+            .multi_valued_vars
+            .iter()
+            .flat_map(|v| v.encoding_vars())
+            .collect();
+
+        let mut report = VarMappingReport::default();
+        for var in self.variable_names() {
+            if model_vars.contains(&var) {
+                continue;
+            }
+
+            let alias_match = aliases
+                .get_target(&var)
+                .filter(|target| model_vars.iter().any(|v| v == target))
+                .map(|target| (target.to_string(), VarMatchKind::Alias));
+            let case_match = || {
+                let normalized_var = normalize_var_name(&var);
+                let mut candidates = model_vars
+                    .iter()
+                    .filter(|v| normalize_var_name(v) == normalized_var);
+                match (candidates.next(), candidates.next()) {
+                    (Some(target), None) => Some((target.clone(), VarMatchKind::CaseInsensitive)),
+                    _ => None,
+                }
+            };
+
+            if let Some((target, kind)) = alias_match.or_else(case_match) {
+                let is_free = !self.variable_names().contains(&target);
+                if is_free && !encoding_vars.contains(&var) {
+                    // both IDs are valid (and the target is not used), this can not fail
+                    self.set_var_id_by_str(&var, &target).unwrap();
+                    report.renamed.push(VarRename {
+                        original_id: var,
+                        new_id: target,
+                        kind,
+                    });
+                    continue;
+                }
+            }
+            report.unmatched.push(UnmatchedVar {
+                suggestions: suggest_similar_vars(&var, model_vars),
+                id: var,
+            });
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::{AliasTable, Dataset, Observation, VarMatchKind};
+    use std::str::FromStr;
+
+    #[test]
+    /// Test mapping dataset variables to model variables via aliases and capitalization.
+    fn test_map_variables() {
+        let obs = Observation::try_from_str("1010", "o").unwrap();
+        let vars = vec!["cln3", "yal040c", "SWI5", "clb1"];
+        let mut dataset = Dataset::new("d", vec![obs], vars).unwrap();
+        let model_vars: Vec<String> = ["CLN3", "SWI5", "CLB2", "MBF"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        let aliases = AliasTable::from_str("CLN3,yal040c\nMBF,yal040c_x").unwrap();
+
+        let report = dataset.map_variables(&model_vars, &aliases);
+        // `cln3` is renamed, so the alias `yal040c` can not be renamed to `CLN3` too
+        assert_eq!(
+            dataset.variable_names(),
+            vec!["CLN3", "yal040c", "SWI5", "clb1"]
+        );
+        assert_eq!(report.renamed.len(), 1);
+        assert_eq!(report.renamed[0].kind, VarMatchKind::CaseInsensitive);
+        assert_eq!(report.unmatched.len(), 2);
+        assert_eq!(report.unmatched[1].id, "clb1");
+        assert_eq!(report.unmatched[1].suggestions, vec!["CLB2"]);
+    }
+}
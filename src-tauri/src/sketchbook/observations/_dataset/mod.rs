@@ -1,7 +1,7 @@
 use crate::sketchbook::ids::{ObservationId, VarId};
-use crate::sketchbook::observations::Observation;
+use crate::sketchbook::observations::{MultiValuedVar, Observation, PerturbationType};
 use crate::sketchbook::Manager;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// **(internal)** Basic utility methods for `Dataset`.
 mod _impl_dataset;
@@ -9,6 +9,12 @@ mod _impl_dataset;
 mod _impl_events;
 /// **(internal)** Implementation of the safe identifier generating.
 mod _impl_id_generating;
+/// **(internal)** Declaring multi-valued variables encoded by Boolean variables.
+mod _impl_multi_valued;
+/// **(internal)** Bulk transformations (merging, splitting, selecting subsets, ...).
+mod _impl_transform;
+/// **(internal)** Mapping dataset variables to model variables.
+mod _impl_var_mapping;
 
 /// An ordered list of observations for given variables.
 /// The order is important for some datasets, for example, to be able to capture time series.
@@ -26,6 +32,11 @@ pub struct Dataset {
     observations: Vec<Observation>,
     /// Variables captured by the observations.
     variables: Vec<VarId>,
+    /// Multi-valued variables, each encoded by several Boolean variables of the dataset.
+    multi_valued_vars: Vec<MultiValuedVar>,
+    /// Perturbations (knockouts, overexpressions) of the experiment the observations come from.
+    /// Perturbed variables do not have to be captured by the observations.
+    perturbations: BTreeMap<VarId, PerturbationType>,
     /// Index map from observation IDs to their index in vector, for faster searching.
     index_map: HashMap<ObservationId, usize>,
 }
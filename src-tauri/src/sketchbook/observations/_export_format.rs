@@ -0,0 +1,62 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Tabular file format to export datasets to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum TableFormat {
+    /// Comma-separated values.
+    Csv,
+    /// Tab-separated values.
+    Tsv,
+}
+
+impl TableFormat {
+    /// Delimiter separating the fields of the format.
+    pub fn delimiter(&self) -> u8 {
+        match self {
+            TableFormat::Csv => b',',
+            TableFormat::Tsv => b'\t',
+        }
+    }
+
+    /// Standard file extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TableFormat::Csv => "csv",
+            TableFormat::Tsv => "tsv",
+        }
+    }
+}
+
+/// Layout of the exported dataset table.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum TableLayout {
+    /// One row per observation, one column per variable. The header lists the variables, the
+    /// first column contains observation IDs (same convention as when loading datasets).
+    Wide,
+    /// One row per observation-variable pair, with columns `ID`, `Variable`, and `Value`.
+    Long,
+}
+
+/// Settings for exporting datasets into tabular files.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DatasetExportConfig {
+    pub format: TableFormat,
+    pub layout: TableLayout,
+}
+
+impl<'de> JsonSerde<'de> for DatasetExportConfig {}
+
+impl DatasetExportConfig {
+    /// Create new `DatasetExportConfig` given a file format and table layout.
+    pub fn new(format: TableFormat, layout: TableLayout) -> DatasetExportConfig {
+        DatasetExportConfig { format, layout }
+    }
+}
+
+impl Default for DatasetExportConfig {
+    /// Default export config is a wide CSV table (that can be loaded back as a dataset).
+    fn default() -> DatasetExportConfig {
+        DatasetExportConfig::new(TableFormat::Csv, TableLayout::Wide)
+    }
+}
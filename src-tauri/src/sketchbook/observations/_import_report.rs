@@ -0,0 +1,115 @@
+use crate::sketchbook::{IssueSeverity, JsonSerde};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Error, Formatter};
+
+/// A single problem found when importing a dataset from a file.
+///
+/// Coordinates `row` and `column` are numbered from 1 (the header is row 1, and the column
+/// with observation IDs is column 1). Either of them is `None` if the problem does not regard
+/// a particular row (or column).
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ImportIssue {
+    pub severity: IssueSeverity,
+    pub row: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl<'de> JsonSerde<'de> for ImportIssue {}
+
+impl Display for ImportIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let severity = match self.severity {
+            IssueSeverity::Error => "ISSUE",
+            IssueSeverity::Warning => "WARNING",
+        };
+        match (self.row, self.column) {
+            (Some(row), Some(col)) => write!(f, "> {severity} at row {row}, column {col}: "),
+            (Some(row), None) => write!(f, "> {severity} at row {row}: "),
+            (None, Some(col)) => write!(f, "> {severity} at column {col}: "),
+            (None, None) => write!(f, "> {severity}: "),
+        }?;
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Report listing all problems found when importing a dataset from a file.
+///
+/// Errors make the import fail, warnings are only informative (the dataset can still be
+/// imported).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DatasetImportReport {
+    issues: Vec<ImportIssue>,
+}
+
+impl<'de> JsonSerde<'de> for DatasetImportReport {}
+
+/// Creating and editing import reports.
+impl DatasetImportReport {
+    /// Create new empty report.
+    pub fn new() -> DatasetImportReport {
+        DatasetImportReport::default()
+    }
+
+    /// Add an error regarding given position (row and column) in the file.
+    pub fn add_error(&mut self, row: Option<usize>, column: Option<usize>, msg: &str) {
+        self.issues.push(ImportIssue {
+            severity: IssueSeverity::Error,
+            row,
+            column,
+            message: msg.to_string(),
+        })
+    }
+
+    /// Add a warning regarding given position (row and column) in the file.
+    pub fn add_warning(&mut self, row: Option<usize>, column: Option<usize>, msg: &str) {
+        self.issues.push(ImportIssue {
+            severity: IssueSeverity::Warning,
+            row,
+            column,
+            message: msg.to_string(),
+        })
+    }
+}
+
+/// Observing import reports.
+impl DatasetImportReport {
+    /// Check whether there are no errors (but there might be warnings).
+    pub fn is_ok(&self) -> bool {
+        self.issues
+            .iter()
+            .all(|i| i.severity != IssueSeverity::Error)
+    }
+
+    /// Check whether there are no issues at all (neither errors, nor warnings).
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Get a list of all issues.
+    pub fn issues(&self) -> &Vec<ImportIssue> {
+        &self.issues
+    }
+
+    /// Summary message of the import, followed by the text rendering of all discovered
+    /// issues (if there are any).
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            "Dataset was imported without issues.".to_string()
+        } else if self.is_ok() {
+            format!("Dataset was imported, but there are warnings:\n{self}")
+        } else {
+            format!("Dataset could not be imported:\n{self}")
+        }
+    }
+}
+
+impl Display for DatasetImportReport {
+    /// Render the issues as text, one per line.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for issue in self.issues.iter() {
+            writeln!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,247 @@
+use super::_impl_load_dataset::PERTURBATIONS_KEY;
+use crate::sketchbook::ids::DatasetId;
+use crate::sketchbook::observations::{
+    Dataset, DatasetExportConfig, MultiValuedVar, ObservationManager, TableLayout, VarValue,
+};
+use std::collections::HashSet;
+use std::path::Path;
+
+impl ObservationManager {
+    /// Convert a dataset into a string with a CSV/TSV table, as specified by `config`.
+    ///
+    /// The wide layout follows the same convention as [Self::load_dataset] (header line with
+    /// variables, then one line per observation with its ID and values), so the result can be
+    /// loaded back. For example:
+    ///    ID,YOX1,CLN3,YHP1
+    ///    Observation1,0,1,0
+    ///    Observation2,1,0,*
+    ///
+    /// Multi-valued variables are written as a single column with their declaration (such as
+    /// `X[low|mid|high]`) and levels, and perturbations on a comment line before the header
+    /// (such as `# perturbations: YOX1:KO`), the same way as they are loaded.
+    ///
+    /// The long layout has a line for each observation-variable pair (with values of the Boolean
+    /// variables, including those encoding multi-valued variables). For example:
+    ///    ID,Variable,Value
+    ///    Observation1,YOX1,0
+    ///    Observation1,CLN3,1
+    ///    ...
+    pub fn export_dataset_str(
+        &self,
+        id: &DatasetId,
+        config: &DatasetExportConfig,
+    ) -> Result<String, String> {
+        let dataset = self.get_dataset(id)?;
+        dataset_to_table(dataset, config)
+    }
+
+    /// Export a dataset into a CSV/TSV file, as specified by `config`.
+    ///
+    /// See [Self::export_dataset_str] for details on the format.
+    pub fn export_dataset(
+        &self,
+        id: &DatasetId,
+        path: &str,
+        config: &DatasetExportConfig,
+    ) -> Result<(), String> {
+        let table_str = self.export_dataset_str(id, config)?;
+        std::fs::write(path, table_str).map_err(|e| e.to_string())
+    }
+
+    /// Export all datasets into CSV/TSV files in a given directory, as specified by `config`.
+    /// Each dataset is written into a file named by its ID, with the extension of the chosen
+    /// format (existing files are overwritten).
+    ///
+    /// Paths to all created files are returned (sorted by dataset IDs).
+    /// See [Self::export_dataset_str] for details on the format.
+    pub fn export_all_datasets(
+        &self,
+        dir_path: &str,
+        config: &DatasetExportConfig,
+    ) -> Result<Vec<String>, String> {
+        let dir_path = Path::new(dir_path);
+        if !dir_path.is_dir() {
+            return Err(format!("`{}` is not a directory.", dir_path.display()));
+        }
+
+        let mut dataset_ids: Vec<&DatasetId> = self.datasets.keys().collect();
+        dataset_ids.sort();
+        let mut file_paths = Vec::new();
+        for id in dataset_ids {
+            let file_name = format!("{id}.{}", config.format.extension());
+            let file_path = dir_path.join(file_name).to_string_lossy().to_string();
+            self.export_dataset(id, &file_path, config)?;
+            file_paths.push(file_path);
+        }
+        Ok(file_paths)
+    }
+}
+
+/// **(internal)** Write a dataset into a string with CSV/TSV table, as specified by `config`.
+///
+/// Perturbations are written on a comment line before the header, and multi-valued variables
+/// are written as columns with their declaration and levels (in the wide layout), the same way
+/// as [ObservationManager::import_dataset_str] expects them.
+fn dataset_to_table(dataset: &Dataset, config: &DatasetExportConfig) -> Result<String, String> {
+    let mut output = Vec::new();
+    if dataset.is_perturbed() {
+        let perturbations: Vec<String> = dataset
+            .perturbations()
+            .iter()
+            .map(|(var, perturbation)| format!("{var}:{perturbation}"))
+            .collect();
+        let line = format!("# {PERTURBATIONS_KEY}: {}\n", perturbations.join(", "));
+        output.extend(line.as_bytes());
+    }
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(config.format.delimiter())
+        .from_writer(output);
+
+    match config.layout {
+        TableLayout::Wide => {
+            let columns = wide_columns(dataset);
+            let mut header = vec!["ID".to_string()];
+            header.extend(columns.iter().map(|(name, _, _)| name.clone()));
+            wtr.write_record(&header).map_err(|e| e.to_string())?;
+            for obs in dataset.observations() {
+                let mut record = vec![obs.get_id().to_string()];
+                for (_, indices, mv_var) in &columns {
+                    let values: Vec<VarValue> = indices
+                        .iter()
+                        .map(|i| obs.value_at_idx(*i))
+                        .collect::<Result<_, String>>()?;
+                    let value_str = match mv_var {
+                        Some(mv_var) => match mv_var.decode_level(&values)? {
+                            Some(level) => mv_var.levels()[level].clone(),
+                            None => VarValue::Any.to_string(),
+                        },
+                        None => values[0].to_string(),
+                    };
+                    record.push(value_str);
+                }
+                wtr.write_record(&record).map_err(|e| e.to_string())?;
+            }
+        }
+        TableLayout::Long => {
+            wtr.write_record(["ID", "Variable", "Value"])
+                .map_err(|e| e.to_string())?;
+            for obs in dataset.observations() {
+                let obs_id = obs.get_id().to_string();
+                for (var, value) in dataset.variables().iter().zip(obs.iter_values()) {
+                    let record = [obs_id.clone(), var.to_string(), value.to_string()];
+                    wtr.write_record(&record).map_err(|e| e.to_string())?;
+                }
+            }
+        }
+    }
+
+    let bytes = wtr.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// **(internal)** Columns of the wide layout, each given by its header, indices of the dataset
+/// variables it consists of, and the multi-valued variable (if the column represents one).
+///
+/// Boolean variables encoding a multi-valued variable are merged into a single column with the
+/// declaration `X[low|mid|high]`, placed at the position of the first of them.
+fn wide_columns(dataset: &Dataset) -> Vec<(String, Vec<usize>, Option<&MultiValuedVar>)> {
+    let mut columns = Vec::new();
+    let mut written_mv_vars = HashSet::new();
+    for (idx, var) in dataset.variables().iter().enumerate() {
+        let mv_var = dataset
+            .multi_valued_vars()
+            .iter()
+            .find(|mv_var| mv_var.encoding_vars().contains(&var.to_string()));
+        match mv_var {
+            Some(mv_var) => {
+                if written_mv_vars.insert(mv_var.get_name()) {
+                    let header = format!("{}[{}]", mv_var.get_name(), mv_var.levels().join("|"));
+                    columns.push((header, dataset.encoding_indices(mv_var), Some(mv_var)));
+                }
+            }
+            None => columns.push((var.to_string(), vec![idx], None)),
+        }
+    }
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::{
+        Dataset, DatasetExportConfig, Observation, ObservationManager, PerturbationType,
+        TableFormat, TableLayout,
+    };
+
+    /// Prepare a manager with a single simple dataset `d`.
+    fn prepare_manager() -> ObservationManager {
+        let obs1 = Observation::try_from_str("01*", "o1").unwrap();
+        let obs2 = Observation::try_from_str("1*0", "o2").unwrap();
+        let dataset = Dataset::new("d", vec![obs1, obs2], vec!["a", "b", "c"]).unwrap();
+        ObservationManager::from_datasets(vec![("d", dataset)]).unwrap()
+    }
+
+    #[test]
+    /// Test exporting a dataset into CSV/TSV tables with both layouts.
+    fn export_dataset_layouts() {
+        let manager = prepare_manager();
+        let id = manager.get_dataset_id("d").unwrap();
+
+        let config = DatasetExportConfig::new(TableFormat::Csv, TableLayout::Wide);
+        let csv_str = manager.export_dataset_str(&id, &config).unwrap();
+        assert_eq!(csv_str, "ID,a,b,c\no1,0,1,*\no2,1,*,0\n");
+
+        let config = DatasetExportConfig::new(TableFormat::Tsv, TableLayout::Long);
+        let tsv_str = manager.export_dataset_str(&id, &config).unwrap();
+        let expected = "ID\tVariable\tValue\no1\ta\t0\no1\tb\t1\no1\tc\t*\n\
+            o2\ta\t1\no2\tb\t*\no2\tc\t0\n";
+        assert_eq!(tsv_str, expected);
+    }
+
+    #[test]
+    /// Test that datasets exported in wide layout can be loaded back.
+    fn export_dataset_round_trip() {
+        let manager = prepare_manager();
+        let id = manager.get_dataset_id("d").unwrap();
+        let original = manager.get_dataset(&id).unwrap();
+
+        for format in [TableFormat::Csv, TableFormat::Tsv] {
+            let config = DatasetExportConfig::new(format, TableLayout::Wide);
+            let table_str = manager.export_dataset_str(&id, &config).unwrap();
+            let (loaded, report) =
+                ObservationManager::import_dataset_str("d", &table_str, None).unwrap();
+            assert!(report.is_empty());
+            assert_eq!(&loaded, original);
+        }
+    }
+
+    #[test]
+    /// Test that multi-valued variables and perturbations are preserved when a dataset is
+    /// exported (in wide layout) and loaded back.
+    fn export_dataset_round_trip_metadata() {
+        let contents = "ID,A,X[low|mid|high]\no1,1,mid\no2,0,*\no3,*,high\n";
+        let (mut dataset, _) = ObservationManager::import_dataset_str("d", contents, None).unwrap();
+        assert_eq!(dataset.multi_valued_vars().len(), 1);
+        dataset
+            .set_perturbation_by_str("A", Some(PerturbationType::KnockOut))
+            .unwrap();
+        dataset
+            .set_perturbation_by_str("B", Some(PerturbationType::OverExpression))
+            .unwrap();
+        let manager = ObservationManager::from_datasets(vec![("d", dataset.clone())]).unwrap();
+        let id = manager.get_dataset_id("d").unwrap();
+
+        let config = DatasetExportConfig::new(TableFormat::Csv, TableLayout::Wide);
+        let csv_str = manager.export_dataset_str(&id, &config).unwrap();
+        let expected = "# perturbations: A:KO, B:OE\n\
+            ID,A,X[low|mid|high]\no1,1,mid\no2,0,*\no3,*,high\n";
+        assert_eq!(csv_str, expected);
+
+        for format in [TableFormat::Csv, TableFormat::Tsv] {
+            let config = DatasetExportConfig::new(format, TableLayout::Wide);
+            let table_str = manager.export_dataset_str(&id, &config).unwrap();
+            let (loaded, _) =
+                ObservationManager::import_dataset_str("d", &table_str, None).unwrap();
+            assert_eq!(loaded, dataset);
+        }
+    }
+}
@@ -1,7 +1,14 @@
-use crate::sketchbook::observations::{Dataset, Observation, ObservationManager, VarValue};
-use std::fs::File;
+use crate::sketchbook::ids::{ObservationId, VarId};
+use crate::sketchbook::observations::{
+    AliasTable, BinarizationConfig, Dataset, DatasetImportReport, MultiValuedVar, Observation,
+    ObservationManager, PerturbationType, VarMappingReport, VarValue,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr;
 
+/// Key of the comment line with perturbations of the dataset (shared with the export).
+pub(super) const PERTURBATIONS_KEY: &str = "perturbations";
+
 impl ObservationManager {
     /// Load a dataset from given CSV file. The header line specifies variables, following lines
     /// represent individual observations (id and values).
@@ -13,31 +20,256 @@ impl ObservationManager {
     ///    Observation1,0,1,0,1,0,1
     ///    Observation2,1,0,*,1,0,*
     ///
+    /// See [Self::import_dataset_str] for details on supported formats. If there are any
+    /// errors, the message lists all of them.
     pub fn load_dataset(name: &str, csv_path: &str) -> Result<Dataset, String> {
-        let csv_file = File::open(csv_path).map_err(|e| e.to_string())?;
-        let mut rdr = csv::Reader::from_reader(csv_file);
+        Self::import_dataset(name, csv_path, None)
+            .map(|(dataset, _)| dataset)
+            .map_err(|report| report.summary())
+    }
 
-        // parse variable names from the header
-        let header = rdr.headers().map_err(|e| e.to_string())?.clone();
-        let variables = header.into_iter().skip(1).collect::<Vec<&str>>().clone();
+    /// Import a dataset from given CSV/TSV file, collecting all discovered problems into
+    /// a structured report. See [Self::import_dataset_str] for details.
+    pub fn import_dataset(
+        name: &str,
+        path: &str,
+        model_vars: Option<&[String]>,
+    ) -> Result<(Dataset, DatasetImportReport), DatasetImportReport> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            let mut report = DatasetImportReport::new();
+            report.add_error(None, None, &format!("File could not be read. {e}"));
+            report
+        })?;
+        Self::import_dataset_str(name, &contents, model_vars)
+    }
+
+    /// Import a dataset from given CSV/TSV file, and map its variables to given model variables
+    /// via aliases and case-insensitive matching (see [Dataset::map_variables]).
+    ///
+    /// Renamed variables and variables that are not in the model (with suggestions of similar
+    /// model variables) are reported as warnings, and also returned in a separate mapping report.
+    pub fn import_dataset_mapped(
+        name: &str,
+        path: &str,
+        model_vars: &[String],
+        aliases: &AliasTable,
+    ) -> Result<(Dataset, DatasetImportReport, VarMappingReport), DatasetImportReport> {
+        let (mut dataset, mut report) = Self::import_dataset(name, path, None)?;
+        let columns = dataset_columns(&dataset);
+        let mapping = dataset.map_variables(model_vars, aliases);
+        mapping.add_to_report(&mut report, &columns);
+        Ok((dataset, report, mapping))
+    }
+
+    /// Import a dataset from a string with CSV/TSV contents. The header line specifies
+    /// variables, following lines represent individual observations (id and values).
+    ///
+    /// The delimiter is detected automatically from the header (comma, tab, or semicolon).
+    /// Values can be `1`/`0`/`*`, `true`/`false`, `T`/`F`, while `NA` or empty cells stand
+    /// for unspecified values (see [VarValue::from_str_lenient]).
+    ///
+    /// Multi-valued variables must be explicitly declared in the header, together with their
+    /// levels, either as `X[low|mid|high]` or as `X[3]` (levels `0, 1, 2`). Values in such
+    /// columns are given by level names or indices. Each such variable `X` is encoded by Boolean
+    /// variables `X_1, ..., X_{n-1}` (see [MultiValuedVar] for details on the encoding), and this
+    /// is reported as a warning. Values other than Boolean ones are rejected in all other columns.
+    ///
+    /// Perturbations of the dataset can be given on a comment line before the header, listing
+    /// perturbed variables with their perturbation (`KO` or `OE`), such as
+    /// `# perturbations: A:KO, B:OE`. Other comment lines (starting with `#`) before the header
+    /// are ignored.
+    ///
+    /// Instead of stopping at the first problem, all problems are collected into a report with
+    /// their coordinates (row and column). This includes invalid values and identifiers,
+    /// duplicate variables or observation IDs, and rows of incorrect length. If `model_vars` are
+    /// provided, dataset variables that are not in this list are reported as warnings.
+    ///
+    /// If there are no errors, the dataset is returned together with the report (that might
+    /// contain warnings). Otherwise, the report is returned as an error.
+    pub fn import_dataset_str(
+        name: &str,
+        contents: &str,
+        model_vars: Option<&[String]>,
+    ) -> Result<(Dataset, DatasetImportReport), DatasetImportReport> {
+        let mut report = DatasetImportReport::new();
+        let contents = contents.trim_start_matches('\u{feff}');
 
-        // parse all raws as observations
+        // leading comment lines can carry metadata (perturbations), rows are numbered from them
+        let (comment_lines, contents) = split_comment_lines(contents);
+        let row_offset = comment_lines.len();
+        let header_row = row_offset + 1;
+        let mut perturbations = BTreeMap::new();
+        for (i, line) in comment_lines.iter().enumerate() {
+            if let Err(e) = parse_perturbations_line(line, &mut perturbations) {
+                report.add_error(Some(i + 1), None, &e);
+            }
+        }
+        if contents.trim().is_empty() {
+            report.add_error(None, None, "The file is empty.");
+            return Err(report);
+        }
+
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(detect_delimiter(contents))
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(contents.as_bytes());
+
+        // parse variable names from the header (first column is for observation IDs)
+        let header = match rdr.headers() {
+            Ok(header) => header.clone(),
+            Err(e) => {
+                report.add_error(Some(header_row), None, &format!("Invalid header. {e}"));
+                return Err(report);
+            }
+        };
+        // each column is either a Boolean variable, or a declared multi-valued variable
+        let mut columns: Vec<&str> = Vec::new();
+        let mut column_vars: Vec<Option<MultiValuedVar>> = Vec::new();
+        let mut seen_columns: HashMap<&str, usize> = HashMap::new();
+        for (i, cell) in header.iter().skip(1).enumerate() {
+            let column = i + 2;
+            let (var, mv_var) = match parse_column_header(cell) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    report.add_error(Some(header_row), Some(column), &e);
+                    (cell, None)
+                }
+            };
+            if let Some(first_column) = seen_columns.get(var) {
+                let msg = format!("Variable `{var}` is already listed in column {first_column}.");
+                report.add_error(Some(header_row), Some(column), &msg);
+            }
+            seen_columns.entry(var).or_insert(column);
+            columns.push(var);
+            column_vars.push(mv_var);
+        }
+
+        // collect all rows (with their row numbers)
+        let mut records = Vec::new();
+        for (i, result) in rdr.records().enumerate() {
+            match result {
+                Ok(record) => {
+                    let row = record.position().map_or(i + 2, |p| p.line() as usize);
+                    records.push((row + row_offset, record));
+                }
+                Err(e) => {
+                    let row = e.position().map_or(i + 2, |p| p.line() as usize) + row_offset;
+                    report.add_error(Some(row), None, &format!("Invalid record. {e}"));
+                }
+            };
+        }
+
+        // expand multi-valued columns into their Boolean encoding variables
+        let mut multi_valued_vars = Vec::new();
+        let mut variables: Vec<String> = Vec::new();
+        for (i, (var, mv_var)) in columns.iter().zip(&column_vars).enumerate() {
+            match mv_var {
+                Some(mv_var) => {
+                    let encoding_vars = mv_var.encoding_vars();
+                    let msg = format!(
+                        "Variable `{var}` has {} levels, it is encoded by Boolean variables {}.",
+                        mv_var.num_levels(),
+                        encoding_vars.join(", ")
+                    );
+                    report.add_warning(Some(header_row), Some(i + 2), &msg);
+                    variables.extend(encoding_vars);
+                    multi_valued_vars.push(mv_var.clone());
+                }
+                None => variables.push(var.to_string()),
+            }
+        }
+        if !multi_valued_vars.is_empty() {
+            let mut seen_vars = HashSet::new();
+            for var in &variables {
+                if !seen_vars.insert(var) {
+                    let msg = format!("Encoding of multi-valued variables clashes with `{var}`.");
+                    report.add_error(Some(header_row), None, &msg);
+                }
+            }
+        }
+        if let Some(model_vars) = model_vars {
+            for (i, (var, mv_var)) in columns.iter().zip(&column_vars).enumerate() {
+                let encoded_vars = match mv_var {
+                    Some(mv_var) => mv_var.encoding_vars(),
+                    None => vec![var.to_string()],
+                };
+                for encoded_var in encoded_vars {
+                    if !model_vars.contains(&encoded_var) {
+                        let msg = format!("Variable `{encoded_var}` is not present in the model.");
+                        report.add_warning(Some(header_row), Some(i + 2), &msg);
+                    }
+                }
+            }
+        }
+
+        // parse all rows as observations
         let mut observations = Vec::new();
-        for result in rdr.records() {
-            let record = result.map_err(|e| e.to_string())?;
-            if record.is_empty() {
-                return Err("Cannot import empty observation.".to_string());
+        let mut seen_ids: HashMap<String, usize> = HashMap::new();
+        for (row, record) in records {
+            if record.len() != header.len() {
+                let msg = format!(
+                    "Row has {} columns, but the header has {}.",
+                    record.len(),
+                    header.len()
+                );
+                report.add_error(Some(row), None, &msg);
+                continue;
+            }
+
+            let id = record.get(0).unwrap();
+            let mut row_valid = true;
+            if let Err(e) = ObservationId::new(id) {
+                report.add_error(Some(row), Some(1), &format!("Invalid observation ID. {e}"));
+                row_valid = false;
+            } else if let Some(first_row) = seen_ids.get(id) {
+                let msg = format!("Observation ID `{id}` is already used in row {first_row}.");
+                report.add_error(Some(row), Some(1), &msg);
+                row_valid = false;
+            } else {
+                seen_ids.insert(id.to_string(), row);
+            }
+
+            let mut values = Vec::new();
+            for (j, value_str) in record.iter().enumerate().skip(1) {
+                let parsed = match &column_vars[j - 1] {
+                    Some(mv_var) => mv_var
+                        .parse_level(value_str)
+                        .and_then(|level| mv_var.encode_level(level)),
+                    None => VarValue::from_str_lenient(value_str).map(|v| vec![v]),
+                };
+                match parsed {
+                    Ok(parsed) => values.extend(parsed),
+                    Err(e) => {
+                        report.add_error(Some(row), Some(j + 1), &e);
+                        row_valid = false;
+                    }
+                }
+            }
+            if row_valid {
+                match Observation::new(values, id) {
+                    Ok(observation) => observations.push(observation),
+                    Err(e) => report.add_error(Some(row), None, &e),
+                }
+            }
+        }
+
+        if !report.is_ok() {
+            return Err(report);
+        }
+        let variables = variables.iter().map(|v| v.as_str()).collect();
+        let dataset = Dataset::new(name, observations, variables).and_then(|mut dataset| {
+            dataset.set_all_multi_valued_vars(multi_valued_vars)?;
+            dataset.set_all_perturbations(perturbations);
+            Ok(dataset)
+        });
+        match dataset {
+            Ok(dataset) => Ok((dataset, report)),
+            Err(e) => {
+                report.add_error(None, None, &e);
+                Err(report)
             }
-            let id: &str = record.get(0).unwrap();
-            let values: Vec<VarValue> = record
-                .iter()
-                .skip(1)
-                .map(VarValue::from_str)
-                .collect::<Result<Vec<VarValue>, String>>()?;
-            let observation = Observation::new(values, id)?;
-            observations.push(observation);
         }
-        Dataset::new(name, observations, variables)
     }
 
     /// Load a dataset from given CSV file, and add it to this `ObservationManager`. The header
@@ -49,4 +281,386 @@ impl ObservationManager {
         let dataset = Self::load_dataset(id, csv_path)?;
         self.add_dataset_by_str(id, dataset)
     }
+
+    /// Load a dataset from given CSV file with continuous (numeric) values, and binarize it.
+    /// The header line specifies variables, following lines represent individual observations
+    /// (id and numeric values). Empty cells, `NA`, and `NaN` are treated as missing values.
+    ///
+    /// Each variable is binarized using its own threshold computed by the method given in
+    /// `config` (values in the uncertainty band around the threshold, as well as missing
+    /// values, are mapped to `*`). The method and all thresholds are stored in the dataset's
+    /// annotation, so that the binarization can be reproduced.
+    ///
+    /// For example, the following might be a valid CSV file for a dataset with 2 observations:
+    ///    ID,YOX1,CLN3,YHP1
+    ///    Observation1,0.52,13.1,NA
+    ///    Observation2,4.7,0.03,2.5
+    ///
+    pub fn load_continuous_dataset(
+        name: &str,
+        csv_path: &str,
+        config: &BinarizationConfig,
+    ) -> Result<Dataset, String> {
+        let contents = std::fs::read_to_string(csv_path).map_err(|e| e.to_string())?;
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(detect_delimiter(&contents))
+            .trim(csv::Trim::All)
+            .from_reader(contents.as_bytes());
+
+        // parse variable names from the header
+        let header = rdr.headers().map_err(|e| e.to_string())?.clone();
+        let variables = header.into_iter().skip(1).collect::<Vec<&str>>().clone();
+
+        // parse all raws as observation IDs and numeric values
+        let mut obs_ids = Vec::new();
+        let mut rows: Vec<Vec<Option<f64>>> = Vec::new();
+        for (row_idx, result) in rdr.records().enumerate() {
+            let record = result.map_err(|e| {
+                let row = e.position().map_or(row_idx + 2, |p| p.line() as usize);
+                format!("Invalid record (row {row}). {e}")
+            })?;
+            if record.is_empty() {
+                return Err("Cannot import empty observation.".to_string());
+            }
+            // rows are numbered from 1, with the header being row 1 (the position of the record
+            // takes multi-line fields into account)
+            let row = record.position().map_or(row_idx + 2, |p| p.line() as usize);
+            obs_ids.push(record.get(0).unwrap().to_string());
+            let mut values = Vec::new();
+            for (col_idx, value) in record.iter().enumerate().skip(1) {
+                values.push(
+                    parse_numeric_value(value)
+                        .map_err(|e| format!("{e} (row {row}, column {})", col_idx + 1))?,
+                );
+            }
+            if values.len() != variables.len() {
+                return Err("Number of variables and length of observations differ.".to_string());
+            }
+            rows.push(values);
+        }
+
+        // binarize values of each variable (column) using its own threshold
+        let mut thresholds = Vec::new();
+        let mut binarized_columns = Vec::new();
+        for (var_idx, var_name) in variables.iter().enumerate() {
+            let column: Vec<Option<f64>> = rows.iter().map(|row| row[var_idx]).collect();
+            let threshold = config.compute_threshold(&column);
+            binarized_columns.push(config.binarize(&column, threshold));
+            thresholds.push((var_name.to_string(), threshold));
+        }
+
+        let mut observations = Vec::new();
+        for (obs_idx, obs_id) in obs_ids.iter().enumerate() {
+            let values: Vec<VarValue> = binarized_columns
+                .iter()
+                .map(|column| column[obs_idx].clone())
+                .collect();
+            observations.push(Observation::new(values, obs_id)?);
+        }
+        let annotation = config.describe(&thresholds);
+        Dataset::new_annotated(name, &annotation, observations, variables)
+    }
+
+    /// Load a dataset from given CSV file with continuous values, binarize it, and add it to
+    /// this `ObservationManager`.
+    ///
+    /// See [Self::load_continuous_dataset] for details.
+    pub fn load_and_add_continuous_dataset(
+        &mut self,
+        csv_path: &str,
+        id: &str,
+        config: &BinarizationConfig,
+    ) -> Result<(), String> {
+        // use same name as ID
+        let dataset = Self::load_continuous_dataset(id, csv_path, config)?;
+        self.add_dataset_by_str(id, dataset)
+    }
+}
+
+/// **(internal)** Detect the delimiter of a CSV-like file from its first non-empty line.
+/// We consider commas, tabs, and semicolons, and choose the most frequent one (commas in
+/// case of ties or if there are none).
+fn detect_delimiter(contents: &str) -> u8 {
+    let first_line = contents
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("");
+    let mut best = (b',', first_line.matches(',').count());
+    for delimiter in [b'\t', b';'] {
+        let count = first_line.matches(delimiter as char).count();
+        if count > best.1 {
+            best = (delimiter, count);
+        }
+    }
+    best.0
+}
+
+/// **(internal)** Map variables of an imported dataset to the columns of the file they were
+/// imported from (the first column is for observation IDs). Boolean variables encoding the same
+/// multi-valued variable share its column.
+fn dataset_columns(dataset: &Dataset) -> HashMap<String, usize> {
+    let mut columns = HashMap::new();
+    let mut last_owner: Option<String> = None;
+    let mut column = 1;
+    for var in dataset.variable_names() {
+        let owner = dataset
+            .multi_valued_vars()
+            .iter()
+            .find(|mv_var| mv_var.encoding_vars().contains(&var))
+            .map_or(var.clone(), |mv_var| mv_var.get_name().to_string());
+        if last_owner.as_ref() != Some(&owner) {
+            column += 1;
+            last_owner = Some(owner);
+        }
+        columns.insert(var, column);
+    }
+    columns
+}
+
+/// **(internal)** Parse a header cell of a column. It is either a name of a Boolean variable, or
+/// a declaration of a multi-valued variable with its (ordered) levels in brackets. The levels
+/// are either given by their names separated by `|` (e.g., `X[low|mid|high]`), or by their
+/// number (e.g., `X[3]` stands for levels `0, 1, 2`).
+fn parse_column_header(cell: &str) -> Result<(&str, Option<MultiValuedVar>), String> {
+    let Some((name, rest)) = cell.split_once('[') else {
+        VarId::new(cell).map_err(|e| format!("Invalid variable name. {e}"))?;
+        return Ok((cell, None));
+    };
+    let name = name.trim();
+    let levels = rest.strip_suffix(']').ok_or(format!(
+        "Invalid declaration of multi-valued variable `{name}`."
+    ))?;
+    let mv_var = match levels.trim().parse::<usize>() {
+        Ok(num_levels) => MultiValuedVar::with_num_levels(name, num_levels)?,
+        Err(_) => MultiValuedVar::new(name, levels.split('|').collect())?,
+    };
+    Ok((name, Some(mv_var)))
+}
+
+/// **(internal)** Split the comment lines (starting with `#`) at the beginning of the file from
+/// the rest of its contents.
+fn split_comment_lines(contents: &str) -> (Vec<&str>, &str) {
+    let mut comment_lines = Vec::new();
+    let mut rest = contents;
+    while rest.trim_start_matches([' ', '\t']).starts_with('#') {
+        let (line, remaining) = rest.split_once('\n').unwrap_or((rest, ""));
+        comment_lines.push(line.trim());
+        rest = remaining;
+    }
+    (comment_lines, rest)
+}
+
+/// **(internal)** Parse a comment line with perturbations (e.g., `# perturbations: A:KO, B:OE`)
+/// and add them to the given map. Comment lines of other kinds are ignored.
+fn parse_perturbations_line(
+    line: &str,
+    perturbations: &mut BTreeMap<VarId, PerturbationType>,
+) -> Result<(), String> {
+    let comment = line.trim_start_matches('#').trim();
+    let Some((key, items)) = comment.split_once(':') else {
+        return Ok(());
+    };
+    if !key.trim().eq_ignore_ascii_case(PERTURBATIONS_KEY) {
+        return Ok(());
+    }
+    for item in items.split(',').filter(|item| !item.trim().is_empty()) {
+        let (var, perturbation) = item
+            .split_once(':')
+            .ok_or(format!("Invalid perturbation `{}`.", item.trim()))?;
+        let var_id = VarId::new(var.trim()).map_err(|e| format!("Invalid perturbation. {e}"))?;
+        let perturbation = PerturbationType::from_str(perturbation.trim())?;
+        if perturbations.insert(var_id, perturbation).is_some() {
+            return Err(format!("Variable `{}` is perturbed twice.", var.trim()));
+        }
+    }
+    Ok(())
+}
+
+/// **(internal)** Parse a numeric value of continuous data. Empty strings, `NA`, and `NaN`
+/// represent missing values.
+fn parse_numeric_value(value: &str) -> Result<Option<f64>, String> {
+    let value = value.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("na") || value.eq_ignore_ascii_case("nan") {
+        return Ok(None);
+    }
+    match value.parse::<f64>() {
+        Ok(number) if number.is_finite() => Ok(Some(number)),
+        _ => Err(format!("Invalid numeric value `{value}`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dataset_columns;
+    use crate::sketchbook::observations::{
+        AliasTable, BinarizationConfig, BinarizationMethod, ObservationManager, PerturbationType,
+    };
+
+    #[test]
+    /// Test importing datasets with various delimiters and value formats.
+    fn import_dataset_formats() {
+        let csv = "ID,A,B,C\no1,1,0,*\no2,true,F,NA\no3,T,false,\n";
+        let tsv = "ID\tA\tB\tC\no1\t1\t0\t*\no2\t1\t0\t*\no3\t1\t0\t*\n";
+        let ssv = "ID;A;B;C\no1;1;0;*\no2;1;0;*\no3;1;0;*\n";
+        for contents in [csv, tsv, ssv] {
+            let (dataset, report) =
+                ObservationManager::import_dataset_str("d", contents, None).unwrap();
+            assert!(report.is_empty());
+            assert_eq!(dataset.variable_names(), vec!["A", "B", "C"]);
+            for obs in dataset.observations() {
+                assert_eq!(obs.to_values_string(), "10*");
+            }
+        }
+    }
+
+    #[test]
+    /// Test that all problems are reported with their coordinates.
+    fn import_dataset_report() {
+        let contents = "ID,A,B,A\no1,1,x,0\no1,0,0,0\no2,1,0\n";
+        let report = ObservationManager::import_dataset_str("d", contents, None).unwrap_err();
+        let positions: Vec<_> = report.issues().iter().map(|i| (i.row, i.column)).collect();
+        let expected = vec![
+            (Some(1), Some(4)), // duplicate variable
+            (Some(2), Some(3)), // invalid value
+            (Some(3), Some(1)), // duplicate observation ID
+            (Some(4), None),    // wrong row length
+        ];
+        assert_eq!(positions, expected);
+        assert!(report
+            .summary()
+            .contains("> ISSUE at row 2, column 3: Invalid value `x`."));
+
+        // variables not in the model are only reported as warnings
+        let contents = "ID,A,B\no1,1,0\n";
+        let model_vars = vec!["A".to_string()];
+        let (_, report) =
+            ObservationManager::import_dataset_str("d", contents, Some(&model_vars)).unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.issues().len(), 1);
+        assert_eq!(report.issues()[0].column, Some(3));
+    }
+
+    #[test]
+    /// Test importing perturbations given on a comment line before the header.
+    fn import_dataset_perturbations() {
+        let contents = "# exported dataset\n# perturbations: A:KO, C:oe\nID,A,B\no1,0,1\n";
+        let (dataset, report) =
+            ObservationManager::import_dataset_str("d", contents, None).unwrap();
+        assert!(report.is_empty());
+        assert_eq!(dataset.variable_names(), vec!["A", "B"]);
+        let perturbations: Vec<_> = dataset
+            .perturbations()
+            .iter()
+            .map(|(var, p)| (var.as_str(), *p))
+            .collect();
+        let expected = vec![
+            ("A", PerturbationType::KnockOut),
+            ("C", PerturbationType::OverExpression),
+        ];
+        assert_eq!(perturbations, expected);
+
+        // invalid perturbations are reported, and rows are numbered including comment lines
+        let contents = "# perturbations: A:XY\nID,A,B\no1,0,x\n";
+        let report = ObservationManager::import_dataset_str("d", contents, None).unwrap_err();
+        let positions: Vec<_> = report.issues().iter().map(|i| (i.row, i.column)).collect();
+        assert_eq!(positions, vec![(Some(1), None), (Some(3), Some(3))]);
+    }
+
+    #[test]
+    /// Test importing a dataset with multi-valued variables (encoded by Boolean variables).
+    fn import_dataset_multi_valued() {
+        // levels are only accepted for declared multi-valued variables
+        let contents = "ID,X,Y\no1,0,1\no2,2,0\no3,1,*\no4,NA,1\n";
+        let report = ObservationManager::import_dataset_str("d", contents, None).unwrap_err();
+        assert_eq!(report.issues()[0].row, Some(3));
+        assert_eq!(report.issues()[0].column, Some(2));
+
+        let contents = "ID,X[3],Y\no1,0,1\no2,2,0\no3,1,*\no4,NA,1\n";
+        let (dataset, report) =
+            ObservationManager::import_dataset_str("d", contents, None).unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.issues().len(), 1);
+        assert_eq!(dataset.variable_names(), vec!["X_1", "X_2", "Y"]);
+        let obs_strings: Vec<String> = dataset
+            .observations()
+            .iter()
+            .map(|o| o.to_values_string())
+            .collect();
+        assert_eq!(obs_strings, vec!["001", "110", "10*", "**1"]);
+        assert_eq!(dataset.multi_valued_vars().len(), 1);
+        assert_eq!(dataset.multi_valued_vars()[0].num_levels(), 3);
+
+        // levels can be given by their names
+        let contents = "ID,X[low|mid|high]\no1,high\no2,low\no3,1\n";
+        let (dataset, _) = ObservationManager::import_dataset_str("d", contents, None).unwrap();
+        assert_eq!(dataset.multi_valued_vars()[0].levels()[2], "high");
+        let obs_strings: Vec<String> = dataset
+            .observations()
+            .iter()
+            .map(|o| o.to_values_string())
+            .collect();
+        assert_eq!(obs_strings, vec!["11", "00", "10"]);
+
+        // invalid declarations are reported
+        let contents = "ID,X[1],Y[a|a]\no1,0,a\n";
+        let report = ObservationManager::import_dataset_str("d", contents, None).unwrap_err();
+        let positions: Vec<_> = report.issues().iter().map(|i| (i.row, i.column)).collect();
+        assert_eq!(positions[..2], [(Some(1), Some(2)), (Some(1), Some(3))]);
+
+        // encoding variables can not clash with other variables
+        let contents = "ID,X[3],X_1\no1,2,1\n";
+        assert!(ObservationManager::import_dataset_str("d", contents, None).is_err());
+    }
+
+    #[test]
+    /// Test that variable mapping issues are reported with their columns.
+    fn import_dataset_mapping_columns() {
+        let contents = "ID,X[3],a,bb\no1,2,1,0\n";
+        let (mut dataset, mut report) =
+            ObservationManager::import_dataset_str("d", contents, None).unwrap();
+        let columns = dataset_columns(&dataset);
+        assert_eq!(columns["X_1"], 2);
+        assert_eq!(columns["X_2"], 2);
+        assert_eq!(columns["a"], 3);
+        assert_eq!(columns["bb"], 4);
+
+        let model_vars = vec!["X_1".to_string(), "X_2".to_string(), "A".to_string()];
+        let mapping = dataset.map_variables(&model_vars, &AliasTable::new());
+        mapping.add_to_report(&mut report, &columns);
+        let positions: Vec<_> = report.issues().iter().map(|i| (i.row, i.column)).collect();
+        // multi-valued variable, renamed `a`, and unmatched `bb`
+        assert_eq!(
+            positions,
+            vec![(Some(1), Some(2)), (Some(1), Some(3)), (Some(1), Some(4))]
+        );
+    }
+
+    #[test]
+    /// Test loading and binarizing a dataset with continuous values.
+    fn load_continuous_dataset() {
+        let config = BinarizationConfig::new(BinarizationMethod::Median, 0.).unwrap();
+        let path = "../data/test_data/data_continuous.csv";
+        let dataset = ObservationManager::load_continuous_dataset("d", path, &config).unwrap();
+
+        assert_eq!(dataset.num_observations(), 4);
+        assert_eq!(dataset.variable_names(), vec!["A", "B", "C"]);
+        let obs_strings: Vec<String> = dataset
+            .observations()
+            .iter()
+            .map(|o| o.to_values_string())
+            .collect();
+        assert_eq!(obs_strings, vec!["01*", "00*", "110", "101"]);
+        assert!(dataset.get_annotation().contains("method: median"));
+        assert!(dataset.get_annotation().contains("A=2.5, B=5.5, C=1.5"));
+
+        // invalid value is reported with its position
+        let path = "../data/test_data/data_continuous_invalid.csv";
+        let err = ObservationManager::load_continuous_dataset("d", path, &config).unwrap_err();
+        assert!(err.contains("(row 3, column 3)"));
+
+        // rows are counted correctly even with multi-line fields
+        let path = "../data/test_data/data_continuous_invalid_multiline.csv";
+        let err = ObservationManager::load_continuous_dataset("d", path, &config).unwrap_err();
+        assert!(err.contains("(row 4, column 3)"));
+    }
 }
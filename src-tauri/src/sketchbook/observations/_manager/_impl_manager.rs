@@ -1,6 +1,8 @@
 use crate::sketchbook::ids::{DatasetId, ObservationId, VarId};
-use crate::sketchbook::observations::{Dataset, DatasetIterator, Observation, ObservationManager};
-use std::collections::{HashMap, HashSet};
+use crate::sketchbook::observations::{
+    Dataset, DatasetIterator, MultiValuedVar, Observation, ObservationManager, PerturbationType,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr;
 
 /// Creating instances of `ObservationManager`.
@@ -118,6 +120,21 @@ impl ObservationManager {
         self.set_dataset_annot(&dataset_id, annot)
     }
 
+    /// Set weight of a given observation in a given dataset (used for soft constraints).
+    /// Weight must be positive.
+    pub fn set_obs_weight_by_str(
+        &mut self,
+        dataset_id: &str,
+        obs_id: &str,
+        weight: u32,
+    ) -> Result<(), String> {
+        let dataset_id = DatasetId::new(dataset_id)?;
+        self.assert_valid_dataset(&dataset_id)?;
+        let dataset = self.datasets.get_mut(&dataset_id).unwrap();
+        let obs_id = dataset.get_obs_id_by_str(obs_id)?;
+        dataset.set_obs_weight(&obs_id, weight)
+    }
+
     /// Set the id of dataset with `original_id` to `new_id`.
     pub fn set_dataset_id(
         &mut self,
@@ -198,6 +215,62 @@ impl ObservationManager {
             .set_all_variables_by_str(new_variables_list)
     }
 
+    /// Set perturbation of a given variable in a dataset, or remove it (if `perturbation`
+    /// is `None`). The variable does not have to be captured by the dataset.
+    pub fn set_perturbation(
+        &mut self,
+        dataset_id: &DatasetId,
+        var_id: VarId,
+        perturbation: Option<PerturbationType>,
+    ) -> Result<(), String> {
+        self.assert_valid_dataset(dataset_id)?;
+        self.datasets
+            .get_mut(dataset_id)
+            .unwrap()
+            .set_perturbation(var_id, perturbation);
+        Ok(())
+    }
+
+    /// Set perturbation of a given variable in a dataset (given by string IDs), or remove
+    /// it (if `perturbation` is `None`).
+    pub fn set_perturbation_by_str(
+        &mut self,
+        dataset_id: &str,
+        var_id: &str,
+        perturbation: Option<PerturbationType>,
+    ) -> Result<(), String> {
+        let dataset_id = DatasetId::new(dataset_id)?;
+        let var_id = VarId::new(var_id)?;
+        self.set_perturbation(&dataset_id, var_id, perturbation)
+    }
+
+    /// Set all multi-valued variables of a dataset at once (replacing the existing ones).
+    pub fn set_all_multi_valued_vars(
+        &mut self,
+        dataset_id: &DatasetId,
+        vars: Vec<MultiValuedVar>,
+    ) -> Result<(), String> {
+        self.assert_valid_dataset(dataset_id)?;
+        self.datasets
+            .get_mut(dataset_id)
+            .unwrap()
+            .set_all_multi_valued_vars(vars)
+    }
+
+    /// Set all perturbations of a dataset at once (replacing the existing ones).
+    pub fn set_all_perturbations(
+        &mut self,
+        dataset_id: &DatasetId,
+        perturbations: BTreeMap<VarId, PerturbationType>,
+    ) -> Result<(), String> {
+        self.assert_valid_dataset(dataset_id)?;
+        self.datasets
+            .get_mut(dataset_id)
+            .unwrap()
+            .set_all_perturbations(perturbations);
+        Ok(())
+    }
+
     /// Remove variable and all the values corresponding to it from a dataset (decrementing
     /// dimension of the dataset in process).
     pub fn remove_var(&mut self, dataset_id: &DatasetId, var_id: &VarId) -> Result<(), String> {
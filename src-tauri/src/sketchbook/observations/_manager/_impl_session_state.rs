@@ -2,13 +2,14 @@ use crate::app::event::Event;
 use crate::app::state::{Consumed, SessionHelper, SessionState};
 use crate::app::DynError;
 use crate::sketchbook::data_structs::{
-    ChangeIdData, DatasetData, DatasetMetaData, ObservationData,
+    ChangeIdData, ContinuousLoadData, DatasetData, DatasetExportData, DatasetImportData,
+    DatasetLoadData, DatasetMergeData, DatasetMetaData, ObservationData,
 };
 use crate::sketchbook::event_utils::{
     make_refresh_event, make_reversible, mk_obs_event, mk_obs_state_change,
 };
-use crate::sketchbook::ids::{DatasetId, ObservationId};
-use crate::sketchbook::observations::{Dataset, ObservationManager};
+use crate::sketchbook::ids::{DatasetId, ObservationId, VarId};
+use crate::sketchbook::observations::{AliasTable, Dataset, ObservationManager, VarMappingReport};
 use crate::sketchbook::JsonSerde;
 
 /* Constants for event path segments in `ObservationManager` for datasets and observations. */
@@ -19,14 +20,32 @@ const ADD_DATASET_PATH: &str = "add";
 const ADD_DEFAULT_DATASET_PATH: &str = "add_default";
 // load dataset from a specific file
 const LOAD_DATASET_PATH: &str = "load";
+// load dataset with continuous values from a specific file (and binarize it)
+const LOAD_CONTINUOUS_DATASET_PATH: &str = "load_continuous";
+// export all datasets into a directory
+const EXPORT_ALL_DATASETS_PATH: &str = "export_all";
+// export particular dataset into a file
+const EXPORT_DATASET_PATH: &str = "export";
+// merge two datasets into a new one
+const MERGE_DATASETS_PATH: &str = "merge";
 // remove particular dataset
 const REMOVE_DATASET_PATH: &str = "remove";
 // set ID of a particular dataset
 const SET_DATASET_ID_PATH: &str = "set_id";
 // set whole content of a particular dataset
 const SET_DATASET_CONTENT_PATH: &str = "set_content";
-// set metadata of a particular dataset (name, variables) - does not set observations
+// set metadata of a particular dataset (name, variables, perturbations, multi-valued variables) - does not set observations
 const SET_DATASET_METADATA_PATH: &str = "set_metadata";
+// split given observations of a dataset into a new dataset
+const SPLIT_DATASET_PATH: &str = "split";
+// keep only a given subset of variables of a dataset
+const SELECT_VARIABLES_PATH: &str = "select_vars";
+// remove duplicate observations of a dataset
+const DEDUPLICATE_OBS_PATH: &str = "deduplicate";
+// reorder observations of a dataset
+const REORDER_OBS_PATH: &str = "reorder_obs";
+// transpose a dataset (swapping variables and observations)
+const TRANSPOSE_DATASET_PATH: &str = "transpose";
 // remove a particular variable from dataset
 const REMOVE_VARIABLE_PATH: &str = "remove_var";
 // add variable to a dataset
@@ -64,7 +83,19 @@ impl SessionState for ObservationManager {
             self.event_add_dataset(event)
         } else if Self::starts_with(LOAD_DATASET_PATH, at_path).is_some() {
             Self::assert_path_length(at_path, 1, component_name)?;
-            self.event_load_dataset(event)
+            self.event_load_dataset(event, None)
+        } else if Self::starts_with(LOAD_CONTINUOUS_DATASET_PATH, at_path).is_some() {
+            Self::assert_path_length(at_path, 1, component_name)?;
+            self.event_load_continuous_dataset(event)
+        } else if Self::starts_with(MERGE_DATASETS_PATH, at_path).is_some() {
+            Self::assert_path_length(at_path, 1, component_name)?;
+            self.event_merge_datasets(event)
+        } else if Self::starts_with(EXPORT_ALL_DATASETS_PATH, at_path).is_some() {
+            Self::assert_path_length(at_path, 1, component_name)?;
+            let payload = Self::clone_payload_str(event, component_name)?;
+            let export_data = DatasetExportData::from_json_str(&payload)?;
+            self.export_all_datasets(&export_data.path, &export_data.config)?;
+            Ok(Consumed::NoChange)
         } else {
             let dataset_id_str = at_path.first().unwrap();
             let dataset_id = self.get_dataset_id(dataset_id_str)?;
@@ -163,26 +194,186 @@ impl ObservationManager {
     }
 
     /// Perform event of loading (and adding) new `dataset` to this `ObservationManager`.
-    pub(super) fn event_load_dataset(&mut self, event: &Event) -> Result<Consumed, DynError> {
+    ///
+    /// If `model_vars` are provided, dataset variables that are not among them are reported (as
+    /// warnings) in the import report sent to the frontend. Variables are not renamed in any way
+    /// (see [Self::event_load_dataset_with_aliases] for that).
+    pub(crate) fn event_load_dataset(
+        &mut self,
+        event: &Event,
+        model_vars: Option<&[String]>,
+    ) -> Result<Consumed, DynError> {
         let component_name = "observations";
 
         // get the payload - a path to a csv file with dataset
         let file_path = Self::clone_payload_str(event, component_name)?;
+        self.event_import_dataset(event, &file_path, model_vars, None)
+    }
+
+    /// Perform event of loading (and adding) new `dataset` to this `ObservationManager`, mapping
+    /// its variables to given `model_vars` using an alias table loaded from a file.
+    pub(crate) fn event_load_dataset_with_aliases(
+        &mut self,
+        event: &Event,
+        model_vars: &[String],
+    ) -> Result<Consumed, DynError> {
+        let component_name = "observations";
+
+        // get the payload - paths to a csv file with dataset and to a file with aliases
+        let payload = Self::clone_payload_str(event, component_name)?;
+        let load_data = DatasetLoadData::from_json_str(&payload)?;
+        let aliases = AliasTable::load(&load_data.alias_path)?;
+        self.event_import_dataset(event, &load_data.path, Some(model_vars), Some(&aliases))
+    }
+
+    /// **(internal)** Load a dataset from a file, add it, and prepare the corresponding
+    /// state-change and reverse events. Dataset variables are checked against `model_vars` (if
+    /// provided), and only mapped to them if `aliases` are provided too.
+    fn event_import_dataset(
+        &mut self,
+        event: &Event,
+        file_path: &str,
+        model_vars: Option<&[String]>,
+        aliases: Option<&AliasTable>,
+    ) -> Result<Consumed, DynError> {
+        // generate new ID (and name at the same time), start indexing at 1
+        let dataset_id: DatasetId = self.generate_dataset_id("dataset", Some(1));
+        // load the dataset (reporting all problems) and add it
+        let (dataset, report, mapping) =
+            if let (Some(model_vars), Some(aliases)) = (model_vars, aliases) {
+                Self::import_dataset_mapped(dataset_id.as_str(), file_path, model_vars, aliases)
+            } else {
+                Self::import_dataset(dataset_id.as_str(), file_path, model_vars)
+                    .map(|(dataset, report)| (dataset, report, VarMappingReport::default()))
+            }
+            .map_err(|report| report.summary())?;
+        let import_data = DatasetImportData::new(&dataset_id, &dataset, &report, &mapping);
+        self.add_dataset(dataset_id.clone(), dataset)?;
+
+        // prepare the state-change event (which sends the loaded dataset and report to frontend)
+        let state_change = mk_obs_state_change(&["load"], &import_data);
+        // and also prepare the reverse, which is a classical `remove` event
+        let reverse_event = mk_obs_event(&[dataset_id.as_str(), "remove"], None);
+        Ok(make_reversible(state_change, event, reverse_event))
+    }
+
+    /// Perform event of loading new `dataset` with continuous values, binarizing it, and
+    /// adding it to this `ObservationManager`.
+    pub(super) fn event_load_continuous_dataset(
+        &mut self,
+        event: &Event,
+    ) -> Result<Consumed, DynError> {
+        let component_name = "observations";
+
+        // get the payload - a path to a csv file with dataset, and binarization settings
+        let payload = Self::clone_payload_str(event, component_name)?;
+        let load_data = ContinuousLoadData::from_json_str(&payload)?;
 
         // generate new ID (and name at the same time), start indexing at 1
         let dataset_id: DatasetId = self.generate_dataset_id("dataset", Some(1));
         // load the dataset and add it
-        let dataset = Self::load_dataset(dataset_id.as_str(), &file_path)?;
+        let dataset =
+            Self::load_continuous_dataset(dataset_id.as_str(), &load_data.path, &load_data.config)?;
+        let import_data = DatasetImportData::new(
+            &dataset_id,
+            &dataset,
+            &Default::default(),
+            &Default::default(),
+        );
+        self.add_dataset(dataset_id.clone(), dataset)?;
+
+        // the state-change is the same as when loading a binary dataset
+        let state_change = mk_obs_state_change(&["load"], &import_data);
+        // and also prepare the reverse, which is a classical `remove` event
+        let reverse_event = mk_obs_event(&[dataset_id.as_str(), "remove"], None);
+        Ok(make_reversible(state_change, event, reverse_event))
+    }
+
+    /// Perform event of merging two existing datasets into a new `dataset` (see [Dataset::merge]).
+    pub(super) fn event_merge_datasets(&mut self, event: &Event) -> Result<Consumed, DynError> {
+        let component_name = "observations";
+
+        // get the payload - IDs of the two datasets to merge
+        let payload = Self::clone_payload_str(event, component_name)?;
+        let merge_data = DatasetMergeData::from_json_str(&payload)?;
+        let first = self.get_dataset_by_str(&merge_data.first_dataset)?;
+        let second = self.get_dataset_by_str(&merge_data.second_dataset)?;
+
+        // generate new ID (and name at the same time), merge the datasets and add the result
+        let dataset_id = self.generate_dataset_id("dataset", Some(1));
+        let dataset = first.merge(second, dataset_id.as_str())?;
         let dataset_data = DatasetData::from_dataset(&dataset_id, &dataset);
-        self.add_dataset_by_str(&dataset_data.id, dataset)?;
+        self.add_dataset(dataset_id, dataset)?;
 
-        // prepare the state-change event (which sends the loaded dataset to frontend)
-        let state_change = mk_obs_state_change(&["load"], &dataset_data);
-        // and also prepare the reverse, which is a classical `remove` event
+        // prepare the state-change and reverse event (which is a remove event)
+        let state_change = mk_obs_state_change(&["add"], &dataset_data);
         let reverse_event = mk_obs_event(&[&dataset_data.id, "remove"], None);
         Ok(make_reversible(state_change, event, reverse_event))
     }
 
+    /// Perform event of splitting given observations of a dataset into a new `dataset`
+    /// (see [Dataset::split]).
+    ///
+    /// This event will be broken into sub-events (setting content of the original dataset, and
+    /// adding the new one).
+    pub(super) fn event_split_dataset(
+        &mut self,
+        event: &Event,
+        dataset_id: DatasetId,
+    ) -> Result<Consumed, DynError> {
+        let component_name = "observations";
+
+        // get the payload - list of IDs of observations to move to the new dataset
+        let payload = Self::clone_payload_str(event, component_name)?;
+        let obs_ids: Vec<String> = serde_json::from_str(&payload)?;
+        let obs_ids = obs_ids
+            .iter()
+            .map(|id| ObservationId::new(id))
+            .collect::<Result<Vec<ObservationId>, String>>()?;
+
+        let new_id = self.generate_dataset_id("dataset", Some(1));
+        let dataset = self.get_dataset(&dataset_id)?;
+        let (remaining, selected) = dataset.split(&obs_ids, new_id.as_str())?;
+
+        let mut event_list = Vec::new();
+        let remaining_data = DatasetData::from_dataset(&dataset_id, &remaining).to_json_str();
+        let at_path = [dataset_id.as_str(), SET_DATASET_CONTENT_PATH];
+        event_list.push(mk_obs_event(&at_path, Some(&remaining_data)));
+        let selected_data = DatasetData::from_dataset(&new_id, &selected).to_json_str();
+        event_list.push(mk_obs_event(&[ADD_DATASET_PATH], Some(&selected_data)));
+        event_list.reverse(); // has to be reversed
+        Ok(Consumed::Restart(event_list))
+    }
+
+    /// Perform event of replacing the whole content of a `dataset` with a new one (given
+    /// `new_dataset`), which is used for all the bulk transformations of a dataset.
+    ///
+    /// The state-change is always a `set_content` event, and the reverse event sets the original
+    /// content back.
+    fn event_replace_dataset_content(
+        &mut self,
+        event: &Event,
+        dataset_id: DatasetId,
+        new_dataset: Dataset,
+    ) -> Result<Consumed, DynError> {
+        let orig_dataset = self.get_dataset(&dataset_id)?;
+        if orig_dataset == &new_dataset {
+            return Ok(Consumed::NoChange);
+        }
+
+        // perform the event, prepare the state-change variant (move id from path to payload)
+        let orig_dataset_data = DatasetData::from_dataset(&dataset_id, orig_dataset);
+        let new_dataset_data = DatasetData::from_dataset(&dataset_id, &new_dataset);
+        self.swap_dataset_content(&dataset_id, new_dataset)?;
+        let state_change = mk_obs_state_change(&["set_content"], &new_dataset_data);
+
+        // prepare the reverse event (setting the original content back)
+        let reverse_at_path = [dataset_id.as_str(), "set_content"];
+        let payload = orig_dataset_data.to_json_str();
+        let reverse_event = mk_obs_event(&reverse_at_path, Some(&payload));
+        Ok(make_reversible(state_change, event, reverse_event))
+    }
+
     /// Perform event of modifying or removing existing `dataset` component of this
     /// `ObservationManager`.
     pub(super) fn event_modify_dataset(
@@ -215,6 +406,12 @@ impl ObservationManager {
                 let reverse_event = mk_obs_event(&["add"], Some(&payload));
                 Ok(make_reversible(state_change, event, reverse_event))
             }
+            Some(&EXPORT_DATASET_PATH) => {
+                let payload = Self::clone_payload_str(event, component_name)?;
+                let export_data = DatasetExportData::from_json_str(&payload)?;
+                self.export_dataset(&dataset_id, &export_data.path, &export_data.config)?;
+                Ok(Consumed::NoChange)
+            }
             Some(&SET_DATASET_ID_PATH) => {
                 // get the payload - string for "new_id"
                 let new_id = Self::clone_payload_str(event, component_name)?;
@@ -235,42 +432,72 @@ impl ObservationManager {
             Some(&SET_DATASET_CONTENT_PATH) => {
                 // get the payload - json string encoding a new dataset data
                 let payload = Self::clone_payload_str(event, component_name)?;
-                let new_dataset_data = DatasetData::from_json_str(&payload)?;
-                let new_dataset = new_dataset_data.to_dataset()?;
-                let orig_dataset = self.get_dataset(&dataset_id)?;
-                if orig_dataset == &new_dataset {
-                    return Ok(Consumed::NoChange);
-                }
-
-                // perform the event, prepare the state-change variant (move id from path to payload)
-                let orig_dataset_data = DatasetData::from_dataset(&dataset_id, orig_dataset);
-                self.swap_dataset_content(&dataset_id, new_dataset)?;
-                let state_change = mk_obs_state_change(&["set_content"], &new_dataset_data);
-
-                // prepare the reverse event (setting the original ID back)
-                let reverse_at_path = [dataset_id.as_str(), "set_content"];
-                let payload = orig_dataset_data.to_json_str();
-                let reverse_event = mk_obs_event(&reverse_at_path, Some(&payload));
-                Ok(make_reversible(state_change, event, reverse_event))
+                let new_dataset = DatasetData::from_json_str(&payload)?.to_dataset()?;
+                self.event_replace_dataset_content(event, dataset_id, new_dataset)
+            }
+            Some(&SPLIT_DATASET_PATH) => self.event_split_dataset(event, dataset_id),
+            Some(&SELECT_VARIABLES_PATH) => {
+                // get the payload - list of IDs of variables to keep
+                let payload = Self::clone_payload_str(event, component_name)?;
+                let var_ids: Vec<String> = serde_json::from_str(&payload)?;
+                let var_ids = var_ids
+                    .iter()
+                    .map(|id| VarId::new(id))
+                    .collect::<Result<Vec<VarId>, String>>()?;
+                let dataset = self.get_dataset(&dataset_id)?;
+                let new_dataset = dataset.select_variables(&var_ids)?;
+                self.event_replace_dataset_content(event, dataset_id, new_dataset)
+            }
+            Some(&DEDUPLICATE_OBS_PATH) => {
+                Self::assert_payload_empty(event, component_name)?;
+                let new_dataset = self.get_dataset(&dataset_id)?.deduplicated();
+                self.event_replace_dataset_content(event, dataset_id, new_dataset)
+            }
+            Some(&REORDER_OBS_PATH) => {
+                // get the payload - list of all observation IDs in the new order
+                let payload = Self::clone_payload_str(event, component_name)?;
+                let obs_ids: Vec<String> = serde_json::from_str(&payload)?;
+                let obs_ids = obs_ids
+                    .iter()
+                    .map(|id| ObservationId::new(id))
+                    .collect::<Result<Vec<ObservationId>, String>>()?;
+                let dataset = self.get_dataset(&dataset_id)?;
+                let new_dataset = dataset.reorder_observations(&obs_ids)?;
+                self.event_replace_dataset_content(event, dataset_id, new_dataset)
+            }
+            Some(&TRANSPOSE_DATASET_PATH) => {
+                Self::assert_payload_empty(event, component_name)?;
+                let new_dataset = self.get_dataset(&dataset_id)?.transposed()?;
+                self.event_replace_dataset_content(event, dataset_id, new_dataset)
             }
             Some(&SET_DATASET_METADATA_PATH) => {
                 // get the payload - json string encoding metadata with (potentially) new name/annotation/variables
                 let payload = Self::clone_payload_str(event, component_name)?;
                 let new_metadata = DatasetMetaData::from_json_str(&payload)?;
                 let orig_dataset = self.get_dataset(&dataset_id)?;
+                let new_perturbations = new_metadata.perturbations_typesafe()?;
                 if orig_dataset.get_name() == new_metadata.name
                     && orig_dataset.get_annotation() == new_metadata.annotation
                     && orig_dataset.variable_names() == new_metadata.variables
+                    && orig_dataset.perturbations() == &new_perturbations
+                    && orig_dataset.multi_valued_vars() == &new_metadata.multi_valued_vars
                 {
                     return Ok(Consumed::NoChange);
                 }
 
-                // perform the event, prepare the state-change variant (move id from path to payload)
+                // the updated dataset is prepared (and validated) on a copy, so that the original
+                // stays unchanged if any part of the metadata is invalid
                 let orig_metadata = DatasetMetaData::from_dataset(&dataset_id, orig_dataset);
-                self.set_dataset_name(&dataset_id, &new_metadata.name)?;
-                self.set_dataset_annot(&dataset_id, &new_metadata.annotation)?;
+                let mut new_dataset = orig_dataset.clone();
+                new_dataset.set_name(&new_metadata.name)?;
+                new_dataset.set_annotation(&new_metadata.annotation);
                 let variables = new_metadata.variables.iter().map(|v| v.as_str()).collect();
-                self.set_all_variables_by_str(dataset_id.as_str(), variables)?;
+                new_dataset.set_all_variables_by_str(variables)?;
+                new_dataset.set_all_perturbations(new_perturbations);
+                new_dataset.set_all_multi_valued_vars(new_metadata.multi_valued_vars.clone())?;
+
+                // perform the event, prepare the state-change variant (move id from path to payload)
+                self.swap_dataset_content(&dataset_id, new_dataset)?;
                 let state_change = mk_obs_state_change(&["set_metadata"], &new_metadata);
 
                 // prepare the reverse event (setting the original ID back)
@@ -3,6 +3,8 @@ use crate::sketchbook::observations::Dataset;
 use crate::sketchbook::Manager;
 use std::collections::HashMap;
 
+/// **(internal)** Functionality for exporting datasets to tabular files.
+mod _impl_export_dataset;
 /// **(internal)** Implementation of the safe identifier generating.
 mod _impl_id_generating;
 /// **(internal)** Functionality for loading datasets from file.
@@ -0,0 +1,223 @@
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::observations::VarValue;
+use crate::sketchbook::utils::assert_ids_unique;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A multi-valued (discrete) variable with ordered levels (e.g., `low`, `mid`, `high`), that is
+/// encoded by several Boolean variables of a dataset.
+///
+/// We use the standard threshold encoding. A variable `X` with `n` levels is encoded by `n - 1`
+/// Boolean variables `X_1, ..., X_{n-1}`, where `X_i` is true iff the level of `X` is at least
+/// `i`. For example, the three levels of a 3-level variable are encoded as `00`, `10`, and `11`.
+/// Valuations like `01` do not encode any level.
+///
+/// Variables are validated when deserialized (the same way as in [MultiValuedVar::new]).
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "UncheckedMultiValuedVar")]
+pub struct MultiValuedVar {
+    name: String,
+    levels: Vec<String>,
+}
+
+/// **(internal)** Raw form of [MultiValuedVar] used for deserialization, before the variable
+/// is validated. It also provides the JSON schema of [MultiValuedVar].
+#[derive(Deserialize, JsonSchema)]
+#[schemars(rename = "MultiValuedVar")]
+struct UncheckedMultiValuedVar {
+    name: String,
+    levels: Vec<String>,
+}
+
+impl TryFrom<UncheckedMultiValuedVar> for MultiValuedVar {
+    type Error = String;
+
+    fn try_from(var: UncheckedMultiValuedVar) -> Result<Self, Self::Error> {
+        let levels = var.levels.iter().map(|l| l.as_str()).collect();
+        MultiValuedVar::new(&var.name, levels)
+    }
+}
+
+/// Creating `MultiValuedVar` instances.
+impl MultiValuedVar {
+    /// Create new multi-valued variable with given (ordered) level names. There must be at least
+    /// two levels, and their names must be unique. The name of the variable must be a valid
+    /// identifier.
+    pub fn new(name: &str, levels: Vec<&str>) -> Result<MultiValuedVar, String> {
+        VarId::new(name)?;
+        if levels.len() < 2 {
+            return Err(format!("Variable `{name}` must have at least two levels."));
+        }
+        let levels: Vec<String> = levels.iter().map(|l| l.trim().to_string()).collect();
+        if levels.iter().any(|l| l.is_empty()) {
+            return Err(format!("Levels of variable `{name}` must not be empty."));
+        }
+        assert_ids_unique(&levels)?;
+        Ok(MultiValuedVar {
+            name: name.to_string(),
+            levels,
+        })
+    }
+
+    /// Create new multi-valued variable with `num_levels` levels named `0, 1, ...`.
+    pub fn with_num_levels(name: &str, num_levels: usize) -> Result<MultiValuedVar, String> {
+        let levels: Vec<String> = (0..num_levels).map(|l| l.to_string()).collect();
+        Self::new(name, levels.iter().map(|l| l.as_str()).collect())
+    }
+
+    /// Create a copy of this variable with a different name (and the same levels).
+    pub fn with_name(&self, name: &str) -> Result<MultiValuedVar, String> {
+        Self::new(name, self.levels.iter().map(|l| l.as_str()).collect())
+    }
+}
+
+/// Observing and encoding `MultiValuedVar` instances.
+impl MultiValuedVar {
+    /// Name of the multi-valued variable.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Ordered names of all levels.
+    pub fn levels(&self) -> &Vec<String> {
+        &self.levels
+    }
+
+    /// Number of levels.
+    pub fn num_levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Names of the Boolean variables encoding this variable, i.e., `X_1, ..., X_{n-1}`.
+    pub fn encoding_vars(&self) -> Vec<String> {
+        (1..self.num_levels())
+            .map(|i| format!("{}_{i}", self.name))
+            .collect()
+    }
+
+    /// Parse a level given either by its name or by its index. Unspecified values
+    /// (see [VarValue::from_str_lenient]) result in `None`.
+    pub fn parse_level(&self, value: &str) -> Result<Option<usize>, String> {
+        let value = value.trim();
+        if let Ok(VarValue::Any) = VarValue::from_str_lenient(value) {
+            return Ok(None);
+        }
+        if let Some(level) = self.levels.iter().position(|l| l == value) {
+            return Ok(Some(level));
+        }
+        match value.parse::<usize>() {
+            Ok(level) if level < self.num_levels() => Ok(Some(level)),
+            _ => Err(format!(
+                "Invalid level `{value}` of variable `{}`.",
+                self.name
+            )),
+        }
+    }
+
+    /// Encode given level (or unspecified level if `None`) into values of the Boolean
+    /// encoding variables.
+    pub fn encode_level(&self, level: Option<usize>) -> Result<Vec<VarValue>, String> {
+        match level {
+            None => Ok(vec![VarValue::Any; self.num_levels() - 1]),
+            Some(level) if level < self.num_levels() => Ok((1..self.num_levels())
+                .map(|i| VarValue::from(level >= i))
+                .collect()),
+            Some(level) => Err(format!(
+                "Variable `{}` does not have level {level}.",
+                self.name
+            )),
+        }
+    }
+
+    /// Decode the level from values of the Boolean encoding variables. Returns `None` if the
+    /// values do not determine a single level (some are unspecified), and error if they are
+    /// not consistent with the encoding (see [Self::is_consistent]).
+    pub fn decode_level(&self, values: &[VarValue]) -> Result<Option<usize>, String> {
+        if values.len() != self.num_levels() - 1 {
+            return Err(format!(
+                "Variable `{}` is encoded by {} values, not {}.",
+                self.name,
+                self.num_levels() - 1,
+                values.len()
+            ));
+        }
+        if !self.is_consistent(values) {
+            return Err(format!(
+                "Values `{}` are not a valid encoding of variable `{}`.",
+                values.iter().map(|v| v.as_str()).collect::<String>(),
+                self.name
+            ));
+        }
+        if values.iter().any(|v| v.is_any()) {
+            return Ok(None);
+        }
+        Ok(Some(
+            values.iter().filter(|v| **v == VarValue::True).count(),
+        ))
+    }
+
+    /// Check whether the (partially specified) values of the Boolean encoding variables are
+    /// consistent with the encoding, i.e., no variable `X_i` is false while `X_j` (for `j > i`)
+    /// is true.
+    pub fn is_consistent(&self, values: &[VarValue]) -> bool {
+        let first_false = values.iter().position(|v| *v == VarValue::False);
+        let last_true = values.iter().rposition(|v| *v == VarValue::True);
+        match (first_false, last_true) {
+            (Some(f), Some(t)) => t < f,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::{MultiValuedVar, VarValue};
+
+    #[test]
+    /// Test encoding and decoding levels of a multi-valued variable.
+    fn test_level_encoding() {
+        let var = MultiValuedVar::new("X", vec!["low", "mid", "high"]).unwrap();
+        assert_eq!(var.encoding_vars(), vec!["X_1", "X_2"]);
+
+        let (t, f, a) = (VarValue::True, VarValue::False, VarValue::Any);
+        assert_eq!(var.parse_level("mid").unwrap(), Some(1));
+        assert_eq!(var.parse_level("2").unwrap(), Some(2));
+        assert_eq!(var.parse_level("NA").unwrap(), None);
+        assert!(var.parse_level("3").is_err());
+
+        assert_eq!(
+            var.encode_level(Some(0)).unwrap(),
+            vec![f.clone(), f.clone()]
+        );
+        assert_eq!(
+            var.encode_level(Some(1)).unwrap(),
+            vec![t.clone(), f.clone()]
+        );
+        assert_eq!(
+            var.encode_level(Some(2)).unwrap(),
+            vec![t.clone(), t.clone()]
+        );
+        assert_eq!(var.encode_level(None).unwrap(), vec![a.clone(), a.clone()]);
+
+        assert_eq!(var.decode_level(&[t.clone(), f.clone()]).unwrap(), Some(1));
+        assert_eq!(var.decode_level(&[t.clone(), a.clone()]).unwrap(), None);
+        assert!(var.decode_level(&[f.clone(), t.clone()]).is_err());
+        assert!(var.decode_level(&[a.clone(), t.clone()]).is_ok());
+    }
+
+    #[test]
+    /// Test that invalid multi-valued variables can not be created.
+    fn test_invalid_multi_valued_var() {
+        assert!(MultiValuedVar::new("X", vec!["low"]).is_err());
+        assert!(MultiValuedVar::new("X", vec!["low", "low"]).is_err());
+        assert!(MultiValuedVar::new("X Y", vec!["low", "high"]).is_err());
+        assert!(MultiValuedVar::with_num_levels("X", 4).is_ok());
+
+        // deserialized variables are validated too
+        let json_str = r#"{"name":"X","levels":["low","low"]}"#;
+        assert!(serde_json::from_str::<MultiValuedVar>(json_str).is_err());
+        let json_str = r#"{"name":"X","levels":["low","high"]}"#;
+        let var: MultiValuedVar = serde_json::from_str(json_str).unwrap();
+        assert_eq!(var, MultiValuedVar::new("X", vec!["low", "high"]).unwrap());
+    }
+}
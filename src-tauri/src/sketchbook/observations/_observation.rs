@@ -1,15 +1,22 @@
+use crate::sketchbook::observations::_packed_values::PackedValues;
 use crate::sketchbook::observations::_var_value::VarValue;
 use crate::sketchbook::{ids::ObservationId, utils::assert_name_valid};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 /// A single named observation, i.e., an ordered vector of binarized values.
+///
+/// Values are stored in a compact form (two bits per value, see [PackedValues]).
+///
+/// Each observation has a positive `weight` (1 by default), which is used when the observation
+/// is a part of a soft constraint (observations with larger weight are preferred to be satisfied).
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Observation {
     id: ObservationId,
     name: String,
     annotation: String,
-    values: Vec<VarValue>,
+    values: PackedValues,
+    weight: u32,
 }
 
 /// Creating observations.
@@ -36,7 +43,8 @@ impl Observation {
             id: ObservationId::new(id)?,
             name: name.to_string(),
             annotation: annot.to_string(),
-            values,
+            values: PackedValues::from(values),
+            weight: 1,
         })
     }
 
@@ -101,12 +109,21 @@ impl Observation {
         self.annotation = annotation.to_string();
     }
 
+    /// Set weight of the observation (used for soft constraints). Weight must be positive.
+    pub fn set_weight(&mut self, weight: u32) -> Result<(), String> {
+        if weight == 0 {
+            return Err("Weight of an observation must be positive.".to_string());
+        }
+        self.weight = weight;
+        Ok(())
+    }
+
     /// Set the value at given idx.
     pub fn set_value(&mut self, index: usize, value: VarValue) -> Result<(), String> {
         if index >= self.num_values() {
             return Err("Index is larger than number of values.".to_string());
         }
-        self.values[index] = value;
+        self.values.set(index, &value);
         Ok(())
     }
 
@@ -122,7 +139,7 @@ impl Observation {
         if values.len() != self.num_values() {
             return Err("Vectors of old and new values differ in length.".to_string());
         }
-        self.values = values;
+        self.values = PackedValues::from(values);
         Ok(())
     }
 
@@ -180,11 +197,21 @@ impl Observation {
         &self.annotation
     }
 
-    /// Get reference to observation's vector of values.
-    pub fn get_values(&self) -> &Vec<VarValue> {
+    /// Get observation's weight (used for soft constraints).
+    pub fn get_weight(&self) -> u32 {
+        self.weight
+    }
+
+    /// Get reference to observation's values (in their compact representation).
+    pub fn get_values(&self) -> &PackedValues {
         &self.values
     }
 
+    /// Iterate over observation's values.
+    pub fn iter_values(&self) -> impl Iterator<Item = VarValue> + '_ {
+        self.values.iter()
+    }
+
     /// Get reference to observation's id.
     pub fn get_id(&self) -> &ObservationId {
         &self.id
@@ -197,33 +224,30 @@ impl Observation {
 
     /// Number of unspecified values in this observation.
     pub fn num_unspecified_values(&self) -> usize {
-        self.values.iter().filter(|&v| *v == VarValue::Any).count()
+        self.values.count(&VarValue::Any)
     }
 
     /// Number of specified values in this observation.
     pub fn num_specified_values(&self) -> usize {
-        self.values.iter().filter(|&v| *v != VarValue::Any).count()
+        self.num_values() - self.num_unspecified_values()
     }
 
     /// Number of ones (`true` values) in this observation.
     pub fn num_ones(&self) -> usize {
-        self.values.iter().filter(|&v| *v == VarValue::True).count()
+        self.values.count(&VarValue::True)
     }
 
     /// Number of zeros (`false` values) in this observation.
     pub fn num_zeros(&self) -> usize {
-        self.values
-            .iter()
-            .filter(|&v| *v == VarValue::False)
-            .count()
+        self.values.count(&VarValue::False)
     }
 
     /// Value at given index.
-    pub fn value_at_idx(&self, index: usize) -> Result<&VarValue, String> {
+    pub fn value_at_idx(&self, index: usize) -> Result<VarValue, String> {
         if index >= self.num_values() {
             return Err("Index is larger than number of values.".to_string());
         }
-        Ok(&self.values[index])
+        Ok(self.values.get(index))
     }
 
     /// Make a string with bit-encoding of values of this `Observation`.
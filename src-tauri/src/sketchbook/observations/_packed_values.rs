@@ -0,0 +1,201 @@
+use crate::sketchbook::observations::VarValue;
+use serde::{Deserialize, Serialize};
+
+/// Number of values packed into a single 64-bit word (two bits per value).
+const VALUES_PER_WORD: usize = 32;
+
+/// Compact representation of a vector of `VarValue` items, using two bits per value.
+///
+/// This is used to store values of observations, since datasets (e.g., derived from single-cell
+/// data) might contain thousands of observations over hundreds of variables. Values are encoded
+/// as `00` (false), `01` (true), and `10` (any). Unused bits of the last word are always zero,
+/// so that the derived equality works as expected.
+///
+/// The serialized form is the same as that of `Vec<VarValue>`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Vec<VarValue>", into = "Vec<VarValue>")]
+pub struct PackedValues {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl From<Vec<VarValue>> for PackedValues {
+    fn from(values: Vec<VarValue>) -> Self {
+        PackedValues::from_values(&values)
+    }
+}
+
+impl From<PackedValues> for Vec<VarValue> {
+    fn from(values: PackedValues) -> Self {
+        values.to_values()
+    }
+}
+
+/// Creating and editing `PackedValues` instances.
+impl PackedValues {
+    /// Pack given slice of values.
+    pub fn from_values(values: &[VarValue]) -> PackedValues {
+        let mut packed = PackedValues {
+            len: values.len(),
+            words: vec![0; values.len().div_ceil(VALUES_PER_WORD)],
+        };
+        for (i, value) in values.iter().enumerate() {
+            packed.write(i, value);
+        }
+        packed
+    }
+
+    /// Set the value at given index. The index must be valid.
+    pub fn set(&mut self, index: usize, value: &VarValue) {
+        assert!(index < self.len, "Index is larger than number of values.");
+        self.write(index, value);
+    }
+
+    /// Insert a value at given index, shifting all values after it. The index must not be
+    /// larger than the number of values.
+    pub fn insert(&mut self, index: usize, value: VarValue) {
+        assert!(index <= self.len, "Index is larger than number of values.");
+        if self.len.is_multiple_of(VALUES_PER_WORD) {
+            self.words.push(0);
+        }
+        let (first_word, shift) = (index / VALUES_PER_WORD, 2 * (index % VALUES_PER_WORD));
+        // shift whole words after the index by one value, carrying the top value of the
+        // previous word (we go from the end, so the previous word is not modified yet)
+        for word in (first_word + 1..self.words.len()).rev() {
+            self.words[word] = (self.words[word] << 2) | (self.words[word - 1] >> 62);
+        }
+        // shift only the values starting at the index in its word
+        let low_mask = (1u64 << shift) - 1;
+        let old = self.words[first_word];
+        self.words[first_word] = (old & low_mask) | ((old & !low_mask) << 2);
+        self.len += 1;
+        self.write(index, &value);
+    }
+
+    /// Remove value at given index, shifting all values after it. The index must be valid.
+    pub fn remove(&mut self, index: usize) {
+        assert!(index < self.len, "Index is larger than number of values.");
+        let (first_word, shift) = (index / VALUES_PER_WORD, 2 * (index % VALUES_PER_WORD));
+        // shift only the values after the index in its word
+        let low_mask = (1u64 << shift) - 1;
+        let old = self.words[first_word];
+        self.words[first_word] = (old & low_mask) | ((old >> 2) & !low_mask);
+        // shift the following words by one value, carrying their lowest value to the previous
+        // word (we go from the start, so the next word is not modified yet)
+        for word in first_word..self.words.len() {
+            if word > first_word {
+                self.words[word] >>= 2;
+            }
+            if word + 1 < self.words.len() {
+                self.words[word] |= (self.words[word + 1] & 0b11) << 62;
+            }
+        }
+        self.len -= 1;
+        self.words.truncate(self.len.div_ceil(VALUES_PER_WORD));
+    }
+
+    /// **(internal)** Write the two-bit code of a value at given index (clearing the old one).
+    fn write(&mut self, index: usize, value: &VarValue) {
+        let code = match value {
+            VarValue::False => 0b00,
+            VarValue::True => 0b01,
+            VarValue::Any => 0b10,
+        };
+        let (word, shift) = (index / VALUES_PER_WORD, 2 * (index % VALUES_PER_WORD));
+        self.words[word] = (self.words[word] & !(0b11 << shift)) | (code << shift);
+    }
+}
+
+/// Observing `PackedValues` instances.
+impl PackedValues {
+    /// Number of values.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Value at given index. The index must be valid.
+    pub fn get(&self, index: usize) -> VarValue {
+        assert!(index < self.len, "Index is larger than number of values.");
+        let (word, shift) = (index / VALUES_PER_WORD, 2 * (index % VALUES_PER_WORD));
+        match (self.words[word] >> shift) & 0b11 {
+            0b00 => VarValue::False,
+            0b01 => VarValue::True,
+            _ => VarValue::Any,
+        }
+    }
+
+    /// Iterate over all values.
+    pub fn iter(&self) -> impl Iterator<Item = VarValue> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+
+    /// Unpack all values into a vector.
+    pub fn to_values(&self) -> Vec<VarValue> {
+        self.iter().collect()
+    }
+
+    /// Number of values equal to the given one.
+    pub fn count(&self, value: &VarValue) -> usize {
+        self.iter().filter(|v| v == value).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::_packed_values::PackedValues;
+    use crate::sketchbook::observations::VarValue;
+
+    #[test]
+    /// Test packing values (across several words) and editing them.
+    fn test_packed_values() {
+        let values: Vec<VarValue> = (0..100)
+            .map(|i| match i % 3 {
+                0 => VarValue::True,
+                1 => VarValue::False,
+                _ => VarValue::Any,
+            })
+            .collect();
+        let mut packed = PackedValues::from_values(&values);
+        assert_eq!(packed.len(), 100);
+        assert_eq!(packed.to_values(), values);
+        assert_eq!(packed.count(&VarValue::Any), 33);
+
+        packed.set(64, &VarValue::Any);
+        assert_eq!(packed.get(64), VarValue::Any);
+        packed.remove(64);
+        packed.insert(64, VarValue::False);
+        assert_eq!(packed, PackedValues::from_values(&values));
+
+        // inserting and removing values in place (at word boundaries too) shifts the rest
+        let mut expected = values.clone();
+        for (index, value) in [
+            (0, VarValue::Any),
+            (31, VarValue::True),
+            (100, VarValue::Any),
+        ] {
+            packed.insert(index, value.clone());
+            expected.insert(index, value);
+            assert_eq!(packed, PackedValues::from_values(&expected));
+        }
+        for index in [102, 32, 31, 0, 63] {
+            packed.remove(index);
+            expected.remove(index);
+            assert_eq!(packed, PackedValues::from_values(&expected));
+        }
+        // removing all the values of the last word drops it
+        let mut short_packed = PackedValues::from_values(&values[..33]);
+        short_packed.remove(0);
+        assert_eq!(short_packed, PackedValues::from_values(&values[1..33]));
+
+        // serialization is the same as for the unpacked vector
+        let json = serde_json::to_string(&packed).unwrap();
+        assert_eq!(json, serde_json::to_string(&expected).unwrap());
+        let deserialized: PackedValues = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, packed);
+    }
+}
@@ -0,0 +1,79 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Enum of possible perturbations of network variables in perturbation experiments.
+///
+/// A perturbed variable is fixed to a constant value (`KnockOut` to 0, `OverExpression` to 1),
+/// and all its regulations are cut.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum PerturbationType {
+    KnockOut,
+    OverExpression,
+}
+
+impl fmt::Display for PerturbationType {
+    /// Transform the perturbation to one of the `KO` or `OE`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PerturbationType::KnockOut => write!(f, "KO"),
+            PerturbationType::OverExpression => write!(f, "OE"),
+        }
+    }
+}
+
+impl FromStr for PerturbationType {
+    type Err = String;
+
+    /// Try to parse the perturbation. Valid strings are `KO` or `OE` (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "KO" => Ok(PerturbationType::KnockOut),
+            "OE" => Ok(PerturbationType::OverExpression),
+            _ => Err(format!("Invalid perturbation string `{s}`.")),
+        }
+    }
+}
+
+impl From<bool> for PerturbationType {
+    /// Perturbation fixing a variable to the given value.
+    fn from(value: bool) -> Self {
+        if value {
+            PerturbationType::OverExpression
+        } else {
+            PerturbationType::KnockOut
+        }
+    }
+}
+
+impl PerturbationType {
+    /// Constant value that the perturbed variable is fixed to.
+    pub fn fixed_value(&self) -> bool {
+        match self {
+            PerturbationType::KnockOut => false,
+            PerturbationType::OverExpression => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::PerturbationType;
+    use std::str::FromStr;
+
+    #[test]
+    /// Test conversions of perturbations to/from strings and values.
+    fn test_perturbation_conversions() {
+        let ko = PerturbationType::from_str("ko").unwrap();
+        assert_eq!(ko, PerturbationType::KnockOut);
+        assert_eq!(ko.to_string(), "KO");
+        assert!(!ko.fixed_value());
+
+        let oe = PerturbationType::from(true);
+        assert_eq!(oe, PerturbationType::from_str("OE").unwrap());
+        assert!(oe.fixed_value());
+
+        assert!(PerturbationType::from_str("X").is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use crate::sketchbook::observations::DatasetImportReport;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Table of aliases for model variables (e.g., alternative gene names), used to map dataset
+/// variables to model variables when importing datasets.
+///
+/// Aliases are matched case-insensitively (see [normalize_var_name]).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AliasTable {
+    /// Map from (normalized) aliases to the names of model variables.
+    aliases: HashMap<String, String>,
+}
+
+/// Creating and editing `AliasTable` instances.
+impl AliasTable {
+    /// Create new empty alias table.
+    pub fn new() -> AliasTable {
+        AliasTable::default()
+    }
+
+    /// Load an alias table from a file. See [Self::from_str] for the format.
+    pub fn load(path: &str) -> Result<AliasTable, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Alias file could not be read. {e}"))?;
+        Self::from_str(&contents)
+    }
+
+    /// Add an alias for a model variable. The same alias can not refer to different variables.
+    pub fn add_alias(&mut self, alias: &str, var: &str) -> Result<(), String> {
+        let key = normalize_var_name(alias);
+        match self.aliases.get(&key) {
+            Some(target) if target != var => Err(format!(
+                "Alias `{alias}` refers to both `{target}` and `{var}`."
+            )),
+            _ => {
+                self.aliases.insert(key, var.to_string());
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for AliasTable {
+    type Err = String;
+
+    /// Parse an alias table from a string. Each line starts with a model variable, followed by
+    /// all its aliases, separated by commas, tabs or semicolons. For example:
+    ///    CLN3,cln-3,YAL040C
+    ///    SWI5,swi5p
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    fn from_str(contents: &str) -> Result<AliasTable, String> {
+        let mut table = AliasTable::new();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut items = line
+                .split([',', '\t', ';'])
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty());
+            let var = items.next().unwrap(); // line is not empty
+            for alias in items {
+                table
+                    .add_alias(alias, var)
+                    .map_err(|e| format!("Invalid alias on line {}: {e}", i + 1))?;
+            }
+        }
+        Ok(table)
+    }
+}
+
+/// Observing `AliasTable` instances.
+impl AliasTable {
+    /// Model variable referred to by given alias (if there is any).
+    pub fn get_target(&self, alias: &str) -> Option<&str> {
+        self.aliases
+            .get(&normalize_var_name(alias))
+            .map(|v| v.as_str())
+    }
+
+    /// Number of aliases in the table.
+    pub fn num_aliases(&self) -> usize {
+        self.aliases.len()
+    }
+}
+
+/// The way a dataset variable was matched to a model variable.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum VarMatchKind {
+    Alias,
+    CaseInsensitive,
+}
+
+/// A dataset variable that was renamed to match a model variable.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VarRename {
+    pub original_id: String,
+    pub new_id: String,
+    pub kind: VarMatchKind,
+}
+
+/// A dataset variable that could not be matched to any model variable, together with
+/// suggestions of similar model variables (ordered by similarity).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UnmatchedVar {
+    pub id: String,
+    pub suggestions: Vec<String>,
+}
+
+/// Report summarizing how the variables of a dataset were mapped to model variables.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VarMappingReport {
+    pub renamed: Vec<VarRename>,
+    pub unmatched: Vec<UnmatchedVar>,
+}
+
+impl<'de> JsonSerde<'de> for VarMappingReport {}
+
+impl VarMappingReport {
+    /// Add all the renamed and unmatched variables as warnings to an import report. The
+    /// `columns` map (original) dataset variables to their columns in the imported file.
+    pub fn add_to_report(
+        &self,
+        report: &mut DatasetImportReport,
+        columns: &HashMap<String, usize>,
+    ) {
+        for rename in &self.renamed {
+            let reason = match rename.kind {
+                VarMatchKind::Alias => "alias",
+                VarMatchKind::CaseInsensitive => "case-insensitive match",
+            };
+            let msg = format!(
+                "Variable `{}` was mapped to model variable `{}` ({reason}).",
+                rename.original_id, rename.new_id
+            );
+            let column = columns.get(&rename.original_id).copied();
+            report.add_warning(Some(1), column, &msg);
+        }
+        for var in &self.unmatched {
+            let mut msg = format!("Variable `{}` is not present in the model.", var.id);
+            if !var.suggestions.is_empty() {
+                let suggestions: Vec<String> =
+                    var.suggestions.iter().map(|s| format!("`{s}`")).collect();
+                msg.push_str(&format!(" Did you mean {}?", suggestions.join(" or ")));
+            }
+            report.add_warning(Some(1), columns.get(&var.id).copied(), &msg);
+        }
+    }
+}
+
+/// Normalize a variable name (or alias) for case-insensitive matching. The same (full Unicode)
+/// lowercase normalization is used for aliases, capitalization matches, and suggestions, so
+/// that all of them agree on non-ASCII names.
+pub fn normalize_var_name(name: &str) -> String {
+    name.to_lowercase()
+}
+
+/// Maximal number of suggestions for each unmatched variable.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Model variables similar to the given (unmatched) variable, ordered by their (case-insensitive)
+/// edit distance. Only variables within distance of a third of the name length (but at least 1)
+/// are suggested.
+pub fn suggest_similar_vars(var: &str, model_vars: &[String]) -> Vec<String> {
+    let max_distance = (var.chars().count() / 3).max(1);
+    let var = normalize_var_name(var);
+    let mut candidates: Vec<(usize, &String)> = model_vars
+        .iter()
+        .map(|v| (edit_distance(&var, &normalize_var_name(v)), v))
+        .filter(|(d, _)| *d <= max_distance)
+        .collect();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, v)| v.clone())
+        .collect()
+}
+
+/// **(internal)** Levenshtein edit distance of two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev_diag = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev_diag + usize::from(ca != *cb);
+            prev_diag = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::_var_mapping::{
+        edit_distance, normalize_var_name, suggest_similar_vars,
+    };
+    use crate::sketchbook::observations::AliasTable;
+    use std::str::FromStr;
+
+    #[test]
+    /// Test parsing alias tables.
+    fn test_alias_table() {
+        let contents = "# comment\nCLN3,cln-3;YAL040C\n\nSWI5\tswi5p\n";
+        let table = AliasTable::from_str(contents).unwrap();
+        assert_eq!(table.num_aliases(), 3);
+        assert_eq!(table.get_target("yal040c"), Some("CLN3"));
+        assert_eq!(table.get_target("SWI5P"), Some("SWI5"));
+        assert_eq!(table.get_target("CLN3"), None);
+
+        // the same alias can not refer to two variables
+        assert!(AliasTable::from_str("A,x\nB,X").is_err());
+    }
+
+    #[test]
+    /// Test that non-ASCII names are normalized the same way in all lookups.
+    fn test_non_ascii_names() {
+        assert_eq!(normalize_var_name("ÄRG_Ω"), normalize_var_name("ärg_ω"));
+        let table = AliasTable::from_str("ARG,Ärger\nOMEGA,Ω1").unwrap();
+        assert_eq!(table.get_target("ärger"), Some("ARG"));
+        assert_eq!(table.get_target("ÄRGER"), Some("ARG"));
+        assert_eq!(table.get_target("ω1"), Some("OMEGA"));
+
+        // aliases differing only in non-ASCII capitalization clash
+        assert!(AliasTable::from_str("A,Ä\nB,ä").is_err());
+    }
+
+    #[test]
+    /// Test edit distance and suggestions of similar variables.
+    fn test_suggestions() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", "abc"), 0);
+
+        let model_vars = vec!["CLN3".to_string(), "CLB2".to_string(), "SWI5".to_string()];
+        assert_eq!(
+            suggest_similar_vars("cln2", &model_vars),
+            vec!["CLB2", "CLN3"]
+        );
+        assert!(suggest_similar_vars("MBF", &model_vars).is_empty());
+    }
+}
@@ -63,6 +63,18 @@ impl From<ExtendedBoolean> for VarValue {
 }
 
 impl VarValue {
+    /// Try to parse the value, accepting more formats than [VarValue::from_str]. Apart from
+    /// `1`, `0`, or `*`, we accept (case-insensitive) `true`/`false`, `T`/`F`, and `NA` or
+    /// an empty string (both standing for unspecified value). Surrounding whitespace is ignored.
+    pub fn from_str_lenient(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "1" | "true" | "t" => Ok(VarValue::True),
+            "0" | "false" | "f" => Ok(VarValue::False),
+            "*" | "na" | "" => Ok(VarValue::Any),
+            _ => Err(format!("Invalid value `{}`.", s.trim())),
+        }
+    }
+
     /// Return `true` value if is not specified.
     pub fn is_any(&self) -> bool {
         *self == VarValue::Any
@@ -1,17 +1,41 @@
 use crate::sketchbook::ids::DatasetId;
 
+/// **(internal)** Binarization of continuous data.
+mod _binarization;
 /// **(internal)** Definition and methods for `Dataset`.
 mod _dataset;
+/// **(internal)** Settings for exporting datasets into tabular files.
+mod _export_format;
+/// **(internal)** Structured report with problems found when importing datasets.
+mod _import_report;
 /// **(internal)** Definition and methods for `ObservationManager`.
 mod _manager;
+/// **(internal)** Definition and methods for `MultiValuedVar`.
+mod _multi_valued_var;
 /// **(internal)** Definition and methods for `Observation`.
 mod _observation;
+/// **(internal)** Compact (two bits per value) storage of observation values.
+mod _packed_values;
+/// **(internal)** Definition and methods for `PerturbationType`.
+mod _perturbation;
+/// **(internal)** Mapping of dataset variables to model variables (aliases, suggestions).
+mod _var_mapping;
 /// **(internal)** Definition and methods for `VarValue`.
 mod _var_value;
 
+pub use _binarization::{BinarizationConfig, BinarizationMethod};
 pub use _dataset::Dataset;
+pub use _export_format::{DatasetExportConfig, TableFormat, TableLayout};
+pub use _import_report::{DatasetImportReport, ImportIssue};
 pub use _manager::ObservationManager;
+pub use _multi_valued_var::MultiValuedVar;
 pub use _observation::Observation;
+pub use _packed_values::PackedValues;
+pub use _perturbation::PerturbationType;
+pub use _var_mapping::{
+    normalize_var_name, suggest_similar_vars, AliasTable, UnmatchedVar, VarMappingReport,
+    VarMatchKind, VarRename,
+};
 pub use _var_value::VarValue;
 
 /// An iterator over all <`DatasetId`, `Dataset`> pairs of a `ObservationManager`.
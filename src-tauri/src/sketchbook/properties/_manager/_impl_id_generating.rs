@@ -1,4 +1,4 @@
-use crate::sketchbook::ids::{DynPropertyId, StatPropertyId};
+use crate::sketchbook::ids::{DynPropertyId, FolMacroId, StatPropertyId};
 use crate::sketchbook::properties::PropertyManager;
 use crate::sketchbook::Manager;
 
@@ -52,4 +52,17 @@ impl PropertyManager {
             start_index,
         )
     }
+
+    /// Generate valid `FolMacroId` that's currently not used by any FOL macro in this
+    /// `PropertyManager`.
+    ///
+    /// The process is the same as for [Self::generate_stat_property_id].
+    pub fn generate_fol_macro_id(&self, ideal_id: &str, start_index: Option<usize>) -> FolMacroId {
+        self.generate_id(
+            ideal_id,
+            &(Self::is_valid_fol_macro_id),
+            self.num_fol_macros(),
+            start_index,
+        )
+    }
 }
@@ -1,11 +1,17 @@
+use crate::algorithms::fo_logic::macro_expansion::{
+    expand_macros, find_macro_cycle, MacroDefinitions,
+};
+use crate::algorithms::fo_logic::parser::parse_fol_formula;
+use crate::algorithms::fo_logic::utils::is_update_fn_symbol;
 use crate::sketchbook::ids::{
-    DatasetId, DynPropertyId, ObservationId, StatPropertyId, UninterpretedFnId, VarId,
+    DatasetId, DynPropertyId, FolMacroId, ObservationId, StatPropertyId, UninterpretedFnId, VarId,
 };
 use crate::sketchbook::model::{Essentiality, Monotonicity};
 use crate::sketchbook::properties::dynamic_props::are_same_dyn_variant;
 use crate::sketchbook::properties::static_props::{are_same_stat_variant, StatPropertyType};
 use crate::sketchbook::properties::{
-    DynPropIterator, DynProperty, PropertyManager, StatPropIterator, StatProperty,
+    DynPropIterator, DynProperty, FolMacro, FolMacroIterator, PropertyManager, StatPropIterator,
+    StatProperty,
 };
 use crate::sketchbook::utils::assert_ids_unique;
 use std::collections::HashMap;
@@ -18,6 +24,7 @@ impl PropertyManager {
         PropertyManager {
             dyn_properties: HashMap::new(),
             stat_properties: HashMap::new(),
+            fol_macros: HashMap::new(),
         }
     }
 
@@ -361,6 +368,106 @@ impl PropertyManager {
         Ok(())
     }
 
+    /// Add new FOL macro.
+    ///
+    /// The macro's ID is used to reference it in formulas, so it must not have a format of an
+    /// update function symbol (`f_VAR`). Adding the macro must not introduce cyclic definitions.
+    pub fn add_fol_macro(&mut self, id: FolMacroId, fol_macro: FolMacro) -> Result<(), String> {
+        self.assert_no_fol_macro(&id)?;
+        if is_update_fn_symbol(id.as_str()) {
+            return Err(format!(
+                "Macro ID `{id}` is reserved for update function symbols."
+            ));
+        }
+        self.fol_macros.insert(id.clone(), fol_macro);
+        if let Err(e) = self.assert_fol_macros_acyclic() {
+            self.fol_macros.remove(&id);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Add new FOL macro with id given by str.
+    pub fn add_fol_macro_by_str(&mut self, id: &str, fol_macro: FolMacro) -> Result<(), String> {
+        let id = FolMacroId::new(id)?;
+        self.add_fol_macro(id, fol_macro)
+    }
+
+    /// Set name for given FOL macro.
+    pub fn set_fol_macro_name(&mut self, id: &FolMacroId, new_name: &str) -> Result<(), String> {
+        self.assert_valid_fol_macro(id)?;
+        let fol_macro = self.fol_macros.get_mut(id).unwrap();
+        fol_macro.set_name(new_name)
+    }
+
+    /// Swap content of a FOL macro with given `id`. The ID must be valid identifier.
+    /// The new definition must not introduce cyclic definitions.
+    pub fn swap_fol_macro_content(
+        &mut self,
+        id: &FolMacroId,
+        new_content: FolMacro,
+    ) -> Result<(), String> {
+        self.assert_valid_fol_macro(id)?;
+        let orig_content = self.fol_macros.insert(id.clone(), new_content).unwrap();
+        if let Err(e) = self.assert_fol_macros_acyclic() {
+            self.fol_macros.insert(id.clone(), orig_content);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Swap content of a FOL macro with given `id`, given by str.
+    pub fn swap_fol_macro_content_by_str(
+        &mut self,
+        id: &str,
+        new_content: FolMacro,
+    ) -> Result<(), String> {
+        let macro_id = FolMacroId::new(id)?;
+        self.swap_fol_macro_content(&macro_id, new_content)
+    }
+
+    /// Change ID of a FOL macro.
+    ///
+    /// Note that formulas referencing the macro by its original ID are not modified.
+    pub fn set_fol_macro_id(
+        &mut self,
+        original_id: &FolMacroId,
+        new_id: FolMacroId,
+    ) -> Result<(), String> {
+        self.assert_valid_fol_macro(original_id)?;
+        self.assert_no_fol_macro(&new_id)?;
+        if is_update_fn_symbol(new_id.as_str()) {
+            return Err(format!(
+                "Macro ID `{new_id}` is reserved for update function symbols."
+            ));
+        }
+
+        if let Some(fol_macro) = self.fol_macros.remove(original_id) {
+            self.fol_macros.insert(new_id.clone(), fol_macro);
+        } else {
+            panic!("Error when modifying macro's id in the macro map.");
+        }
+        Ok(())
+    }
+
+    /// Change ID of a FOL macro, with IDs given as string slices.
+    pub fn set_fol_macro_id_by_str(
+        &mut self,
+        original_id: &str,
+        new_id: &str,
+    ) -> Result<(), String> {
+        let original_id = FolMacroId::new(original_id)?;
+        let new_id = FolMacroId::new(new_id)?;
+        self.set_fol_macro_id(&original_id, new_id)
+    }
+
+    /// Remove FOL macro.
+    pub fn remove_fol_macro(&mut self, id: &FolMacroId) -> Result<(), String> {
+        self.assert_valid_fol_macro(id)?;
+        self.fol_macros.remove(id).unwrap();
+        Ok(())
+    }
+
     /// Go through all static properties that are automatically generated from the regulation
     /// graph and make their IDs consistent with the variables they reference.
     ///
@@ -437,6 +544,36 @@ impl PropertyManager {
             Err(format!("Static property with id {id} does not exist."))
         }
     }
+
+    /// **(internal)** Utility method to ensure there is no FOL macro with given ID yet.
+    fn assert_no_fol_macro(&self, id: &FolMacroId) -> Result<(), String> {
+        if self.is_valid_fol_macro_id(id) {
+            Err(format!("Macro with id {id} already exists."))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// **(internal)** Utility method to ensure there is a FOL macro with given ID.
+    fn assert_valid_fol_macro(&self, id: &FolMacroId) -> Result<(), String> {
+        if self.is_valid_fol_macro_id(id) {
+            Ok(())
+        } else {
+            Err(format!("Macro with id {id} does not exist."))
+        }
+    }
+
+    /// **(internal)** Utility method to ensure that FOL macros do not (transitively)
+    /// reference themselves.
+    fn assert_fol_macros_acyclic(&self) -> Result<(), String> {
+        if let Some(cycle) = find_macro_cycle(&self.get_macro_definitions()) {
+            return Err(format!(
+                "Macro definitions are cyclic: {}.",
+                cycle.join(" -> ")
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Observing the `PropertyManager`.
@@ -471,6 +608,71 @@ impl PropertyManager {
         self.stat_properties.iter()
     }
 
+    /// The number of FOL macros in this `PropertyManager`.
+    pub fn num_fol_macros(&self) -> usize {
+        self.fol_macros.len()
+    }
+
+    /// Check if there is a FOL macro with given Id.
+    pub fn is_valid_fol_macro_id(&self, id: &FolMacroId) -> bool {
+        self.fol_macros.contains_key(id)
+    }
+
+    /// Return an iterator over all FOL macros of this model.
+    pub fn fol_macros(&self) -> FolMacroIterator {
+        self.fol_macros.iter()
+    }
+
+    /// Return a valid macro's `FolMacroId` corresponding to the given str `id`.
+    ///
+    /// Return `Err` if such macro does not exist (and the ID is invalid).
+    pub fn get_fol_macro_id(&self, id: &str) -> Result<FolMacroId, String> {
+        let macro_id = FolMacroId::from_str(id)?;
+        if self.is_valid_fol_macro_id(&macro_id) {
+            return Ok(macro_id);
+        }
+        Err(format!("Macro with ID {id} does not exist."))
+    }
+
+    /// Return a `FolMacro` corresponding to a given `FolMacroId`.
+    ///
+    /// Return `Err` if such macro does not exist (the ID is invalid in this context).
+    pub fn get_fol_macro(&self, id: &FolMacroId) -> Result<&FolMacro, String> {
+        let fol_macro = self
+            .fol_macros
+            .get(id)
+            .ok_or(format!("Macro with ID {id} does not exist."))?;
+        Ok(fol_macro)
+    }
+
+    /// Collect definitions of all FOL macros (formal parameters and body syntax trees, indexed
+    /// by macro IDs) in a form used by the expansion algorithms.
+    pub fn get_macro_definitions(&self) -> MacroDefinitions {
+        self.fol_macros
+            .iter()
+            .map(|(id, m)| {
+                let definition = (m.get_params().clone(), m.get_body().tree().clone());
+                (id.to_string(), definition)
+            })
+            .collect()
+    }
+
+    /// Expand all references to FOL macros in the given formula string, and return the
+    /// resulting formula string. If the formula does not use any macros, it is returned as is.
+    ///
+    /// See [expand_macros] for details on the expansion.
+    pub fn expand_fol_macros(&self, formula: &str) -> Result<String, String> {
+        if self.fol_macros.is_empty() {
+            return Ok(formula.to_string());
+        }
+        let tree = parse_fol_formula(formula)?;
+        let expanded_tree = expand_macros(&tree, &self.get_macro_definitions())?;
+        if expanded_tree == tree {
+            return Ok(formula.to_string());
+        }
+        Ok(expanded_tree.to_string())
+    }
+
     /// Return a valid dynamic property's `DynPropertyId` corresponding to the given str `id`.
     ///
     /// Return `Err` if such property does not exist (and the ID is invalid).
@@ -1,5 +1,5 @@
-use crate::sketchbook::ids::{DynPropertyId, StatPropertyId};
-use crate::sketchbook::properties::{DynProperty, StatProperty};
+use crate::sketchbook::ids::{DynPropertyId, FolMacroId, StatPropertyId};
+use crate::sketchbook::properties::{DynProperty, FolMacro, StatProperty};
 use crate::sketchbook::Manager;
 use std::collections::HashMap;
 
@@ -10,7 +10,8 @@ mod _impl_manager;
 /// **(internal)** Implementation of event-based API for the [crate::app::state::SessionState] trait.
 mod _impl_session_state;
 
-/// Class to manage all properties of the sketch.
+/// Class to manage all properties of the sketch, together with named FOL macros that
+/// can be referenced in static properties.
 ///
 /// `PropertyManager` can be managed through its classical Rust API, as well as
 /// through the external events (as it implements the `SessionState` trait).
@@ -18,6 +19,7 @@ mod _impl_session_state;
 pub struct PropertyManager {
     dyn_properties: HashMap<DynPropertyId, DynProperty>,
     stat_properties: HashMap<StatPropertyId, StatProperty>,
+    fol_macros: HashMap<FolMacroId, FolMacro>,
 }
 
 impl Manager for PropertyManager {}
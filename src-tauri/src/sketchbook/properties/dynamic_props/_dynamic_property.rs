@@ -54,6 +54,7 @@ impl DynProperty {
         let property = ExistsFixedPoint {
             dataset,
             observation,
+            soft: false,
         };
         let variant = DynPropertyType::ExistsFixedPoint(property);
         Self::new_raw(name, variant, annotation)
@@ -83,7 +84,10 @@ impl DynProperty {
     /// Create `DynProperty` instance describing existence of a trajectory corresponding to
     /// observations from a given observation (in the given order).
     pub fn mk_trajectory(name: &str, dataset: Option<DatasetId>, annotation: &str) -> DynProperty {
-        let property = ExistsTrajectory { dataset };
+        let property = ExistsTrajectory {
+            dataset,
+            soft: false,
+        };
         let variant = DynPropertyType::ExistsTrajectory(property);
         Self::new_raw(name, variant, annotation)
     }
@@ -117,11 +121,44 @@ impl DynProperty {
         let property = HasAttractor {
             dataset,
             observation,
+            soft: false,
         };
         let variant = DynPropertyType::HasAttractor(property);
         Self::new_raw(name, variant, annotation)
     }
 
+    /// Create `DynProperty` instance describing that all observations of a dataset are
+    /// reachable from its initial observation.
+    pub fn mk_states_reachable(
+        name: &str,
+        dataset: Option<DatasetId>,
+        initial_observation: Option<ObservationId>,
+        annotation: &str,
+    ) -> DynProperty {
+        let property = StatesReachable {
+            dataset,
+            initial_observation,
+        };
+        let variant = DynPropertyType::StatesReachable(property);
+        Self::new_raw(name, variant, annotation)
+    }
+
+    /// Create `DynProperty` instance describing that all states of a dataset lie in the
+    /// basins of attractors of given phenotypes (given by another dataset).
+    pub fn mk_states_in_basins(
+        name: &str,
+        dataset: Option<DatasetId>,
+        phenotypes: Option<DatasetId>,
+        annotation: &str,
+    ) -> DynProperty {
+        let property = StatesInBasins {
+            dataset,
+            phenotypes,
+        };
+        let variant = DynPropertyType::StatesInBasins(property);
+        Self::new_raw(name, variant, annotation)
+    }
+
     /// Create default `DynProperty` instance of specified variant.
     pub fn default(variant: SimpleDynPropertyType) -> DynProperty {
         match variant {
@@ -131,6 +168,8 @@ impl DynProperty {
             SimpleDynPropertyType::ExistsTrajectory => Self::default_trajectory(),
             SimpleDynPropertyType::AttractorCount => Self::default_attractor_count(),
             SimpleDynPropertyType::HasAttractor => Self::default_has_attractor(),
+            SimpleDynPropertyType::StatesReachable => Self::default_states_reachable(),
+            SimpleDynPropertyType::StatesInBasins => Self::default_states_in_basins(),
         }
     }
 
@@ -168,6 +207,18 @@ impl DynProperty {
     pub fn default_has_attractor() -> DynProperty {
         Self::mk_has_attractor("Attractor existence", None, None, "")
     }
+
+    /// Create default `DynProperty` instance for the reachability of dataset's states, with
+    /// empty `dataset` and `initial_observation` fields.
+    pub fn default_states_reachable() -> DynProperty {
+        Self::mk_states_reachable("States reachability", None, None, "")
+    }
+
+    /// Create default `DynProperty` instance for the dataset's states lying in phenotype
+    /// basins, with empty `dataset` and `phenotypes` fields.
+    pub fn default_states_in_basins() -> DynProperty {
+        Self::mk_states_in_basins("States in phenotype basins", None, None, "")
+    }
 }
 
 /// Editing dynamic properties.
@@ -192,6 +243,8 @@ impl DynProperty {
             DynPropertyType::ExistsTrapSpace(prop) => prop.dataset = new_dataset,
             DynPropertyType::ExistsTrajectory(prop) => prop.dataset = new_dataset,
             DynPropertyType::HasAttractor(prop) => prop.dataset = new_dataset,
+            DynPropertyType::StatesReachable(prop) => prop.dataset = new_dataset,
+            DynPropertyType::StatesInBasins(prop) => prop.dataset = new_dataset,
             // Other cases do not have a dataset field
             other_variant => {
                 return Err(format!(
@@ -209,6 +262,7 @@ impl DynProperty {
             DynPropertyType::ExistsFixedPoint(prop) => prop.observation = new_obs,
             DynPropertyType::ExistsTrapSpace(prop) => prop.observation = new_obs,
             DynPropertyType::HasAttractor(prop) => prop.observation = new_obs,
+            DynPropertyType::StatesReachable(prop) => prop.initial_observation = new_obs,
             // Other cases do not have a observation field
             other_variant => {
                 return Err(format!(
@@ -264,6 +318,23 @@ impl DynProperty {
         }
     }
 
+    /// Switch the property into a "soft" mode (or back), where applicable. If not applicable,
+    /// return `Err`. See [DynPropertyType::is_soft] for details.
+    pub fn set_soft(&mut self, soft: bool) -> Result<(), String> {
+        match &mut self.variant {
+            DynPropertyType::ExistsFixedPoint(prop) => prop.soft = soft,
+            DynPropertyType::HasAttractor(prop) => prop.soft = soft,
+            DynPropertyType::ExistsTrajectory(prop) => prop.soft = soft,
+            // Other cases can not be soft
+            other_variant => {
+                return Err(format!(
+                    "{other_variant:?} can not be used as soft constraint."
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Update property's sub-fields, if the property is of `ExistsTrapSpace` variant.
     /// If not applicable, return `Err`.
     pub fn set_trap_space_details(
@@ -282,6 +353,26 @@ impl DynProperty {
             ))
         }
     }
+
+    /// If the property is referencing the given dataset (in its `dataset` or `phenotypes`
+    /// sub-field), set that dataset to the new value. Other properties are left unchanged.
+    pub fn set_dataset_id_if_present(&mut self, old_id: &DatasetId, new_id: DatasetId) {
+        let datasets = match &mut self.variant {
+            DynPropertyType::ExistsFixedPoint(prop) => vec![&mut prop.dataset],
+            DynPropertyType::ExistsTrapSpace(prop) => vec![&mut prop.dataset],
+            DynPropertyType::ExistsTrajectory(prop) => vec![&mut prop.dataset],
+            DynPropertyType::HasAttractor(prop) => vec![&mut prop.dataset],
+            DynPropertyType::StatesReachable(prop) => vec![&mut prop.dataset],
+            DynPropertyType::StatesInBasins(prop) => vec![&mut prop.dataset, &mut prop.phenotypes],
+            // Other cases do not reference datasets
+            _ => vec![],
+        };
+        for dataset in datasets {
+            if dataset.as_ref() == Some(old_id) {
+                *dataset = Some(new_id.clone());
+            }
+        }
+    }
 }
 
 /// Observing dynamic properties.
@@ -333,6 +424,16 @@ impl DynProperty {
                     return Err(missing_field_msg.to_string());
                 }
             }
+            DynPropertyType::StatesReachable(p) => {
+                if p.dataset.is_none() || p.initial_observation.is_none() {
+                    return Err(missing_field_msg.to_string());
+                }
+            }
+            DynPropertyType::StatesInBasins(p) => {
+                if p.dataset.is_none() || p.phenotypes.is_none() {
+                    return Err(missing_field_msg.to_string());
+                }
+            }
         }
         Ok(())
     }
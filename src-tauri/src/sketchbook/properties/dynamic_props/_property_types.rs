@@ -16,10 +16,13 @@ pub struct GenericDynProp {
 
 /// Variant of `DynProperty` requiring existence of a fixed point corresponding to
 /// a particular `observation` of a particular `dataset`.
+///
+/// If `soft` is set, each observation is a soft constraint (see [DynPropertyType::is_soft]).
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct ExistsFixedPoint {
     pub dataset: Option<DatasetId>,
     pub observation: Option<ObservationId>,
+    pub soft: bool,
 }
 
 /// Variant of `DynProperty` requiring existence of a trap space corresponding to
@@ -35,9 +38,13 @@ pub struct ExistsTrapSpace {
 
 /// Variant of `DynProperty` requiring existence of a trajectory between observations
 /// of a particular `dataset` (in a given order).
+///
+/// If `soft` is set, the whole time series is a single soft constraint (with weight being
+/// the sum of weights of its observations, see [DynPropertyType::is_soft]).
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct ExistsTrajectory {
     pub dataset: Option<DatasetId>,
+    pub soft: bool,
 }
 
 /// Variant of `DynProperty` requiring that the number of attractors falls into the range
@@ -52,10 +59,37 @@ pub struct AttractorCount {
 /// is specified or not):
 /// 1) all observations of a particular dataset correspond to an attractor
 /// 2) a particular `observation` of a particular `dataset` correspond to an attractor
+///
+/// If `soft` is set, each observation is a soft constraint (see [DynPropertyType::is_soft]).
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct HasAttractor {
     pub dataset: Option<DatasetId>,
     pub observation: Option<ObservationId>,
+    pub soft: bool,
+}
+
+/// Variant of `DynProperty` requiring that all observations of a particular `dataset` are
+/// reachable from its `initial_observation`.
+///
+/// This is meant for single-cell style data, where the dataset is treated as a set of states
+/// (one per cell) instead of separate attractor requirements. Each observation must contain
+/// a state reachable from some state of the initial observation.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct StatesReachable {
+    pub dataset: Option<DatasetId>,
+    pub initial_observation: Option<ObservationId>,
+}
+
+/// Variant of `DynProperty` requiring that all states of all observations of a particular
+/// `dataset` lie in the (weak) basins of attractors of given phenotypes. That is, from every
+/// such state, there must be a path to an attractor that intersects one of the observations
+/// of the `phenotypes` dataset.
+///
+/// Like [StatesReachable], this is meant for single-cell style data.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct StatesInBasins {
+    pub dataset: Option<DatasetId>,
+    pub phenotypes: Option<DatasetId>,
 }
 
 // Two versions of the enum to cover all variants of the dynamic properties.
@@ -70,12 +104,31 @@ generate_property_enums!(
         ExistsTrapSpace(ExistsTrapSpace),
         ExistsTrajectory(ExistsTrajectory),
         AttractorCount(AttractorCount),
-        HasAttractor(HasAttractor)
+        HasAttractor(HasAttractor),
+        StatesReachable(StatesReachable),
+        StatesInBasins(StatesInBasins)
     }
 );
 
 impl<'de> JsonSerde<'de> for SimpleDynPropertyType {}
 
+impl DynPropertyType {
+    /// Check whether the property is in a "soft" mode.
+    ///
+    /// Soft properties do not have to be fully satisfied. Instead, the inference looks for
+    /// networks that satisfy the maximal weighted sum of the corresponding observations, and
+    /// reports which observations are violated. Only `ExistsFixedPoint`, `HasAttractor` and
+    /// `ExistsTrajectory` properties can be soft.
+    pub fn is_soft(&self) -> bool {
+        match self {
+            DynPropertyType::ExistsFixedPoint(p) => p.soft,
+            DynPropertyType::HasAttractor(p) => p.soft,
+            DynPropertyType::ExistsTrajectory(p) => p.soft,
+            _ => false,
+        }
+    }
+}
+
 /// Check if two DynPropertyType instances are of the same variant.
 pub fn are_same_dyn_variant(a: &DynPropertyType, b: &DynPropertyType) -> bool {
     discriminant(a) == discriminant(b)
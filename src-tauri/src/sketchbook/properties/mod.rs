@@ -1,4 +1,4 @@
-use crate::sketchbook::ids::{DynPropertyId, StatPropertyId};
+use crate::sketchbook::ids::{DynPropertyId, FolMacroId, StatPropertyId};
 
 /// **(internal)** Definition and methods for `PropertyManager`.
 mod _manager;
@@ -13,10 +13,13 @@ mod _macros;
 
 pub use _manager::PropertyManager;
 pub use dynamic_props::{DynProperty, HctlFormula};
-pub use static_props::{FirstOrderFormula, StatProperty};
+pub use static_props::{FirstOrderFormula, FolMacro, StatProperty};
 
 /// An iterator over all <`DynPropertyId`, `DynProperty`> pairs of a `PropertyManager`.
 pub type DynPropIterator<'a> = std::collections::hash_map::Iter<'a, DynPropertyId, DynProperty>;
 
 /// An iterator over all <`StatPropertyId`, `StatProperty`> pairs of a `PropertyManager`.
 pub type StatPropIterator<'a> = std::collections::hash_map::Iter<'a, StatPropertyId, StatProperty>;
+
+/// An iterator over all <`FolMacroId`, `FolMacro`> pairs of a `PropertyManager`.
+pub type FolMacroIterator<'a> = std::collections::hash_map::Iter<'a, FolMacroId, FolMacro>;
@@ -0,0 +1,186 @@
+use crate::algorithms::fo_logic::fol_tree::{FolTreeNode, NodeType};
+use crate::algorithms::fo_logic::operator_enums::{Atom, Quantifier};
+use crate::algorithms::fo_logic::parser::parse_fol_formula;
+use crate::algorithms::fo_logic::utils::{collect_unique_fol_vars, validate_and_rename_vars};
+use crate::sketchbook::ids::FolMacroId;
+use crate::sketchbook::properties::FirstOrderFormula;
+use crate::sketchbook::utils::{assert_ids_unique, assert_name_valid};
+use serde::{Deserialize, Serialize};
+
+/// A named FOL sub-formula (macro) with formal parameters, that can be referenced inside any
+/// FOL formula of static properties (or their contexts), in the same way as a function symbol.
+///
+/// For example, a macro `act` with parameters `[x, y]` and body `x => y` can be used in
+/// formula `\forall z: act(f_A(0, z), f_A(1, z))`. References are expanded before the
+/// properties are evaluated.
+///
+/// The formal parameters are the only free variables allowed in the body.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct FolMacro {
+    name: String,
+    annotation: String,
+    params: Vec<String>,
+    raw_body: String,
+    processed_body: FirstOrderFormula,
+}
+
+/// Creating FOL macros.
+impl FolMacro {
+    /// Create new `FolMacro` instance given its name, formal parameters, body formula, and
+    /// annotation. The parameters and body must be in a correct format (which is verified).
+    pub fn try_new(
+        name: &str,
+        params: Vec<String>,
+        raw_body: &str,
+        annotation: &str,
+    ) -> Result<FolMacro, String> {
+        assert_name_valid(name)?;
+        let processed_body = Self::check_definition(&params, raw_body)?;
+        Ok(FolMacro {
+            name: name.to_string(),
+            annotation: annotation.to_string(),
+            params,
+            raw_body: raw_body.to_string(),
+            processed_body,
+        })
+    }
+
+    /// **(internal)** Check that all parameters are valid and unique variable names, and that
+    /// the body is a valid FOL formula where the parameters are the only free variables.
+    fn check_definition(params: &[String], raw_body: &str) -> Result<FirstOrderFormula, String> {
+        let param_list = params.iter().map(|p| p.as_str()).collect();
+        assert_ids_unique(&param_list)
+            .map_err(|_| "Macro parameters must be unique.".to_string())?;
+        for param in params {
+            let is_var = matches!(
+                parse_fol_formula(param).map(|t| t.node_type),
+                Ok(NodeType::Terminal(Atom::Var(ref name))) if name == param
+            );
+            if !is_var {
+                return Err(format!(
+                    "Macro parameter `{param}` is not a valid variable name."
+                ));
+            }
+        }
+
+        let processed_body = FirstOrderFormula::try_from_str(raw_body)?;
+        let quantified_vars = collect_unique_fol_vars(processed_body.tree());
+        if let Some(param) = params.iter().find(|p| quantified_vars.contains(*p)) {
+            return Err(format!(
+                "Macro parameter `{param}` can't be quantified in the macro's body."
+            ));
+        }
+
+        // parameters are the only free variables, so the body must be closed once we quantify them
+        let mut closed_tree = processed_body.tree().clone();
+        for param in params.iter().rev() {
+            closed_tree = FolTreeNode::mk_quantifier(closed_tree, param, Quantifier::Exists);
+        }
+        // we have to provide some placeholder name (for renaming), but it does not matter here
+        validate_and_rename_vars(closed_tree, "PLACEHOLDER")
+            .map_err(|e| format!("Invalid macro body: {e}"))?;
+        Ok(processed_body)
+    }
+}
+
+/// Editing FOL macros.
+impl FolMacro {
+    /// Set macro's name.
+    pub fn set_name(&mut self, new_name: &str) -> Result<(), String> {
+        assert_name_valid(new_name)?;
+        self.name = new_name.to_string();
+        Ok(())
+    }
+
+    /// Set macro's annotation string.
+    pub fn set_annotation(&mut self, annotation: &str) {
+        self.annotation = annotation.to_string()
+    }
+
+    /// Change formal parameters and body of the macro. Both must be valid together.
+    pub fn set_definition(&mut self, params: Vec<String>, raw_body: &str) -> Result<(), String> {
+        // first check everything is valid, then update fields
+        self.processed_body = Self::check_definition(&params, raw_body)?;
+        self.params = params;
+        self.raw_body = raw_body.to_string();
+        Ok(())
+    }
+
+    /// If the macro's body references the given (other) FOL macro, set these references to
+    /// the new ID. Otherwise, the macro is left unchanged.
+    pub fn set_macro_id_if_present(
+        &mut self,
+        old_id: &FolMacroId,
+        new_id: &FolMacroId,
+    ) -> Result<(), String> {
+        let tree = self.processed_body.tree();
+        let new_tree = tree.substitute_fn_symbol(old_id.as_str(), new_id.as_str());
+        if new_tree == *tree {
+            return Ok(());
+        }
+        self.set_definition(self.params.clone(), &new_tree.to_string())
+    }
+}
+
+/// Observing FOL macros.
+impl FolMacro {
+    /// Get macro's name.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get annotation string.
+    pub fn get_annotation(&self) -> &str {
+        &self.annotation
+    }
+
+    /// Get list of formal parameters.
+    pub fn get_params(&self) -> &Vec<String> {
+        &self.params
+    }
+
+    /// Number of formal parameters.
+    pub fn get_arity(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Get the body formula as provided by the user.
+    pub fn get_raw_body(&self) -> &str {
+        &self.raw_body
+    }
+
+    /// Get the processed body formula.
+    pub fn get_body(&self) -> &FirstOrderFormula {
+        &self.processed_body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::properties::static_props::FolMacro;
+
+    /// Shorthand to prepare a list of parameter names.
+    fn mk_params(params: &[&str]) -> Vec<String> {
+        params.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    /// Test creating valid and invalid macro definitions.
+    fn macro_definitions() {
+        assert!(FolMacro::try_new("m", mk_params(&["x", "y"]), "x => y", "").is_ok());
+        assert!(FolMacro::try_new("m", mk_params(&[]), "\\exists x: f(x)", "").is_ok());
+        assert!(FolMacro::try_new("m", mk_params(&["x"]), "\\exists y: f(x, y)", "").is_ok());
+
+        // free variable that is not a parameter
+        assert!(FolMacro::try_new("m", mk_params(&["x"]), "x & z", "").is_err());
+        // duplicate parameters
+        assert!(FolMacro::try_new("m", mk_params(&["x", "x"]), "x", "").is_err());
+        // parameter quantified in body
+        assert!(FolMacro::try_new("m", mk_params(&["x"]), "\\exists x: f(x)", "").is_err());
+        // invalid parameter names
+        assert!(FolMacro::try_new("m", mk_params(&["x y"]), "x", "").is_err());
+        assert!(FolMacro::try_new("m", mk_params(&["1"]), "true", "").is_err());
+        // invalid body
+        assert!(FolMacro::try_new("m", mk_params(&["x"]), "x &", "").is_err());
+    }
+}
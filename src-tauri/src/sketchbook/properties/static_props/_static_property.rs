@@ -1,4 +1,5 @@
-use crate::sketchbook::ids::{StatPropertyId, UninterpretedFnId, VarId};
+use crate::algorithms::fo_logic::parser::parse_fol_formula;
+use crate::sketchbook::ids::{FolMacroId, StatPropertyId, UninterpretedFnId, VarId};
 use crate::sketchbook::model::{Essentiality, Monotonicity};
 use crate::sketchbook::properties::static_props::*;
 use crate::sketchbook::properties::FirstOrderFormula;
@@ -500,6 +501,56 @@ impl StatProperty {
         }
         Ok(())
     }
+
+    /// If the property is referencing the given uninterpreted fn (as its target), set that
+    /// function to the new value. Other properties are left unchanged.
+    pub fn set_fn_id_if_present(&mut self, old_id: &UninterpretedFnId, new_id: UninterpretedFnId) {
+        let target = match &mut self.variant {
+            StatPropertyType::FnInputEssential(prop) => &mut prop.target,
+            StatPropertyType::FnInputMonotonic(prop) => &mut prop.target,
+            StatPropertyType::FnInputEssentialContext(prop) => &mut prop.target,
+            StatPropertyType::FnInputMonotonicContext(prop) => &mut prop.target,
+            // Other cases do not reference uninterpreted fns
+            _ => return,
+        };
+        if target.as_ref() == Some(old_id) {
+            *target = Some(new_id);
+        }
+    }
+
+    /// If the property's formula (or its context formula) references the given FOL macro,
+    /// set these references to the new ID. Other properties are left unchanged.
+    ///
+    /// Formulas that can not be parsed are left unchanged too (they can't contain valid
+    /// references anyway).
+    pub fn set_fol_macro_id_if_present(
+        &mut self,
+        old_id: &FolMacroId,
+        new_id: &FolMacroId,
+    ) -> Result<(), String> {
+        let formula = match &self.variant {
+            StatPropertyType::GenericStatProp(prop) => Some(prop.raw_formula.as_str()),
+            StatPropertyType::FnInputEssentialContext(prop) => prop.context.as_deref(),
+            StatPropertyType::FnInputMonotonicContext(prop) => prop.context.as_deref(),
+            StatPropertyType::RegulationEssentialContext(prop) => prop.context.as_deref(),
+            StatPropertyType::RegulationMonotonicContext(prop) => prop.context.as_deref(),
+            // Other cases do not contain any formulas
+            _ => None,
+        };
+        let Some(Ok(tree)) = formula.map(parse_fol_formula) else {
+            return Ok(());
+        };
+
+        let new_tree = tree.substitute_fn_symbol(old_id.as_str(), new_id.as_str());
+        if new_tree == tree {
+            return Ok(());
+        }
+        if let StatPropertyType::GenericStatProp(_) = self.variant {
+            self.set_formula(&new_tree.to_string())
+        } else {
+            self.set_context(new_tree.to_string())
+        }
+    }
 }
 
 /// Observing static properties.
@@ -1,10 +1,13 @@
 /// **(internal)** Definition and methods for `FirstOrderFormula`.
 mod _first_order_formula;
+/// **(internal)** Definition and methods for `FolMacro`.
+mod _fol_macro;
 /// **(internal)** Variants of static properties.
 mod _property_types;
 /// **(internal)** Definition and methods for `StatProperty`.
 mod _static_property;
 
 pub use _first_order_formula::FirstOrderFormula;
+pub use _fol_macro::FolMacro;
 pub use _property_types::*;
 pub use _static_property::StatProperty;
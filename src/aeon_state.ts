@@ -11,7 +11,9 @@ import {
   type DynamicProperty,
   type StaticProperty,
   type DynamicPropertyType,
-  type StaticPropertyType
+  type StaticPropertyType,
+  type PerturbationType,
+  type IMultiValuedVar
 } from './html/util/data-interfaces'
 
 import {
@@ -21,6 +23,7 @@ import {
 
 /** An object representing all relevant parts of the whole sketch. */
 export interface SketchData {
+  format_version: number
   model: ModelData
   datasets: DatasetData[]
   dyn_properties: DynamicProperty[]
@@ -28,6 +31,21 @@ export interface SketchData {
   annotation: string
 }
 
+/** An object representing a single issue discovered by the sketch consistency check. */
+export interface ConsistencyIssue {
+  severity: 'Error' | 'Warning'
+  component: 'Model' | 'Datasets' | 'StaticProperties' | 'DynamicProperties'
+  entity_id: string | null
+  message: string
+}
+
+/** An object representing results of the sketch consistency check. */
+export interface ConsistencyReportData {
+  consistent: boolean
+  issues: ConsistencyIssue[]
+  summary: string
+}
+
 /** An object representing all relevant parts of a model. */
 export interface ModelData {
   variables: VariableData[]
@@ -93,6 +111,7 @@ export interface ObservationData {
   annotation: string
   dataset: string
   values: string // string with `0`/`1`/`*`, for instance: "0001**110"
+  weight: number // positive weight used when the observation is a part of a soft constraint
 }
 
 /** An object representing all information regarding a whole dataset. */
@@ -102,6 +121,30 @@ export interface DatasetData {
   annotation: string
   observations: ObservationData[]
   variables: string[]
+  perturbations: Record<string, PerturbationType> // perturbed variables (not necessarily in `variables`)
+  multi_valued_vars: IMultiValuedVar[] // multi-valued variables encoded by Boolean `variables`
+}
+
+/** An object representing a single issue discovered when importing a dataset. */
+export interface ImportIssue {
+  severity: 'Error' | 'Warning'
+  row: number | null
+  column: number | null
+  message: string
+}
+
+/** An object describing how dataset variables were mapped to model variables during import. */
+export interface VarMappingReport {
+  renamed: Array<{ original_id: string, new_id: string, kind: 'Alias' | 'CaseInsensitive' }>
+  unmatched: Array<{ id: string, suggestions: string[] }>
+}
+
+/** An object representing a newly imported dataset and issues found during the import. */
+export interface DatasetImportData {
+  dataset: DatasetData
+  issues: ImportIssue[]
+  summary: string
+  mapping: VarMappingReport
 }
 
 /**
@@ -113,11 +156,56 @@ export interface DatasetMetaData {
   name: string
   annotation: string
   variables: string[]
+  perturbations: Record<string, PerturbationType>
+  multi_valued_vars: IMultiValuedVar[]
 }
 
 /** An object representing information needed for loading a dataset. */
 export interface DatasetLoadData { path: string, id: string }
 
+/**
+ * Method used to compute binarization thresholds of continuous data (either a fixed
+ * threshold, or per-variable mean, median, or boundary of 2-means clustering).
+ */
+export type BinarizationMethod = { Threshold: number } | 'Mean' | 'Median' | 'KMeans'
+
+/**
+ * An object representing settings for binarization of continuous data. The `uncertainty`
+ * band is relative to the range of each variable's values.
+ */
+export interface BinarizationConfig { method: BinarizationMethod, uncertainty: number }
+
+/** An object representing settings for exporting datasets into CSV/TSV tables. */
+export interface DatasetExportConfig { format: 'Csv' | 'Tsv', layout: 'Wide' | 'Long' }
+
+/**
+ * Strategy to resolve conflicts when merging another sketch into the current one (rename
+ * the conflicting components of the other sketch, or keep the current/other version).
+ */
+export type MergeStrategy = 'Rename' | 'PreferLeft' | 'PreferRight'
+
+/** A single conflict found when merging sketches, together with its resolution. */
+export interface MergeConflict { component: string, id: string, resolution: string }
+
+/** An object representing the merged sketch and conflicts found during the merge. */
+export interface SketchMergeResultData {
+  sketch: SketchData
+  conflicts: MergeConflict[]
+  summary: string
+}
+
+/**
+ * Mode of inputs added when extracting a sub-sketch (regulators from outside of the
+ * selection are either left free, or made constant sources).
+ */
+export type InputMode = 'Free' | 'ConstantSource'
+
+/** A property dropped when extracting a sub-sketch, together with the reason. */
+export interface DroppedProperty { id: string, reason: string }
+
+/** An object representing results of extracting a sub-sketch (added inputs and dropped properties). */
+export interface ExtractionReport { inputs: string[], dropped_properties: DroppedProperty[] }
+
 /** An object representing information needed for variable id change. */
 export interface VariableIdUpdateData { original_id: string, new_id: string }
 
@@ -179,12 +267,31 @@ interface AeonState {
     exportSketch: (path: string) => void
     /** Export the sketch data to a file in the extended AEON format. */
     exportAeon: (path: string) => void
+    /** Export the sketch data to a SBML-qual file (sketch-specific details in annotations). */
+    exportSbml: (path: string) => void
+    /** Export the model to a BoolNet bnet file (unsupported features are reported in comments). */
+    exportBnet: (path: string) => void
+    /** Warnings about parts of the sketch that could not be exported to bnet. */
+    exportBnetWarnings: Observable<string[]>
     /** Import the sketch data from a special sketch JSON file. */
     importSketch: (path: string) => void
     /** Import the sketch data from a AEON file. */
     importAeon: (path: string) => void
     /** Import model from a SBML file. */
     importSbml: (path: string) => void
+    /** Import model from a BoolNet bnet file. */
+    importBnet: (path: string) => void
+    /** Merge sketch from a JSON or AEON file into the current one, resolving conflicts by
+     * given strategy (`Rename`, `PreferLeft` or `PreferRight`). */
+    mergeSketch: (path: string, strategy: MergeStrategy) => void
+    /** The whole merged sketch instance, together with the merge report. */
+    sketchMerged: Observable<SketchMergeResultData>
+    /** Export the sub-sketch induced by given variables to a JSON or AEON file, handling the
+     * outside regulators by given input mode. Properties referring to removed variables are
+     * dropped. The current sketch is not modified. */
+    extractSubSketch: (variables: string[], inputMode: InputMode, path: string) => void
+    /** Results of a sub-sketch extraction (added inputs and dropped properties). */
+    extractionResults: Observable<ExtractionReport>
     /** Set the sketch to a "default" mode, essentially emptying it and starting anew. */
     newSketch: () => void
     /** The whole replaced sketch instance (after importing or starting a new sketch). */
@@ -195,8 +302,12 @@ interface AeonState {
     annotationChanged: Observable<string>
     /** Run the explicit consistency check on the sketch. */
     checkConsistency: () => void
-    /** Results of an explicit consistency check (a summary message). */
-    consistencyResults: Observable<string>
+    /** Results of an explicit consistency check (a structured report with a summary message). */
+    consistencyResults: Observable<ConsistencyReportData>
+    /** Run the explicit lint pass over properties of a consistent sketch. */
+    lintProperties: () => void
+    /** Results of an explicit lint pass (a structured report with a summary message). */
+    lintResults: Observable<ConsistencyReportData>
 
     /** The state of the main model. */
     model: {
@@ -317,6 +428,9 @@ interface AeonState {
       regulationEssentialityChanged: Observable<RegulationData>
       /** Set essentiality of a regulation specified by its regulator and target. */
       setRegulationEssentiality: (regulatorId: string, targetId: string, newEssentiality: Essentiality) => void
+      /** Apply a text patch with bulk edits of regulations and update functions (lines like
+       * `A -> B`, `-A -| C` or `$C: A & !B`). The whole patch is applied (and undone) at once. */
+      applyPatch: (patch: string) => void
 
       /** Layout-related setter events: */
 
@@ -359,9 +473,17 @@ interface AeonState {
       addDefaultDataset: () => void
       /** DatasetData for a newly loaded dataset (from a csv file).
        *  This is intentionally different than `datasetCreated`, since loaded datasets might require some processing. */
-      datasetLoaded: Observable<DatasetData>
+      datasetLoaded: Observable<DatasetImportData>
       /** Load a new dataset from a CSV file. */
       loadDataset: (path: string) => void
+      /** Load a new dataset from a CSV file, mapping its variables to the model using an alias file. */
+      loadDatasetWithAliases: (path: string, aliasPath: string) => void
+      /** Load a new dataset with continuous values from a CSV file, and binarize it. */
+      loadContinuousDataset: (path: string, config: BinarizationConfig) => void
+      /** Export dataset with given ID into a CSV/TSV file. */
+      exportDataset: (id: string, path: string, config: DatasetExportConfig) => void
+      /** Export all datasets into CSV/TSV files in a given directory (one file per dataset). */
+      exportAllDatasets: (dirPath: string, config: DatasetExportConfig) => void
       /** DatasetData of a removed dataset. */
       datasetRemoved: Observable<DatasetData>
       /** Remove dataset with given ID. */
@@ -378,6 +500,18 @@ interface AeonState {
       datasetContentChanged: Observable<DatasetData>
       /** Set content (variables, observations - everything) of dataset with given ID. */
       setDatasetContent: (id: string, newContent: DatasetData) => void
+      /** Merge two datasets into a new one (variables aligned by ID, missing values set to `*`). */
+      mergeDatasets: (firstId: string, secondId: string) => void
+      /** Move given observations of a dataset into a new dataset. */
+      splitDataset: (id: string, observationIds: string[]) => void
+      /** Keep only given variables of a dataset (in the given order). */
+      selectDatasetVariables: (id: string, varIds: string[]) => void
+      /** Remove duplicate observations of a dataset (weights of duplicates are summed). */
+      deduplicateDataset: (id: string) => void
+      /** Reorder observations of a dataset (list must contain all observation IDs). */
+      reorderObservations: (id: string, observationIds: string[]) => void
+      /** Transpose a dataset (observations become variables and vice versa). */
+      transposeDataset: (id: string) => void
       /** DatasetMetaData (with updated `variables`) of a modified dataset. */
       datasetVariableChanged: Observable<DatasetMetaData>
       /** Set variable's ID within a specified dataset. */
@@ -579,8 +713,12 @@ export const aeonState: AeonState = {
   },
   sketch: {
     sketchRefreshed: new Observable<SketchData>(['sketch', 'get_whole_sketch']),
-    consistencyResults: new Observable<string>(['sketch', 'consistency_results']),
+    consistencyResults: new Observable<ConsistencyReportData>(['sketch', 'consistency_results']),
+    lintResults: new Observable<ConsistencyReportData>(['sketch', 'lint_results']),
+    exportBnetWarnings: new Observable<string[]>(['sketch', 'export_bnet_warnings']),
     sketchReplaced: new Observable<SketchData>(['sketch', 'set_all']),
+    sketchMerged: new Observable<SketchMergeResultData>(['sketch', 'merge_results']),
+    extractionResults: new Observable<ExtractionReport>(['sketch', 'extraction_results']),
     annotationChanged: new Observable<string>(['sketch', 'set_annotation']),
 
     refreshSketch (): void {
@@ -598,6 +736,18 @@ export const aeonState: AeonState = {
         payload: path
       })
     },
+    exportSbml (path: string): void {
+      aeonEvents.emitAction({
+        path: ['sketch', 'export_sbml'],
+        payload: path
+      })
+    },
+    exportBnet (path: string): void {
+      aeonEvents.emitAction({
+        path: ['sketch', 'export_bnet'],
+        payload: path
+      })
+    },
     importSketch (path: string): void {
       aeonEvents.emitAction({
         path: ['sketch', 'import_sketch'],
@@ -616,6 +766,24 @@ export const aeonState: AeonState = {
         payload: path
       })
     },
+    importBnet (path: string): void {
+      aeonEvents.emitAction({
+        path: ['sketch', 'import_bnet'],
+        payload: path
+      })
+    },
+    mergeSketch (path: string, strategy: MergeStrategy): void {
+      aeonEvents.emitAction({
+        path: ['sketch', 'merge_sketch'],
+        payload: JSON.stringify({ path, strategy })
+      })
+    },
+    extractSubSketch (variables: string[], inputMode: InputMode, path: string): void {
+      aeonEvents.emitAction({
+        path: ['sketch', 'extract_sub_sketch'],
+        payload: JSON.stringify({ variables, input_mode: inputMode, path })
+      })
+    },
     newSketch (): void {
       aeonEvents.emitAction({
         path: ['sketch', 'new_sketch'],
@@ -628,6 +796,12 @@ export const aeonState: AeonState = {
         payload: null
       })
     },
+    lintProperties (): void {
+      aeonEvents.emitAction({
+        path: ['sketch', 'lint_properties'],
+        payload: null
+      })
+    },
     setAnnotation (annotation: string): void {
       aeonEvents.emitAction({
         path: ['sketch', 'set_annotation'],
@@ -811,6 +985,12 @@ export const aeonState: AeonState = {
           payload: JSON.stringify(newEssentiality)
         })
       },
+      applyPatch (patch: string): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'model', 'apply_patch'],
+          payload: patch
+        })
+      },
       addLayout (layoutId: string, layoutName: string): void {
         aeonEvents.emitAction({
           path: ['sketch', 'model', 'layout', 'add'],
@@ -845,7 +1025,7 @@ export const aeonState: AeonState = {
       },
 
       datasetCreated: new Observable<DatasetData>(['sketch', 'observations', 'add']),
-      datasetLoaded: new Observable<DatasetData>(['sketch', 'observations', 'load']),
+      datasetLoaded: new Observable<DatasetImportData>(['sketch', 'observations', 'load']),
       datasetRemoved: new Observable<DatasetData>(['sketch', 'observations', 'remove']),
       datasetIdChanged: new Observable<DatasetIdUpdateData>(['sketch', 'observations', 'set_id']),
       datasetContentChanged: new Observable<DatasetData>(['sketch', 'observations', 'set_content']),
@@ -872,6 +1052,30 @@ export const aeonState: AeonState = {
           payload: path
         })
       },
+      loadDatasetWithAliases (path: string, aliasPath: string): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', 'load_with_aliases'],
+          payload: JSON.stringify({ path, alias_path: aliasPath })
+        })
+      },
+      loadContinuousDataset (path: string, config: BinarizationConfig): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', 'load_continuous'],
+          payload: JSON.stringify({ path, config })
+        })
+      },
+      exportDataset (id: string, path: string, config: DatasetExportConfig): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', id, 'export'],
+          payload: JSON.stringify({ path, config })
+        })
+      },
+      exportAllDatasets (dirPath: string, config: DatasetExportConfig): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', 'export_all'],
+          payload: JSON.stringify({ path: dirPath, config })
+        })
+      },
       removeDataset (id: string): void {
         aeonEvents.emitAction({
           path: ['sketch', 'observations', id, 'remove'],
@@ -890,6 +1094,42 @@ export const aeonState: AeonState = {
           payload: JSON.stringify(newContent)
         })
       },
+      mergeDatasets (firstId: string, secondId: string): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', 'merge'],
+          payload: JSON.stringify({ first_dataset: firstId, second_dataset: secondId })
+        })
+      },
+      splitDataset (id: string, observationIds: string[]): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', id, 'split'],
+          payload: JSON.stringify(observationIds)
+        })
+      },
+      selectDatasetVariables (id: string, varIds: string[]): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', id, 'select_vars'],
+          payload: JSON.stringify(varIds)
+        })
+      },
+      deduplicateDataset (id: string): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', id, 'deduplicate'],
+          payload: null
+        })
+      },
+      reorderObservations (id: string, observationIds: string[]): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', id, 'reorder_obs'],
+          payload: JSON.stringify(observationIds)
+        })
+      },
+      transposeDataset (id: string): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', id, 'transpose'],
+          payload: null
+        })
+      },
       setDatasetMetadata (id: string, metadata: DatasetMetaData): void {
         aeonEvents.emitAction({
           path: ['sketch', 'observations', id, 'set_metadata'],
@@ -2,7 +2,7 @@ import { css, html, LitElement, unsafeCSS, type TemplateResult, type PropertyVal
 import { customElement, property, state } from 'lit/decorators.js'
 import style_less from './analysis-tab.less?inline'
 import { ContentData } from '../../util/data-interfaces'
-import { aeonState } from '../../../aeon_state'
+import { aeonState, type ConsistencyReportData } from '../../../aeon_state'
 
 /** Component responsible for the analysis tab of the editor session. */
 @customElement('analysis-tab')
@@ -10,6 +10,7 @@ export class AnalysisTab extends LitElement {
   static styles = css`${unsafeCSS(style_less)}`
   @property() contentData: ContentData = ContentData.create()
   @state() consistency_results: string | null = null
+  @state() lint_results: string | null = null
 
   constructor () {
     super()
@@ -18,6 +19,10 @@ export class AnalysisTab extends LitElement {
     aeonState.sketch.consistencyResults.addEventListener(
       this.#onConsistencyResults.bind(this)
     )
+    // listen for the lint results event (lint runs after a successful consistency check)
+    aeonState.sketch.lintResults.addEventListener(
+      this.#onLintResults.bind(this)
+    )
   }
 
   protected updated (_changedProperties: PropertyValues): void {
@@ -27,6 +32,7 @@ export class AnalysisTab extends LitElement {
     // as they are no longer valid.
     if (_changedProperties.has('contentData')) {
       this.consistency_results = null
+      this.lint_results = null
     }
   }
 
@@ -37,17 +43,31 @@ export class AnalysisTab extends LitElement {
 
   /** Run the consistency check by calling the backend. */
   checkConsistency (): void {
+    this.lint_results = null
     aeonState.sketch.checkConsistency()
   }
 
-  /** Process and save the consistency check results. */
-  #onConsistencyResults (results: string): void {
-    this.consistency_results = results
+  /**
+   * Process and save the consistency check results. Properties of a consistent sketch are
+   * then also checked by the lint pass (which is only meaningful for consistent sketches).
+   */
+  #onConsistencyResults (results: ConsistencyReportData): void {
+    this.consistency_results = results.summary
     console.log('Received consistency check results.')
+    if (results.consistent) {
+      aeonState.sketch.lintProperties()
+    }
+  }
+
+  /** Process and save the lint results. */
+  #onLintResults (results: ConsistencyReportData): void {
+    this.lint_results = results.summary
+    console.log('Received lint results.')
   }
 
   closeConsistencyResults (): void {
     this.consistency_results = null
+    this.lint_results = null
   }
 
   protected render (): TemplateResult {
@@ -83,6 +103,13 @@ export class AnalysisTab extends LitElement {
                   ? html`
                     <div class="results-window" style="display: flex; justify-content: center; align-items: center; flex-direction: column;">
                       <textarea rows="12" cols="60" readonly style="text-align: center;">${this.consistency_results}</textarea>
+                      <!-- Lint results are shown below the consistency results once they arrive -->
+                      ${this.lint_results !== null
+                          ? html`
+                            <h4 class="uk-margin-small-top uk-margin-small-bottom">Property lints</h4>
+                            <textarea id="lint-results" rows="8" cols="60" readonly style="text-align: center;">${this.lint_results}</textarea>
+                          `
+                          : ''}
                       <button class="uk-button uk-button-small uk-button-danger uk-margin-top"
                               @click="${this.closeConsistencyResults}">Close</button>
                     </div>
@@ -35,6 +35,10 @@ export default class Menu extends LitElement {
       label: 'Import SBML',
       action: () => { void this.importSbmlModel() }
     },
+    {
+      label: 'Import BNET',
+      action: () => { void this.importBnetModel() }
+    },
     {
       label: 'Export JSON',
       action: () => { void this.exportSketch() }
@@ -43,6 +47,14 @@ export default class Menu extends LitElement {
       label: 'Export AEON',
       action: () => { void this.exportAeon() }
     },
+    {
+      label: 'Export SBML',
+      action: () => { void this.exportSbml() }
+    },
+    {
+      label: 'Export BNET',
+      action: () => { void this.exportBnet() }
+    },
     {
       label: 'Quit',
       action: () => { void this.quit() }
@@ -102,6 +114,22 @@ export default class Menu extends LitElement {
     }))
   }
 
+  async importBnetModel (): Promise<void> {
+    const confirmation = await dialog.ask('Importing new model will erase the current sketch. Do you want to proceed?', {
+      type: 'warning',
+      okLabel: 'Import',
+      cancelLabel: 'Cancel',
+      title: 'Import new model'
+    })
+    if (!confirmation) return
+
+    this.dispatchEvent(new CustomEvent('import-bnet', {
+      bubbles: true,
+      composed: true,
+      detail: {}
+    }))
+  }
+
   async exportSketch (): Promise<void> {
     const filePath = await save({
       title: 'Export sketch in JSON format...',
@@ -132,6 +160,36 @@ export default class Menu extends LitElement {
     aeonState.sketch.exportAeon(filePath)
   }
 
+  async exportSbml (): Promise<void> {
+    const filePath = await save({
+      title: 'Export sketch in SBML-qual format...',
+      filters: [{
+        name: '*.sbml',
+        extensions: ['sbml', 'xml']
+      }],
+      defaultPath: 'project_name_here'
+    })
+    if (filePath === null) return
+
+    console.log('exporting sbml to', filePath)
+    aeonState.sketch.exportSbml(filePath)
+  }
+
+  async exportBnet (): Promise<void> {
+    const filePath = await save({
+      title: 'Export model in BNET format...',
+      filters: [{
+        name: '*.bnet',
+        extensions: ['bnet']
+      }],
+      defaultPath: 'project_name_here'
+    })
+    if (filePath === null) return
+
+    console.log('exporting bnet to', filePath)
+    aeonState.sketch.exportBnet(filePath)
+  }
+
   async newSketch (): Promise<void> {
     const confirmation = await dialog.ask('Starting new sketch will erase the current one. Do you want to proceed?', {
       type: 'warning',
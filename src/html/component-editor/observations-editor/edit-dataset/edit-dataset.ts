@@ -4,7 +4,7 @@ import style_less from './edit-dataset.less?inline'
 import { emit, type Event as TauriEvent, once } from '@tauri-apps/api/event'
 import { appWindow } from '@tauri-apps/api/window'
 import { map } from 'lit/directives/map.js'
-import { type IObservationSet } from '../../../util/data-interfaces'
+import { type IMultiValuedVar, type IObservationSet, type PerturbationType } from '../../../util/data-interfaces'
 
 @customElement('edit-dataset')
 export default class EditDataset extends LitElement {
@@ -12,6 +12,7 @@ export default class EditDataset extends LitElement {
   @query('#dataset-id') idField: HTMLInputElement | undefined
   @state() data: IObservationSet | undefined
   @state() origData: IObservationSet | undefined
+  @state() perturbationsError: string | null = null
   id = ''
 
   async firstUpdated (): Promise<void> {
@@ -26,6 +27,8 @@ export default class EditDataset extends LitElement {
 
   private async handleSubmit (event: Event): Promise<void> {
     event.preventDefault()
+    // invalid perturbations must be fixed before saving (they are never silently dropped)
+    if (this.perturbationsError !== null) return
     await emit('edit_dataset_dialog', {
       id: this.id,
       data: this.data
@@ -33,6 +36,51 @@ export default class EditDataset extends LitElement {
     await appWindow.close()
   }
 
+  /** Format perturbations as a comma-separated list of `variable:KO` or `variable:OE` items. */
+  private formatPerturbations (perturbations: Record<string, PerturbationType>): string {
+    return Object.entries(perturbations)
+      .map(([variable, perturbation]) => `${variable}:${perturbation === 'KnockOut' ? 'KO' : 'OE'}`)
+      .join(', ')
+  }
+
+  /** Parse perturbations from a comma-separated list. Throws an error describing the first invalid item. */
+  private parsePerturbations (value: string): Record<string, PerturbationType> {
+    const perturbations: Record<string, PerturbationType> = {}
+    value.split(',').forEach(item => {
+      if (item.trim() === '') return
+      const parts = item.split(':').map(part => part.trim())
+      const [variable, perturbation] = parts
+      if (parts.length !== 2 || variable === '') {
+        throw new Error(`Invalid perturbation \`${item.trim()}\`, expected \`variable:KO\` or \`variable:OE\`.`)
+      }
+      if (perturbations[variable] !== undefined) {
+        throw new Error(`Variable \`${variable}\` is perturbed twice.`)
+      }
+      if (perturbation.toUpperCase() === 'KO') {
+        perturbations[variable] = 'KnockOut'
+      } else if (perturbation.toUpperCase() === 'OE') {
+        perturbations[variable] = 'OverExpression'
+      } else {
+        throw new Error(`Invalid perturbation \`${perturbation}\` of \`${variable}\`, expected \`KO\` or \`OE\`.`)
+      }
+    })
+    return perturbations
+  }
+
+  /** Format multi-valued variables as a semicolon-separated list of `X: level0, level1, ...` items. */
+  private formatMultiValued (vars: IMultiValuedVar[]): string {
+    return vars.map(v => `${v.name}: ${v.levels.join(', ')}`).join('; ')
+  }
+
+  /** Parse multi-valued variables from a semicolon-separated list (invalid items are ignored). */
+  private parseMultiValued (value: string): IMultiValuedVar[] {
+    return value.split(';').flatMap(item => {
+      const [name, levels] = item.split(':').map(part => part.trim())
+      if (name === undefined || name === '' || levels === undefined) return []
+      return [{ name, levels: levels.split(',').map(level => level.trim()) }]
+    })
+  }
+
   private getValue<T>(data: T, key: string): T[keyof T] {
     const newKey = key as keyof typeof data
     return data[newKey]
@@ -83,8 +131,40 @@ export default class EditDataset extends LitElement {
                   </div>
                 </div>`
             })}
+
+            <!-- Editable field for perturbations (knockouts and overexpressions) -->
+            <h4 class="uk-text-bold uk-margin-top">Perturbations</h4>
+            <div class="uk-margin-small">
+              <input class="uk-input" .value="${this.formatPerturbations(this.data?.perturbations ?? {})}"
+                    @change="${(e: InputEvent) => {
+                      if (this.data === undefined) return
+                      try {
+                        this.data.perturbations = this.parsePerturbations((e.target as HTMLInputElement).value)
+                        this.perturbationsError = null
+                      } catch (error) {
+                        this.perturbationsError = (error as Error).message
+                      }
+                    }}"
+                    id="dataset-perturbations" type="text" placeholder="e.g., A:KO, B:OE"/>
+              ${this.perturbationsError !== null
+                ? html`<div class="uk-text-danger uk-text-small">${this.perturbationsError}</div>`
+                : ''}
+            </div>
+
+            <!-- Editable field for multi-valued variables (encoded by Boolean variables X_1, X_2, ...) -->
+            <h4 class="uk-text-bold uk-margin-top">Multi-valued variables</h4>
+            <div class="uk-margin-small">
+              <input class="uk-input" .value="${this.formatMultiValued(this.data?.multi_valued_vars ?? [])}"
+                    @change="${(e: InputEvent) => {
+                      if (this.data !== undefined) {
+                        this.data.multi_valued_vars = this.parseMultiValued((e.target as HTMLInputElement).value)
+                      }
+                    }}"
+                    id="dataset-multi-valued" type="text" placeholder="e.g., X: low, mid, high"/>
+            </div>
           </div>
-          <button class="uk-button uk-button-primary uk-width-1-1 uk-margin-small-top" @click="${this.handleSubmit}">Save</button>
+          <button class="uk-button uk-button-primary uk-width-1-1 uk-margin-small-top" @click="${this.handleSubmit}"
+                  ?disabled="${this.perturbationsError !== null}">Save</button>
         </form>
       </div>
     `
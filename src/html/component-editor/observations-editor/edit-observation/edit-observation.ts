@@ -38,7 +38,8 @@ export default class EditObservation extends LitElement {
 
   private setValue<T>(data: T, key: string, value: string): void {
     const newKey = key as keyof typeof data
-    data[newKey] = value as T[keyof T]
+    // weight is the only numeric field of an observation
+    data[newKey] = (key === 'weight' ? Number(value) : value) as T[keyof T]
   }
 
   render (): TemplateResult {
@@ -12,6 +12,7 @@ import {
   type DatasetMetaData,
   type DatasetData,
   type DatasetIdUpdateData,
+  type DatasetImportData,
   type ObservationData,
   type ObservationIdUpdateData
 } from '../../../aeon_state'
@@ -104,8 +105,12 @@ export default class ObservationsEditor extends LitElement {
     aeonState.sketch.observations.loadDataset(fileName)
   }
 
-  #onDatasetLoaded (data: DatasetData): void {
-    const newDataset = convertToIObservationSet(data)
+  #onDatasetLoaded (data: DatasetImportData): void {
+    // dataset was imported, but the user should know about the problems found in the file
+    if (data.issues.length > 0) {
+      void dialog.message(data.summary, { type: 'warning', title: 'Dataset import warnings' })
+    }
+    const newDataset = convertToIObservationSet(data.dataset)
     // just call import dialog, dataset will be filtered and then added
     void this.importObservations(newDataset.id, newDataset.observations, newDataset.variables)
   }
@@ -140,7 +145,9 @@ export default class ObservationsEditor extends LitElement {
         name,
         annotation: '',
         observations: event.payload,
-        variables
+        variables,
+        perturbations: {},
+        multi_valued_vars: []
       }
       // temporarily add the dataset in its current placeholder version, and send an event to backend with changes
       this.updateObservations(this.contentData.observations.concat(modifiedDataset))
@@ -202,6 +209,7 @@ export default class ObservationsEditor extends LitElement {
           selected: obs.selected,
           name: obs.name,
           annotation: obs.annotation,
+          weight: obs.weight,
           id: obs.id
         }
 
@@ -222,14 +230,18 @@ export default class ObservationsEditor extends LitElement {
         name: data.name,
         annotation: data.annotation,
         variables: data.variables,
+        perturbations: data.perturbations,
+        multi_valued_vars: data.multi_valued_vars,
         observations
       }
     } else {
-      // otherwise only change the name and annotation
+      // otherwise only change the name, annotation, perturbations, and multi-valued variables
       datasets[datasetIndex] = {
         ...datasets[datasetIndex],
         name: data.name,
-        annotation: data.annotation
+        annotation: data.annotation,
+        perturbations: data.perturbations,
+        multi_valued_vars: data.multi_valued_vars
       }
     }
     this.updateObservations(datasets)
@@ -331,14 +343,16 @@ export default class ObservationsEditor extends LitElement {
       id: datasetData.id,
       annotation: datasetData.annotation,
       name: datasetData.name,
-      variables: datasetData.variables
+      variables: datasetData.variables,
+      perturbations: datasetData.perturbations,
+      multi_valued_vars: datasetData.multi_valued_vars
     }
 
     // id might have changed
     if (origDataset.id !== datasetData.id) {
       aeonState.sketch.observations.setDatasetId(origDataset.id, datasetData.id)
     }
-    // name or annotation might have changed
+    // name, annotation, perturbations, or multi-valued variables might have changed
     setTimeout(() => {
       aeonState.sketch.observations.setDatasetMetadata(datasetData.id, datasetMetaData)
     }, 50)
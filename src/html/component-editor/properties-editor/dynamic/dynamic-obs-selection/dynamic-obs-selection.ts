@@ -3,6 +3,7 @@ import { customElement, property, query } from 'lit/decorators.js'
 import style_less from './dynamic-obs-selection.less?inline'
 import {
   DynamicPropertyType,
+  type IHasAttractorDynamicProperty,
   type IObservationSet,
   type ITrapSpaceDynamicProperty
 } from '../../../../util/data-interfaces'
@@ -54,6 +55,18 @@ export default class DynamicObsSelection extends AbstractDynamicProperty {
     })
   }
 
+  /** Whether the property is a soft constraint (not applicable to trap spaces). */
+  get isSoft (): boolean {
+    return (this.property as unknown as IHasAttractorDynamicProperty).soft
+  }
+
+  softChanged (): void {
+    this.updateProperty({
+      ...this.property,
+      soft: !this.isSoft
+    })
+  }
+
   protected updated (_changedProperties: PropertyValues): void {
     super.updated(_changedProperties)
     const obsIndex = this.observations.findIndex(dataset => dataset.id === this.property.dataset)
@@ -112,6 +125,14 @@ export default class DynamicObsSelection extends AbstractDynamicProperty {
               <label class="pointer" for="non-percolable">non-percolable</label>
             </div>
           </div>`)}
+        ${when(this.property.variant !== DynamicPropertyType.TrapSpace, () => html`
+          <div class="uk-flex uk-flex-row uk-flex-around">
+            <div class="toggle">
+              <input class="uk-checkbox" type="checkbox" id="soft" name="soft" ?checked=${this.isSoft}
+                     @change=${this.softChanged} />
+              <label class="pointer" for="soft">soft (allow violated observations)</label>
+            </div>
+          </div>`)}
       </div>
       <hr class="uk-margin-top uk-margin-bottom uk-margin-left uk-margin-right">
     `
@@ -69,6 +69,7 @@ export default class RootComponent extends LitElement {
     window.addEventListener('import-json', (e) => { void this.importJson(e) })
     window.addEventListener('import-aeon', (e) => { void this.importAeon(e) })
     window.addEventListener('import-sbml', (e) => { void this.importSbml(e) })
+    window.addEventListener('import-bnet', (e) => { void this.importBnet(e) })
 
     // variable-related events
     this.addEventListener('add-variable', this.addNewVariable)
@@ -105,6 +106,7 @@ export default class RootComponent extends LitElement {
     // updating model components, and distributing the rest (observations, properties) to particular sub-modules
     aeonState.sketch.sketchRefreshed.addEventListener(this.#onSketchRefreshed.bind(this))
     aeonState.sketch.sketchReplaced.addEventListener(this.#onSketchRefreshed.bind(this))
+    aeonState.sketch.sketchMerged.addEventListener((data) => { this.#onSketchRefreshed(data.sketch) })
 
     // event listener to capture changes from sub-modules (FunctionEditor, ObservationEditor, or PropertiesEditor)
     // with updated uninterpreted functions
@@ -212,6 +214,30 @@ export default class RootComponent extends LitElement {
     await this.importSbmlInternal()
   }
 
+  /** Import new BNET model to the current editor. */
+  async importBnet (_event: Event): Promise<void> {
+    const selected = await open({
+      title: 'Import bnet model...',
+      multiple: false,
+      filters: [{
+        name: '*.bnet',
+        extensions: ['bnet']
+      }]
+    })
+    if (selected === null) return
+
+    let importFile = ''
+    if (Array.isArray(selected)) {
+      if (selected.length === 0) return
+      importFile = selected[0]
+    } else {
+      importFile = selected
+    }
+
+    console.log('importing', importFile)
+    aeonState.sketch.importBnet(importFile)
+  }
+
   /** Open the dialog to select JSON sketch and invoke backend to load it. */
   private async importJsonInternal (): Promise<boolean> {
     const selected = await open({
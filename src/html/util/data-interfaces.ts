@@ -73,6 +73,7 @@ export interface IObservation {
   id: string
   name: string
   annotation: string
+  weight: number
 
   [key: string]: string | number | boolean
 }
@@ -84,8 +85,23 @@ export interface IObservationSet {
   annotation: string
   observations: IObservation[]
   variables: string[]
+  perturbations: Record<string, PerturbationType>
+  multi_valued_vars: IMultiValuedVar[]
 }
 
+/** Perturbation of a variable in a dataset (knockout fixes it to 0, overexpression to 1). */
+export type PerturbationType = 'KnockOut' | 'OverExpression'
+
+/**
+ * Multi-valued variable of a dataset with ordered levels. Variable `X` with `n` levels is encoded
+ * by Boolean dataset variables `X_1, ..., X_{n-1}` (`X_i` is true iff the level is at least `i`).
+ */
+export interface IMultiValuedVar {
+  name: string
+  levels: string[]
+}
+
+
 /** Enum representing all supported types of static properties. */
 export enum StaticPropertyType {
   Generic = 'GenericStatProp',
@@ -106,7 +122,9 @@ export enum DynamicPropertyType {
   TrapSpace = 'ExistsTrapSpace',
   ExistsTrajectory = 'ExistsTrajectory',
   AttractorCount = 'AttractorCount',
-  HasAttractor = 'HasAttractor'
+  HasAttractor = 'HasAttractor',
+  StatesReachable = 'StatesReachable',
+  StatesInBasins = 'StatesInBasins'
 }
 
 /** Typesafe representation of property template types. */
@@ -124,6 +142,7 @@ export interface IProperty {
 export interface IFixedPointDynamicProperty extends IProperty {
   dataset: string | null
   observation: string | null
+  soft: boolean
 }
 
 /** Template dynamic property for trap space existence. */
@@ -137,6 +156,7 @@ export interface ITrapSpaceDynamicProperty extends IProperty {
 /** Template dynamic property for trajectory existence. */
 export interface IExistsTrajectoryDynamicProperty extends IProperty {
   dataset: string | null
+  soft: boolean
 }
 
 /** Template dynamic property for attractor count. */
@@ -149,6 +169,19 @@ export interface IAttractorCountDynamicProperty extends IProperty {
 export interface IHasAttractorDynamicProperty extends IProperty {
   dataset: string | null
   observation: string | null
+  soft: boolean
+}
+
+/** Template dynamic property for reachability of all dataset's states from an initial observation. */
+export interface IStatesReachableDynamicProperty extends IProperty {
+  dataset: string | null
+  initial_observation: string | null
+}
+
+/** Template dynamic property for all dataset's states lying in basins of phenotype attractors. */
+export interface IStatesInBasinsDynamicProperty extends IProperty {
+  dataset: string | null
+  phenotypes: string | null
 }
 
 /** Generic dynamic property given by an HCTL formula. */
@@ -163,6 +196,8 @@ export type DynamicProperty =
   | IExistsTrajectoryDynamicProperty
   | IAttractorCountDynamicProperty
   | IHasAttractorDynamicProperty
+  | IStatesReachableDynamicProperty
+  | IStatesInBasinsDynamicProperty
   | IGenericDynamicProperty
 
 /** Template static property for essentiality of a function's input. */
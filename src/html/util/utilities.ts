@@ -103,6 +103,7 @@ export function convertToIObservation (observationData: ObservationData, variabl
     id: observationData.id,
     name: observationData.name,
     annotation: observationData.annotation,
+    weight: observationData.weight,
     selected: false
   }
   variables.forEach(((v, idx) => {
@@ -126,7 +127,8 @@ export function convertFromIObservation (
     name: observation.name,
     annotation: observation.annotation,
     dataset: datasetId,
-    values: valueString
+    values: valueString,
+    weight: observation.weight
   }
 }
 
@@ -140,7 +142,9 @@ export function convertToIObservationSet (datasetData: DatasetData): IObservatio
     name: datasetData.name,
     annotation: datasetData.annotation,
     observations,
-    variables: datasetData.variables
+    variables: datasetData.variables,
+    perturbations: datasetData.perturbations,
+    multi_valued_vars: datasetData.multi_valued_vars
   }
 }
 
@@ -154,7 +158,9 @@ export function convertFromIObservationSet (dataset: IObservationSet): DatasetDa
     name: dataset.name,
     annotation: dataset.annotation,
     observations,
-    variables: dataset.variables
+    variables: dataset.variables,
+    perturbations: dataset.perturbations,
+    multi_valued_vars: dataset.multi_valued_vars
   }
 }
 
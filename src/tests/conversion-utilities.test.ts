@@ -77,13 +77,15 @@ const mockObservationData: ObservationData = {
   name: 'Observation 1',
   annotation: 'Annotation for observation 1',
   dataset: 'dataset1',
-  values: '01*'
+  values: '01*',
+  weight: 1
 }
 
 const mockIObservation: IObservation = {
   id: 'obs1',
   name: 'Observation 1',
   annotation: 'Annotation for observation 1',
+  weight: 1,
   selected: false,
   var0: '0',
   var1: '1',
@@ -109,7 +111,9 @@ const mockDatasetData: DatasetData = {
   name: 'Dataset 1',
   annotation: 'Annotation for dataset 1',
   observations: [mockObservationData],
-  variables: ['var0', 'var1', 'var2']
+  variables: ['var0', 'var1', 'var2'],
+  perturbations: { var1: 'KnockOut' },
+  multi_valued_vars: []
 }
 
 const mockIObservationSet: IObservationSet = {
@@ -117,7 +121,9 @@ const mockIObservationSet: IObservationSet = {
   name: 'Dataset 1',
   annotation: 'Annotation for dataset 1',
   observations: [mockIObservation],
-  variables: ['var0', 'var1', 'var2']
+  variables: ['var0', 'var1', 'var2'],
+  perturbations: { var1: 'KnockOut' },
+  multi_valued_vars: []
 }
 
 // Test converting DatasetData to IObservationSet.